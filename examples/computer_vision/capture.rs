@@ -0,0 +1,47 @@
+use std::fs;
+
+use airsim_client::{ComputerVisionClient, ImageType, NetworkResult, Pose3, Position3, Quaternion};
+use async_std::task;
+
+async fn capture_dataset() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = ComputerVisionClient::connect(address, vehicle_name).await?;
+
+    // confirm connection
+    log::info!("confirm connection");
+    let res = client.confirm_connection().await?;
+    log::info!("Response: {:?}", res);
+
+    // widen the field of view of the front camera
+    log::info!("set camera fov");
+    client.sim_set_camera_fov("0", 120.0, None).await?;
+
+    // move the vehicle along a line and capture an image at every step
+    for i in 0..10 {
+        let pose = Pose3::new(
+            Position3::new(i as f32 * 2.0, 0.0, -5.0),
+            Quaternion::new(1.0, 0.0, 0.0, 0.0),
+        );
+        client.sim_set_vehicle_pose(pose, true).await?;
+
+        let pose = client.sim_get_vehicle_pose().await?;
+        log::info!("capture image at {pose:?}");
+
+        let img = client.sim_get_image("0", ImageType::Scene, None).await?;
+        fs::write(format!("capture_{i}.png"), img.0)?;
+    }
+
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(capture_dataset())
+}
@@ -1,4 +1,4 @@
-use airsim_client::{ImageType, MultiRotorClient, NetworkResult};
+use airsim_client::{ImageRequest, ImageRequests, ImageSaver, ImageType, MultiRotorClient, NetworkResult};
 use async_std::task;
 
 #[allow(clippy::no_effect)]
@@ -80,19 +80,22 @@ async fn connect_drone() -> NetworkResult<()> {
     // use camera
     log::info!("get vehicle images");
     let img = client.sim_get_image("high_res", ImageType::Scene, Some(false)).await?;
-    // let _img = client
-    //     .sim_get_images(
-    //         ImageRequests(vec![ImageRequest {
-    //             camera_name: "high_res".to_string(),
-    //             image_type: ImageType::Scene,
-    //             pixels_as_float: false,
-    //             compress: false,
-    //         }]),
-    //         Some(false),
-    //     )
-    //     .await;
+    log::info!("image response: {} bytes", img.0.len());
 
-    log::info!("image response: {img:?}");
+    // save a scene and a depth image of the same frame to disk
+    log::info!("save vehicle images");
+    let images = client
+        .sim_get_images(
+            ImageRequests(vec![
+                ImageRequest::scene("low_res"),
+                ImageRequest::depth_planar("low_res"),
+            ]),
+            Some(false),
+        )
+        .await?;
+    let mut saver = ImageSaver::new("images");
+    let paths = saver.save_frame(&images).await?;
+    log::info!("saved images: {paths:?}");
 
     client.arm_disarm(false).await?;
     client.enable_api_control(false).await?;
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use airsim_client::{DrivetrainType, MultiRotorClient, NetworkResult, Position3, YawMode};
+use airsim_client::{DrivetrainType, MultiRotorClient, NetworkResult, Path, Position3, Vector3, YawMode};
 use async_std::task;
 
 use futures::future::FutureExt;
@@ -53,12 +53,36 @@ async fn connect_drone() -> NetworkResult<()> {
             3.0,
             1000.0,
             DrivetrainType::ForwardOnly,
-            YawMode::new(false, 90.0),
+            YawMode::angle(90.0),
             None,
             None,
         )
         .await?;
 
+    // abort a long path from the same client, e.g. when obstacle avoidance kicks in
+    log::info!("move on path, cancelled after 5 seconds");
+    let path = Path(vec![
+        Vector3::new(-25.0, 0.0, -20.0),
+        Vector3::new(-50.0, 50.0, -20.0),
+        Vector3::new(-50.0, -50.0, -25.0),
+    ]);
+    let (moved, cancelled) = futures::join!(
+        client.move_on_path_async(
+            path,
+            5.0,
+            1000.0,
+            DrivetrainType::MaxDegreeOfFreedom,
+            YawMode::angle(90.0),
+            None,
+            None,
+        ),
+        async {
+            task::sleep(Duration::from_secs(5)).await;
+            client.cancel_last_task().await
+        }
+    );
+    log::info!("path completed: {:?}, cancelled: {:?}", moved?, cancelled?);
+
     // land drone
     log::info!("land drone");
     let res = client.land_async(20.0).await?;
@@ -0,0 +1,54 @@
+use airsim_client::safety::{Fence, SafetyAction, SafetyConfig, SafetyMonitor};
+use airsim_client::{MoveOptions, MultiRotorClient, NetworkResult, Position3, Vector3};
+use async_std::task;
+
+async fn geofence() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // arm drone
+    log::info!("arm drone");
+    client.arm_disarm(true).await?;
+
+    // take off
+    log::info!("take off drone");
+    client.take_off_async(20.0).await?;
+
+    // stay within 20 meters of home and below 15 meters, hover as soon as either is breached
+    let config = SafetyConfig::new()
+        .fence(Fence::NedBox {
+            min: Vector3::new(-20.0, -20.0, -100.0),
+            max: Vector3::new(20.0, 20.0, 100.0),
+        })
+        .max_altitude(15.0)
+        .action(SafetyAction::Hover)
+        .on_breach(|breach| log::warn!("safety limit breached: {breach:?}"));
+    let monitor = SafetyMonitor::start(&client, config);
+
+    // try to fly out of the fence
+    log::info!("fly out of the fence");
+    client
+        .move_to_position_with(Position3::new(50.0, 0.0, -5.0), &MoveOptions::new().timeout_sec(30.0))
+        .await?;
+
+    let breach = monitor.stop().await?;
+    log::info!("monitor stopped after {breach:?}");
+
+    client.land_async(20.0).await?;
+    client.arm_disarm(false).await?;
+    client.enable_api_control(false).await?;
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(geofence())
+}
@@ -32,7 +32,7 @@ async fn connect_drone() -> NetworkResult<()> {
                 -10000.0,
                 15.0,
                 DrivetrainType::MaxDegreeOfFreedom,
-                YawMode::new(false, 0.0),
+                YawMode::angle(0.0),
             )
             .await
             .unwrap();
@@ -0,0 +1,58 @@
+use airsim_client::{
+    DrivetrainType, MavlinkBridge, MavlinkConfig, MultiRotorClient, NetworkResult, Position3, YawMode,
+};
+use async_std::task;
+
+async fn mirror_flight() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // mirror the drone to a QGroundControl listening on the default port
+    log::info!("start MAVLink bridge");
+    let bridge = MavlinkBridge::start(&client, MavlinkConfig::default().rate_hz(20.0)).await?;
+
+    // arm drone
+    log::info!("arm drone");
+    client.arm_disarm(true).await?;
+
+    // take off
+    log::info!("take off drone");
+    client.take_off_async(20.0).await?;
+
+    // fly away from home, to watch the drone move on the map of the GCS
+    log::info!("move drone");
+    client
+        .move_to_position_async(
+            Position3::new(50.0, 30.0, -20.0),
+            5.0,
+            60.0,
+            DrivetrainType::MaxDegreeOfFreedom,
+            YawMode::angle(0.0),
+            None,
+            None,
+        )
+        .await?;
+
+    // land
+    log::info!("land drone");
+    client.land_async(60.0).await?;
+
+    // stop the bridge
+    log::info!("stop MAVLink bridge");
+    bridge.stop().await?;
+
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(mirror_flight())
+}
@@ -32,7 +32,7 @@ async fn connect_drone() -> NetworkResult<()> {
             Velocity3::new(-2.0, 2.0, -15.0),
             10.0,
             DrivetrainType::MaxDegreeOfFreedom,
-            YawMode::new(false, 45.0),
+            YawMode::angle(45.0),
         )
         .await?;
     log::info!("done!");
@@ -44,7 +44,7 @@ async fn connect_drone() -> NetworkResult<()> {
             -15.0,
             15.0,
             DrivetrainType::MaxDegreeOfFreedom,
-            YawMode::new(false, 45.0),
+            YawMode::angle(45.0),
         )
         .await?;
     log::info!("done!");
@@ -66,7 +66,7 @@ async fn connect_drone() -> NetworkResult<()> {
             Velocity3::new(-3.0, -3.0, -3.0),
             6.0,
             DrivetrainType::MaxDegreeOfFreedom,
-            YawMode::new(false, -45.0),
+            YawMode::angle(-45.0),
         )
         .await?;
     log::info!("done!");
@@ -78,17 +78,21 @@ async fn connect_drone() -> NetworkResult<()> {
             -10.0,
             15.0,
             DrivetrainType::MaxDegreeOfFreedom,
-            YawMode::new(false, 45.0),
+            YawMode::angle(45.0),
         )
         .await?;
     log::info!("done!");
 
     log::info!("move to Z in local NED frame of vehicle");
     client
-        .move_to_z_async(-40.0, 6.0, 1000.0, YawMode::new(false, 70.0), None, None)
+        .move_to_z_async(-40.0, 6.0, 1000.0, YawMode::angle(70.0), None, None)
         .await?;
     log::info!("done!");
 
+    log::info!("hover in place");
+    client.hover_async().await?;
+    log::info!("done!");
+
     log::info!("go home");
     client.go_home_async(20.0).await?;
     log::info!("got home");
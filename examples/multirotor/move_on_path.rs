@@ -1,4 +1,4 @@
-use airsim_client::{DrivetrainType, MultiRotorClient, NetworkResult, Path, Vector3, YawMode};
+use airsim_client::{DrivetrainType, MoveOptions, MultiRotorClient, NetworkResult, Path, PathEvent, Vector3, YawMode};
 use async_std::task;
 
 async fn connect_drone() -> NetworkResult<()> {
@@ -37,13 +37,34 @@ async fn connect_drone() -> NetworkResult<()> {
             5.0,
             1000.0,
             DrivetrainType::MaxDegreeOfFreedom,
-            YawMode::new(false, 90.0),
+            YawMode::angle(90.0),
             None,
             None,
         )
         .await?;
     log::info!("done!");
 
+    log::info!("move back on path, reporting the progress");
+    let options = MoveOptions::new().velocity(5.0).timeout_sec(1000.0);
+    client
+        .move_on_path_monitored(
+            Path(vec![Vector3::new(-50.0, 50.0, -20.0), Vector3::new(-25.0, 0.0, -20.0)]),
+            &options,
+            2.0,
+            2.0,
+            |event| match event {
+                PathEvent::WaypointReached(index) => log::info!("reached waypoint {index}"),
+                PathEvent::Progress(progress) => log::info!(
+                    "{} waypoints reached, {:.1} m to the next, {:.1} m off track",
+                    progress.completed,
+                    progress.distance_to_next,
+                    progress.cross_track_error
+                ),
+            },
+        )
+        .await?;
+    log::info!("done!");
+
     log::info!("go home");
     client.go_home_async(20.0).await?;
     log::info!("got home");
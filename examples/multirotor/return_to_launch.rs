@@ -0,0 +1,45 @@
+use airsim_client::maneuvers::{rtl, RtlConfig};
+use airsim_client::{MoveOptions, MultiRotorClient, NetworkResult, Position3};
+use async_std::task;
+
+async fn return_to_launch() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // arm drone
+    log::info!("arm drone");
+    client.arm_disarm(true).await?;
+
+    // take off
+    log::info!("take off drone");
+    let outcome = client.take_off_and_wait(20.0).await?;
+    log::info!("take off: {outcome:?}");
+
+    // fly away from home
+    log::info!("fly away");
+    client
+        .move_to_position_with(Position3::new(40.0, 20.0, -5.0), &MoveOptions::new().timeout_sec(30.0))
+        .await?;
+
+    // come back at 30 meters above home and land
+    log::info!("return to launch");
+    let outcome = rtl(&client, &RtlConfig::new().climb_altitude(30.0)).await?;
+    log::info!("return to launch: {outcome:?}");
+
+    client.arm_disarm(false).await?;
+    client.enable_api_control(false).await?;
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(return_to_launch())
+}
@@ -0,0 +1,38 @@
+use airsim_client::ops::CommandResult;
+use airsim_client::scenarios::{box_pattern, figure_eight, survey_grid, SurveyGrid};
+use airsim_client::MultiRotorClient;
+use async_std::task;
+
+async fn fly_scenarios() -> CommandResult {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // box pattern then figure eight, in one flight
+    log::info!("box pattern and figure eight");
+    box_pattern(20.0, 10.0, 4.0)
+        .then(figure_eight(15.0, 10.0, 5.0, 32))
+        .run(&client)
+        .await?;
+
+    // image survey of a 40 m x 30 m area
+    log::info!("survey grid");
+    let grid = SurveyGrid::new().area(40.0, 30.0).spacing(10.0).altitude(25.0);
+    let images = survey_grid(&grid).run(&client).await?;
+    log::info!("captured {} images", images.len());
+
+    client.enable_api_control(false).await?;
+    log::info!("Mission done!");
+    Ok(())
+}
+
+fn main() -> CommandResult {
+    env_logger::init();
+    task::block_on(fly_scenarios())
+}
@@ -65,6 +65,20 @@ async fn connect_drone() -> NetworkResult<()> {
         .await?;
     log::info!("Response: {:?}", res);
 
+    log::info!("restore default PID gains");
+    client
+        .set_angle_rate_controller_gains(AngularControllerGains::angle_rate_default())
+        .await?;
+    client
+        .set_angle_level_controller_gains(AngularControllerGains::angle_level_default())
+        .await?;
+    client
+        .set_velocity_controller_gains(LinearControllerGains::velocity_default())
+        .await?;
+    client
+        .set_position_controller_gains(LinearControllerGains::position_default())
+        .await?;
+
     // disarm drone
     log::info!("disarm drone");
     client.arm_disarm(false).await?;
@@ -0,0 +1,55 @@
+use airsim_client::{DrivetrainType, MoveOptions, NetworkResult, Position3, Swarm, YawMode};
+use async_std::task;
+
+async fn fly_swarm() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_names = ["Drone1", "Drone2", "Drone3"];
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect swarm");
+    let swarm = Swarm::connect(address, &vehicle_names).await?;
+
+    // arm drones
+    log::info!("arm drones");
+    let res = swarm.arm_disarm_all(true).await;
+    log::info!("Response: {res:?}");
+
+    // take off
+    log::info!("take off drones");
+    let res = swarm.take_off_all(20.0).await;
+    log::info!("Response: {res:?}");
+
+    log::info!("move to positions");
+    let options = MoveOptions::new()
+        .velocity(3.0)
+        .timeout_sec(60.0)
+        .drivetrain(DrivetrainType::MaxDegreeOfFreedom)
+        .yaw_mode(YawMode::angle(0.0));
+    let res = swarm
+        .move_to_positions(
+            vec![
+                ("Drone1", Position3::new(10.0, 0.0, -10.0)),
+                ("Drone2", Position3::new(10.0, 5.0, -12.0)),
+                ("Drone3", Position3::new(10.0, -5.0, -14.0)),
+            ],
+            &options,
+        )
+        .await;
+    log::info!("Response: {res:?}");
+
+    // land drones
+    log::info!("land drones");
+    let res = swarm.land_all(20.0).await;
+    log::info!("Response: {res:?}");
+
+    swarm.arm_disarm_all(false).await;
+    log::info!("Mission done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(fly_swarm())
+}
@@ -58,7 +58,7 @@ async fn connect_drone() -> NetworkResult<()> {
             3.0,
             1000.0,
             DrivetrainType::ForwardOnly,
-            YawMode::new(false, 90.0),
+            YawMode::angle(90.0),
             None,
             None,
         )
@@ -70,7 +70,7 @@ async fn connect_drone() -> NetworkResult<()> {
             7.0,
             1000.0,
             DrivetrainType::ForwardOnly,
-            YawMode::new(false, 180.0),
+            YawMode::angle(180.0),
             None,
             None,
         )
@@ -83,7 +83,7 @@ async fn connect_drone() -> NetworkResult<()> {
             6.0,
             1000.0,
             DrivetrainType::ForwardOnly,
-            YawMode::new(false, 70.0),
+            YawMode::angle(70.0),
             None,
             None,
         )
@@ -0,0 +1,42 @@
+use airsim_client::{MultiRotorClient, NetworkResult, TelemetryConfig, TelemetryRecorder};
+use async_std::task;
+
+async fn record_flight() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // start recording at 100 Hz
+    log::info!("start telemetry recording");
+    let recorder = TelemetryRecorder::start(&client, TelemetryConfig::new("telemetry.csv").rate_hz(100.0))?;
+
+    // arm drone
+    log::info!("arm drone");
+    client.arm_disarm(true).await?;
+
+    // take off
+    log::info!("take off drone");
+    client.take_off_async(20.0).await?;
+
+    // land
+    log::info!("land drone");
+    client.land_async(20.0).await?;
+
+    // stop recording
+    log::info!("stop telemetry recording");
+    recorder.stop().await?;
+
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(record_flight())
+}
@@ -0,0 +1,41 @@
+use airsim_client::teleop::{KeyboardInput, Teleop, TeleopConfig};
+use airsim_client::{MultiRotorClient, NetworkResult};
+use async_std::task;
+
+async fn teleop() -> NetworkResult<()> {
+    let address = "127.0.0.1:41451";
+    let vehicle_name = "";
+
+    log::info!("Start!");
+
+    // connect
+    log::info!("connect");
+    let client = MultiRotorClient::connect(address, vehicle_name).await?;
+    client.enable_api_control(true).await?;
+
+    // arm drone
+    log::info!("arm drone");
+    client.arm_disarm(true).await?;
+
+    // take off
+    log::info!("take off drone");
+    client.take_off_async(20.0).await?;
+
+    // w/s forward and backward, a/d left and right, r/f up and down, q/e yaw, space to stop, Esc to land
+    let mut keyboard = KeyboardInput::new(0.25)?;
+    let mut teleop = Teleop::new(TeleopConfig::new().max_speed(4.0));
+    teleop.run(&client, &mut keyboard).await?;
+    drop(keyboard);
+
+    log::info!("land drone");
+    client.land_async(20.0).await?;
+    client.arm_disarm(false).await?;
+    client.enable_api_control(false).await?;
+    log::info!("Done!");
+    Ok(())
+}
+
+fn main() -> NetworkResult<()> {
+    env_logger::init();
+    task::block_on(teleop())
+}
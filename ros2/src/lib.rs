@@ -0,0 +1,267 @@
+//! Conversions between the types of airsim-client and the ROS 2 common_interfaces messages generated for rclrs
+//!
+//! AirSim uses NED world frames and FRD body frames, ROS uses ENU world frames and FLU body frames (REP-103): poses
+//! and IMU readings are converted between them. Message headers carry the AirSim timestamp with an empty
+//! `frame_id`, to be filled in by the node publishing them.
+//!
+//! The message crates are generated by colcon rather than published, so this crate only builds inside a colcon
+//! workspace set up for rclrs, see the README of airsim-client.
+
+use std::f64::consts::FRAC_1_SQRT_2;
+use std::fmt;
+
+use airsim_client::{GnssFixType, GpsData, ImageResponse, ImuData, Pose3, Position3, Quaternion, Vector3};
+use builtin_interfaces::msg::Time;
+use geometry_msgs::msg::{Point, Pose};
+use sensor_msgs::msg::{Image, Imu, NavSatFix, NavSatStatus};
+use std_msgs::msg::Header;
+
+// NavSatStatus and NavSatFix constants
+const STATUS_NO_FIX: i8 = -1;
+const STATUS_FIX: i8 = 0;
+const SERVICE_GPS: u16 = 1;
+const COVARIANCE_TYPE_APPROXIMATED: u8 = 1;
+
+/// Conversion of an AirSim type to a ROS 2 message
+pub trait ToMsg<M> {
+    fn to_msg(&self) -> M;
+}
+
+/// Conversion of a ROS 2 message to an AirSim type
+pub trait FromMsg<M> {
+    fn from_msg(msg: &M) -> Self;
+}
+
+/// Conversion of an AirSim type to a ROS 2 message, for the values some messages can't hold
+pub trait TryToMsg<M> {
+    type Error;
+
+    fn try_to_msg(&self) -> Result<M, Self::Error>;
+}
+
+/// Why an image response can't be converted to a `sensor_msgs/Image`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+    /// the image is compressed, request it with `compress` set to false
+    Compressed,
+    /// the size of the pixels doesn't match a mono, BGR or BGRA image of the reported width and height
+    UnknownChannels,
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compressed => write!(f, "compressed images can't be converted to sensor_msgs/Image"),
+            Self::UnknownChannels => write!(f, "the pixels are neither mono8, bgr8 nor bgra8"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// Header stamped with an AirSim timestamp in nanoseconds
+fn header(timestamp: u64) -> Header {
+    Header {
+        stamp: Time {
+            sec: (timestamp / 1_000_000_000) as i32,
+            nanosec: (timestamp % 1_000_000_000) as u32,
+        },
+        frame_id: String::new(),
+    }
+}
+
+/// Vector of an FRD body frame in the matching FLU body frame
+fn flu_vector3(vector: Vector3) -> geometry_msgs::msg::Vector3 {
+    geometry_msgs::msg::Vector3 {
+        x: vector.x as f64,
+        y: -vector.y as f64,
+        z: -vector.z as f64,
+    }
+}
+
+/// Swaps the attitude of an FRD body in a NED frame and the attitude of an FLU body in an ENU frame
+///
+/// Both are the same rotation applied on either side: a half turn around the north-east diagonal between the world
+/// frames and a half turn around the x axis between the body frames, so the conversion is its own inverse
+fn swap_frames(w: f64, x: f64, y: f64, z: f64) -> [f64; 4] {
+    [
+        FRAC_1_SQRT_2 * (w + z),
+        FRAC_1_SQRT_2 * (x + y),
+        FRAC_1_SQRT_2 * (x - y),
+        FRAC_1_SQRT_2 * (w - z),
+    ]
+}
+
+fn enu_quaternion(q: Quaternion) -> geometry_msgs::msg::Quaternion {
+    let [w, x, y, z] = swap_frames(q.w as f64, q.x as f64, q.y as f64, q.z as f64);
+    geometry_msgs::msg::Quaternion { x, y, z, w }
+}
+
+impl ToMsg<Pose> for Pose3 {
+    /// NED position and FRD attitude to ENU position and FLU attitude
+    fn to_msg(&self) -> Pose {
+        Pose {
+            position: Point {
+                x: self.position.y as f64,
+                y: self.position.x as f64,
+                z: -self.position.z as f64,
+            },
+            orientation: enu_quaternion(self.orientation),
+        }
+    }
+}
+
+impl FromMsg<Pose> for Pose3 {
+    /// ENU position and FLU attitude to NED position and FRD attitude
+    fn from_msg(pose: &Pose) -> Self {
+        let q = &pose.orientation;
+        let [w, x, y, z] = swap_frames(q.w, q.x, q.y, q.z);
+        Pose3::new(
+            Position3::new(pose.position.y as f32, pose.position.x as f32, -pose.position.z as f32),
+            Quaternion::new(w as f32, x as f32, y as f32, z as f32),
+        )
+    }
+}
+
+impl ToMsg<Imu> for ImuData {
+    /// Orientation in the ENU frame, rates and accelerations in the FLU body frame. Covariances are unknown and
+    /// left at zero
+    fn to_msg(&self) -> Imu {
+        Imu {
+            header: header(self.timestamp),
+            orientation: enu_quaternion(Quaternion::from(self.orientation)),
+            angular_velocity: flu_vector3(self.angular_velocity),
+            linear_acceleration: flu_vector3(self.linear_acceleration),
+            ..Default::default()
+        }
+    }
+}
+
+impl ToMsg<NavSatFix> for GpsData {
+    /// Covariance is approximated from the horizontal and vertical position errors of the GNSS report
+    fn to_msg(&self) -> NavSatFix {
+        let report = &self.gnss_report;
+        let has_fix = self.is_valid && matches!(report.fix_type, GnssFixType::GnssFix2DFix | GnssFixType::GnssFix3DFix);
+
+        let eph = report.eph as f64;
+        let epv = report.epv as f64;
+
+        NavSatFix {
+            header: header(self.timestamp),
+            status: NavSatStatus {
+                status: if has_fix { STATUS_FIX } else { STATUS_NO_FIX },
+                service: SERVICE_GPS,
+            },
+            latitude: report.geo_point.latitude as f64,
+            longitude: report.geo_point.longitude as f64,
+            altitude: report.geo_point.altitude as f64,
+            position_covariance: [eph * eph, 0.0, 0.0, 0.0, eph * eph, 0.0, 0.0, 0.0, epv * epv],
+            position_covariance_type: COVARIANCE_TYPE_APPROXIMATED,
+        }
+    }
+}
+
+impl TryToMsg<Image> for ImageResponse {
+    type Error = ImageError;
+
+    /// Float images become `32FC1` images, other uncompressed images `mono8`, `bgr8` or `bgra8` images depending
+    /// on their number of channels. Compressed images can't be converted, request them with `compress` set to false
+    fn try_to_msg(&self) -> Result<Image, ImageError> {
+        if self.compress && !self.pixels_as_float {
+            return Err(ImageError::Compressed);
+        }
+
+        let (encoding, channel_bytes, data) = if self.pixels_as_float {
+            let data = self
+                .image_data_float
+                .iter()
+                .flat_map(|pixel| pixel.to_le_bytes())
+                .collect();
+            ("32FC1", 4, data)
+        } else {
+            let pixels = self.width as usize * self.height as usize;
+            let channels = match self.image_data_uint8.len().checked_div(pixels) {
+                Some(channels) if channels * pixels == self.image_data_uint8.len() => channels,
+                _ => return Err(ImageError::UnknownChannels),
+            };
+            let encoding = match channels {
+                1 => "mono8",
+                3 => "bgr8",
+                4 => "bgra8",
+                _ => return Err(ImageError::UnknownChannels),
+            };
+            (encoding, channels as u32, self.image_data_uint8.clone())
+        };
+
+        Ok(Image {
+            header: header(self.time_stamp),
+            height: self.height,
+            width: self.width,
+            encoding: encoding.to_owned(),
+            is_bigendian: 0,
+            step: self.width * channel_bytes,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use airsim_client::{ImageResponse, ImageType, Pose3, Position3, Quaternion, Quaternionr, Vector3};
+    use geometry_msgs::msg::Pose;
+
+    use crate::{FromMsg, ImageError, ToMsg, TryToMsg};
+
+    #[test]
+    fn test_pose_round_trip() {
+        let pose = Pose3::new(
+            Position3::new(1.0, -2.0, -3.0),
+            Quaternionr::from_euler(0.1, 0.2, 0.3).into(),
+        );
+        let msg: Pose = pose.to_msg();
+        assert_eq!((msg.position.x, msg.position.y, msg.position.z), (-2.0, 1.0, 3.0));
+
+        let back = Pose3::from_msg(&msg);
+        assert!((back.position.x - pose.position.x).abs() < 1e-6);
+        assert!((back.orientation.w - pose.orientation.w).abs() < 1e-6);
+        assert!((back.orientation.z - pose.orientation.z).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_north_facing_pose_yaws_left_in_enu() {
+        let pose = Pose3::new(Position3::new(0.0, 0.0, 0.0), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        let msg: Pose = pose.to_msg();
+
+        // facing north is a quarter turn to the left of facing east
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((msg.orientation.w - half).abs() < 1e-9);
+        assert!((msg.orientation.z - half).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_image_encoding_follows_channels() {
+        let mut response = ImageResponse {
+            image_data_uint8: vec![0; 8],
+            image_data_float: vec![],
+            camera_name: "0".to_owned(),
+            camera_position: Vector3::new(0.0, 0.0, 0.0),
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            time_stamp: 0,
+            message: String::new(),
+            pixels_as_float: false,
+            compress: false,
+            width: 2,
+            height: 1,
+            image_type: ImageType::Scene,
+        };
+        let image = response.try_to_msg().unwrap();
+        assert_eq!((image.encoding.as_str(), image.step), ("bgra8", 8));
+
+        response.image_data_uint8 = vec![0; 6];
+        let image = response.try_to_msg().unwrap();
+        assert_eq!((image.encoding.as_str(), image.step), ("bgr8", 6));
+
+        response.image_data_uint8 = vec![0; 5];
+        assert_eq!(response.try_to_msg().err(), Some(ImageError::UnknownChannels));
+    }
+}
@@ -0,0 +1,277 @@
+//! Interactive shell driving a multirotor, to sanity check a simulator setup before writing code
+//!
+//! Usage: `airsim-cli [address] [vehicle_name]`, connecting to `127.0.0.1:41451` and the default vehicle when
+//! omitted. Type `help` for the commands, `Tab` completes them.
+
+use std::env;
+
+use airsim_client::{DrivetrainType, ImageType, MultiRotorClient, NetworkResult, Position3, YawMode};
+use async_std::task;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:41451";
+const TIMEOUT_SEC: f32 = 60.0;
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("help", "list the commands"),
+    ("arm", "arm the motors"),
+    ("disarm", "disarm the motors"),
+    ("takeoff", "take off and hover"),
+    ("land", "land where the drone is"),
+    ("hover", "stop and hover in place"),
+    (
+        "goto",
+        "goto <x> <y> <z> <velocity>: fly to a NED position, in meters and m/s",
+    ),
+    ("state", "print the position, velocity and landed state"),
+    (
+        "img",
+        "img <camera> <image_type> <path>: save a compressed image of a camera",
+    ),
+    ("reset", "reset the vehicle to its starting pose"),
+    ("quit", "leave the shell"),
+];
+
+const IMAGE_TYPES: &[(&str, ImageType)] = &[
+    ("scene", ImageType::Scene),
+    ("depth_planar", ImageType::DepthPlanar),
+    ("depth_perspective", ImageType::DepthPerspective),
+    ("depth_vis", ImageType::DepthVis),
+    ("disparity", ImageType::DisparityNormalized),
+    ("segmentation", ImageType::Segmentation),
+    ("surface_normals", ImageType::SurfaceNormals),
+    ("infrared", ImageType::Infrared),
+    ("optical_flow", ImageType::OpticalFlow),
+    ("optical_flow_vis", ImageType::OpticalFlowVis),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Help,
+    Arm,
+    Disarm,
+    Takeoff,
+    Land,
+    Hover,
+    Goto {
+        x: f32,
+        y: f32,
+        z: f32,
+        velocity: f32,
+    },
+    State,
+    Image {
+        camera: String,
+        image_type: ImageType,
+        path: String,
+    },
+    Reset,
+    Quit,
+}
+
+impl Command {
+    /// Parse a line typed in the shell, `None` for a blank line
+    fn parse(line: &str) -> Result<Option<Self>, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let command = match words.as_slice() {
+            [] => return Ok(None),
+            ["help"] => Command::Help,
+            ["arm"] => Command::Arm,
+            ["disarm"] => Command::Disarm,
+            ["takeoff"] => Command::Takeoff,
+            ["land"] => Command::Land,
+            ["hover"] => Command::Hover,
+            ["goto", x, y, z, velocity] => Command::Goto {
+                x: number(x)?,
+                y: number(y)?,
+                z: number(z)?,
+                velocity: number(velocity)?,
+            },
+            ["state"] => Command::State,
+            ["img", camera, image_type, path] => Command::Image {
+                camera: camera.to_string(),
+                image_type: IMAGE_TYPES
+                    .iter()
+                    .find(|(name, _)| name == image_type)
+                    .map(|&(_, image_type)| image_type)
+                    .ok_or_else(|| format!("unknown image type {image_type:?}"))?,
+                path: path.to_string(),
+            },
+            ["reset"] => Command::Reset,
+            ["quit" | "exit"] => Command::Quit,
+            [name, ..] => match COMMANDS.iter().find(|(command, _)| command == name) {
+                Some((_, usage)) => return Err(format!("usage: {usage}")),
+                None => return Err(format!("unknown command {name:?}, type `help` for the commands")),
+            },
+        };
+        Ok(Some(command))
+    }
+
+    async fn run(self, client: &MultiRotorClient) -> NetworkResult<()> {
+        match self {
+            Command::Help => {
+                for (name, usage) in COMMANDS {
+                    println!("{name:>8}  {usage}");
+                }
+            }
+            Command::Arm => println!("armed: {}", client.arm_disarm(true).await?),
+            Command::Disarm => println!("disarmed: {}", client.arm_disarm(false).await?),
+            Command::Takeoff => println!("took off: {}", client.take_off_async(TIMEOUT_SEC).await?),
+            Command::Land => println!("landed: {}", client.land_async(TIMEOUT_SEC).await?),
+            Command::Hover => println!("hovering: {}", client.hover_async().await?),
+            Command::Goto { x, y, z, velocity } => {
+                let reached = client
+                    .move_to_position_async(
+                        Position3::new(x, y, z),
+                        velocity,
+                        TIMEOUT_SEC,
+                        DrivetrainType::MaxDegreeOfFreedom,
+                        YawMode::rate(0.0),
+                        None,
+                        None,
+                    )
+                    .await?;
+                println!("reached: {reached}");
+            }
+            Command::State => {
+                let state = client.get_multirotor_state().await?;
+                let kinematics = state.kinematics_estimated;
+                println!("position: {}", kinematics.position);
+                println!("velocity: {:?}", kinematics.linear_velocity);
+                println!("gps:      {}", state.gps_location);
+                println!("landed:   {:?}", state.landed_state);
+            }
+            Command::Image {
+                camera,
+                image_type,
+                path,
+            } => {
+                let image = client.sim_get_image(&camera, image_type, None).await?;
+                std::fs::write(&path, &image.0)?;
+                println!("saved {} bytes to {path}", image.0.len());
+            }
+            Command::Reset => println!("reset: {}", client.reset().await?),
+            Command::Quit => {}
+        }
+        Ok(())
+    }
+}
+
+fn number(word: &str) -> Result<f32, String> {
+    word.parse().map_err(|_| format!("{word:?} isn't a number"))
+}
+
+/// Completes the command names, and the image types of `img`
+struct CliHelper;
+
+impl Completer for CliHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |space| space + 1);
+        let word = &line[start..];
+
+        let candidates: Vec<&str> = match line[..start].split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => COMMANDS.iter().map(|&(name, _)| name).collect(),
+            ["img", _] => IMAGE_TYPES.iter().map(|&(name, _)| name).collect(),
+            _ => vec![],
+        };
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(|candidate| format!("{candidate} "))
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for CliHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CliHelper {}
+
+impl Validator for CliHelper {}
+
+impl Helper for CliHelper {}
+
+fn main() -> NetworkResult<()> {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| DEFAULT_ADDRESS.to_owned());
+    // the client keeps the name for the whole session
+    let vehicle_name: &'static str = Box::leak(args.next().unwrap_or_default().into_boxed_str());
+
+    let client = task::block_on(MultiRotorClient::connect(&address, vehicle_name))?;
+    task::block_on(client.enable_api_control(true))?;
+    println!("connected to {address}, type `help` for the commands");
+
+    let mut editor: Editor<CliHelper, DefaultHistory> =
+        Editor::new().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    editor.set_helper(Some(CliHelper));
+
+    loop {
+        let line = match editor.readline("airsim> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(e) => {
+                eprintln!("error: {e}");
+                break;
+            }
+        };
+        let _ = editor.add_history_entry(line.as_str());
+
+        match Command::parse(&line) {
+            Ok(Some(Command::Quit)) => break,
+            Ok(Some(command)) => {
+                if let Err(e) = task::block_on(command.run(&client)) {
+                    eprintln!("error: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    task::block_on(client.enable_api_control(false))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use airsim_client::ImageType;
+
+    use super::Command;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse("  "), Ok(None));
+        assert_eq!(Command::parse("takeoff"), Ok(Some(Command::Takeoff)));
+        assert_eq!(
+            Command::parse("goto 10 -5.5 -20 3"),
+            Ok(Some(Command::Goto {
+                x: 10.0,
+                y: -5.5,
+                z: -20.0,
+                velocity: 3.0,
+            }))
+        );
+        assert_eq!(
+            Command::parse("img front_center depth_vis out.png"),
+            Ok(Some(Command::Image {
+                camera: "front_center".to_owned(),
+                image_type: ImageType::DepthVis,
+                path: "out.png".to_owned(),
+            }))
+        );
+        assert!(Command::parse("goto 1 2").unwrap_err().starts_with("usage: goto"));
+        assert!(Command::parse("goto 1 2 x 4").is_err());
+        assert!(Command::parse("fly").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::NetworkResult;
+use crate::{ImageRequest, ImageRequests, ImageResponse, MultiRotorClient, NetworkError};
+
+/// Latest response to a request, with the time its RPC was sent
+type Frame = Option<(Instant, Arc<ImageResponse>)>;
+
+#[derive(Clone)]
+/// Cache of the latest image per request, shared by the consumers of the same cameras
+///
+/// A request asked for again within `max_age` of the RPC which captured its image gets that image instead of a new
+/// one, and consumers asking for the same request while it's in flight wait for its response, so e.g. detection,
+/// tracking and recording modules of one process all reading the scene of the same camera cost one RPC per frame.
+/// Requests differing in anything, e.g. compression, are cached apart. Clones share the cache.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use airsim_client::camera::FrameCache;
+/// use airsim_client::{ImageRequest, MultiRotorClient, NetworkResult};
+///
+/// # async fn run(client: &MultiRotorClient) -> NetworkResult<()> {
+/// let frames = FrameCache::new(client, Duration::from_millis(20));
+/// let scene = ImageRequest::scene("front_center");
+/// let (detection, tracking) = futures::join!(frames.get(&scene), frames.get(&scene));
+/// assert_eq!(detection?.time_stamp, tracking?.time_stamp);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FrameCache {
+    client: MultiRotorClient,
+    max_age: Duration,
+    frames: Arc<Mutex<HashMap<ImageRequest, Arc<async_std::sync::Mutex<Frame>>>>>,
+}
+
+impl FrameCache {
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle carrying the cameras, the cache shares its connection
+    ///     max_age (Duration): How long after its RPC was sent an image is served from the cache
+    pub fn new(client: &MultiRotorClient, max_age: Duration) -> Self {
+        Self {
+            client: client.clone(),
+            max_age,
+            frames: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// How long after its RPC was sent an image is served from the cache
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Image of `request`, from the cache if it's recent enough
+    ///
+    /// args:
+    ///     request (&ImageRequest): Image to capture
+    pub async fn get(&self, request: &ImageRequest) -> NetworkResult<Arc<ImageResponse>> {
+        let frame = Arc::clone(self.frames.lock().unwrap().entry(request.clone()).or_default());
+
+        // held during the RPC, so concurrent consumers of the request wait for its image rather than send their own
+        let mut frame = frame.lock().await;
+        if let Some((sent, image)) = frame.as_ref() {
+            if sent.elapsed() <= self.max_age {
+                return Ok(Arc::clone(image));
+            }
+        }
+
+        let sent = Instant::now();
+        let image = self
+            .client
+            .sim_get_images(ImageRequests(vec![request.clone()]), None)
+            .await?
+            .into_iter()
+            .next()
+            .map(Arc::new)
+            .ok_or_else(|| NetworkError::UnexpectedResponse {
+                method: "simGetImages".into(),
+                message: format!("no image for camera {}", request.camera_name),
+            })?;
+        *frame = Some((sent, Arc::clone(&image)));
+        Ok(image)
+    }
+
+    /// Drop every cached image, the next request of each sends an RPC
+    pub fn clear(&self) {
+        self.frames.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::camera::FrameCache;
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{ImageRequest, MultiRotorClient};
+
+    fn image_response(time_stamp: u64) -> Value {
+        let vector = Value::Map(vec![
+            (Value::from("x_val"), Value::F32(0.0)),
+            (Value::from("y_val"), Value::F32(0.0)),
+            (Value::from("z_val"), Value::F32(0.0)),
+        ]);
+        let quaternion = Value::Map(vec![
+            (Value::from("w_val"), Value::F32(1.0)),
+            (Value::from("x_val"), Value::F32(0.0)),
+            (Value::from("y_val"), Value::F32(0.0)),
+            (Value::from("z_val"), Value::F32(0.0)),
+        ]);
+        Value::Array(vec![Value::Map(vec![
+            (Value::from("image_data_uint8"), Value::Binary(vec![1, 2, 3])),
+            (Value::from("image_data_float"), Value::Array(vec![])),
+            (Value::from("camera_name"), Value::from("0")),
+            (Value::from("camera_position"), vector),
+            (Value::from("camera_orientation"), quaternion),
+            (Value::from("time_stamp"), Value::from(time_stamp)),
+            (Value::from("message"), Value::from("")),
+            (Value::from("pixels_as_float"), Value::Boolean(false)),
+            (Value::from("compress"), Value::Boolean(true)),
+            (Value::from("width"), Value::from(1)),
+            (Value::from("height"), Value::from(1)),
+            (Value::from("image_type"), Value::from(0)),
+        ])])
+    }
+
+    #[test]
+    fn test_shares_recent_frames() {
+        let server = MockAirsimServer::start().unwrap();
+        let mut frames = 0;
+        server.respond_with("simGetImages", move |_| {
+            frames += 1;
+            Ok(image_response(frames))
+        });
+
+        async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            let cache = FrameCache::new(&client, Duration::from_secs(60));
+            let scene = ImageRequest::scene("0");
+
+            let shared = cache.clone();
+            let (first, second) = futures::join!(cache.get(&scene), shared.get(&scene));
+            assert_eq!((first.unwrap().time_stamp, second.unwrap().time_stamp), (1, 1));
+            assert_eq!(
+                cache
+                    .get(&ImageRequest::scene_uncompressed("0"))
+                    .await
+                    .unwrap()
+                    .time_stamp,
+                2
+            );
+
+            cache.clear();
+            assert_eq!(cache.get(&scene).await.unwrap().time_stamp, 3);
+        });
+        assert_eq!(server.calls("simGetImages"), 3);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{CameraInfo, Vector3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Pinhole model of an AirSim camera
+///
+/// Points are expressed in the camera frame used by AirSim: x forward along the optical axis, y right and z down.
+/// Pixel coordinates have their origin in the top left corner of the image, u pointing right and v pointing down.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intrinsics {
+    /// focal length along u in pixels
+    pub fx: f32,
+    /// focal length along v in pixels
+    pub fy: f32,
+    /// principal point along u in pixels
+    pub cx: f32,
+    /// principal point along v in pixels
+    pub cy: f32,
+    /// image width in pixels
+    pub width: u32,
+    /// image height in pixels
+    pub height: u32,
+}
+
+impl Intrinsics {
+    /// Intrinsics of a camera with square pixels and the principal point in the image center
+    ///
+    /// args:
+    ///     fov_degrees (f32): Horizontal field of view in degrees
+    ///     width (u32): Image width in pixels
+    ///     height (u32): Image height in pixels
+    pub fn from_fov(fov_degrees: f32, width: u32, height: u32) -> Self {
+        let fx = width as f32 / 2.0 / (fov_degrees.to_radians() / 2.0).tan();
+
+        Self {
+            fx,
+            fy: fx,
+            cx: width as f32 / 2.0,
+            cy: height as f32 / 2.0,
+            width,
+            height,
+        }
+    }
+
+    /// Intrinsics of the camera described by `camera_info`, for images of `width` x `height` pixels
+    ///
+    /// The image size isn't part of `CameraInfo`, use the size of an image captured by the camera,
+    /// e.g. `ImageResponse::width` and `ImageResponse::height`
+    pub fn from_camera_info(camera_info: &CameraInfo, width: u32, height: u32) -> Self {
+        Self::from_fov(camera_info.fov, width, height)
+    }
+
+    /// Pixel coordinates (u, v) a point in the camera frame is imaged at
+    ///
+    /// Returns None for points behind the camera. The pixel may lie outside of the image.
+    pub fn project(&self, point: Vector3) -> Option<(f32, f32)> {
+        if point.x <= 0.0 {
+            return None;
+        }
+
+        Some((
+            self.cx + self.fx * point.y / point.x,
+            self.cy + self.fy * point.z / point.x,
+        ))
+    }
+
+    /// Point in the camera frame imaged at pixel (u, v), at `depth` meters along the optical axis
+    ///
+    /// `depth` is the planar depth as in `ImageType::DepthPlanar` images
+    pub fn unproject(&self, u: f32, v: f32, depth: f32) -> Vector3 {
+        Vector3::new(depth, (u - self.cx) * depth / self.fx, (v - self.cy) * depth / self.fy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::camera::Intrinsics;
+    use crate::Vector3;
+
+    #[test]
+    fn test_from_fov() {
+        let intrinsics = Intrinsics::from_fov(90.0, 640, 480);
+        assert!((intrinsics.fx - 320.0).abs() < 1e-3);
+        assert_eq!(intrinsics.fx, intrinsics.fy);
+        assert_eq!((intrinsics.cx, intrinsics.cy), (320.0, 240.0));
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let intrinsics = Intrinsics::from_fov(90.0, 640, 480);
+        let point = Vector3::new(4.0, -1.0, 0.5);
+
+        let (u, v) = intrinsics.project(point).unwrap();
+        let back = intrinsics.unproject(u, v, point.x);
+        assert!((back.y - point.y).abs() < 1e-4);
+        assert!((back.z - point.z).abs() < 1e-4);
+
+        assert!(intrinsics.project(Vector3::new(-1.0, 0.0, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{Pose3, Position3, Quaternionr, Vector3};
+
+/// Pose of a vehicle camera pointing at `target`, relative to the vehicle as expected by `sim_set_camera_pose`
+///
+/// The camera keeps its mounting position and only turns, with no roll relative to the vehicle. All poses and the
+/// target have to be in the same frame, e.g. the frame of the vehicle's starting point
+///
+/// args:
+///     vehicle (&Pose3): pose of the vehicle
+///     camera (&Pose3): current pose of the camera, e.g. `CameraInfo::pose`
+///     target (Vector3): point to look at
+pub fn look_at(vehicle: &Pose3, camera: &Pose3, target: Vector3) -> Pose3 {
+    let to_vehicle = vehicle.inverse();
+    let position = to_vehicle.transform_point(Vector3::from(camera.position));
+    let target = to_vehicle.transform_point(target);
+
+    let (dx, dy, dz) = (target.x - position.x, target.y - position.y, target.z - position.z);
+    let yaw = dy.atan2(dx);
+    // NED: a target below the camera (positive dz) needs a negative pitch
+    let pitch = (-dz).atan2(dx.hypot(dy));
+
+    Pose3::new(
+        Position3::from(position),
+        Quaternionr::from_euler(0.0, pitch, yaw).into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::camera::look_at;
+    use crate::{Pose3, Position3, Quaternionr, Vector3};
+
+    #[test]
+    fn test_look_at_target_ahead_and_below() {
+        // vehicle 10 m up and turned to the east, camera 0.5 m in front of it
+        let vehicle = Pose3::new(
+            Position3::new(0.0, 0.0, -10.0),
+            Quaternionr::from_euler(0.0, 0.0, std::f32::consts::FRAC_PI_2).into(),
+        );
+        let camera = Pose3::new(Position3::new(0.0, 0.5, -10.0), vehicle.orientation);
+
+        let pose = look_at(&vehicle, &camera, Vector3::new(0.0, 10.5, 0.0));
+        assert!((pose.position.x - 0.5).abs() < 1e-5 && pose.position.y.abs() < 1e-5);
+
+        // 10 m ahead and 10 m below the camera
+        let (roll, pitch, yaw) = Quaternionr::from(pose.orientation).to_euler_angles();
+        assert!(roll.abs() < 1e-5 && yaw.abs() < 1e-5);
+        assert!((pitch + std::f32::consts::FRAC_PI_4).abs() < 1e-5);
+
+        // the optical axis goes through the target
+        let world = vehicle.compose(&pose);
+        let axis = Quaternionr::from(world.orientation).rotate_vector(Vector3::new(1.0, 0.0, 0.0));
+        assert!(axis.x.abs() < 1e-5 && (axis.y - axis.z).abs() < 1e-5);
+    }
+}
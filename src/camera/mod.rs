@@ -0,0 +1,9 @@
+mod frame_cache;
+mod intrinsics;
+mod look_at;
+mod point_cloud;
+
+pub use frame_cache::FrameCache;
+pub use intrinsics::Intrinsics;
+pub use look_at::look_at;
+pub use point_cloud::{PointCloud, PointCloudFrame};
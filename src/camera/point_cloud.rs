@@ -0,0 +1,115 @@
+use crate::{FloatImage, ImageResponse, Pose3, Position3, Quaternionr, Vector3};
+
+use super::Intrinsics;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Frame the points of a `PointCloud` are expressed in
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointCloudFrame {
+    /// camera frame: x forward along the optical axis, y right and z down
+    Camera,
+    /// world NED frame, using the pose of the camera
+    World,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Points reconstructed from a depth image
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointCloud(pub Vec<Vector3>);
+
+impl PointCloud {
+    /// Reconstruct the points seen in a planar depth image
+    ///
+    /// Pixels without a finite, positive depth are skipped
+    ///
+    /// args:
+    ///     depth (&FloatImage): `ImageType::DepthPlanar` image, in meters
+    ///     intrinsics (&Intrinsics): Pinhole model of the camera that captured the image
+    ///     camera_pose (&Pose3): Pose of the camera in the world frame when the image was captured
+    ///     frame (PointCloudFrame): Frame to express the points in
+    pub fn from_depth(
+        depth: &FloatImage,
+        intrinsics: &Intrinsics,
+        camera_pose: &Pose3,
+        frame: PointCloudFrame,
+    ) -> Self {
+        let orientation = Quaternionr::from(camera_pose.orientation);
+        let position = camera_pose.position;
+
+        let mut points = Vec::with_capacity(depth.data.len());
+        for v in 0..depth.height {
+            for u in 0..depth.width {
+                let d = match depth.get(u, v) {
+                    Some(d) if d.is_finite() && d > 0.0 => d,
+                    _ => continue,
+                };
+
+                // sample the center of the pixel
+                let point = intrinsics.unproject(u as f32 + 0.5, v as f32 + 0.5, d);
+                let point = match frame {
+                    PointCloudFrame::Camera => point,
+                    PointCloudFrame::World => {
+                        let rotated = orientation.rotate_vector(point);
+                        Vector3::new(rotated.x + position.x, rotated.y + position.y, rotated.z + position.z)
+                    }
+                };
+                points.push(point);
+            }
+        }
+
+        Self(points)
+    }
+
+    /// Reconstruct the points of a `DepthPlanar` response requested with `pixels_as_float`, using the camera pose
+    /// of the response
+    ///
+    /// Returns None if the response doesn't hold a float image
+    pub fn from_depth_response(
+        response: &ImageResponse,
+        intrinsics: &Intrinsics,
+        frame: PointCloudFrame,
+    ) -> Option<Self> {
+        let depth = FloatImage::from_response(response)?;
+        let camera_pose = Pose3::new(
+            Position3::new(
+                response.camera_position.x,
+                response.camera_position.y,
+                response.camera_position.z,
+            ),
+            response.camera_orientation,
+        );
+
+        Some(Self::from_depth(&depth, intrinsics, &camera_pose, frame))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::camera::{Intrinsics, PointCloud, PointCloudFrame};
+    use crate::{FloatImage, Pose3, Position3, Quaternion};
+
+    #[test]
+    fn test_from_depth_world_frame() {
+        let depth = FloatImage {
+            width: 2,
+            height: 2,
+            data: vec![2.0, f32::INFINITY, 2.0, 0.0],
+        };
+        let intrinsics = Intrinsics::from_fov(90.0, 2, 2);
+        let camera_pose = Pose3::new(Position3::new(10.0, 0.0, -5.0), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+
+        let cloud = PointCloud::from_depth(&depth, &intrinsics, &camera_pose, PointCloudFrame::World);
+        assert_eq!(cloud.len(), 2);
+        assert!(cloud.0.iter().all(|p| (p.x - 12.0).abs() < 1e-5));
+        assert!((cloud.0[0].z - (-5.0 - 1.0)).abs() < 1e-5);
+        assert!((cloud.0[1].z - (-5.0 + 1.0)).abs() < 1e-5);
+    }
+}
@@ -1,76 +1,326 @@
+use async_std::channel::{unbounded, Receiver, Sender};
 use async_std::net::ToSocketAddrs;
 use msgpack_rpc::{
     message::{Request, Response},
     Utf8String,
 };
 use rmpv::Value;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use crate::{
+    camera,
+    clients::batch::RpcBatch,
     error::NetworkResult,
-    types::{environment::EnvironmentState, geopoint::GeoPoint, pose::Pose3},
-    CompressedImage, ImageRequests, ImageType, MsgPackClient, NetworkError, SceneObjects, Vector3, WeatherParameter,
+    replay::RpcRecorder,
+    types::{
+        environment::EnvironmentState,
+        geopoint::GeoPoint,
+        pose::Pose3,
+        retry_policy::{CONTINUE_FOR_FRAMES, CONTINUE_FOR_TIME, SPAWN_OBJECT},
+    },
+    CameraInfo, CollisionInfo, Color, CompressedImage, DetectionInfo, DistortionParams, FloatEncoding, ImageRequests,
+    ImageResponse, ImageType, KinematicsState, MeshData, Middleware, MsgPackClient, NetworkError, PlotDuration,
+    RetryPolicy, SceneObjects, Snapshot, StereoPair, TimeOfDay, Vector3, WeatherParameter, Wrench,
 };
+#[cfg(feature = "serde")]
+use crate::{AirsimSettings, ClockType, SensorInfo};
 
+/// RPCs of the maneuvers tracked by `wait_on_last_task`, their last param is the name of the vehicle
+const TASK_RPCS: [&str; 22] = [
+    "takeoff",
+    "land",
+    "goHome",
+    "hover",
+    "moveByVelocityBodyFrame",
+    "moveByVelocityZBodyFrame",
+    "moveByVelocity",
+    "moveByVelocityZ",
+    "moveOnPath",
+    "moveToGPS",
+    "moveToPosition",
+    "moveToZ",
+    "moveByManual",
+    "moveByMotorPWMs",
+    "rotateToYaw",
+    "rotateByYawRate",
+    "moveByRollPitchYawZ",
+    "moveByRollPitchYawThrottle",
+    "moveByRollPitchYawrateThrottle",
+    "moveByRollPitchYawrateZ",
+    "moveByAngleRatesZ",
+    "moveByAngleRatesThrottle",
+];
+
+/// Connection to the AirSim RPC server
+///
+/// The client can be shared between tasks, e.g. behind an `Arc`, and RPCs sent concurrently are pipelined on the
+/// single connection: each request is tagged with a msgpack-rpc id and gets the response with the same id as soon
+/// as it arrives. Requests are written in the order the RPCs are called but the server may answer them in any order,
+/// so a slow `simGetImages` doesn't hold back a control RPC sent after it. Await an RPC before calling the next one
+/// when they have to run in order.
 pub struct AirsimClient {
     client: MsgPackClient,
     last_request_id: AtomicU32,
+    retry_policy: RwLock<RetryPolicy>,
+    middlewares: RwLock<Vec<Arc<dyn Middleware>>>,
+    float_encoding: FloatEncoding,
+    /// last maneuver of each vehicle, the channel is closed once its RPC returned
+    last_tasks: Mutex<HashMap<String, Receiver<()>>>,
 }
 
 impl AirsimClient {
+    /// Connects with the default settings and enables API control of `vehicle_name`, see `AirsimClientBuilder`
+    /// for timeouts, retries and the settings of the socket
     pub async fn connect(addrs: impl ToSocketAddrs, vehicle_name: &str) -> NetworkResult<Self> {
+        let client = MsgPackClient::connect(addrs).await?;
+        Self::open(
+            client,
+            Some(vehicle_name),
+            FloatEncoding::default(),
+            RetryPolicy::disabled(),
+        )
+        .await
+    }
+
+    /// Client over an established connection, checked with a ping
+    ///
+    /// args:
+    ///     client (MsgPackClient): Connection to the AirSim RPC server
+    ///     vehicle_name (Option<&str>): Name of the vehicle to enable API control of, `None` to leave it as it is
+    ///     float_encoding (FloatEncoding): Width of the floats in the params
+    ///     retry_policy (RetryPolicy): Retry policy of the RPCs, including the ping
+    pub(crate) async fn open(
+        client: MsgPackClient,
+        vehicle_name: Option<&str>,
+        float_encoding: FloatEncoding,
+        retry_policy: RetryPolicy,
+    ) -> NetworkResult<Self> {
         let airsim = Self {
             last_request_id: AtomicU32::new(0),
-            client: MsgPackClient::connect(addrs).await?,
+            client,
+            retry_policy: RwLock::new(retry_policy),
+            middlewares: RwLock::new(vec![]),
+            float_encoding,
+            last_tasks: Mutex::new(HashMap::new()),
         };
         airsim.ping().await?;
-        airsim.enable_api_control(true, Some(vehicle_name)).await?;
+        if let Some(vehicle_name) = vehicle_name {
+            airsim.enable_api_control(true, Some(vehicle_name)).await?;
+        }
         Ok(airsim)
     }
 
-    #[allow(deprecated)]
+    /// Closes the connection, after disabling API control if asked to
+    ///
+    /// RPCs still in flight fail with `NetworkError::Recv` and later ones with `NetworkError::Send`, also on the
+    /// vehicle clients sharing the connection. Returns once the socket is released. Dropping the client closes the
+    /// connection as well, without waiting and without disabling API control
+    ///
+    /// args:
+    ///     disable_api_control (bool): Whether to disable API control before closing
+    ///     vehicle_name (Option<&str>): Name of the vehicle to release
+    pub async fn close(&self, disable_api_control: bool, vehicle_name: Option<&str>) -> NetworkResult<()> {
+        // close even if the server is gone, then report why API control is still enabled
+        let released = if disable_api_control {
+            self.enable_api_control(false, vehicle_name).await.map(|_| ())
+        } else {
+            Ok(())
+        };
+        self.client.close().await;
+        released
+    }
+
+    /// Ids only have to be unique among the requests in flight, so they wrap around on overflow
     fn new_request_id(&self) -> u32 {
-        self.last_request_id
-            // TODO: method below is deprecated
-            .compare_and_swap(u32::max_value(), 0, Ordering::AcqRel);
-        self.last_request_id.fetch_add(1, Ordering::AcqRel)
+        self.last_request_id.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Sends an RPC, retrying it according to the retry policy of the client
     pub(crate) async fn unary_rpc(&self, method: String, params: Option<Vec<Value>>) -> NetworkResult<Response> {
-        self.client
+        self.rpc(method, params, None).await
+    }
+
+    /// Sends an RPC addressed to a vehicle, retrying it according to the retry policy of the client
+    ///
+    /// The vehicle name is used to trace the RPC and to track the last task of the vehicle, its position in the
+    /// params differs between RPCs
+    pub(crate) async fn vehicle_rpc(
+        &self,
+        method: String,
+        params: Option<Vec<Value>>,
+        vehicle_name: &str,
+    ) -> NetworkResult<Response> {
+        self.rpc(method, params, Some(vehicle_name)).await
+    }
+
+    async fn rpc(
+        &self,
+        method: String,
+        params: Option<Vec<Value>>,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<Response> {
+        // inside `RetryPolicy::run` its policy applies instead, retrying here too would multiply the attempts
+        let policy = if RetryPolicy::is_idempotent(&method) && !RetryPolicy::is_overridden() {
+            self.retry_policy()
+        } else {
+            RetryPolicy::disabled()
+        };
+        self.unary_rpc_with_policy(method, params, vehicle_name, policy).await
+    }
+
+    /// Sends an RPC retried according to `policy` instead of the policy of the client
+    pub(crate) async fn unary_rpc_with_policy(
+        &self,
+        method: String,
+        params: Option<Vec<Value>>,
+        vehicle_name: Option<&str>,
+        policy: RetryPolicy,
+    ) -> NetworkResult<Response> {
+        let params = params.unwrap_or_default();
+
+        // dropped when the RPC returns or is cancelled, which wakes up `wait_on_last_task`
+        let _task = TASK_RPCS
+            .contains(&method.as_str())
+            .then(|| self.start_task(vehicle_name.unwrap_or_default()));
+
+        policy.run(|| self.send_rpc(&method, &params, vehicle_name)).await
+    }
+
+    /// Registers a maneuver as the last task of its vehicle, until the returned sender is dropped
+    fn start_task(&self, vehicle_name: &str) -> Sender<()> {
+        let (sender, receiver) = unbounded();
+        self.last_tasks
+            .lock()
+            .unwrap()
+            .insert(vehicle_name.to_owned(), receiver);
+        sender
+    }
+
+    async fn send_rpc(
+        &self,
+        method: &str,
+        params: &[Value],
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))] vehicle_name: Option<&str>,
+    ) -> NetworkResult<Response> {
+        let middlewares = self.middlewares.read().unwrap().clone();
+        let mut params = params.to_vec();
+        for middleware in &middlewares {
+            middleware.on_request(method, &mut params).await;
+        }
+        self.float_encoding.apply(&mut params);
+
+        #[cfg(feature = "tracing")]
+        let trace = RpcTrace::start(method, vehicle_name);
+        let started = Instant::now();
+
+        let response = self
+            .client
             .request(Request {
                 id: self.new_request_id(),
-                method,
-                params: params.unwrap_or_default(),
+                method: method.to_owned(),
+                params,
             })
-            .await
+            .await;
+
+        #[cfg(feature = "tracing")]
+        trace.finish(&response);
+
+        let response = match response {
+            Ok(Response { result: Err(raw), .. }) => Err(NetworkError::Rpc {
+                method: method.to_owned(),
+                message: raw.as_str().map(str::to_owned).unwrap_or_else(|| raw.to_string()),
+                raw,
+            }),
+            response => response,
+        };
+
+        let latency = started.elapsed();
+        for middleware in &middlewares {
+            match &response {
+                Ok(Response { result: Ok(result), .. }) => middleware.on_response(method, result, latency),
+                Ok(_) => {}
+                Err(e) => middleware.on_error(method, e, latency),
+            }
+        }
+        response
+    }
+
+    /// Adds a middleware, run around every RPC sent by the client after the middlewares added before
+    ///
+    /// Vehicle clients sharing the connection, like the members of a `Swarm`, share the middlewares as well
+    ///
+    /// args:
+    ///     middleware (impl Middleware): Hooks to run
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.middlewares.write().unwrap().push(Arc::new(middleware));
+    }
+
+    /// Appends every request and response of the client to an RPC log, see `replay::RpcRecorder`
+    ///
+    /// args:
+    ///     path (impl AsRef<Path>): Path of the log, an existing file is replaced
+    pub fn with_recorder(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        self.add_middleware(RpcRecorder::create(path)?);
+        Ok(self)
+    }
+
+    /// Width of the floats in the params of the RPCs sent by the client
+    pub fn float_encoding(&self) -> FloatEncoding {
+        self.float_encoding
+    }
+
+    /// Queue RPCs to send back to back without awaiting in between, see `RpcBatch`
+    pub fn batch(&self) -> RpcBatch<'_> {
+        RpcBatch::new(self)
+    }
+
+    /// Retry policy applied to every RPC sent by the client
+    pub fn retry_policy(&self) -> RetryPolicy {
+        *self.retry_policy.read().unwrap()
+    }
+
+    /// Sets the retry policy applied to every RPC sent by the client, by default RPCs are not retried
+    ///
+    /// Vehicle clients sharing the connection, like the members of a `Swarm`, share the policy as well.
+    /// RPCs that must not run twice, like `simSpawnObject`, are never retried
+    ///
+    /// args:
+    ///     policy (RetryPolicy): When and how often to retry failed RPCs
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = policy;
     }
 
-    /// Get client version
-    fn get_client_version() -> u64 {
+    /// Version of the AirSim RPC API implemented by this client
+    pub fn get_client_version() -> u64 {
         1
     }
 
     /// Get AirSim server version
-    async fn get_server_version(&self) -> NetworkResult<u64> {
-        self.unary_rpc("getServerVersion".to_owned(), None).await.map(|res| {
-            res.result
-                .unwrap_or_else(|_| rmpv::Value::Integer(0.into()))
-                .as_u64()
-                .unwrap_or(0)
-        })
+    ///
+    /// Servers too old to know the version RPCs report version 0
+    pub async fn get_server_version(&self) -> NetworkResult<u64> {
+        Self::version_or_zero(self.unary_rpc("getServerVersion".to_owned(), None).await)
     }
 
-    /// Get minimum required client version
-    async fn get_min_required_client_version(&self) -> NetworkResult<u64> {
-        self.unary_rpc("getMinRequiredClientVersion".to_owned(), None)
-            .await
-            .map(|res| {
-                res.result
-                    .unwrap_or_else(|_| rmpv::Value::Integer(0.into()))
-                    .as_u64()
-                    .unwrap_or(0)
-            })
+    /// Get minimum client version required by the server
+    ///
+    /// Servers too old to know the version RPCs report version 0
+    pub async fn get_min_required_client_version(&self) -> NetworkResult<u64> {
+        Self::version_or_zero(self.unary_rpc("getMinRequiredClientVersion".to_owned(), None).await)
+    }
+
+    /// Servers too old to know the version RPCs report version 0
+    fn version_or_zero(response: NetworkResult<Response>) -> NetworkResult<u64> {
+        match response {
+            Ok(res) => Ok(res.result.ok().and_then(|version| version.as_u64()).unwrap_or(0)),
+            Err(NetworkError::Rpc { .. }) => Ok(0),
+            Err(e) => Err(e),
+        }
     }
 
     #[inline]
@@ -78,6 +328,41 @@ impl AirsimClient {
         Self::get_client_version()
     }
 
+    /// Checks that the server and this client support each other's version
+    ///
+    /// Returns `NetworkError::Incompatible` naming the side to upgrade, rather than the decode errors of RPCs whose
+    /// arguments or results changed between versions
+    pub async fn check_compatibility(&self) -> NetworkResult<()> {
+        let client_version = Self::get_client_version();
+        let client_min_version = self.get_min_required_client_version().await?;
+        let server_version = self.get_server_version().await?;
+        let server_min_version = Self::get_min_required_server_version();
+
+        log::info!(
+            "Client version: {} , Min required: {} ",
+            client_version,
+            client_min_version
+        );
+        log::info!(
+            "Server version: {} , Min required: {} ",
+            server_version,
+            server_min_version
+        );
+
+        let outdated = if server_version < server_min_version {
+            "server"
+        } else if client_version < client_min_version {
+            "client"
+        } else {
+            return Ok(());
+        };
+        Err(NetworkError::Incompatible {
+            outdated,
+            client_version,
+            server_version,
+        })
+    }
+
     /// Reset the vehicle to its original starting state
     ///
     /// Note that you must call `enable_api_control` and `arm_disarm` again after the call to reset
@@ -99,28 +384,87 @@ impl AirsimClient {
     }
 
     /// Checks state of the connection
+    ///
+    /// Logs a warning if the versions of the client and server are incompatible, see `check_compatibility` to
+    /// fail instead
     pub async fn confirm_connection(&self) -> NetworkResult<bool> {
         let connected = self.ping().await?;
 
         log::info!("Connected to Airsim: {}", connected);
 
-        let client_v = Self::get_client_version();
-        let client_min_v = self.get_min_required_client_version().await?;
-        let server_v = self.get_server_version().await?;
-        let server_min_v = Self::get_min_required_server_version();
-
-        log::info!("Client version: {} , Min required: {} ", client_v, client_min_v);
-        log::info!("Server version: {} , Min required: {} ", server_v, server_min_v);
-
-        if server_v < server_min_v {
-            log::error!("AirSim server is of older version and not supported by this client. Please upgrade!")
-        } else if client_v < client_min_v {
-            log::error!("AirSim client is of older version and not supported by this server. Please upgrade!")
+        match self.check_compatibility().await {
+            Err(e @ NetworkError::Incompatible { .. }) => log::warn!("{e}, please upgrade!"),
+            result => result?,
         }
 
         Ok(connected)
     }
 
+    /// Returns the content of the settings.json AirSim is currently running with
+    pub async fn get_settings_string(&self) -> NetworkResult<String> {
+        self.unary_rpc("getSettingsString".into(), None)
+            .await
+            .map(|response| response.result.unwrap())
+            .map(|val| val.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Returns the vehicles, cameras and sensors defined in the settings.json AirSim is currently running with
+    #[cfg(feature = "serde")]
+    pub async fn get_settings(&self) -> NetworkResult<AirsimSettings> {
+        let settings = self.get_settings_string().await?;
+        AirsimSettings::from_json(&settings).map_err(|e| NetworkError::Settings { message: e.to_string() })
+    }
+
+    /// Returns the sensors of a vehicle, as defined by the settings.json AirSim is currently running with
+    ///
+    /// AirSim has no RPC to enable or disable a sensor at runtime, a disabled sensor stays disabled until the
+    /// settings change and AirSim restarts. Check `SensorInfo::enabled` before reading a sensor
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle, defaults to the only vehicle of the settings
+    #[cfg(feature = "serde")]
+    pub async fn sim_list_sensors(&self, vehicle_name: Option<&str>) -> NetworkResult<Vec<SensorInfo>> {
+        self.get_settings()
+            .await
+            .map(|settings| settings.vehicle_sensors(vehicle_name.unwrap_or("")))
+    }
+
+    /// Returns the clock the simulation runs with, as set in settings.json or picked by AirSim
+    #[cfg(feature = "serde")]
+    pub async fn sim_get_clock_type(&self) -> NetworkResult<ClockType> {
+        self.get_settings().await.map(|settings| settings.clock_type())
+    }
+
+    /// Returns the `ClockSpeed` of the settings.json AirSim is currently running with, 1 if it isn't set
+    ///
+    /// This is the speed AirSim started with, not the current one: `sim_set_clock_speed` doesn't change it. Measure
+    /// the current speed with `MultiRotorClient::real_time_factor`. Needs the `serde` feature
+    #[cfg(feature = "serde")]
+    pub async fn configured_clock_speed(&self) -> NetworkResult<f32> {
+        self.get_settings()
+            .await
+            .map(|settings| settings.clock_speed.unwrap_or(1.0))
+    }
+
+    /// Sets the time dilation of Unreal with the `slomo` console command, which AirSim also runs at startup to apply
+    /// `ClockSpeed`
+    ///
+    /// AirSim has no RPC for its clock speed: the physics and rendering follow the new speed, but the scalable clock
+    /// stamping the vehicles and sensors keeps the `ClockSpeed` of settings.json. Measure the speed reached with
+    /// `MultiRotorClient::real_time_factor`. Returns True if the command was run, fails with
+    /// `NetworkError::InvalidArgument` on a speed which isn't positive and finite
+    ///
+    /// args:
+    ///     speed (f32): Speed relative to the wall clock, e.g. 2 for twice as fast
+    pub async fn sim_set_clock_speed(&self, speed: f32) -> NetworkResult<bool> {
+        if !(speed > 0.0 && speed.is_finite()) {
+            return Err(NetworkError::InvalidArgument {
+                message: format!("clock speed has to be positive and finite, got {speed}"),
+            });
+        }
+        self.sim_run_console_command(&format!("slomo {speed}")).await
+    }
+
     /// Pauses simulation
     ///
     /// args:
@@ -143,7 +487,7 @@ impl AirsimClient {
     /// args:
     ///     seconds (f64): Time to run the simulation for
     pub async fn sim_continue_for_time(&self, seconds: f64) -> NetworkResult<()> {
-        self.unary_rpc("simContinueFortime".into(), Some(vec![Value::F64(seconds)]))
+        self.unary_rpc(CONTINUE_FOR_TIME.into(), Some(vec![Value::F64(seconds)]))
             .await
             .map(|_| ())
     }
@@ -154,386 +498,2350 @@ impl AirsimClient {
     /// args:
     ///     frames (i64): Frames to run the simulation for
     pub async fn sim_continue_for_frames(&self, frames: i64) -> NetworkResult<()> {
-        self.unary_rpc("simContinueFortime".into(), Some(vec![Value::Integer(frames.into())]))
+        self.unary_rpc(CONTINUE_FOR_FRAMES.into(), Some(vec![Value::Integer(frames.into())]))
             .await
             .map(|_| ())
     }
 
-    /// Light Control APIs
-    /// For more documentation: https://github.com/microsoft/AirSim/blob/b272597854f389e03bf7d9b9581666c91f2e24f9/docs/apis.md#light-control-apis
+    /// Recording API
     ///
-    /// Change intensity of named light. This method should be called after a `sim_spawn_object()` call
-    ///
-    /// args:
-    ///     light_name (str): Name of light to change
-    ///     intensity (f32): New intensity value
-    pub async fn sim_set_light_intensity(&self, light_name: &str, intensity: f32) -> NetworkResult<bool> {
-        let light_name: Utf8String = light_name.into();
-
-        self.unary_rpc(
-            "simSetLightIntensity".into(),
-            Some(vec![Value::String(light_name), Value::F32(intensity)]),
-        )
-        .await
-        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    /// Start recording data (images and poses) to disk, as configured by the `Recording` settings.
+    /// Recording can also be toggled from the viewport with the `R` key
+    pub async fn start_recording(&self) -> NetworkResult<()> {
+        self.unary_rpc("startRecording".into(), None).await.map(|_| ())
     }
 
-    /// Change intensity of named light
+    /// Recording API
     ///
-    /// args:
-    ///     light_name (str): Name of light to change
-    ///     intensity (f32): New intensity value
-    pub async fn sim_list_scene_objects(&self, name_regex: &str) -> NetworkResult<SceneObjects> {
-        let name_regex: Utf8String = name_regex.into();
-
-        self.unary_rpc("simListSceneObjects".into(), Some(vec![Value::String(name_regex)]))
-            .await
-            .map(SceneObjects::from)
+    /// Stop recording data to disk
+    pub async fn stop_recording(&self) -> NetworkResult<()> {
+        self.unary_rpc("stopRecording".into(), None).await.map(|_| ())
     }
 
-    /// The position inside the returned Pose is in the world frame
+    /// Recording API
     ///
-    /// args:
-    ///     object_name (&str): Object to get the Pose (Position3) of
-    pub async fn sim_get_object_pose(&self, name_regex: &str) -> NetworkResult<Pose3> {
-        let name_regex: Utf8String = name_regex.into();
-
-        self.unary_rpc("simGetObjectPose".into(), Some(vec![Value::String(name_regex)]))
+    /// Returns True if recording is currently in progress
+    pub async fn is_recording(&self) -> NetworkResult<bool> {
+        self.unary_rpc("isRecording".into(), None)
             .await
-            .map(Pose3::from)
+            .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
 
-    /// Removes selected object from the world
+    /// Runs an Unreal console command, e.g. `stat fps` or `r.ScreenPercentage 50`
     ///
-    /// Returns True if object is queued for removal
+    /// Returns True if the command was run
     ///
     /// args:
-    ///     object_name (&str): Name of object to be removed
-    pub async fn sim_destroy_object(&self, name_regex: &str) -> NetworkResult<bool> {
-        let name_regex: Utf8String = name_regex.into();
+    ///     command (&str): Console command to run
+    pub async fn sim_run_console_command(&self, command: &str) -> NetworkResult<bool> {
+        let command: Utf8String = command.into();
 
-        self.unary_rpc("simDestroyObject".into(), Some(vec![Value::String(name_regex)]))
+        self.unary_rpc("simRunConsoleCommand".into(), Some(vec![Value::String(command)]))
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
 
-    /// Spawned selected object in the world
+    /// Debug API
     ///
-    /// NOTE!!: This method currently crashes the AirSim application
+    /// Prints the specified message in the simulator's window.
     ///
-    /// Returns name of spawned object, in case it had to be modified
+    /// If `message_param` is supplied, then it's printed next to the message and in that case if this API is called
+    /// with same message value but different `message_param` again then previous line is overwritten with new line
+    /// (instead of API creating new line on display).
     ///
     /// args:
-    ///     object_name (&str): Name of object to be removed
-    ///     asset_name (&str): Name of asset(mesh) in the project database: PointLightBP or SpotLightBP
-    ///     pose (Pose3): Desired pose of object
-    ///     scale (Vector3): Desired scale of object
-    ///     physics_enabled (Option<bool>): Whether to enable physics for the object
-    ///     is_blueprint (Option<bool>): Whether to spawn a blueprint or an actor
-    #[allow(clippy::too_many_arguments)]
-    pub async fn sim_spawn_object(
+    ///     message (&str): Message to be printed
+    ///     message_param (Option<&str>): Parameter to be printed next to the message
+    ///     severity (Option<u32>): Range 0-3, inclusive, corresponding to the severity of the message
+    pub async fn sim_print_log_message(
         &self,
-        name_regex: &str,
-        asset_name: &str,
-        pose: Pose3,
-        scale: Vector3,
-        physics_enabled: Option<bool>,
-        is_blueprint: Option<bool>,
-    ) -> NetworkResult<String> {
-        let name_regex: Utf8String = name_regex.into();
-        let asset_name: Utf8String = asset_name.into();
-        let physics_enabled = physics_enabled.unwrap_or(false);
-        let is_blueprint = is_blueprint.unwrap_or(false);
+        message: &str,
+        message_param: Option<&str>,
+        severity: Option<u32>,
+    ) -> NetworkResult<()> {
+        let message: Utf8String = message.into();
+        let message_param: Utf8String = message_param.unwrap_or("").into();
+        let severity = severity.unwrap_or(0);
 
         self.unary_rpc(
-            "simSpawnObject".into(),
+            "simPrintLogMessage".into(),
             Some(vec![
-                Value::String(name_regex),
-                Value::String(asset_name),
-                pose.as_msgpack(),
-                scale.as_msgpack(),
-                Value::Boolean(physics_enabled),
-                Value::Boolean(is_blueprint),
+                Value::String(message),
+                Value::String(message_param),
+                Value::Integer(severity.into()),
             ]),
         )
         .await
-        .map(|response| response.result.unwrap())
-        .map(|val| val.as_str().unwrap().to_string())
+        .map(|_| ())
     }
 
-    /// Runtime swap texture API
+    /// Debug API
     ///
-    /// Returns vector of objects which matched the provided tags and had the texture swap perfomed
-    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    /// Modify the color and thickness of the line traced behind the vehicle
+    ///
+    /// The trace is toggled in the simulator with the `T` key, a thickness of 0 hides the line while it's enabled
     ///
     /// args:
-    ///     tags (str): String of "," or ", " delimited tags to identify on which actors to perform the swap
-    ///     tex_id (Option<i32>): Indexes the array of textures assigned to each actor undergoing a swap
-    ///     component_id (Option<i32>): Id of the component
-    ///     material_id (Option<i32>): Id of the material
-    pub async fn sim_swap_textures(
+    ///     color_rgba ([f32; 4]): Desired RGBA values from 0.0 to 1.0
+    ///     thickness (f32): Thickness of the line
+    ///     vehicle_name (Option<&str>): Name of the vehicle to set the trace line of
+    pub async fn sim_set_trace_line(
         &self,
-        _tags: &str,
-        _tex_id: Option<i32>,
-        _component_id: Option<i32>,
-        _material_id: Option<i32>,
-    ) -> NetworkResult<Vec<String>> {
-        unimplemented!("todo")
+        color_rgba: [f32; 4],
+        thickness: f32,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<()> {
+        let [r, g, b, a] = color_rgba;
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simSetTraceLine".into(),
+            Some(vec![
+                Color::new(r, g, b, a).as_msgpack(),
+                Value::F32(thickness),
+                Value::String(vehicle_name.into()),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
     }
 
-    /// Runtime swap texture API
-    ///
-    /// Returns True if material was set
-    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    /// Debug API
     ///
-    /// args:
-    ///     object_name (&str): Name of the object to set material for
-    ///     material_name (&str): Name of the material to set for object
-    ///     component_id (Option<i32>): Id of the component
-    pub async fn sim_set_object_material(
-        &self,
-        _tags: &str,
-        _tex_id: Option<i32>,
-        _component_id: Option<i32>,
-        _material_id: Option<i32>,
-    ) -> NetworkResult<bool> {
-        unimplemented!("todo")
+    /// Clear any persistent markers - those plotted with `PlotDuration::Persistent`
+    pub async fn sim_flush_persistent_markers(&self) -> NetworkResult<()> {
+        self.unary_rpc("simFlushPersistentMarkers".into(), None)
+            .await
+            .map(|_| ())
     }
 
-    /// Runtime swap texture API
+    /// Debug API
     ///
-    /// Returns True if material was set
-    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    /// Plot a list of 3D points in World NED frame
     ///
     /// args:
-    ///     object_name (&str): Name of the object to set material for
-    ///     material_name (&str): Name of the material to set for object
-    ///     component_id (Option<i32>): Id of the component
-    pub async fn sim_set_object_material_from_texture(
+    ///     points (&[Vector3]): List of points to plot
+    ///     color (Color): Color of the points
+    ///     size (f32): Size of the points
+    ///     duration (PlotDuration): How long the points stay in the viewport
+    pub async fn sim_plot_points(
         &self,
-        _tags: &str,
-        _tex_id: Option<i32>,
-        _component_id: Option<i32>,
-        _material_id: Option<i32>,
-    ) -> NetworkResult<bool> {
-        unimplemented!("todo")
+        points: &[Vector3],
+        color: Color,
+        size: f32,
+        duration: PlotDuration,
+    ) -> NetworkResult<()> {
+        let points = points.iter().map(Vector3::as_msgpack).collect();
+        let (duration, is_persistent) = duration.as_msgpack();
+
+        self.unary_rpc(
+            "simPlotPoints".into(),
+            Some(vec![
+                Value::Array(points),
+                color.as_msgpack(),
+                Value::F32(size),
+                duration,
+                is_persistent,
+            ]),
+        )
+        .await
+        .map(|_| ())
     }
 
-    /// Time API
+    /// Debug API
     ///
-    /// Control the position of Sun in the environment
-    /// Sun's position is computed using the coordinates specified in `OriginGeopoint` in settings for the date-time specified in the argument,
-    /// else if the string is empty, current date & time is used
+    /// Plots a line strip in World NED frame, defined from points[0] to points[1], points[1] to points[2], ... ,
+    /// points[n-2] to points[n-1]
     ///
     /// args:
-    ///    is_enabled (bool): True to enable time-of-day effect, False to reset the position to original
-    ///    start_datetime (Option<bool>): Date & Time in %Y-%m-%d %H:%M:%S format, e.g. `2018-02-12 15:20:00`
-    ///    is_start_datetime_dst (Option<bool): True to adjust for Daylight Savings Time
-    ///    celestial_clock_speed (Option<f32>): Run celestial clock faster or slower than simulation clock
-    ///                                         E.g. Value 100 means for every 1 second of simulation clock, Sun's position is advanced by 100 seconds
-    ///                                         so Sun will move in sky much faster
-    ///    update_interval_secs (Option<f32>): Interval to update the Sun's position
-    ///    move_sun (Option<bool>): Whether or not to move the Sun
-    pub async fn sim_set_time_of_day(
+    ///     points (&[Vector3]): List of 3D locations of line start and end points, specified as Vector3 objects
+    ///     color (Color): Color of the line
+    ///     thickness (f32): Thickness of the line
+    ///     duration (PlotDuration): How long the line stays in the viewport
+    pub async fn sim_plot_line_strip(
         &self,
-        _is_enabled: bool,
-        _start_datetime: &str,
-        _is_start_datetime_dst: Option<bool>,
-        _celestial_clock_speed: Option<f32>,
-        _update_interval_secs: Option<f32>,
-        _move_sun: Option<bool>,
+        points: &[Vector3],
+        color: Color,
+        thickness: f32,
+        duration: PlotDuration,
     ) -> NetworkResult<()> {
-        unimplemented!("todo")
-    }
+        let points = points.iter().map(Vector3::as_msgpack).collect();
+        let (duration, is_persistent) = duration.as_msgpack();
 
-    /// Weather API
-    ///
-    /// Enable Weather effects. Needs to be called before using `sim_set_weather_parameter()` method
-    /// args:
-    ///     enable (bool): true to enable, false to disable
-    pub async fn sim_enable_weather(&self, _enable: bool) -> NetworkResult<()> {
-        unimplemented!("todo")
+        self.unary_rpc(
+            "simPlotLineStrip".into(),
+            Some(vec![
+                Value::Array(points),
+                color.as_msgpack(),
+                Value::F32(thickness),
+                duration,
+                is_persistent,
+            ]),
+        )
+        .await
+        .map(|_| ())
     }
 
-    /// Weather API
+    /// Debug API
     ///
-    /// Enable various weather effects
+    /// Plots a line list in World NED frame, defined from points[0] to points[1], points[2] to points[3], ... ,
+    /// points[n-2] to points[n-1]
+    ///
+    /// Fails with `NetworkError::InvalidArgument` without sending anything if the number of points is odd
     ///
     /// args:
-    ///     param (WeatherParameter): Weather effect to be enabled
-    ///     val (f32): Intensity of the effect, Range 0-1
-    pub async fn sim_set_weather_parameter(&self, _param: WeatherParameter, val: f32) -> NetworkResult<()> {
-        if val.is_sign_negative() || val > 1.0 {
-            panic!("val outside of valid range 0.0 to 1.0")
+    ///     points (&[Vector3]): List of 3D locations of line start and end points, specified as Vector3 objects.
+    ///                          Must be even
+    ///     color (Color): Color of the lines
+    ///     thickness (f32): Thickness of the lines
+    ///     duration (PlotDuration): How long the lines stay in the viewport
+    pub async fn sim_plot_line_list(
+        &self,
+        points: &[Vector3],
+        color: Color,
+        thickness: f32,
+        duration: PlotDuration,
+    ) -> NetworkResult<()> {
+        if points.len() % 2 != 0 {
+            return Err(NetworkError::InvalidArgument {
+                message: format!(
+                    "points must hold line start and end points, got an odd {}",
+                    points.len()
+                ),
+            });
         }
 
-        unimplemented!("todo")
-    }
-}
+        let points = points.iter().map(Vector3::as_msgpack).collect();
+        let (duration, is_persistent) = duration.as_msgpack();
 
-/// Vehicle specific functions
-impl AirsimClient {
-    /// Enables or disables API control for vehicle corresponding to vehicle_name
+        self.unary_rpc(
+            "simPlotLineList".into(),
+            Some(vec![
+                Value::Array(points),
+                color.as_msgpack(),
+                Value::F32(thickness),
+                duration,
+                is_persistent,
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Debug API
+    ///
+    /// Plots a list of arrows in World NED frame, defined from points_start[0] to points_end[0],
+    /// points_start[1] to points_end[1], ... , points_start[n-1] to points_end[n-1]
+    ///
+    /// Fails with `NetworkError::InvalidArgument` without sending anything if the lists have different lengths
+    ///
+    /// args:
+    ///     points_start (&[Vector3]): List of 3D start positions of arrow start positions
+    ///     points_end (&[Vector3]): List of 3D end positions of arrow start positions
+    ///     color (Color): Color of the arrows
+    ///     thickness (f32): Thickness of the arrows
+    ///     arrow_size (f32): Size of the arrow heads
+    ///     duration (PlotDuration): How long the arrows stay in the viewport
+    pub async fn sim_plot_arrows(
+        &self,
+        points_start: &[Vector3],
+        points_end: &[Vector3],
+        color: Color,
+        thickness: f32,
+        arrow_size: f32,
+        duration: PlotDuration,
+    ) -> NetworkResult<()> {
+        if points_start.len() != points_end.len() {
+            return Err(NetworkError::InvalidArgument {
+                message: format!(
+                    "points_start and points_end must have the same length, got {} and {}",
+                    points_start.len(),
+                    points_end.len()
+                ),
+            });
+        }
+
+        let points_start = points_start.iter().map(Vector3::as_msgpack).collect();
+        let points_end = points_end.iter().map(Vector3::as_msgpack).collect();
+        let (duration, is_persistent) = duration.as_msgpack();
+
+        self.unary_rpc(
+            "simPlotArrows".into(),
+            Some(vec![
+                Value::Array(points_start),
+                Value::Array(points_end),
+                color.as_msgpack(),
+                Value::F32(thickness),
+                Value::F32(arrow_size),
+                duration,
+                is_persistent,
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Debug API
+    ///
+    /// Plots a list of strings at desired positions in World NED frame
+    ///
+    /// Strings can't be persistent, `PlotDuration::Persistent` draws them for a single frame. Fails with
+    /// `NetworkError::InvalidArgument` without sending anything if the lists have different lengths
+    ///
+    /// args:
+    ///     strings (&[&str]): List of strings to plot
+    ///     positions (&[Vector3]): List of positions where the strings should be plotted. Should be in one-to-one correspondence with the strings' list
+    ///     scale (f32): Font scale of transform name
+    ///     color (Color): Color of the strings
+    ///     duration (PlotDuration): How long the strings stay in the viewport
+    pub async fn sim_plot_strings(
+        &self,
+        strings: &[&str],
+        positions: &[Vector3],
+        scale: f32,
+        color: Color,
+        duration: PlotDuration,
+    ) -> NetworkResult<()> {
+        if strings.len() != positions.len() {
+            return Err(NetworkError::InvalidArgument {
+                message: format!(
+                    "strings and positions must have the same length, got {} and {}",
+                    strings.len(),
+                    positions.len()
+                ),
+            });
+        }
+
+        let strings = strings.iter().map(|s| Value::String((*s).into())).collect();
+        let positions = positions.iter().map(Vector3::as_msgpack).collect();
+        let (duration, _) = duration.as_msgpack();
+
+        self.unary_rpc(
+            "simPlotStrings".into(),
+            Some(vec![
+                Value::Array(strings),
+                Value::Array(positions),
+                Value::F32(scale),
+                color.as_msgpack(),
+                duration,
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Debug API
+    ///
+    /// Plots a list of transforms in World NED frame
+    ///
+    /// args:
+    ///     poses (&[Pose3]): List of poses representing the transforms to plot
+    ///     scale (f32): Length of transforms' axes
+    ///     thickness (f32): Thickness of transforms' axes
+    ///     duration (PlotDuration): How long the transforms stay in the viewport
+    pub async fn sim_plot_transforms(
+        &self,
+        poses: &[Pose3],
+        scale: f32,
+        thickness: f32,
+        duration: PlotDuration,
+    ) -> NetworkResult<()> {
+        let poses = poses.iter().map(Pose3::as_msgpack).collect();
+        let (duration, is_persistent) = duration.as_msgpack();
+
+        self.unary_rpc(
+            "simPlotTransforms".into(),
+            Some(vec![
+                Value::Array(poses),
+                Value::F32(scale),
+                Value::F32(thickness),
+                duration,
+                is_persistent,
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns whether the target point is visible from the perspective of the source point
+    ///
+    /// args:
+    ///     point1 (GeoPoint): Source point
+    ///     point2 (GeoPoint): Target point
+    pub async fn sim_test_line_of_sight_between_points(
+        &self,
+        point1: GeoPoint,
+        point2: GeoPoint,
+    ) -> NetworkResult<bool> {
+        self.unary_rpc(
+            "simTestLineOfSightBetweenPoints".into(),
+            Some(vec![point1.as_msgpack(), point2.as_msgpack()]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Returns the extents of the world as the minimum and maximum corner of its bounding box
+    pub async fn sim_get_world_extents(&self) -> NetworkResult<(Vector3, Vector3)> {
+        self.unary_rpc("simGetWorldExtents".into(), None)
+            .await
+            .map(|response| match response.result {
+                Ok(res) => {
+                    let extents = res.as_array().unwrap();
+                    (extents[0].to_owned().into(), extents[1].to_owned().into())
+                }
+                Err(_) => panic!("Could not decode result from world extents msgpack"),
+            })
+    }
+
+    /// Construct and save a binvox-formatted voxel grid of the environment
+    ///
+    /// args:
+    ///     position (Vector3): Global position around which the voxel grid is centered in m
+    ///     x (u32): Size of the voxel grid along the x axis in m
+    ///     y (u32): Size of the voxel grid along the y axis in m
+    ///     z (u32): Size of the voxel grid along the z axis in m
+    ///     res (f32): Resolution of the voxel grid in m
+    ///     output_file (&str): Name of the output file to save the voxel grid as
+    pub async fn sim_create_voxel_grid(
+        &self,
+        position: Vector3,
+        x: u32,
+        y: u32,
+        z: u32,
+        res: f32,
+        output_file: &str,
+    ) -> NetworkResult<bool> {
+        let output_file: Utf8String = output_file.into();
+
+        self.unary_rpc(
+            "simCreateVoxelGrid".into(),
+            Some(vec![
+                position.as_msgpack(),
+                Value::Integer(x.into()),
+                Value::Integer(y.into()),
+                Value::Integer(z.into()),
+                Value::F32(res),
+                Value::String(output_file),
+            ]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Light Control APIs
+    /// For more documentation: https://github.com/microsoft/AirSim/blob/b272597854f389e03bf7d9b9581666c91f2e24f9/docs/apis.md#light-control-apis
+    ///
+    /// Change intensity of named light. This method should be called after a `sim_spawn_object()` call
+    ///
+    /// args:
+    ///     light_name (str): Name of light to change
+    ///     intensity (f32): New intensity value
+    pub async fn sim_set_light_intensity(&self, light_name: &str, intensity: f32) -> NetworkResult<bool> {
+        let light_name: Utf8String = light_name.into();
+
+        self.unary_rpc(
+            "simSetLightIntensity".into(),
+            Some(vec![Value::String(light_name), Value::F32(intensity)]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Lists the lights of the scene, e.g. to sweep their intensity with `sim_set_light_intensity()`
+    ///
+    /// AirSim has no notion of lights in its object listing, lights are found by their name: without a regex the
+    /// Unreal default names of light actors are matched (`PointLight`, `SpotLight`, `RectLight`, `DirectionalLight`
+    /// and `SkyLight`), lights spawned with another name need their own regex
+    ///
+    /// args:
+    ///     name_regex (Option<&str>): Regex the names of the lights have to match
+    pub async fn sim_list_lights(&self, name_regex: Option<&str>) -> NetworkResult<SceneObjects> {
+        let name_regex = name_regex.unwrap_or(".*(PointLight|SpotLight|RectLight|DirectionalLight|SkyLight).*");
+        self.sim_list_scene_objects(name_regex).await
+    }
+
+    /// Lists the objects present in the environment
+    ///
+    /// args:
+    ///     name_regex (&str): Regex the names of the objects have to match, `.*` lists every object
+    pub async fn sim_list_scene_objects(&self, name_regex: &str) -> NetworkResult<SceneObjects> {
+        let name_regex: Utf8String = name_regex.into();
+
+        self.unary_rpc("simListSceneObjects".into(), Some(vec![Value::String(name_regex)]))
+            .await
+            .map(SceneObjects::from)
+    }
+
+    /// Returns the geometry of every static mesh of the scene, e.g. to export the ground truth environment
+    ///
+    /// The response can be large, expect this call to take a while on big environments
+    pub async fn sim_get_mesh_position_vertex_buffers(&self) -> NetworkResult<Vec<MeshData>> {
+        self.unary_rpc("simGetMeshPositionVertexBuffers".into(), None)
+            .await
+            .map(MeshData::list_from)
+    }
+
+    /// The position inside the returned Pose is in the world frame
+    ///
+    /// args:
+    ///     object_name (&str): Object to get the Pose (Position3) of
+    pub async fn sim_get_object_pose(&self, name_regex: &str) -> NetworkResult<Pose3> {
+        let name_regex: Utf8String = name_regex.into();
+
+        self.unary_rpc("simGetObjectPose".into(), Some(vec![Value::String(name_regex)]))
+            .await
+            .map(Pose3::from)
+    }
+
+    /// Removes selected object from the world
+    ///
+    /// Returns True if object is queued for removal
+    ///
+    /// args:
+    ///     object_name (&str): Name of object to be removed
+    pub async fn sim_destroy_object(&self, name_regex: &str) -> NetworkResult<bool> {
+        let name_regex: Utf8String = name_regex.into();
+
+        self.unary_rpc("simDestroyObject".into(), Some(vec![Value::String(name_regex)]))
+            .await
+            .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Lists the assets which can be spawned with `sim_spawn_object`
+    ///
+    /// The assets differ per environment, listing them avoids hardcoding their names
+    pub async fn sim_list_assets(&self) -> NetworkResult<Vec<String>> {
+        self.unary_rpc("simListAssets".into(), None)
+            .await
+            .map(|response| SceneObjects::from(response).0)
+    }
+
+    /// Spawned selected object in the world
+    ///
+    /// NOTE!!: This method currently crashes the AirSim application
+    ///
+    /// Returns name of spawned object, in case it had to be modified
+    ///
+    /// args:
+    ///     object_name (&str): Name of object to be removed
+    ///     asset_name (&str): Name of asset(mesh) in the project database: PointLightBP or SpotLightBP, see `sim_list_assets`
+    ///     pose (Pose3): Desired pose of object
+    ///     scale (Vector3): Desired scale of object
+    ///     physics_enabled (Option<bool>): Whether to enable physics for the object
+    ///     is_blueprint (Option<bool>): Whether to spawn a blueprint or an actor
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sim_spawn_object(
+        &self,
+        name_regex: &str,
+        asset_name: &str,
+        pose: Pose3,
+        scale: Vector3,
+        physics_enabled: Option<bool>,
+        is_blueprint: Option<bool>,
+    ) -> NetworkResult<String> {
+        let name_regex: Utf8String = name_regex.into();
+        let asset_name: Utf8String = asset_name.into();
+        let physics_enabled = physics_enabled.unwrap_or(false);
+        let is_blueprint = is_blueprint.unwrap_or(false);
+
+        self.unary_rpc(
+            SPAWN_OBJECT.into(),
+            Some(vec![
+                Value::String(name_regex),
+                Value::String(asset_name),
+                pose.as_msgpack(),
+                scale.as_msgpack(),
+                Value::Boolean(physics_enabled),
+                Value::Boolean(is_blueprint),
+            ]),
+        )
+        .await
+        .map(|response| response.result.unwrap())
+        .map(|val| val.as_str().unwrap().to_string())
+    }
+
+    /// Gets scale of an object in the world
+    ///
+    /// args:
+    ///     object_name (&str): Object to get the scale of
+    pub async fn sim_get_object_scale(&self, object_name: &str) -> NetworkResult<Vector3> {
+        let object_name: Utf8String = object_name.into();
+
+        self.unary_rpc("simGetObjectScale".into(), Some(vec![Value::String(object_name)]))
+            .await
+            .map(|response| response.result.unwrap().into())
+    }
+
+    /// Sets scale of an object in the world
+    ///
+    /// Returns True if scale change was successful
+    ///
+    /// args:
+    ///     object_name (&str): Object to set the scale of
+    ///     scale (Vector3): Desired scale of object
+    pub async fn sim_set_object_scale(&self, object_name: &str, scale: Vector3) -> NetworkResult<bool> {
+        let object_name: Utf8String = object_name.into();
+
+        self.unary_rpc(
+            "simSetObjectScale".into(),
+            Some(vec![Value::String(object_name), scale.as_msgpack()]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Runtime swap texture API
+    ///
+    /// Returns vector of objects which matched the provided tags and had the texture swap perfomed
+    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    ///
+    /// args:
+    ///     tags (str): String of "," or ", " delimited tags to identify on which actors to perform the swap
+    ///     tex_id (Option<i32>): Indexes the array of textures assigned to each actor undergoing a swap
+    ///     component_id (Option<i32>): Id of the component
+    ///     material_id (Option<i32>): Id of the material
+    pub async fn sim_swap_textures(
+        &self,
+        tags: &str,
+        tex_id: Option<i32>,
+        component_id: Option<i32>,
+        material_id: Option<i32>,
+    ) -> NetworkResult<Vec<String>> {
+        let tags: Utf8String = tags.into();
+
+        self.unary_rpc(
+            "simSwapTextures".into(),
+            Some(vec![
+                Value::String(tags),
+                Value::Integer(tex_id.unwrap_or(0).into()),
+                Value::Integer(component_id.unwrap_or(0).into()),
+                Value::Integer(material_id.unwrap_or(0).into()),
+            ]),
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(res) => res
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|name| name.as_str().unwrap().to_string())
+                .collect(),
+            Err(_) => panic!("Could not decode result from simSwapTextures msgpack"),
+        })
+    }
+
+    /// Runtime swap texture API
+    ///
+    /// Returns True if material was set
+    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    ///
+    /// args:
+    ///     object_name (&str): Name of the object to set material for
+    ///     material_name (&str): Name of the material to set for object
+    ///     component_id (Option<i32>): Id of the component
+    pub async fn sim_set_object_material(
+        &self,
+        object_name: &str,
+        material_name: &str,
+        component_id: Option<i32>,
+    ) -> NetworkResult<bool> {
+        let object_name: Utf8String = object_name.into();
+        let material_name: Utf8String = material_name.into();
+
+        self.unary_rpc(
+            "simSetObjectMaterial".into(),
+            Some(vec![
+                Value::String(object_name),
+                Value::String(material_name),
+                Value::Integer(component_id.unwrap_or(0).into()),
+            ]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Runtime swap texture API
+    ///
+    /// Returns True if material was set
+    /// See https://microsoft.github.io/AirSim/retexturing/ for details
+    ///
+    /// args:
+    ///     object_name (&str): Name of the object to set material for
+    ///     texture_path (&str): Path to the texture to set for object
+    ///     component_id (Option<i32>): Id of the component
+    pub async fn sim_set_object_material_from_texture(
+        &self,
+        object_name: &str,
+        texture_path: &str,
+        component_id: Option<i32>,
+    ) -> NetworkResult<bool> {
+        let object_name: Utf8String = object_name.into();
+        let texture_path: Utf8String = texture_path.into();
+
+        self.unary_rpc(
+            "simSetObjectMaterialFromTexture".into(),
+            Some(vec![
+                Value::String(object_name),
+                Value::String(texture_path),
+                Value::Integer(component_id.unwrap_or(0).into()),
+            ]),
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Time API
+    ///
+    /// Control the position of Sun in the environment
+    /// Sun's position is computed using the coordinates specified in `OriginGeopoint` in settings for the date-time specified in the argument,
+    /// else if the string is empty, current date & time is used
+    ///
+    /// args:
+    ///    is_enabled (bool): True to enable time-of-day effect, False to reset the position to original
+    ///    start_datetime (&str): Date & Time in %Y-%m-%d %H:%M:%S format, e.g. `2018-02-12 15:20:00`
+    ///    is_start_datetime_dst (Option<bool): True to adjust for Daylight Savings Time
+    ///    celestial_clock_speed (Option<f32>): Run celestial clock faster or slower than simulation clock
+    ///                                         E.g. Value 100 means for every 1 second of simulation clock, Sun's position is advanced by 100 seconds
+    ///                                         so Sun will move in sky much faster
+    ///    update_interval_secs (Option<f32>): Interval to update the Sun's position
+    ///    move_sun (Option<bool>): Whether or not to move the Sun
+    pub async fn sim_set_time_of_day(
+        &self,
+        is_enabled: bool,
+        start_datetime: &str,
+        is_start_datetime_dst: Option<bool>,
+        celestial_clock_speed: Option<f32>,
+        update_interval_secs: Option<f32>,
+        move_sun: Option<bool>,
+    ) -> NetworkResult<()> {
+        let defaults = TimeOfDay::default();
+        let time_of_day = TimeOfDay::new(is_enabled)
+            .start_datetime(start_datetime)
+            .is_start_datetime_dst(is_start_datetime_dst.unwrap_or(defaults.is_start_datetime_dst))
+            .celestial_clock_speed(celestial_clock_speed.unwrap_or(defaults.celestial_clock_speed))
+            .update_interval_secs(update_interval_secs.unwrap_or(defaults.update_interval_secs))
+            .move_sun(move_sun.unwrap_or(defaults.move_sun));
+
+        self.sim_set_time_of_day_with(&time_of_day).await
+    }
+
+    /// Time API
+    ///
+    /// Same as `sim_set_time_of_day`, but takes the settings as a `TimeOfDay`
+    ///
+    /// args:
+    ///    time_of_day (&TimeOfDay): time-of-day settings, see `TimeOfDay::new`
+    pub async fn sim_set_time_of_day_with(&self, time_of_day: &TimeOfDay) -> NetworkResult<()> {
+        self.unary_rpc("simSetTimeOfDay".into(), Some(time_of_day.as_msgpack()))
+            .await
+            .map(|_| ())
+    }
+
+    /// Weather API
+    ///
+    /// Enable Weather effects. Needs to be called before using `sim_set_weather_parameter()` method
+    /// args:
+    ///     enable (bool): true to enable, false to disable
+    pub async fn sim_enable_weather(&self, enable: bool) -> NetworkResult<()> {
+        self.unary_rpc("simEnableWeather".into(), Some(vec![Value::Boolean(enable)]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Weather API
+    ///
+    /// Enable various weather effects
+    ///
+    /// args:
+    ///     param (WeatherParameter): Weather effect to be enabled
+    ///     val (f32): Intensity of the effect, Range 0-1
+    pub async fn sim_set_weather_parameter(&self, param: WeatherParameter, val: f32) -> NetworkResult<()> {
+        if !(0.0..=1.0).contains(&val) {
+            return Err(NetworkError::InvalidArgument {
+                message: format!("weather intensity has to be between 0.0 and 1.0, got {val}"),
+            });
+        }
+
+        self.unary_rpc(
+            "simSetWeatherParameter".into(),
+            Some(vec![param.as_msgpack(), Value::F32(val)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Weather API
+    ///
+    /// Set the density of the fog, enabling weather effects first
+    ///
+    /// AirSim only exposes the density, the height falloff and color of the exponential height fog are set in the
+    /// Unreal level
+    ///
+    /// args:
+    ///     density (f32): Density of the fog, from 0 for clear air to 1 for no visibility
+    pub async fn sim_set_fog(&self, density: f32) -> NetworkResult<()> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(NetworkError::InvalidArgument {
+                message: format!("fog density has to be between 0.0 and 1.0, got {density}"),
+            });
+        }
+
+        self.sim_enable_weather(true).await?;
+        self.sim_set_weather_parameter(WeatherParameter::Fog, density).await
+    }
+
+    /// Move the geo reference of the world, the GPS coordinate of the origin of the NED frame
+    ///
+    /// Only servers built with geo reference support implement this RPC, others fail with `NetworkError::Rpc` and
+    /// the origin has to be set with `OriginGeopoint` in settings.json before AirSim starts
+    ///
+    /// args:
+    ///     origin (GeoPoint): GPS coordinate of the origin of the world
+    pub async fn sim_set_world_origin_geo_point(&self, origin: GeoPoint) -> NetworkResult<()> {
+        self.unary_rpc("simSetWorldOriginGeoPoint".into(), Some(vec![origin.as_msgpack()]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Set an external force applied to the vehicles until it's set again, e.g. to simulate a payload or a gust
+    ///
+    /// AirSim applies the force in the world (NED) frame, to every vehicle of the simulation. Set a zero force to
+    /// remove it
+    ///
+    /// args:
+    ///     force (Vector3): force in Newtons
+    pub async fn sim_set_ext_force(&self, force: Vector3) -> NetworkResult<()> {
+        self.unary_rpc("simSetExtForce".into(), Some(vec![force.as_msgpack()]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Apply an external wrench for `duration` seconds, then remove it, e.g. for disturbance rejection tests
+    ///
+    /// AirSim only applies forces, a wrench with a torque fails with `NetworkError::InvalidArgument` without sending
+    /// anything.
+    /// Dropping the future before it completes leaves the force applied until the next `sim_set_ext_force`
+    ///
+    /// args:
+    ///     wrench (Wrench): force in Newtons, in the world (NED) frame
+    ///     duration (f32): time to apply the wrench, in seconds
+    pub async fn sim_apply_wrench(&self, wrench: Wrench, duration: f32) -> NetworkResult<()> {
+        if wrench.has_torque() {
+            return Err(NetworkError::InvalidArgument {
+                message: "AirSim applies external forces only, the wrench holds a torque".into(),
+            });
+        }
+
+        self.sim_set_ext_force(wrench.force).await?;
+        async_std::task::sleep(Duration::from_secs_f32(duration.max(0.0))).await;
+        self.sim_set_ext_force(Vector3::zeros()).await
+    }
+}
+
+impl Drop for AirsimClient {
+    fn drop(&mut self) {
+        // only signals the background task, blocking here could deadlock the executor running the drop
+        self.client.shutdown();
+    }
+}
+
+/// Vehicle specific functions
+impl AirsimClient {
+    /// Enables or disables API control for vehicle corresponding to vehicle_name
+    ///
+    /// args:
+    ///     is_enabled (bool): True to enable, False to disable API control
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn enable_api_control(&self, is_enabled: bool, vehicle_name: Option<&str>) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "enableApiControl".into(),
+            Some(vec![Value::Boolean(is_enabled), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Returns true if API control is established.
+    ///
+    /// If false (which is default) then API calls would be ignored. After a successful call
+    /// to `enableApiControl`, `isApiControlEnabled` should return true.
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn is_api_control_enabled(
+        &self,
+        is_enabled: bool,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "isApiControlEnabled".into(),
+            Some(vec![Value::Boolean(is_enabled), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Cancel previous Async task
+    ///
+    /// Maps to the `cancelLastTask` RPC, returns true if the request went through
+    ///
+    /// args:
+    ///      vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn cancel_last_task(&self, vehicle_name: Option<&str>) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "cancelLastTask".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok())
+    }
+
+    /// Wait until the previous Async task of the vehicle is done
+    ///
+    /// The server has no RPC for it, like the C++ client this waits on the last maneuver sent through this client,
+    /// e.g. `move_on_path_async` awaited in another task. Maneuvers sent by other connections aren't seen. Returns
+    /// true right away if no maneuver is running, false if the timeout passed before the task completed
+    ///
+    /// args:
+    ///      timeout_sec (Option<f32>): Maximum time to wait in seconds, waits indefinitely if None
+    ///      vehicle_name (Option<&str>): Name of the vehicle to wait on
+    pub(crate) async fn wait_on_last_task(
+        &self,
+        timeout_sec: Option<f32>,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<bool> {
+        let last_task = self.last_tasks.lock().unwrap().get(vehicle_name.unwrap_or("")).cloned();
+        let last_task = match last_task {
+            Some(last_task) => last_task,
+            None => return Ok(true),
+        };
+
+        // nothing is ever sent, `recv` fails once the maneuver dropped its sender
+        let done = last_task.recv();
+        match timeout_sec.filter(|timeout_sec| timeout_sec.is_finite()) {
+            Some(timeout_sec) => {
+                let timeout = Duration::from_secs_f32(timeout_sec.max(0.0));
+                Ok(async_std::future::timeout(timeout, done).await.is_ok())
+            }
+            None => {
+                let _ = done.await;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns true if API control is established.
+    ///
+    /// If false (which is default) then API calls would be ignored. After a successful call
+    /// to `enableApiControl`, `isApiControlEnabled` should return true.
+    ///
+    /// args:
+    ///     arm (bool): True to arm, False to disarm the vehicle
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn arm_disarm(&self, arm: bool, vehicle_name: Option<&str>) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "armDisarm".into(),
+            Some(vec![Value::Boolean(arm), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Get the Home location of the vehicle
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn get_home_geo_point(&self, vehicle_name: Option<&str>) -> Result<GeoPoint, NetworkError> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "getHomeGeoPoint".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(GeoPoint::from)
+    }
+
+    /// Set the Home location of the vehicle, e.g. to replay a GPS track logged at a real site
+    ///
+    /// GPS readings and the GPS moves of the vehicle are relative to the new home. Only servers built with geo
+    /// reference support implement this RPC, others fail with `NetworkError::Rpc`
+    ///
+    /// args:
+    ///     home (GeoPoint): GPS coordinate of the home
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn set_home_geo_point(&self, home: GeoPoint, vehicle_name: Option<&str>) -> NetworkResult<()> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "setHomeGeoPoint".into(),
+            Some(vec![home.as_msgpack(), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Get the ground truth kinematics of the vehicle
+    ///
+    /// The position inside the returned KinematicsState is in the frame of the vehicle's starting point
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    pub(crate) async fn sim_get_ground_truth_kinematics(
+        &self,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<KinematicsState> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simGetGroundTruthKinematics".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(KinematicsState::from)
+    }
+
+    /// Get the last collision of the vehicle
+    ///
+    /// The info is kept until the next collision, compare the timestamps to tell collisions apart
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle
+    pub(crate) async fn sim_get_collision_info(&self, vehicle_name: Option<&str>) -> NetworkResult<CollisionInfo> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simGetCollisionInfo".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.unwrap().into())
+    }
+
+    /// Set the kinematics state of the vehicle
+    ///
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation) to
+    /// floating point nan values
+    ///
+    /// args:
+    ///     state (KinematicsState): Desired kinematics state of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    ///     vehicle_name (Option<&str>): Name of the vehicle to move
+    pub(crate) async fn sim_set_kinematics(
+        &self,
+        state: KinematicsState,
+        ignore_collision: bool,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<()> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simSetKinematics".into(),
+            Some(vec![
+                state.as_msgpack(),
+                Value::Boolean(ignore_collision),
+                Value::String(vehicle_name.into()),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Get the ground truth environment state of the vehicle
+    ///
+    /// The environment state holds the position, geo point, gravity and air pressure, temperature and density at the
+    /// vehicle's location
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to get the environment state of
+    pub(crate) async fn sim_get_ground_truth_environment(
+        &self,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<EnvironmentState> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simGetGroundTruthEnvironment".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(EnvironmentState::from)
+    }
+
+    /// Camera API
+    ///
+    /// Returns binary string literal of compressed png image in presented as an vector of bytes
+    ///
+    /// Returns bytes of png format image which can be dumped into abinary file to create .png image
+    /// See https://microsoft.github.io/AirSim/image_apis/ for details
+    ///
+    /// Lighter than `sim_get_images` for a single compressed scene image, the server sends the bytes of the image
+    /// without the pose and size of the camera
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
+    ///     camera_name (String): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_image(
+        &self,
+        vehicle_name: Option<&str>,
+        camera_name: &str,
+        image_type: ImageType,
+        external: Option<bool>,
+    ) -> Result<CompressedImage, NetworkError> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let camera_name: Utf8String = camera_name.into();
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetImage".into(),
+            Some(vec![
+                Value::String(camera_name),
+                image_type.as_msgpack(),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(CompressedImage::from)
+    }
+
+    /// Camera API
+    ///
+    /// Get multiple images
+    /// See https://microsoft.github.io/AirSim/image_apis/ for details and examples
+    /// Args:
+    ///     requests (list[ImageRequest]): Images required
+    ///     vehicle_name (str, optional): Name of vehicle associated with the camera
+    ///     external (bool, optional): Whether the camera is an External Camera
+    /// Returns:
+    ///     Vec<ImageResponse>: One response per request, in the order of the requests
+    pub(crate) async fn sim_get_images(
+        &self,
+        requests: ImageRequests,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> Result<Vec<ImageResponse>, NetworkError> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetImages".into(),
+            Some(vec![
+                requests.as_msgpack(),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(ImageResponse::list_from)
+    }
+
+    /// Captures images and the ground truth kinematics of the vehicle at the same simulation tick
+    ///
+    /// The simulation is paused while both are queried, and resumed afterwards unless it was already paused
+    ///
+    /// args:
+    ///     requests (ImageRequests): Images required
+    ///     vehicle_name (Option<&str>): Name of the vehicle to capture
+    pub(crate) async fn capture_snapshot(
+        &self,
+        requests: ImageRequests,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<Snapshot> {
+        let was_paused = self.sim_is_pause().await?;
+        if !was_paused {
+            self.sim_pause(true).await?;
+        }
+
+        let (images, state) = futures::join!(
+            self.sim_get_images(requests, vehicle_name, None),
+            self.sim_get_ground_truth_kinematics(vehicle_name)
+        );
+
+        // resume before reporting a failed query, the simulation must not stay paused
+        if !was_paused {
+            self.sim_pause(false).await?;
+        }
+
+        let images = images?;
+        Ok(Snapshot {
+            timestamp: images.first().map_or(0, |image| image.time_stamp),
+            images,
+            state: state?,
+        })
+    }
+
+    /// Captures the images of two cameras in a single `simGetImages` call, with the baseline between them
+    ///
+    /// Depth, disparity and optical flow images are returned as floats, the other types as raw bytes. Fails with
+    /// `NetworkError::UnexpectedResponse` if the server doesn't return an image for each camera
+    ///
+    /// args:
+    ///     left_camera (&str): Name of the left camera
+    ///     right_camera (&str): Name of the right camera
+    ///     image_type (ImageType): Type of image required from both cameras
+    ///     vehicle_name (Option<&str>): Name of vehicle which the cameras correspond to
+    pub(crate) async fn capture_stereo(
+        &self,
+        left_camera: &str,
+        right_camera: &str,
+        image_type: ImageType,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<StereoPair> {
+        let (images, left_info, right_info) = futures::join!(
+            self.sim_get_images(
+                StereoPair::requests(left_camera, right_camera, image_type),
+                vehicle_name,
+                None
+            ),
+            self.sim_get_camera_info(left_camera, vehicle_name, None),
+            self.sim_get_camera_info(right_camera, vehicle_name, None)
+        );
+
+        let mut images = images?.into_iter();
+        match (images.next(), images.next()) {
+            (Some(left), Some(right)) => Ok(StereoPair::new(left, right, left_info?, right_info?)),
+            _ => Err(NetworkError::UnexpectedResponse {
+                method: "simGetImages".to_owned(),
+                message: "no image for both stereo cameras".to_owned(),
+            }),
+        }
+    }
+
+    /// Turns a camera of the vehicle towards a point, keeping the camera at its mounting position
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to turn
+    ///     target (Vector3): Point to look at, in the frame of the vehicle's starting point
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    pub(crate) async fn camera_look_at(
+        &self,
+        camera_name: &str,
+        target: Vector3,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<()> {
+        let (vehicle, camera) = futures::join!(
+            self.sim_get_vehicle_pose(vehicle_name),
+            self.sim_get_camera_info(camera_name, vehicle_name, None),
+        );
+        let pose = camera::look_at(&vehicle?, &camera?.pose, target);
+        self.sim_set_camera_pose(camera_name, pose, vehicle_name, None).await
+    }
+
+    /// Returns whether the target point is visible from the perspective of the vehicle
+    ///
+    /// args:
+    ///     point (GeoPoint): Target point
+    ///     vehicle_name (Option<&str>): Name of the vehicle to test the line of sight from
+    pub(crate) async fn sim_test_line_of_sight_to_point(
+        &self,
+        point: GeoPoint,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simTestLineOfSightToPoint".into(),
+            Some(vec![point.as_msgpack(), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// Set the pose of the vehicle
+    ///
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation)
+    /// to floating point nan values
+    ///
+    /// args:
+    ///     pose (Pose3): Desired Pose of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    ///     vehicle_name (Option<&str>): Name of the vehicle to move
+    pub(crate) async fn sim_set_vehicle_pose(
+        &self,
+        pose: Pose3,
+        ignore_collision: bool,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<()> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simSetVehiclePose".into(),
+            Some(vec![
+                pose.as_msgpack(),
+                Value::Boolean(ignore_collision),
+                Value::String(vehicle_name.into()),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Get the pose of the vehicle
+    ///
+    /// The position inside the returned Pose3 is in the frame of the vehicle's starting point
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle to get the Pose of
+    pub(crate) async fn sim_get_vehicle_pose(&self, vehicle_name: Option<&str>) -> NetworkResult<Pose3> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simGetVehiclePose".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(Pose3::from)
+    }
+
+    /// Camera API
+    ///
+    /// Control the pose of a selected camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     pose (Pose3): Pose representing the desired position and orientation of the camera
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_camera_pose(
+        &self,
+        camera_name: &str,
+        pose: Pose3,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetCameraPose".into(),
+            Some(vec![
+                Value::String(camera_name),
+                pose.as_msgpack(),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Get details about the camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_camera_info(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<CameraInfo> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetCameraInfo".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(CameraInfo::from)
+    }
+
+    /// Camera API
+    ///
+    /// Control the field of view of a selected camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     fov_degrees (f32): Value of field of view in degrees
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_camera_fov(
+        &self,
+        camera_name: &str,
+        fov_degrees: f32,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetCameraFov".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::F32(fov_degrees),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Set the distance the camera is focused at. Takes effect once manual focus is enabled
+    ///
+    /// args:
+    ///     focus_distance_cm (f32): Focus distance in cm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_focus_distance(
+        &self,
+        focus_distance_cm: f32,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetFocusDistance".into(),
+            Some(vec![
+                Value::F32(focus_distance_cm),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Returns the distance the camera is focused at, in cm
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_focus_distance(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<f32> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetFocusDistance".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(res) => res.as_f64().unwrap() as f32,
+            Err(_) => panic!("Could not decode result from simGetFocusDistance msgpack"),
+        })
+    }
+
+    /// Camera API
+    ///
+    /// Set the aperture of the camera, lower f-stops give a shallower depth of field
+    ///
+    /// args:
+    ///     focus_aperture (f32): Aperture as f-stop
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_focus_aperture(
+        &self,
+        focus_aperture: f32,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetFocusAperture".into(),
+            Some(vec![
+                Value::F32(focus_aperture),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Returns the aperture of the camera, as f-stop
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_focus_aperture(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<f32> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetFocusAperture".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(res) => res.as_f64().unwrap() as f32,
+            Err(_) => panic!("Could not decode result from simGetFocusAperture msgpack"),
+        })
+    }
+
+    /// Camera API
+    ///
+    /// Switch the camera between manual focus and auto focus
+    ///
+    /// args:
+    ///     enable (bool): True to focus at the distance set by `sim_set_focus_distance`, False for auto focus
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_enable_manual_focus(
+        &self,
+        enable: bool,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simEnableManualFocus".into(),
+            Some(vec![
+                Value::Boolean(enable),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the lens presets of a cine camera, e.g. `"12mm"` or `"85mm Prime f/1.8"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_preset_lens_settings(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetPresetLensSettings".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_owned))
+                .collect(),
+            _ => panic!("Could not decode result from simGetPresetLensSettings msgpack"),
+        })
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the lens of a cine camera: focal and aperture ranges and diaphragm blades
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_lens_settings(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<String> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetLensSettings".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(Value::String(description)) => description.into_str().unwrap_or_default(),
+            _ => panic!("Could not decode result from simGetLensSettings msgpack"),
+        })
+    }
+
+    /// Camera API
+    ///
+    /// Switch the lens of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_lens_settings (&str): Name of the preset, see `sim_get_preset_lens_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_preset_lens_settings(
+        &self,
+        preset_lens_settings: &str,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetPresetLensSettings".into(),
+            Some(vec![
+                Value::String(preset_lens_settings.into()),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the filmback presets of a cine camera, e.g. `"35mm Full Aperture"` or `"Super 35mm"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_preset_filmback_settings(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetPresetFilmbackSettings".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_owned))
+                .collect(),
+            _ => panic!("Could not decode result from simGetPresetFilmbackSettings msgpack"),
+        })
+    }
+
+    /// Camera API
+    ///
+    /// Switch the filmback, i.e. the sensor, of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_filmback_settings (&str): Name of the preset, see `sim_get_preset_filmback_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_preset_filmback_settings(
+        &self,
+        preset_filmback_settings: &str,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetPresetFilmbackSettings".into(),
+            Some(vec![
+                Value::String(preset_filmback_settings.into()),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the filmback of a cine camera: sensor width, height and aspect ratio
     ///
     /// args:
-    ///     is_enabled (bool): True to enable, False to disable API control
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    pub(crate) async fn enable_api_control(&self, is_enabled: bool, vehicle_name: Option<&str>) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_filmback_settings(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<String> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc(
-            "enableApiControl".into(),
-            Some(vec![Value::Boolean(is_enabled), Value::String(vehicle_name)]),
+        self.vehicle_rpc(
+            "simGetFilmbackSettings".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
         )
         .await
-        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+        .map(|response| match response.result {
+            Ok(Value::String(description)) => description.into_str().unwrap_or_default(),
+            _ => panic!("Could not decode result from simGetFilmbackSettings msgpack"),
+        })
     }
 
-    /// Returns true if API control is established.
+    /// Camera API
     ///
-    /// If false (which is default) then API calls would be ignored. After a successful call
-    /// to `enableApiControl`, `isApiControlEnabled` should return true.
+    /// Set the sensor size of a cine camera, e.g. to match the sensor of a real camera
     ///
     /// args:
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    pub(crate) async fn is_api_control_enabled(
+    ///     sensor_width (f32): Width of the sensor in mm
+    ///     sensor_height (f32): Height of the sensor in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_filmback_settings(
         &self,
-        is_enabled: bool,
+        sensor_width: f32,
+        sensor_height: f32,
+        camera_name: &str,
         vehicle_name: Option<&str>,
-    ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc(
-            "isApiControlEnabled".into(),
-            Some(vec![Value::Boolean(is_enabled), Value::String(vehicle_name)]),
+        self.vehicle_rpc(
+            "simSetFilmbackSettings".into(),
+            Some(vec![
+                Value::F32(sensor_width),
+                Value::F32(sensor_height),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
         )
         .await
-        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+        .map(|_| ())
     }
 
-    /// Cancel previous Async task
+    /// Camera API
+    ///
+    /// Returns the focal length of a cine camera, in mm
     ///
     /// args:
-    ///      vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    pub(crate) async fn cancel_last_task(&self, vehicle_name: Option<&str>) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_focal_length(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<f32> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc("cancelLastTask".into(), Some(vec![Value::String(vehicle_name)]))
-            .await
-            .map(|response| response.result.is_ok())
+        self.vehicle_rpc(
+            "simGetFocalLength".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(res) => res.as_f64().unwrap() as f32,
+            Err(_) => panic!("Could not decode result from simGetFocalLength msgpack"),
+        })
     }
 
-    /// Returns true if API control is established.
+    /// Camera API
     ///
-    /// If false (which is default) then API calls would be ignored. After a successful call
-    /// to `enableApiControl`, `isApiControlEnabled` should return true.
+    /// Set the focal length of a cine camera, the field of view follows from it and the filmback
     ///
     /// args:
-    ///     arm (bool): True to arm, False to disarm the vehicle
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    pub(crate) async fn arm_disarm(&self, arm: bool, vehicle_name: Option<&str>) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ///     focal_length (f32): Focal length in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_focal_length(
+        &self,
+        focal_length: f32,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc(
-            "armDisarm".into(),
-            Some(vec![Value::Boolean(arm), Value::String(vehicle_name)]),
+        self.vehicle_rpc(
+            "simSetFocalLength".into(),
+            Some(vec![
+                Value::F32(focal_length),
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
         )
         .await
-        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+        .map(|_| ())
     }
 
-    /// Get the Home location of the vehicle
+    /// Camera API
+    ///
+    /// Returns the lens distortion of a camera
     ///
     /// args:
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    pub(crate) async fn get_home_geo_point(&self, vehicle_name: Option<&str>) -> Result<GeoPoint, NetworkError> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_distortion_params(
+        &self,
+        camera_name: &str,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<DistortionParams> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc("getHomeGeoPoint".into(), Some(vec![Value::String(vehicle_name)]))
-            .await
-            .map(GeoPoint::from)
+        self.vehicle_rpc(
+            "simGetDistortionParams".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(DistortionParams::from)
     }
 
-    /// Get the environment state of the simulation
-    pub(crate) async fn get_environment_state(&self, vehicle_name: Option<&str>) -> Result<EnvironmentState, NetworkError> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    /// Camera API
+    ///
+    /// Set one coefficient of the lens distortion of a camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     param_name (&str): Name of the coefficient, one of `K1`, `K2`, `K3`, `P1` and `P2`
+    ///     value (f32): Value of the coefficient
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_distortion_param(
+        &self,
+        camera_name: &str,
+        param_name: &str,
+        value: f32,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc("getEnvironmentState".into(), Some(vec![Value::String(vehicle_name)]))
-            .await
-            .map(EnvironmentState::from)
+        self.vehicle_rpc(
+            "simSetDistortionParam".into(),
+            Some(vec![
+                Value::String(camera_name),
+                Value::String(param_name.into()),
+                Value::F32(value),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
     }
 
     /// Camera API
     ///
-    /// Returns binary string literal of compressed png image in presented as an vector of bytes
+    /// Set the lens distortion of a camera, one `simSetDistortionParam` per coefficient
     ///
-    /// Returns bytes of png format image which can be dumped into abinary file to create .png image
-    /// See https://microsoft.github.io/AirSim/image_apis/ for details
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     params (DistortionParams): Coefficients of the distortion
+    ///     vehicle_name (Option<&str>): Name of vehicle which the camera corresponds to
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_set_distortion_params(
+        &self,
+        camera_name: &str,
+        params: DistortionParams,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        for (param_name, value) in params.named() {
+            self.sim_set_distortion_param(camera_name, param_name, value, vehicle_name, external)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Detection API
+    ///
+    /// Set detection radius for all cameras
     ///
     /// args:
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    ///     camera_name (String): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
     ///     image_type (ImageType): Type of image required
+    ///     radius_cm (f32): Radius in [cm]
+    ///     vehicle_name (Option<&str>): Name of the vehicle associated with the camera
     ///     external (Option<bool>): Whether the camera is an External Camera
-    pub(crate) async fn sim_get_image(
+    pub(crate) async fn sim_set_detection_filter_radius(
         &self,
+        camera_name: &str,
+        image_type: ImageType,
+        radius_cm: f32,
         vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simSetDetectionFilterRadius".into(),
+            Some(vec![
+                Value::String(camera_name),
+                image_type.as_msgpack(),
+                Value::F32(radius_cm),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Detection API
+    ///
+    /// Add mesh name to detect in wild card format
+    /// For example: mesh name "Car_*" will detect all instances named "Car_*"
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     mesh_name (&str): mesh name in wild card format
+    ///     vehicle_name (Option<&str>): Name of the vehicle associated with the camera
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_add_detection_filter_mesh_name(
+        &self,
         camera_name: &str,
         image_type: ImageType,
+        mesh_name: &str,
+        vehicle_name: Option<&str>,
         external: Option<bool>,
-    ) -> Result<CompressedImage, NetworkError> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ) -> NetworkResult<()> {
         let camera_name: Utf8String = camera_name.into();
+        let mesh_name: Utf8String = mesh_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
         let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc(
-            "simGetImage".into(),
+        self.vehicle_rpc(
+            "simAddDetectionFilterMeshName".into(),
             Some(vec![
                 Value::String(camera_name),
                 image_type.as_msgpack(),
-                Value::String(vehicle_name),
+                Value::String(mesh_name),
+                Value::String(vehicle_name.into()),
                 Value::Boolean(external),
             ]),
+            vehicle_name,
         )
         .await
-        .map(|response| {
-            println!("resp: {response:?}");
-            CompressedImage::from(response)
-        })
+        .map(|_| ())
     }
 
-    /// Camera API
+    /// Detection API
     ///
-    /// Get multiple images
-    /// See https://microsoft.github.io/AirSim/image_apis/ for details and examples
-    /// Args:
-    ///     requests (list[ImageRequest]): Images required
-    ///     vehicle_name (str, optional): Name of vehicle associated with the camera
-    ///     external (bool, optional): Whether the camera is an External Camera
-    /// Returns:
-    ///     list[ImageResponse]:
-    #[allow(dead_code)]
-    pub(crate) async fn sim_get_images(
+    /// Clear all mesh names from detection filter
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     vehicle_name (Option<&str>): Name of the vehicle associated with the camera
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_clear_detection_mesh_names(
         &self,
-        requests: ImageRequests,
+        camera_name: &str,
+        image_type: ImageType,
         vehicle_name: Option<&str>,
         external: Option<bool>,
-    ) -> Result<(), NetworkError> {
-        let vehicle_name: Utf8String = vehicle_name.unwrap_or("").into();
+    ) -> NetworkResult<()> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
         let external: bool = external.unwrap_or(false);
 
-        self.unary_rpc(
-            "simGetImages".into(),
+        self.vehicle_rpc(
+            "simClearDetectionMeshNames".into(),
             Some(vec![
-                requests.as_msgpack(),
-                Value::String(vehicle_name),
+                Value::String(camera_name),
+                image_type.as_msgpack(),
+                Value::String(vehicle_name.into()),
                 Value::Boolean(external),
             ]),
+            vehicle_name,
         )
         .await
-        .map(|response| {
-            println!("resp: {response:?}");
-            // CompressedImage::from(response)
-        })
+        .map(|_| ())
+    }
+
+    /// Detection API
+    ///
+    /// Get current detections
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     vehicle_name (Option<&str>): Name of the vehicle associated with the camera
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    pub(crate) async fn sim_get_detections(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        vehicle_name: Option<&str>,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<DetectionInfo>> {
+        let camera_name: Utf8String = camera_name.into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+        let external: bool = external.unwrap_or(false);
+
+        self.vehicle_rpc(
+            "simGetDetections".into(),
+            Some(vec![
+                Value::String(camera_name),
+                image_type.as_msgpack(),
+                Value::String(vehicle_name.into()),
+                Value::Boolean(external),
+            ]),
+            vehicle_name,
+        )
+        .await
+        .map(DetectionInfo::list_from)
+    }
+}
+
+/// Request name, vehicle and start time of an RPC, reported once the response is in
+#[cfg(feature = "tracing")]
+struct RpcTrace {
+    method: String,
+    vehicle_name: String,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl RpcTrace {
+    fn start(method: &str, vehicle_name: Option<&str>) -> Self {
+        Self {
+            method: method.to_owned(),
+            vehicle_name: vehicle_name.unwrap_or_default().to_owned(),
+            start: std::time::Instant::now(),
+        }
+    }
+
+    fn finish(self, response: &NetworkResult<Response>) {
+        let latency_ms = self.start.elapsed().as_secs_f64() * 1e3;
+
+        match response {
+            Ok(Response { result: Ok(_), .. }) => tracing::debug!(
+                method = %self.method,
+                vehicle = %self.vehicle_name,
+                latency_ms,
+                "rpc succeeded"
+            ),
+            Ok(Response { result: Err(error), .. }) => tracing::warn!(
+                method = %self.method,
+                vehicle = %self.vehicle_name,
+                latency_ms,
+                error = %error,
+                "rpc returned an error"
+            ),
+            Err(error) => tracing::error!(
+                method = %self.method,
+                vehicle = %self.vehicle_name,
+                latency_ms,
+                error = %error,
+                "rpc failed"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{
+        AirsimClient, Color, MultiRotorClient, NetworkError, PlotDuration, RetryPolicy, Vector3, WeatherParameter,
+        Wrench,
+    };
+
+    #[test]
+    fn test_plot_rejects_mismatched_lists() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("simPlotArrows", Value::Nil);
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            let points = [
+                Vector3::zeros(),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+            ];
+            let (color, duration) = (Color::RED, PlotDuration::Seconds(1.0));
+
+            let lines = client.sim_plot_line_list(&points, color, 5.0, duration).await;
+            assert!(matches!(lines, Err(NetworkError::InvalidArgument { .. })));
+            let arrows = client
+                .sim_plot_arrows(&points, &points[1..], color, 5.0, 2.0, duration)
+                .await;
+            assert!(matches!(arrows, Err(NetworkError::InvalidArgument { .. })));
+            let strings = client
+                .sim_plot_strings(&["origin"], &points, 1.0, color, duration)
+                .await;
+            assert!(matches!(strings, Err(NetworkError::InvalidArgument { .. })));
+
+            let arrows = client
+                .sim_plot_arrows(&points, &points, color, 5.0, 2.0, duration)
+                .await;
+            assert!(arrows.is_ok());
+        });
+        assert_eq!(server.calls("simPlotLineList"), 0);
+        assert_eq!(server.calls("simPlotStrings"), 0);
+        assert_eq!(server.calls("simPlotArrows"), 1);
+    }
+
+    #[test]
+    fn test_wait_on_last_task() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond_with("hover", |_| {
+            thread::sleep(Duration::from_millis(200));
+            Ok(Value::Boolean(true))
+        });
+
+        async_std::task::block_on(async {
+            let drone = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            assert!(drone.wait_on_last_task(Some(0.0)).await.unwrap());
+
+            let hover = async_std::task::spawn({
+                let drone = drone.clone();
+                async move { drone.hover_async().await }
+            });
+            async_std::task::sleep(Duration::from_millis(50)).await;
+
+            assert!(!drone.wait_on_last_task(Some(0.01)).await.unwrap());
+            assert!(drone.wait_on_last_task(None).await.unwrap());
+            assert!(hover.await.unwrap());
+        });
+        assert_eq!(server.calls("waitOnLastTask"), 0);
+    }
+
+    #[test]
+    fn test_set_clock_speed_rejects_invalid_speeds() {
+        let server = MockAirsimServer::start().unwrap();
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            for speed in [0.0, -2.0, f32::NAN, f32::INFINITY] {
+                let result = client.sim_set_clock_speed(speed).await;
+                assert!(matches!(result, Err(NetworkError::InvalidArgument { .. })), "{speed}");
+            }
+        });
+        assert_eq!(server.calls("simRunConsoleCommand"), 0);
+    }
+
+    #[test]
+    fn test_continue_rpcs_are_sent_once() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("simContinueForTime", Value::Nil);
+        server.respond("simContinueForFrames", Value::Nil);
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            client.sim_continue_for_time(0.5).await.unwrap();
+            client.sim_continue_for_frames(10).await.unwrap();
+        });
+        assert_eq!(server.calls("simContinueForTime"), 1);
+        assert_eq!(server.calls("simContinueForFrames"), 1);
+        assert!(!RetryPolicy::is_idempotent("simContinueForTime"));
+        assert!(!RetryPolicy::is_idempotent("simContinueForFrames"));
+    }
+
+    #[test]
+    fn test_weather_rejects_invalid_intensities() {
+        let server = MockAirsimServer::start().unwrap();
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            for intensity in [-0.1, 1.5, f32::NAN] {
+                let result = client
+                    .sim_set_weather_parameter(WeatherParameter::Rain, intensity)
+                    .await;
+                assert!(
+                    matches!(result, Err(NetworkError::InvalidArgument { .. })),
+                    "{intensity}"
+                );
+                let result = client.sim_set_fog(intensity).await;
+                assert!(
+                    matches!(result, Err(NetworkError::InvalidArgument { .. })),
+                    "{intensity}"
+                );
+            }
+        });
+        assert_eq!(server.calls("simEnableWeather"), 0);
+        assert_eq!(server.calls("simSetWeatherParameter"), 0);
+    }
+
+    #[test]
+    fn test_apply_wrench() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("simSetExtForce", Value::Nil);
+        let force = Vector3::new(0.0, 0.0, 5.0);
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+
+            let twisted = Wrench::new(force, Vector3::new(1.0, 0.0, 0.0));
+            let result = client.sim_apply_wrench(twisted, 0.0).await;
+            assert!(matches!(result, Err(NetworkError::InvalidArgument { .. })));
+            assert_eq!(server.calls("simSetExtForce"), 0);
+
+            client.sim_apply_wrench(Wrench::force(force), 0.01).await.unwrap();
+        });
+        // applied, then removed
+        let forces: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.method == "simSetExtForce")
+            .map(|request| request.params)
+            .collect();
+        assert_eq!(
+            forces,
+            vec![vec![force.as_msgpack()], vec![Vector3::zeros().as_msgpack()]]
+        );
     }
 }
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::{future, task};
+
+use crate::error::NetworkResult;
+
+use super::airsim_client::AirsimClient;
+
+/// How long the task spawned by a drop waits for AirSim to disarm and take back control, so an unresponsive server
+/// can't keep it alive
+const DROP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// API control of a vehicle, given back when the guard is released or dropped
+///
+/// Returned by `enable_api_control_guarded`. Dropping the guard, including while unwinding from a panic, disarms a
+/// multirotor and disables API control, so an aborted experiment doesn't leave the motors spinning in the simulator.
+/// The drop is best effort: it doesn't block, the RPCs are sent from a spawned task which gives up after 5 seconds
+/// and only logs failures. A process exiting right after the drop may not get to send them: await `release` to be
+/// sure API control was given back, it reports failures too.
+#[must_use = "API control is disabled as soon as the guard is dropped"]
+pub struct ApiControlGuard {
+    airsim_client: Arc<AirsimClient>,
+    vehicle_name: &'static str,
+    disarm: bool,
+    released: bool,
+}
+
+impl ApiControlGuard {
+    pub(crate) fn new(airsim_client: Arc<AirsimClient>, vehicle_name: &'static str, disarm: bool) -> Self {
+        Self {
+            airsim_client,
+            vehicle_name,
+            disarm,
+            released: false,
+        }
+    }
+
+    /// Name of the vehicle under API control
+    pub fn vehicle_name(&self) -> &'static str {
+        self.vehicle_name
+    }
+
+    /// Disarm and disable API control now
+    pub async fn release(mut self) -> NetworkResult<()> {
+        self.released = true;
+        restore(&self.airsim_client, self.vehicle_name, self.disarm).await
+    }
+}
+
+async fn restore(airsim_client: &AirsimClient, vehicle_name: &str, disarm: bool) -> NetworkResult<()> {
+    if disarm {
+        airsim_client.arm_disarm(false, Some(vehicle_name)).await?;
+    }
+    airsim_client.enable_api_control(false, Some(vehicle_name)).await?;
+    Ok(())
+}
+
+impl Drop for ApiControlGuard {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        // blocking here would stall the executor thread running the drop
+        let airsim_client = self.airsim_client.clone();
+        let vehicle_name = self.vehicle_name;
+        let disarm = self.disarm;
+        task::spawn(async move {
+            match future::timeout(DROP_TIMEOUT, restore(&airsim_client, vehicle_name, disarm)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => log::warn!("could not give back API control of {vehicle_name}: {e}"),
+                Err(_) => {
+                    log::warn!("could not give back API control of {vehicle_name}: no answer within {DROP_TIMEOUT:?}")
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::MultiRotorClient;
+
+    #[test]
+    fn test_guard_disarms_on_panic() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("armDisarm", Value::Boolean(true));
+        let addr = server.addr().to_string();
+
+        let result = std::panic::catch_unwind(move || {
+            async_std::task::block_on(async {
+                let client = MultiRotorClient::connect(&addr, "Drone1").await.unwrap();
+                let _guard = client.enable_api_control_guarded().await.unwrap();
+                client.arm_disarm(true).await.unwrap();
+                panic!("experiment aborted");
+            })
+        });
+        assert!(result.is_err());
+
+        // the drop sends the RPCs from a spawned task
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let released = || {
+            let requests = server.requests();
+            requests.last().map_or(false, |r| {
+                r.method == "enableApiControl" && r.params[0] == Value::Boolean(false)
+            })
+        };
+        while !released() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let requests = server.requests();
+        let last: Vec<(&str, &[Value])> = requests[requests.len() - 2..]
+            .iter()
+            .map(|r| (r.method.as_str(), r.params.as_slice()))
+            .collect();
+        assert_eq!(
+            last[0],
+            ("armDisarm", &[Value::Boolean(false), Value::from("Drone1")][..])
+        );
+        assert_eq!(
+            last[1],
+            ("enableApiControl", &[Value::Boolean(false), Value::from("Drone1")][..])
+        );
+    }
+}
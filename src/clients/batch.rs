@@ -0,0 +1,174 @@
+use futures::future::join_all;
+use rmpv::Value;
+
+use crate::error::NetworkResult;
+
+use super::airsim_client::AirsimClient;
+
+/// RPCs queued to be sent back to back, see `AirsimClient::batch`
+///
+/// All the requests are written before any response is awaited, so they reach the server within a few microseconds
+/// of each other, e.g. to arm and launch every drone of a swarm in the same simulation tick. The server handles them
+/// concurrently, in no guaranteed order: only batch RPCs independent of each other, and send the ones depending on
+/// another in a later batch. A batch is not a transaction either, a failed RPC doesn't undo the others.
+///
+/// ```no_run
+/// use airsim_client::AirsimClient;
+///
+/// # async fn run(client: &AirsimClient) -> airsim_client::NetworkResult<()> {
+/// let drones = ["Drone1", "Drone2", "Drone3"];
+/// let mut batch = client.batch();
+/// for drone in drones {
+///     batch = batch.enable_api_control(true, drone);
+/// }
+/// for result in batch.send().await {
+///     result?;
+/// }
+///
+/// // arming needs API control
+/// let mut batch = client.batch();
+/// for drone in drones {
+///     batch = batch.arm_disarm(true, drone);
+/// }
+/// for result in batch.send().await {
+///     result?;
+/// }
+///
+/// let mut batch = client.batch();
+/// for drone in drones {
+///     batch = batch.take_off(20.0, drone);
+/// }
+/// batch.send().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RpcBatch<'a> {
+    client: &'a AirsimClient,
+    calls: Vec<(String, Vec<Value>, Option<String>)>,
+}
+
+impl<'a> RpcBatch<'a> {
+    pub(crate) fn new(client: &'a AirsimClient) -> Self {
+        Self { client, calls: vec![] }
+    }
+
+    /// Queue any RPC
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC
+    ///     params (Vec<Value>): Params of the RPC
+    pub fn call(mut self, method: &str, params: Vec<Value>) -> Self {
+        self.calls.push((method.to_owned(), params, None));
+        self
+    }
+
+    /// Queue an RPC addressed to a vehicle
+    fn vehicle_call(mut self, method: &str, params: Vec<Value>, vehicle_name: &str) -> Self {
+        self.calls
+            .push((method.to_owned(), params, Some(vehicle_name.to_owned())));
+        self
+    }
+
+    /// Queue enabling or disabling API control of a vehicle, see `MultiRotorClient::enable_api_control`
+    pub fn enable_api_control(self, is_enabled: bool, vehicle_name: &str) -> Self {
+        self.vehicle_call(
+            "enableApiControl",
+            vec![Value::Boolean(is_enabled), Value::String(vehicle_name.into())],
+            vehicle_name,
+        )
+    }
+
+    /// Queue arming or disarming a vehicle, see `MultiRotorClient::arm_disarm`
+    pub fn arm_disarm(self, arm: bool, vehicle_name: &str) -> Self {
+        self.vehicle_call(
+            "armDisarm",
+            vec![Value::Boolean(arm), Value::String(vehicle_name.into())],
+            vehicle_name,
+        )
+    }
+
+    /// Queue the takeoff of a multirotor, see `MultiRotorClient::take_off_async`
+    ///
+    /// The response only comes once the vehicle reached its takeoff altitude or timed out
+    pub fn take_off(self, timeout_sec: f32, vehicle_name: &str) -> Self {
+        self.vehicle_call(
+            "takeoff",
+            vec![Value::F32(timeout_sec), Value::String(vehicle_name.into())],
+            vehicle_name,
+        )
+    }
+
+    /// Queue the landing of a multirotor, see `MultiRotorClient::land_async`
+    pub fn land(self, timeout_sec: f32, vehicle_name: &str) -> Self {
+        self.vehicle_call(
+            "land",
+            vec![Value::F32(timeout_sec), Value::String(vehicle_name.into())],
+            vehicle_name,
+        )
+    }
+
+    /// Queue a hover of a multirotor in place, see `MultiRotorClient::hover_async`
+    pub fn hover(self, vehicle_name: &str) -> Self {
+        self.vehicle_call("hover", vec![Value::String(vehicle_name.into())], vehicle_name)
+    }
+
+    /// Number of queued RPCs
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether no RPC is queued
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Send the queued RPCs and wait for all the responses
+    ///
+    /// Returns the result of every RPC in the order they were queued, which is not necessarily the order the server
+    /// ran them in
+    pub async fn send(self) -> Vec<NetworkResult<Value>> {
+        let client = self.client;
+        let rpcs = self.calls.into_iter().map(|(method, params, vehicle_name)| async move {
+            let response = match vehicle_name {
+                Some(vehicle_name) => client.vehicle_rpc(method, Some(params), &vehicle_name).await,
+                None => client.unary_rpc(method, Some(params)).await,
+            };
+            response.map(|response| response.result.unwrap_or(Value::Nil))
+        });
+        join_all(rpcs).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::AirsimClient;
+
+    #[test]
+    fn test_batch_sends_every_rpc() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("armDisarm", Value::Boolean(true));
+        server.respond("takeoff", Value::Boolean(true));
+
+        let results = async_std::task::block_on(async {
+            let client = AirsimClient::connect(server.addr(), "").await.unwrap();
+            let batch = client
+                .batch()
+                .arm_disarm(true, "Drone1")
+                .arm_disarm(true, "Drone2")
+                .take_off(20.0, "Drone1")
+                .call("simUnknown", vec![]);
+            assert_eq!(batch.len(), 4);
+            batch.send().await
+        });
+
+        // results in the order of the batch
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[2].as_ref().unwrap(), &Value::Boolean(true));
+        assert!(results[3].is_err());
+
+        assert_eq!(server.calls("armDisarm"), 2);
+        assert_eq!(server.calls("takeoff"), 1);
+        assert_eq!(server.calls("simUnknown"), 1);
+    }
+}
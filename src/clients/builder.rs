@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::NetworkResult;
+use crate::msgpack::ConnectionOptions;
+use crate::{
+    CarClient, ComputerVisionClient, Fleet, FloatEncoding, MsgPackClient, MultiRotorClient, RetryPolicy, Swarm,
+};
+
+use super::airsim_client::AirsimClient;
+
+/// Address of the AirSim RPC server on the local machine, on its default port
+const DEFAULT_ADDRESS: &str = "127.0.0.1:41451";
+
+#[derive(Debug, Clone)]
+/// Settings of a connection to the AirSim RPC server
+///
+/// Defaults to the same settings as `AirsimClient::connect`: the server on the local machine, the default vehicle
+/// with API control enabled, no timeout, no retry, F32 floats, Nagle's algorithm on and a 50 kB read buffer
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use airsim_client::{AirsimClientBuilder, RetryPolicy};
+///
+/// # async fn run() -> airsim_client::NetworkResult<()> {
+/// let drone = AirsimClientBuilder::new()
+///     .address("172.22.224.1:41451")
+///     .vehicle_name("Drone1")
+///     .connect_timeout(Duration::from_secs(5))
+///     .rpc_timeout(Duration::from_secs(30))
+///     .retry_policy(RetryPolicy::new())
+///     .tcp_nodelay(true)
+///     .connect_multirotor()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AirsimClientBuilder {
+    address: String,
+    vehicle_name: &'static str,
+    enable_api_control: bool,
+    retry_policy: RetryPolicy,
+    float_encoding: FloatEncoding,
+    connection: ConnectionOptions,
+}
+
+impl Default for AirsimClientBuilder {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS.to_owned(),
+            vehicle_name: "",
+            enable_api_control: true,
+            retry_policy: RetryPolicy::disabled(),
+            float_encoding: FloatEncoding::default(),
+            connection: ConnectionOptions::default(),
+        }
+    }
+}
+
+impl AirsimClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Address of the AirSim RPC server, `host:port`
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_owned();
+        self
+    }
+
+    /// Vehicle the clients send their commands to, empty for the default vehicle
+    pub fn vehicle_name(mut self, vehicle_name: &'static str) -> Self {
+        self.vehicle_name = vehicle_name;
+        self
+    }
+
+    /// Whether to enable API control of the vehicle once connected
+    pub fn enable_api_control(mut self, enable_api_control: bool) -> Self {
+        self.enable_api_control = enable_api_control;
+        self
+    }
+
+    /// Time to establish the TCP connection, fails with a `TimedOut` I/O error past it
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connection.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Time to wait for the response to each RPC, fails with a `TimedOut` I/O error past it
+    ///
+    /// Long running RPCs like `moveToPosition` only answer once the movement is done, keep it above their timeouts
+    pub fn rpc_timeout(mut self, rpc_timeout: Duration) -> Self {
+        self.connection.request_timeout = Some(rpc_timeout);
+        self
+    }
+
+    /// Retry policy of the RPCs, the initial ping included, see `AirsimClient::set_retry_policy`
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Width of the floats in the params, `F64` for servers rejecting F32
+    pub fn float_encoding(mut self, float_encoding: FloatEncoding) -> Self {
+        self.float_encoding = float_encoding;
+        self
+    }
+
+    /// Whether to disable Nagle's algorithm, sending small RPCs without delay
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.connection.nodelay = nodelay;
+        self
+    }
+
+    /// Size of the buffer the socket is read into, in bytes. Larger buffers take large images in fewer reads
+    pub fn read_buffer_size(mut self, read_buffer_size: usize) -> Self {
+        self.connection.read_buffer_size = read_buffer_size;
+        self
+    }
+
+    /// Connect to the server, checked with a ping
+    pub async fn connect(self) -> NetworkResult<AirsimClient> {
+        let client = MsgPackClient::connect_with(self.address.as_str(), self.connection).await?;
+        let vehicle_name = Some(self.vehicle_name).filter(|_| self.enable_api_control);
+        AirsimClient::open(client, vehicle_name, self.float_encoding, self.retry_policy).await
+    }
+
+    /// Connect to the server and control the multirotor named by `vehicle_name`
+    pub async fn connect_multirotor(self) -> NetworkResult<MultiRotorClient> {
+        let vehicle_name = self.vehicle_name;
+        let airsim_client = self.connect().await?;
+        Ok(MultiRotorClient::with_connection(Arc::new(airsim_client), vehicle_name))
+    }
+
+    /// Connect to the server and control the car named by `vehicle_name`
+    pub async fn connect_car(self) -> NetworkResult<CarClient> {
+        let vehicle_name = self.vehicle_name;
+        let airsim_client = self.connect().await?;
+        Ok(CarClient::with_connection(Arc::new(airsim_client), vehicle_name))
+    }
+
+    /// Connect to the server and control the ComputerVision vehicle named by `vehicle_name`
+    pub async fn connect_computer_vision(self) -> NetworkResult<ComputerVisionClient> {
+        let vehicle_name = self.vehicle_name;
+        let airsim_client = self.connect().await?;
+        Ok(ComputerVisionClient::with_connection(airsim_client, vehicle_name))
+    }
+
+    /// Connect to the server and control the multirotors named by `vehicle_names` as a `Swarm`
+    ///
+    /// `vehicle_name` is ignored, API control is enabled for every vehicle of the swarm instead unless disabled
+    pub async fn connect_swarm(self, vehicle_names: &[&'static str]) -> NetworkResult<Swarm> {
+        let enable_api_control = self.enable_api_control;
+        let airsim_client = self.enable_api_control(false).connect().await?;
+        Swarm::with_connection(Arc::new(airsim_client), vehicle_names, enable_api_control).await
+    }
+
+    /// Connect to the server and control the cars and multirotors named by `cars` and `multirotors` as a `Fleet`
+    ///
+    /// `vehicle_name` is ignored, API control is enabled for every vehicle of the fleet instead unless disabled
+    pub async fn connect_fleet(self, cars: &[&'static str], multirotors: &[&'static str]) -> NetworkResult<Fleet> {
+        let enable_api_control = self.enable_api_control;
+        let airsim_client = self.enable_api_control(false).connect().await?;
+        Fleet::with_connection(Arc::new(airsim_client), cars, multirotors, enable_api_control).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClientBuilder, NetworkError};
+
+    #[test]
+    fn test_rpc_timeout() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond_with("getServerVersion", |_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(Value::from(1))
+        });
+
+        async_std::task::block_on(async {
+            let client = AirsimClientBuilder::new()
+                .address(&server.addr().to_string())
+                .enable_api_control(false)
+                .rpc_timeout(Duration::from_millis(100))
+                .tcp_nodelay(true)
+                .connect()
+                .await
+                .unwrap();
+
+            match client.get_server_version().await {
+                Err(NetworkError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+                other => panic!("expected a timeout, got {other:?}"),
+            }
+            // the mock answers one request at a time, wait for the late response before the next one
+            async_std::task::sleep(Duration::from_millis(500)).await;
+            assert!(client.ping().await.unwrap());
+        });
+        assert_eq!(server.calls("enableApiControl"), 0);
+    }
+
+    #[test]
+    fn test_fleet_enables_api_control_of_its_vehicles_only() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("enableApiControl", Value::Nil);
+
+        async_std::task::block_on(async {
+            let fleet = AirsimClientBuilder::new()
+                .address(&server.addr().to_string())
+                .connect_fleet(&["Car1"], &["Drone1", "Drone2"])
+                .await
+                .unwrap();
+            assert_eq!(fleet.swarm().drones().len(), 2);
+        });
+
+        let enabled: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "enableApiControl")
+            .map(|r| r.params[1].as_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(enabled, ["Car1", "Drone1", "Drone2"]);
+    }
+}
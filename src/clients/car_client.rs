@@ -1,13 +1,20 @@
+use std::sync::Arc;
+
 use async_std::net::ToSocketAddrs;
-use msgpack_rpc::Utf8String;
 use rmpv::Value;
 
-use crate::error::NetworkResult;
+use crate::error::{NetworkError, NetworkResult};
+use crate::{
+    CompressedImage, EnvironmentState, GeoPoint, ImageRequests, ImageResponse, ImageType, KinematicsState, Middleware,
+    Pose3, RetryPolicy,
+};
 
 use super::airsim_client::AirsimClient;
+use super::api_control_guard::ApiControlGuard;
 
+#[derive(Clone)]
 pub struct CarClient {
-    airsim_client: AirsimClient,
+    airsim_client: Arc<AirsimClient>,
     vehicle_name: &'static str,
 }
 
@@ -15,11 +22,24 @@ impl CarClient {
     pub async fn connect(addrs: impl ToSocketAddrs, vehicle_name: &'static str) -> NetworkResult<Self> {
         let airsim_client = AirsimClient::connect(addrs, vehicle_name).await?;
         Ok(Self {
-            airsim_client,
+            airsim_client: Arc::new(airsim_client),
             vehicle_name,
         })
     }
 
+    /// Control another vehicle over an already established connection
+    pub(crate) fn with_connection(airsim_client: Arc<AirsimClient>, vehicle_name: &'static str) -> Self {
+        Self {
+            airsim_client,
+            vehicle_name,
+        }
+    }
+
+    /// Name of the vehicle this client sends its commands to
+    pub fn vehicle_name(&self) -> &'static str {
+        self.vehicle_name
+    }
+
     /// Reset the vehicle to its original starting state
     ///
     /// Note that you must call `enable_ap, Some(vehicle_name)i_control` and `arm_disarm` again after the call to reset
@@ -40,6 +60,47 @@ impl CarClient {
         self.airsim_client.confirm_connection().await
     }
 
+    /// Get AirSim server version
+    #[inline(always)]
+    pub async fn get_server_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_server_version().await
+    }
+
+    /// Get minimum client version required by the server
+    #[inline(always)]
+    pub async fn get_min_required_client_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_min_required_client_version().await
+    }
+
+    /// Checks that the server and this client support each other's version
+    #[inline(always)]
+    pub async fn check_compatibility(&self) -> NetworkResult<()> {
+        self.airsim_client.check_compatibility().await
+    }
+
+    /// Closes the connection, after disabling API control of the vehicle if asked to
+    ///
+    /// RPCs still in flight fail, as do the RPCs of every client sharing the connection
+    ///
+    /// args:
+    ///     disable_api_control (bool): Whether to disable API control before closing
+    #[inline(always)]
+    pub async fn close(&self, disable_api_control: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .close(disable_api_control, Some(self.vehicle_name))
+            .await
+    }
+
+    #[inline(always)]
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.airsim_client.set_retry_policy(policy)
+    }
+
+    #[inline(always)]
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.airsim_client.add_middleware(middleware)
+    }
+
     /// Enables or disables API control for vehicle corresponding to vehicle_name
     ///
     /// args:
@@ -52,6 +113,18 @@ impl CarClient {
             .await
     }
 
+    /// Enables API control until the returned guard is dropped, which disables API control
+    ///
+    /// Keep the guard alive for the whole experiment, e.g. `let _guard = client.enable_api_control_guarded().await?;`
+    pub async fn enable_api_control_guarded(&self) -> NetworkResult<ApiControlGuard> {
+        self.enable_api_control(true).await?;
+        Ok(ApiControlGuard::new(
+            Arc::clone(&self.airsim_client),
+            self.vehicle_name,
+            false,
+        ))
+    }
+
     /// Returns true if API control is established.
     ///
     /// If false (which is default) then API calls would be ignored. After a successful call
@@ -72,6 +145,20 @@ impl CarClient {
         self.airsim_client.cancel_last_task(Some(self.vehicle_name)).await
     }
 
+    /// Wait until the previous Async task is done
+    ///
+    /// Lets a task synchronize on a maneuver awaited by another task of the same client, returns true right away
+    /// if none is running and false if `timeout_sec` passed before the task completed
+    ///
+    /// args:
+    ///     timeout_sec (Option<f32>): Maximum time to wait in seconds, waits indefinitely if None
+    #[inline(always)]
+    pub async fn wait_on_last_task(&self, timeout_sec: Option<f32>) -> NetworkResult<bool> {
+        self.airsim_client
+            .wait_on_last_task(timeout_sec, Some(self.vehicle_name))
+            .await
+    }
+
     /// Returns true if API control is established.
     ///
     /// If false (which is default) then API calls would be ignored. After a successful call
@@ -85,20 +172,158 @@ impl CarClient {
         self.airsim_client.arm_disarm(arm, Some(self.vehicle_name)).await
     }
 
+    /// Get the ground truth kinematics of the vehicle
+    ///
+    /// The position inside the returned KinematicsState is in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn sim_get_ground_truth_kinematics(&self) -> NetworkResult<KinematicsState> {
+        self.airsim_client
+            .sim_get_ground_truth_kinematics(Some(self.vehicle_name))
+            .await
+    }
+
+    /// Get the ground truth environment state of the vehicle
+    ///
+    /// The environment state holds the position, geo point, gravity and air pressure, temperature and density at the
+    /// vehicle's location
+    #[inline(always)]
+    pub async fn sim_get_ground_truth_environment(&self) -> NetworkResult<EnvironmentState> {
+        self.airsim_client
+            .sim_get_ground_truth_environment(Some(self.vehicle_name))
+            .await
+    }
+
+    /// Current simulation time in nanoseconds, read from the timestamp of the car state
+    ///
+    /// All vehicles and sensors are stamped with the same clock, so this can be used to align their logs
+    pub async fn sim_time(&self) -> NetworkResult<u64> {
+        let vehicle_name = self.vehicle_name;
+        let response = self
+            .airsim_client
+            .vehicle_rpc(
+                "getCarState".into(),
+                Some(vec![Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
+            .await?;
+
+        response
+            .result
+            .ok()
+            .as_ref()
+            .and_then(Value::as_map)
+            .and_then(|state| state.iter().find(|(key, _)| key.as_str() == Some("timestamp")))
+            .and_then(|(_, timestamp)| timestamp.as_u64())
+            .ok_or_else(|| NetworkError::UnexpectedResponse {
+                method: "getCarState".to_owned(),
+                message: "the car state has no timestamp".to_owned(),
+            })
+    }
+
+    /// Returns whether the target point is visible from the perspective of the vehicle
+    ///
+    /// args:
+    ///     point (GeoPoint): Target point
+    #[inline(always)]
+    pub async fn sim_test_line_of_sight_to_point(&self, point: GeoPoint) -> NetworkResult<bool> {
+        self.airsim_client
+            .sim_test_line_of_sight_to_point(point, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Set the pose of the vehicle
+    ///
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation)
+    /// to floating point nan values
+    ///
+    /// args:
+    ///     pose (Pose3): Desired Pose of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    #[inline(always)]
+    pub async fn sim_set_vehicle_pose(&self, pose: Pose3, ignore_collision: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_vehicle_pose(pose, ignore_collision, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Get the pose of the vehicle
+    ///
+    /// The position inside the returned Pose3 is in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn sim_get_vehicle_pose(&self) -> NetworkResult<Pose3> {
+        self.airsim_client.sim_get_vehicle_pose(Some(self.vehicle_name)).await
+    }
+
+    /// Set the kinematics state of the vehicle
+    ///
+    /// args:
+    ///     state (KinematicsState): Desired kinematics state of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    #[inline(always)]
+    pub async fn sim_set_kinematics(&self, state: KinematicsState, ignore_collision: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_kinematics(state, ignore_collision, Some(self.vehicle_name))
+            .await
+    }
+
     /// Takeoff vehicle to 3m above ground. Vehicle should not be moving when this API is used
     ///
     /// Args:
     ///     timeout_sec (Option<u64>): Timeout for the vehicle to reach desired altitude
     ///     vehicle_name (Option<String>): Name of the vehicle to send this command to
     pub async fn take_off_async(&self, timeout_sec: u64) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "takeoff".into(),
-                Some(vec![Value::Integer(timeout_sec.into()), Value::String(vehicle_name)]),
+                Some(vec![
+                    Value::Integer(timeout_sec.into()),
+                    Value::String(vehicle_name.into()),
+                ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
+
+    /// Camera API
+    ///
+    /// Get a single compressed image, faster than `sim_get_images` when only the png bytes are needed
+    /// See https://microsoft.github.io/AirSim/image_apis/ for details
+    ///
+    /// Args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_image(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        external: Option<bool>,
+    ) -> NetworkResult<CompressedImage> {
+        self.airsim_client
+            .sim_get_image(Some(self.vehicle_name), camera_name, image_type, external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Get multiple images
+    /// See https://microsoft.github.io/AirSim/image_apis/ for details and examples
+    ///
+    /// Args:
+    ///     requests (ImageRequests): Images required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_images(
+        &self,
+        requests: ImageRequests,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<ImageResponse>> {
+        self.airsim_client
+            .sim_get_images(requests, Some(self.vehicle_name), external)
+            .await
+    }
 }
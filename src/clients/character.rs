@@ -0,0 +1,480 @@
+use std::collections::HashMap;
+
+use msgpack_rpc::Utf8String;
+use rmpv::Value;
+
+use crate::error::NetworkResult;
+use crate::{Pose3, Quaternion};
+
+use super::airsim_client::AirsimClient;
+
+/// Character API, to animate the humans of the ComputerVision scenarios
+///
+/// Every call takes the name of the character actor in the level, an empty name picks the first character
+impl AirsimClient {
+    /// Set the weight of a face expression, e.g. `"BlinkLeft"`
+    ///
+    /// args:
+    ///     expression_name (&str): Name of the expression, see `sim_char_get_available_face_expressions`
+    ///     value (f32): Weight of the expression, Range 0-1
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_face_expression(
+        &self,
+        expression_name: &str,
+        value: f32,
+        character_name: &str,
+    ) -> NetworkResult<()> {
+        let expression_name: Utf8String = expression_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharSetFaceExpression".into(),
+            Some(vec![
+                Value::String(expression_name),
+                Value::F32(value),
+                Value::String(character_name),
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns the weight of a face expression
+    ///
+    /// args:
+    ///     expression_name (&str): Name of the expression
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_get_face_expression(
+        &self,
+        expression_name: &str,
+        character_name: &str,
+    ) -> NetworkResult<f32> {
+        let expression_name: Utf8String = expression_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharGetFaceExpression".into(),
+            Some(vec![Value::String(expression_name), Value::String(character_name)]),
+        )
+        .await
+        .map(|response| as_f32(response.result))
+    }
+
+    /// Returns the names of the face expressions the characters support
+    pub async fn sim_char_get_available_face_expressions(&self) -> NetworkResult<Vec<String>> {
+        self.unary_rpc("simCharGetAvailableFaceExpressions".into(), None)
+            .await
+            .map(|response| match response.result {
+                Ok(Value::Array(names)) => names
+                    .iter()
+                    .filter_map(|name| name.as_str().map(str::to_owned))
+                    .collect(),
+                _ => panic!("Could not decode result from simCharGetAvailableFaceExpressions msgpack"),
+            })
+    }
+
+    /// Set how dark the skin of the character is
+    ///
+    /// args:
+    ///     value (f32): Darkness of the skin, Range 0-1
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_skin_darkness(&self, value: f32, character_name: &str) -> NetworkResult<()> {
+        self.set_character_value("simCharSetSkinDarkness", value, character_name)
+            .await
+    }
+
+    /// Returns how dark the skin of the character is, between 0 and 1
+    ///
+    /// args:
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_get_skin_darkness(&self, character_name: &str) -> NetworkResult<f32> {
+        self.get_character_value("simCharGetSkinDarkness", character_name).await
+    }
+
+    /// Set how aged the skin of the character looks
+    ///
+    /// args:
+    ///     value (f32): Ageing of the skin, Range 0-1
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_skin_ageing(&self, value: f32, character_name: &str) -> NetworkResult<()> {
+        self.set_character_value("simCharSetSkinAgeing", value, character_name)
+            .await
+    }
+
+    /// Returns how aged the skin of the character looks, between 0 and 1
+    ///
+    /// args:
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_get_skin_ageing(&self, character_name: &str) -> NetworkResult<f32> {
+        self.get_character_value("simCharGetSkinAgeing", character_name).await
+    }
+
+    /// Set the weight of a face preset, e.g. `"FACS_0"`
+    ///
+    /// args:
+    ///     preset_name (&str): Name of the preset
+    ///     value (f32): Weight of the preset, Range 0-1
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_face_preset(
+        &self,
+        preset_name: &str,
+        value: f32,
+        character_name: &str,
+    ) -> NetworkResult<()> {
+        let preset_name: Utf8String = preset_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharSetFacePreset".into(),
+            Some(vec![
+                Value::String(preset_name),
+                Value::F32(value),
+                Value::String(character_name),
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Set the weights of several face presets at once
+    ///
+    /// args:
+    ///     presets (&HashMap<String, f32>): Weight of every preset, by name
+    ///     character_name (&str): Name of the character
+    pub async fn sim_set_face_presets(
+        &self,
+        presets: &HashMap<String, f32>,
+        character_name: &str,
+    ) -> NetworkResult<()> {
+        let presets = presets
+            .iter()
+            .map(|(name, value)| (Value::String(name.as_str().into()), Value::F32(*value)))
+            .collect();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simSetFacePresets".into(),
+            Some(vec![Value::Map(presets), Value::String(character_name)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Turn the head of the character
+    ///
+    /// args:
+    ///     rotation (Quaternion): Rotation of the head relative to the body
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_head_rotation(&self, rotation: Quaternion, character_name: &str) -> NetworkResult<()> {
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharSetHeadRotation".into(),
+            Some(vec![rotation.as_msgpack(), Value::String(character_name)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns the rotation of the head of the character relative to its body
+    ///
+    /// args:
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_get_head_rotation(&self, character_name: &str) -> NetworkResult<Quaternion> {
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharGetHeadRotation".into(),
+            Some(vec![Value::String(character_name)]),
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(rotation) => Quaternion::from(rotation),
+            Err(_) => panic!("Could not decode result from simCharGetHeadRotation msgpack"),
+        })
+    }
+
+    /// Set the pose of a bone of the character's skeleton, e.g. `"hand_r"`
+    ///
+    /// args:
+    ///     bone_name (&str): Name of the bone
+    ///     pose (Pose3): Pose of the bone relative to its parent
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_set_bone_pose(
+        &self,
+        bone_name: &str,
+        pose: Pose3,
+        character_name: &str,
+    ) -> NetworkResult<()> {
+        let bone_name: Utf8String = bone_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharSetBonePose".into(),
+            Some(vec![
+                Value::String(bone_name),
+                pose.as_msgpack(),
+                Value::String(character_name),
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns the pose of a bone of the character's skeleton
+    ///
+    /// args:
+    ///     bone_name (&str): Name of the bone
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_get_bone_pose(&self, bone_name: &str, character_name: &str) -> NetworkResult<Pose3> {
+        let bone_name: Utf8String = bone_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharGetBonePose".into(),
+            Some(vec![Value::String(bone_name), Value::String(character_name)]),
+        )
+        .await
+        .map(Pose3::from)
+    }
+
+    /// Give a bone back to the animation of the character
+    ///
+    /// args:
+    ///     bone_name (&str): Name of the bone
+    ///     character_name (&str): Name of the character
+    pub async fn sim_char_reset_bone_pose(&self, bone_name: &str, character_name: &str) -> NetworkResult<()> {
+        let bone_name: Utf8String = bone_name.into();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simCharResetBonePose".into(),
+            Some(vec![Value::String(bone_name), Value::String(character_name)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Set the poses of several bones at once
+    ///
+    /// args:
+    ///     poses (&HashMap<String, Pose3>): Pose of every bone, by name
+    ///     character_name (&str): Name of the character
+    pub async fn sim_set_bone_poses(&self, poses: &HashMap<String, Pose3>, character_name: &str) -> NetworkResult<()> {
+        let poses = poses
+            .iter()
+            .map(|(name, pose)| (Value::String(name.as_str().into()), pose.as_msgpack()))
+            .collect();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simSetBonePoses".into(),
+            Some(vec![Value::Map(poses), Value::String(character_name)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns the poses of several bones, by name
+    ///
+    /// args:
+    ///     bone_names (&[&str]): Names of the bones
+    ///     character_name (&str): Name of the character
+    pub async fn sim_get_bone_poses(
+        &self,
+        bone_names: &[&str],
+        character_name: &str,
+    ) -> NetworkResult<HashMap<String, Pose3>> {
+        let bone_names = bone_names.iter().map(|name| Value::String((*name).into())).collect();
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            "simGetBonePoses".into(),
+            Some(vec![Value::Array(bone_names), Value::String(character_name)]),
+        )
+        .await
+        .map(|response| match response.result {
+            Ok(Value::Map(poses)) => poses
+                .into_iter()
+                .map(|(name, pose)| (name.as_str().unwrap_or_default().to_owned(), Pose3::from(pose)))
+                .collect(),
+            _ => panic!("Could not decode result from simGetBonePoses msgpack"),
+        })
+    }
+
+    async fn set_character_value(&self, method: &str, value: f32, character_name: &str) -> NetworkResult<()> {
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(
+            method.into(),
+            Some(vec![Value::F32(value), Value::String(character_name)]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn get_character_value(&self, method: &str, character_name: &str) -> NetworkResult<f32> {
+        let character_name: Utf8String = character_name.into();
+
+        self.unary_rpc(method.into(), Some(vec![Value::String(character_name)]))
+            .await
+            .map(|response| as_f32(response.result))
+    }
+}
+
+fn as_f32(result: Result<Value, Value>) -> f32 {
+    match result.ok().and_then(|value| value.as_f64()) {
+        Some(value) => value as f32,
+        None => panic!("Could not decode result from character msgpack"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClient, Pose3, Position3, Quaternion};
+
+    fn params(server: &MockAirsimServer, method: &str) -> Vec<Value> {
+        server
+            .requests()
+            .into_iter()
+            .find(|request| request.method == method)
+            .map(|request| request.params)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_setters_send_args_in_order() {
+        let server = MockAirsimServer::start().unwrap();
+        for method in [
+            "simCharSetFaceExpression",
+            "simCharSetSkinDarkness",
+            "simCharSetSkinAgeing",
+            "simCharSetFacePreset",
+            "simCharSetHeadRotation",
+            "simCharSetBonePose",
+            "simCharResetBonePose",
+            "simSetFacePresets",
+            "simSetBonePoses",
+        ] {
+            server.respond(method, Value::Nil);
+        }
+        let pose = Pose3::new(Position3::new(1.0, 2.0, 3.0), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        let rotation = Quaternion::new(0.0, 0.0, 0.0, 1.0);
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            client
+                .sim_char_set_face_expression("BlinkLeft", 0.5, "Human")
+                .await
+                .unwrap();
+            client.sim_char_set_skin_darkness(0.25, "Human").await.unwrap();
+            client.sim_char_set_skin_ageing(0.75, "Human").await.unwrap();
+            client.sim_char_set_face_preset("FACS_0", 1.0, "Human").await.unwrap();
+            client.sim_char_set_head_rotation(rotation, "Human").await.unwrap();
+            client.sim_char_set_bone_pose("hand_r", pose, "Human").await.unwrap();
+            client.sim_char_reset_bone_pose("hand_r", "Human").await.unwrap();
+            client
+                .sim_set_face_presets(&HashMap::from([("FACS_1".to_owned(), 0.5)]), "Human")
+                .await
+                .unwrap();
+            client
+                .sim_set_bone_poses(&HashMap::from([("head".to_owned(), pose)]), "Human")
+                .await
+                .unwrap();
+        });
+
+        let human = Value::from("Human");
+        assert_eq!(
+            params(&server, "simCharSetFaceExpression"),
+            [Value::from("BlinkLeft"), Value::F32(0.5), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharSetSkinDarkness"),
+            [Value::F32(0.25), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharSetSkinAgeing"),
+            [Value::F32(0.75), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharSetFacePreset"),
+            [Value::from("FACS_0"), Value::F32(1.0), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharSetHeadRotation"),
+            [rotation.as_msgpack(), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharSetBonePose"),
+            [Value::from("hand_r"), pose.as_msgpack(), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simCharResetBonePose"),
+            [Value::from("hand_r"), human.clone()]
+        );
+        assert_eq!(
+            params(&server, "simSetFacePresets"),
+            [
+                Value::Map(vec![(Value::from("FACS_1"), Value::F32(0.5))]),
+                human.clone()
+            ]
+        );
+        assert_eq!(
+            params(&server, "simSetBonePoses"),
+            [Value::Map(vec![(Value::from("head"), pose.as_msgpack())]), human]
+        );
+    }
+
+    #[test]
+    fn test_getters_decode_results() {
+        let server = MockAirsimServer::start().unwrap();
+        let pose = Pose3::new(Position3::new(1.0, 2.0, 3.0), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        server.respond("simCharGetFaceExpression", Value::F64(0.5));
+        server.respond("simCharGetSkinDarkness", Value::F64(0.25));
+        server.respond("simCharGetSkinAgeing", Value::F64(0.75));
+        server.respond(
+            "simCharGetAvailableFaceExpressions",
+            Value::Array(vec![Value::from("BlinkLeft"), Value::from("Smile")]),
+        );
+        server.respond(
+            "simCharGetHeadRotation",
+            Quaternion::new(0.0, 0.0, 0.0, 1.0).as_msgpack(),
+        );
+        server.respond("simCharGetBonePose", pose.as_msgpack());
+        server.respond(
+            "simGetBonePoses",
+            Value::Map(vec![(Value::from("head"), pose.as_msgpack())]),
+        );
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+
+            let expression = client.sim_char_get_face_expression("BlinkLeft", "Human").await.unwrap();
+            assert_eq!(expression, 0.5);
+            assert_eq!(client.sim_char_get_skin_darkness("Human").await.unwrap(), 0.25);
+            assert_eq!(client.sim_char_get_skin_ageing("Human").await.unwrap(), 0.75);
+            assert_eq!(
+                client.sim_char_get_available_face_expressions().await.unwrap(),
+                ["BlinkLeft", "Smile"]
+            );
+            assert_eq!(client.sim_char_get_head_rotation("Human").await.unwrap().z, 1.0);
+
+            let bone_pose = client.sim_char_get_bone_pose("hand_r", "Human").await.unwrap();
+            assert_eq!(bone_pose.position.z, 3.0);
+            let bone_poses = client.sim_get_bone_poses(&["head"], "Human").await.unwrap();
+            assert_eq!(bone_poses["head"].position.y, 2.0);
+        });
+
+        assert_eq!(
+            params(&server, "simCharGetFaceExpression"),
+            [Value::from("BlinkLeft"), Value::from("Human")]
+        );
+        assert_eq!(
+            params(&server, "simGetBonePoses"),
+            [Value::Array(vec![Value::from("head")]), Value::from("Human")]
+        );
+    }
+}
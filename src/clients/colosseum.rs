@@ -0,0 +1,115 @@
+use msgpack_rpc::Utf8String;
+use rmpv::Value;
+
+use crate::error::{NetworkError, NetworkResult};
+use crate::{GpuLidarData, Pose3};
+
+use super::airsim_client::AirsimClient;
+
+/// RPCs of the AirSim forks, from Cosys-AirSim, absent from Microsoft AirSim
+///
+/// Servers without them, like Microsoft AirSim, answer them with a "rpc method not found" server error.
+/// Cosys-AirSim changes the shape of its messages between releases, responses that can't be decoded fail with
+/// `NetworkError::UnexpectedResponse` rather than being guessed at
+impl AirsimClient {
+    /// Returns the names of the objects of the instance segmentation, in the order of their segmentation colors
+    pub async fn sim_list_instance_segmentation_objects(&self) -> NetworkResult<Vec<String>> {
+        self.unary_rpc("simListInstanceSegmentationObjects".into(), None)
+            .await
+            .and_then(|response| match response.result {
+                Ok(Value::Array(names)) => Ok(names
+                    .iter()
+                    .filter_map(|name| name.as_str().map(str::to_owned))
+                    .collect()),
+                _ => Err(unexpected("simListInstanceSegmentationObjects", "not a list of names")),
+            })
+    }
+
+    /// Returns the poses of the objects of the instance segmentation, in the order of
+    /// `sim_list_instance_segmentation_objects`
+    ///
+    /// args:
+    ///     ned (bool): Whether to return the poses in the NED frame of AirSim, else in the frame of Unreal
+    ///     only_visible (bool): Whether to only list the objects visible to a camera
+    pub async fn sim_list_instance_segmentation_poses(
+        &self,
+        ned: bool,
+        only_visible: bool,
+    ) -> NetworkResult<Vec<Pose3>> {
+        self.unary_rpc(
+            "simListInstanceSegmentationPoses".into(),
+            Some(vec![Value::Boolean(ned), Value::Boolean(only_visible)]),
+        )
+        .await
+        .and_then(|response| match response.result {
+            Ok(Value::Array(poses)) if poses.iter().all(is_pose) => Ok(poses.into_iter().map(Pose3::from).collect()),
+            _ => Err(unexpected("simListInstanceSegmentationPoses", "not a list of poses")),
+        })
+    }
+
+    /// Get the last scan of a GPU lidar
+    ///
+    /// args:
+    ///     lidar_name (Option<&str>): Name of the lidar, specified in settings.json. Defaults to the first one
+    ///     vehicle_name (Option<&str>): Name of the vehicle carrying the lidar
+    pub(crate) async fn get_gpu_lidar_data(
+        &self,
+        lidar_name: Option<&str>,
+        vehicle_name: Option<&str>,
+    ) -> NetworkResult<GpuLidarData> {
+        let lidar_name: Utf8String = lidar_name.unwrap_or("").into();
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "getGPULidarData".into(),
+            Some(vec![Value::String(lidar_name), Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(GpuLidarData::from)
+    }
+}
+
+/// A pose is a map of position and orientation, whatever else the fork adds to it
+fn is_pose(value: &Value) -> bool {
+    matches!(value.as_map(), Some(fields) if fields.len() >= 2)
+}
+
+fn unexpected(method: &str, message: &str) -> NetworkError {
+    NetworkError::UnexpectedResponse {
+        method: method.to_owned(),
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClient, NetworkError, Pose3};
+
+    #[test]
+    fn test_instance_segmentation_decoding() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond(
+            "simListInstanceSegmentationObjects",
+            Value::Array(vec![Value::from("Cube"), Value::from("Sphere")]),
+        );
+        server.respond("simListInstanceSegmentationPoses", Value::Map(vec![]));
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+            let objects = client.sim_list_instance_segmentation_objects().await.unwrap();
+            assert_eq!(objects, ["Cube", "Sphere"]);
+
+            let poses = client.sim_list_instance_segmentation_poses(true, false).await;
+            assert!(matches!(poses, Err(NetworkError::UnexpectedResponse { .. })));
+
+            server.respond(
+                "simListInstanceSegmentationPoses",
+                Value::Array(vec![Pose3::identity().as_msgpack(), Pose3::identity().as_msgpack()]),
+            );
+            let poses = client.sim_list_instance_segmentation_poses(true, false).await.unwrap();
+            assert_eq!(poses.len(), 2);
+        });
+    }
+}
@@ -0,0 +1,537 @@
+use async_std::net::ToSocketAddrs;
+
+use crate::error::NetworkResult;
+use crate::{
+    CameraInfo, CompressedImage, DistortionParams, ImageRequests, ImageType, Middleware, Pose3, RetryPolicy, Snapshot,
+    StereoPair, Vector3,
+};
+
+use super::airsim_client::AirsimClient;
+
+/// Client for AirSim's ComputerVision mode
+///
+/// The ComputerVision vehicle has no physics and no flight controller, it is moved around by setting its pose.
+/// This makes it the vehicle of choice to capture datasets, so only pose, camera and image APIs are exposed.
+pub struct ComputerVisionClient {
+    airsim_client: AirsimClient,
+    vehicle_name: &'static str,
+}
+
+impl ComputerVisionClient {
+    pub async fn connect(addrs: impl ToSocketAddrs, vehicle_name: &'static str) -> NetworkResult<Self> {
+        let airsim_client = AirsimClient::connect(addrs, vehicle_name).await?;
+        Ok(Self::with_connection(airsim_client, vehicle_name))
+    }
+
+    /// Control the vehicle over an already established connection
+    pub(crate) fn with_connection(airsim_client: AirsimClient, vehicle_name: &'static str) -> Self {
+        Self {
+            airsim_client,
+            vehicle_name,
+        }
+    }
+
+    /// Reset the vehicle to its original starting state
+    #[inline(always)]
+    pub async fn reset(&self) -> NetworkResult<bool> {
+        self.airsim_client.reset().await
+    }
+
+    /// If connection is established then this call will return `True` otherwise
+    /// the request will be blocked until timeout (default value)
+    #[inline(always)]
+    pub async fn ping(&self) -> NetworkResult<bool> {
+        self.airsim_client.ping().await
+    }
+
+    #[inline(always)]
+    pub async fn confirm_connection(&self) -> NetworkResult<bool> {
+        self.airsim_client.confirm_connection().await
+    }
+
+    /// Get AirSim server version
+    #[inline(always)]
+    pub async fn get_server_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_server_version().await
+    }
+
+    /// Get minimum client version required by the server
+    #[inline(always)]
+    pub async fn get_min_required_client_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_min_required_client_version().await
+    }
+
+    /// Checks that the server and this client support each other's version
+    #[inline(always)]
+    pub async fn check_compatibility(&self) -> NetworkResult<()> {
+        self.airsim_client.check_compatibility().await
+    }
+
+    /// Closes the connection, after disabling API control of the vehicle if asked to
+    ///
+    /// RPCs still in flight fail, as do the RPCs of every client sharing the connection
+    ///
+    /// args:
+    ///     disable_api_control (bool): Whether to disable API control before closing
+    #[inline(always)]
+    pub async fn close(&self, disable_api_control: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .close(disable_api_control, Some(self.vehicle_name))
+            .await
+    }
+
+    #[inline(always)]
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.airsim_client.set_retry_policy(policy)
+    }
+
+    #[inline(always)]
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.airsim_client.add_middleware(middleware)
+    }
+
+    /// Enables or disables API control for the vehicle
+    ///
+    /// args:
+    ///     is_enabled (bool): True to enable, False to disable API control
+    #[inline(always)]
+    pub async fn enable_api_control(&self, is_enabled: bool) -> NetworkResult<bool> {
+        self.airsim_client
+            .enable_api_control(is_enabled, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Set the pose of the vehicle
+    ///
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation)
+    /// to floating point nan values
+    ///
+    /// args:
+    ///     pose (Pose3): Desired Pose of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    #[inline(always)]
+    pub async fn sim_set_vehicle_pose(&self, pose: Pose3, ignore_collision: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_vehicle_pose(pose, ignore_collision, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Get the pose of the vehicle
+    ///
+    /// The position inside the returned Pose3 is in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn sim_get_vehicle_pose(&self) -> NetworkResult<Pose3> {
+        self.airsim_client.sim_get_vehicle_pose(Some(self.vehicle_name)).await
+    }
+
+    /// Camera API
+    ///
+    /// Control the pose of a selected camera, relative to the vehicle
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     pose (Pose3): Pose representing the desired position and orientation of the camera
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_camera_pose(
+        &self,
+        camera_name: &str,
+        pose: Pose3,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_camera_pose(camera_name, pose, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Get details about the camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_camera_info(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<CameraInfo> {
+        self.airsim_client
+            .sim_get_camera_info(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Control the field of view of a selected camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     fov_degrees (f32): Value of field of view in degrees
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_camera_fov(
+        &self,
+        camera_name: &str,
+        fov_degrees: f32,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_camera_fov(camera_name, fov_degrees, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the distance the camera is focused at. Takes effect once manual focus is enabled
+    ///
+    /// args:
+    ///     focus_distance_cm (f32): Focus distance in cm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focus_distance(
+        &self,
+        focus_distance_cm: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focus_distance(focus_distance_cm, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the distance the camera is focused at, in cm
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focus_distance(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focus_distance(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the aperture of the camera, lower f-stops give a shallower depth of field
+    ///
+    /// args:
+    ///     focus_aperture (f32): Aperture as f-stop
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focus_aperture(
+        &self,
+        focus_aperture: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focus_aperture(focus_aperture, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the aperture of the camera, as f-stop
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focus_aperture(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focus_aperture(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the camera between manual focus and auto focus
+    ///
+    /// args:
+    ///     enable (bool): True to focus at the distance set by `sim_set_focus_distance`, False for auto focus
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_enable_manual_focus(
+        &self,
+        enable: bool,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_enable_manual_focus(enable, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the lens presets of a cine camera, e.g. `"12mm"` or `"85mm Prime f/1.8"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_preset_lens_settings(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        self.airsim_client
+            .sim_get_preset_lens_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the lens of a cine camera: focal and aperture ranges and diaphragm blades
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_lens_settings(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<String> {
+        self.airsim_client
+            .sim_get_lens_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the lens of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_lens_settings (&str): Name of the preset, see `sim_get_preset_lens_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_preset_lens_settings(
+        &self,
+        preset_lens_settings: &str,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_preset_lens_settings(preset_lens_settings, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the filmback presets of a cine camera, e.g. `"35mm Full Aperture"` or `"Super 35mm"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_preset_filmback_settings(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        self.airsim_client
+            .sim_get_preset_filmback_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the filmback, i.e. the sensor, of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_filmback_settings (&str): Name of the preset, see `sim_get_preset_filmback_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_preset_filmback_settings(
+        &self,
+        preset_filmback_settings: &str,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_preset_filmback_settings(preset_filmback_settings, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the filmback of a cine camera: sensor width, height and aspect ratio
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_filmback_settings(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<String> {
+        self.airsim_client
+            .sim_get_filmback_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the sensor size of a cine camera, e.g. to match the sensor of a real camera
+    ///
+    /// args:
+    ///     sensor_width (f32): Width of the sensor in mm
+    ///     sensor_height (f32): Height of the sensor in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_filmback_settings(
+        &self,
+        sensor_width: f32,
+        sensor_height: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_filmback_settings(
+                sensor_width,
+                sensor_height,
+                camera_name,
+                Some(self.vehicle_name),
+                external,
+            )
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the focal length of a cine camera, in mm
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focal_length(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focal_length(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the focal length of a cine camera, the field of view follows from it and the filmback
+    ///
+    /// args:
+    ///     focal_length (f32): Focal length in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focal_length(
+        &self,
+        focal_length: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focal_length(focal_length, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the lens distortion of a camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_distortion_params(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<DistortionParams> {
+        self.airsim_client
+            .sim_get_distortion_params(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the lens distortion of a camera, e.g. the one calibrated on a real camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     params (DistortionParams): Coefficients of the distortion
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_distortion_params(
+        &self,
+        camera_name: &str,
+        params: DistortionParams,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_distortion_params(camera_name, params, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns binary string literal of compressed png image in presented as an vector of bytes
+    ///
+    /// Returns bytes of png format image which can be dumped into abinary file to create .png image
+    /// See https://microsoft.github.io/AirSim/image_apis/ for details
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_image(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        external: Option<bool>,
+    ) -> NetworkResult<CompressedImage> {
+        self.airsim_client
+            .sim_get_image(Some(self.vehicle_name), camera_name, image_type, external)
+            .await
+    }
+
+    /// Captures images and the ground truth kinematics of the vehicle at the same simulation tick,
+    /// pausing the simulation while both are queried
+    ///
+    /// args:
+    ///     requests (ImageRequests): Images required
+    #[inline(always)]
+    pub async fn capture_snapshot(&self, requests: ImageRequests) -> NetworkResult<Snapshot> {
+        self.airsim_client
+            .capture_snapshot(requests, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Captures the images of two cameras in a single request, with the baseline between them
+    ///
+    /// args:
+    ///     left_camera (&str): Name of the left camera
+    ///     right_camera (&str): Name of the right camera
+    ///     image_type (ImageType): Type of image required from both cameras
+    #[inline(always)]
+    pub async fn capture_stereo(
+        &self,
+        left_camera: &str,
+        right_camera: &str,
+        image_type: ImageType,
+    ) -> NetworkResult<StereoPair> {
+        self.airsim_client
+            .capture_stereo(left_camera, right_camera, image_type, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Turns a camera of the vehicle towards a point, keeping the camera at its mounting position
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to turn
+    ///     target (Vector3): Point to look at, in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn camera_look_at(&self, camera_name: &str, target: Vector3) -> NetworkResult<()> {
+        self.airsim_client
+            .camera_look_at(camera_name, target, Some(self.vehicle_name))
+            .await
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use futures::future::join_all;
+
+use crate::error::NetworkResult;
+use crate::{
+    AirsimClientBuilder, CarClient, CompressedImage, ImageRequests, ImageResponse, ImageType, MultiRotorClient, Pose3,
+    Swarm, SwarmResults,
+};
+
+use super::airsim_client::AirsimClient;
+
+/// Client of a single vehicle of a `Fleet`, whatever its type
+#[derive(Clone)]
+pub enum Vehicle {
+    Car(CarClient),
+    MultiRotor(MultiRotorClient),
+}
+
+impl Vehicle {
+    /// Name of the vehicle this client sends its commands to
+    pub fn vehicle_name(&self) -> &'static str {
+        match self {
+            Vehicle::Car(car) => car.vehicle_name(),
+            Vehicle::MultiRotor(drone) => drone.vehicle_name(),
+        }
+    }
+
+    /// Client of the vehicle if it is a car
+    pub fn as_car(&self) -> Option<&CarClient> {
+        match self {
+            Vehicle::Car(car) => Some(car),
+            Vehicle::MultiRotor(_) => None,
+        }
+    }
+
+    /// Client of the vehicle if it is a multirotor
+    pub fn as_multirotor(&self) -> Option<&MultiRotorClient> {
+        match self {
+            Vehicle::Car(_) => None,
+            Vehicle::MultiRotor(drone) => Some(drone),
+        }
+    }
+
+    /// Enables or disables API control for the vehicle
+    ///
+    /// args:
+    ///     is_enabled (bool): True to enable, False to disable API control
+    pub async fn enable_api_control(&self, is_enabled: bool) -> NetworkResult<bool> {
+        match self {
+            Vehicle::Car(car) => car.enable_api_control(is_enabled).await,
+            Vehicle::MultiRotor(drone) => drone.enable_api_control(is_enabled).await,
+        }
+    }
+
+    /// Get the pose of the vehicle
+    ///
+    /// The position inside the returned Pose3 is in the frame of the vehicle's starting point
+    pub async fn sim_get_vehicle_pose(&self) -> NetworkResult<Pose3> {
+        match self {
+            Vehicle::Car(car) => car.sim_get_vehicle_pose().await,
+            Vehicle::MultiRotor(drone) => drone.sim_get_vehicle_pose().await,
+        }
+    }
+
+    /// Set the pose of the vehicle
+    ///
+    /// args:
+    ///     pose (Pose3): Desired Pose of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    pub async fn sim_set_vehicle_pose(&self, pose: Pose3, ignore_collision: bool) -> NetworkResult<()> {
+        match self {
+            Vehicle::Car(car) => car.sim_set_vehicle_pose(pose, ignore_collision).await,
+            Vehicle::MultiRotor(drone) => drone.sim_set_vehicle_pose(pose, ignore_collision).await,
+        }
+    }
+
+    /// Get a single compressed image from a camera of the vehicle
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera
+    ///     image_type (ImageType): Type of image required
+    pub async fn sim_get_image(&self, camera_name: &str, image_type: ImageType) -> NetworkResult<CompressedImage> {
+        match self {
+            Vehicle::Car(car) => car.sim_get_image(camera_name, image_type, None).await,
+            Vehicle::MultiRotor(drone) => drone.sim_get_image(camera_name, image_type, None).await,
+        }
+    }
+
+    /// Get multiple images from the cameras of the vehicle
+    ///
+    /// args:
+    ///     requests (ImageRequests): Images required
+    pub async fn sim_get_images(&self, requests: ImageRequests) -> NetworkResult<Vec<ImageResponse>> {
+        match self {
+            Vehicle::Car(car) => car.sim_get_images(requests, None).await,
+            Vehicle::MultiRotor(drone) => drone.sim_get_images(requests, None).await,
+        }
+    }
+}
+
+/// Cars and multirotors controlled over one shared connection, e.g. for a drone observing traffic
+///
+/// Like `Swarm`, commands sent to the whole fleet run concurrently and report the result of each vehicle separately.
+/// Type specific commands go through `car` and `multirotor`, or `swarm` for all multirotors at once.
+pub struct Fleet {
+    airsim_client: Arc<AirsimClient>,
+    swarm: Swarm,
+    vehicles: Vec<Vehicle>,
+}
+
+impl Fleet {
+    /// Connect to AirSim and enable API control for every vehicle
+    ///
+    /// Use `AirsimClientBuilder::connect_fleet` for other connection settings
+    ///
+    /// args:
+    ///     addrs (&str): Address of the AirSim server
+    ///     cars (&[&str]): Names of the cars, as in settings.json
+    ///     multirotors (&[&str]): Names of the multirotors, as in settings.json
+    pub async fn connect(addrs: &str, cars: &[&'static str], multirotors: &[&'static str]) -> NetworkResult<Self> {
+        AirsimClientBuilder::new()
+            .address(addrs)
+            .connect_fleet(cars, multirotors)
+            .await
+    }
+
+    /// Control the vehicles over an already established connection
+    pub(crate) async fn with_connection(
+        airsim_client: Arc<AirsimClient>,
+        cars: &[&'static str],
+        multirotors: &[&'static str],
+        enable_api_control: bool,
+    ) -> NetworkResult<Self> {
+        let cars: Vec<_> = cars
+            .iter()
+            .map(|name| CarClient::with_connection(Arc::clone(&airsim_client), name))
+            .collect();
+        if enable_api_control {
+            for car in &cars {
+                car.enable_api_control(true).await?;
+            }
+        }
+        let swarm = Swarm::with_connection(Arc::clone(&airsim_client), multirotors, enable_api_control).await?;
+
+        let multirotors = swarm.drones().iter().cloned().map(Vehicle::MultiRotor);
+        Ok(Self {
+            vehicles: cars.into_iter().map(Vehicle::Car).chain(multirotors).collect(),
+            airsim_client,
+            swarm,
+        })
+    }
+
+    /// Clients of all vehicles in the fleet, cars first
+    pub fn vehicles(&self) -> &[Vehicle] {
+        &self.vehicles
+    }
+
+    /// Multirotors of the fleet, to command them all at once
+    pub fn swarm(&self) -> &Swarm {
+        &self.swarm
+    }
+
+    /// Client of a single vehicle in the fleet
+    pub fn vehicle(&self, vehicle_name: &str) -> Option<&Vehicle> {
+        self.vehicles
+            .iter()
+            .find(|vehicle| vehicle.vehicle_name() == vehicle_name)
+    }
+
+    /// Client of a single car in the fleet
+    pub fn car(&self, vehicle_name: &str) -> Option<&CarClient> {
+        self.vehicle(vehicle_name).and_then(Vehicle::as_car)
+    }
+
+    /// Client of a single multirotor in the fleet
+    pub fn multirotor(&self, vehicle_name: &str) -> Option<&MultiRotorClient> {
+        self.vehicle(vehicle_name).and_then(Vehicle::as_multirotor)
+    }
+
+    /// Reset all vehicles to their original starting state and enable API control again
+    pub async fn reset(&self) -> NetworkResult<bool> {
+        let reset = self.airsim_client.reset().await?;
+        for vehicle in &self.vehicles {
+            vehicle.enable_api_control(true).await?;
+        }
+        Ok(reset)
+    }
+
+    /// Get the pose of all vehicles
+    pub async fn get_poses(&self) -> SwarmResults<Pose3> {
+        let results = join_all(self.vehicles.iter().map(Vehicle::sim_get_vehicle_pose)).await;
+        self.zip_names(results)
+    }
+
+    /// Get the same images from the cameras of every vehicle
+    ///
+    /// args:
+    ///     requests (ImageRequests): Images required from each vehicle
+    pub async fn get_images(&self, requests: ImageRequests) -> SwarmResults<Vec<ImageResponse>> {
+        let results = join_all(
+            self.vehicles
+                .iter()
+                .map(|vehicle| vehicle.sim_get_images(requests.clone())),
+        )
+        .await;
+        self.zip_names(results)
+    }
+
+    fn zip_names<T>(&self, results: Vec<NetworkResult<T>>) -> SwarmResults<T> {
+        self.vehicles.iter().map(Vehicle::vehicle_name).zip(results).collect()
+    }
+}
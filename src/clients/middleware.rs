@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rmpv::Value;
+
+use crate::NetworkError;
+
+/// Hooks run by `AirsimClient` around every RPC it sends, e.g. to collect metrics, rate limit or rewrite requests
+///
+/// Every hook has an empty default, implement only the ones needed. Middlewares run in the order they were added,
+/// once per attempt if the RPC is retried.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the request is sent. Awaiting delays the request, changing `params` changes what is sent
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC, e.g. `getMultirotorState`
+    ///     params (&mut Vec<Value>): Arguments of the RPC
+    async fn on_request(&self, _method: &str, _params: &mut Vec<Value>) {}
+
+    /// Called with the result of every successful RPC
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC
+    ///     result (&Value): Result sent by the server
+    ///     latency (Duration): Time between sending the request and receiving the response
+    fn on_response(&self, _method: &str, _result: &Value, _latency: Duration) {}
+
+    /// Called with the error of every failed RPC, including errors reported by the server
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC
+    ///     error (&NetworkError): Reason of the failure
+    ///     latency (Duration): Time between sending the request and the failure
+    fn on_error(&self, _method: &str, _error: &NetworkError, _latency: Duration) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClient, Middleware, NetworkError};
+
+    #[derive(Default)]
+    struct Counter {
+        responses: AtomicUsize,
+        errors: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Middleware for Arc<Counter> {
+        async fn on_request(&self, method: &str, params: &mut Vec<Value>) {
+            if method == "simRunConsoleCommand" {
+                params[0] = Value::from("stat unit");
+            }
+        }
+
+        fn on_response(&self, _method: &str, _result: &Value, _latency: Duration) {
+            self.responses.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_error(&self, _method: &str, _error: &NetworkError, _latency: Duration) {
+            self.errors.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_middleware_hooks() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("simRunConsoleCommand", Value::Boolean(true));
+        let counter = Arc::new(Counter::default());
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(server.addr(), "").await.unwrap();
+            client.add_middleware(Arc::clone(&counter));
+
+            assert!(client.sim_run_console_command("stat fps").await.unwrap());
+            assert!(client.sim_is_pause().await.is_err());
+        });
+
+        assert_eq!(counter.responses.load(Ordering::SeqCst), 1);
+        assert_eq!(counter.errors.load(Ordering::SeqCst), 1);
+        assert_eq!(server.requests()[2].params, vec![Value::from("stat unit")]);
+    }
+}
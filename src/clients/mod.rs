@@ -1,3 +1,16 @@
 pub(crate) mod airsim_client;
+pub(crate) mod api_control_guard;
+pub(crate) mod batch;
+pub(crate) mod builder;
 pub(crate) mod car_client;
+#[cfg(feature = "character")]
+pub(crate) mod character;
+#[cfg(feature = "colosseum")]
+pub(crate) mod colosseum;
+pub(crate) mod computer_vision_client;
+pub(crate) mod fleet;
+pub(crate) mod middleware;
 pub(crate) mod multi_rotor_client;
+#[cfg(feature = "racing")]
+pub(crate) mod racing;
+pub(crate) mod swarm;
@@ -1,35 +1,71 @@
 use core::panic;
-
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_std::task;
+use futures::future::{self, join_all, FutureExt};
+use futures::stream::{Stream, StreamExt};
+use futures::{pin_mut, select};
 use msgpack_rpc::Utf8String;
 use rmpv::Value;
 
+use crate::periodic;
+use crate::safety::CollisionWatcher;
 use crate::types::drive_train::DrivetrainType;
+use crate::types::environment::EnvironmentState;
 use crate::types::gains::AngularControllerGains;
 use crate::types::geopoint::GeoPoint;
-use crate::types::image::ImageRequests;
-use crate::types::multi_rotor_state::MultiRotorState;
-use crate::types::pose::{Orientation2, Orientation3, Position3, Velocity3};
+use crate::types::image::{ImageRequest, ImageRequests, ImageResponse};
+use crate::types::maneuver::ManeuverOutcome;
+use crate::types::move_options::MoveOptions;
+use crate::types::multi_rotor_state::{LandedState, MultiRotorState};
+use crate::types::pose::{KinematicsState, Orientation2, Orientation3, Pose3, Position3, Velocity3};
 use crate::types::pwm::PWM;
 use crate::types::rc_data::RCData;
-use crate::types::sensors::{BarometerData, DistanceSensorData, GpsData, ImuData, MagnetometerData};
+use crate::types::sensors::{BarometerData, DistanceSensorData, GpsData, ImuData, LidarData, MagnetometerData};
 use crate::types::yaw_mode::YawMode;
+#[cfg(feature = "serde")]
+use crate::SensorInfo;
 use crate::{error::NetworkResult, NetworkError};
-use crate::{CompressedImage, ImageType, LinearControllerGains, Path, RotorStates, Velocity2};
+use crate::{
+    AglAltitude, AglConfig, CameraInfo, CollisionInfo, CompressedImage, DetectionInfo, DistortionParams, ImageType,
+    LinearControllerGains, Middleware, Path, PathEvent, PathTracker, PolarScan, RetryPolicy, RotorStates, Snapshot,
+    StereoPair, Vector3, Velocity2, Wrench,
+};
 
 use super::airsim_client::AirsimClient;
+use super::api_control_guard::ApiControlGuard;
+
+/// Interval at which the multirotor state is polled while waiting for a maneuver
+const MANEUVER_POLL_PERIOD: Duration = Duration::from_millis(100);
 
+#[derive(Clone)]
 pub struct MultiRotorClient {
-    airsim_client: AirsimClient,
+    airsim_client: Arc<AirsimClient>,
     vehicle_name: &'static str,
 }
 
 impl MultiRotorClient {
+    /// Connects with the default settings, see `AirsimClientBuilder::connect_multirotor` for timeouts, retries and the
+    /// settings of the socket
     pub async fn connect(addrs: &str, vehicle_name: &'static str) -> NetworkResult<Self> {
         let airsim_client = AirsimClient::connect(addrs, vehicle_name).await?;
-        Ok(Self {
+        Ok(Self::with_connection(Arc::new(airsim_client), vehicle_name))
+    }
+
+    /// Control another vehicle over an already established connection
+    pub(crate) fn with_connection(airsim_client: Arc<AirsimClient>, vehicle_name: &'static str) -> Self {
+        Self {
             airsim_client,
             vehicle_name,
-        })
+        }
+    }
+
+    /// Name of the vehicle this client sends its commands to
+    pub fn vehicle_name(&self) -> &'static str {
+        self.vehicle_name
     }
 
     /// Reset the vehicle to its original starting state
@@ -52,6 +88,47 @@ impl MultiRotorClient {
         self.airsim_client.confirm_connection().await
     }
 
+    /// Get AirSim server version
+    #[inline(always)]
+    pub async fn get_server_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_server_version().await
+    }
+
+    /// Get minimum client version required by the server
+    #[inline(always)]
+    pub async fn get_min_required_client_version(&self) -> NetworkResult<u64> {
+        self.airsim_client.get_min_required_client_version().await
+    }
+
+    /// Checks that the server and this client support each other's version
+    #[inline(always)]
+    pub async fn check_compatibility(&self) -> NetworkResult<()> {
+        self.airsim_client.check_compatibility().await
+    }
+
+    /// Closes the connection, after disabling API control of the vehicle if asked to
+    ///
+    /// RPCs still in flight fail, as do the RPCs of every client sharing the connection
+    ///
+    /// args:
+    ///     disable_api_control (bool): Whether to disable API control before closing
+    #[inline(always)]
+    pub async fn close(&self, disable_api_control: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .close(disable_api_control, Some(self.vehicle_name))
+            .await
+    }
+
+    #[inline(always)]
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.airsim_client.set_retry_policy(policy)
+    }
+
+    #[inline(always)]
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.airsim_client.add_middleware(middleware)
+    }
+
     /// Enables or disables API control for vehicle corresponding to vehicle_name
     ///
     /// args:
@@ -64,6 +141,18 @@ impl MultiRotorClient {
             .await
     }
 
+    /// Enables API control until the returned guard is dropped, which disarms the vehicle and disables API control
+    ///
+    /// Keep the guard alive for the whole experiment, e.g. `let _guard = client.enable_api_control_guarded().await?;`
+    pub async fn enable_api_control_guarded(&self) -> NetworkResult<ApiControlGuard> {
+        self.enable_api_control(true).await?;
+        Ok(ApiControlGuard::new(
+            Arc::clone(&self.airsim_client),
+            self.vehicle_name,
+            true,
+        ))
+    }
+
     /// Returns true if API control is established.
     ///
     /// If false (which is default) then API calls would be ignored. After a successful call
@@ -79,11 +168,30 @@ impl MultiRotorClient {
     }
 
     /// Cancel previous Async task
+    ///
+    /// Aborts the maneuver currently running on the vehicle, e.g. a long `move_on_path_async`,
+    /// so a higher-priority command can take over. The aborted call returns once the server
+    /// has stopped the maneuver. Requests are multiplexed over the connection, so this can be
+    /// awaited concurrently with the maneuver on the same client.
     #[inline(always)]
     pub async fn cancel_last_task(&self) -> NetworkResult<bool> {
         self.airsim_client.cancel_last_task(Some(self.vehicle_name)).await
     }
 
+    /// Wait until the previous Async task is done
+    ///
+    /// Lets a task synchronize on a maneuver awaited by another task of the same client, returns true right away
+    /// if none is running and false if `timeout_sec` passed before the task completed
+    ///
+    /// args:
+    ///     timeout_sec (Option<f32>): Maximum time to wait in seconds, waits indefinitely if None
+    #[inline(always)]
+    pub async fn wait_on_last_task(&self, timeout_sec: Option<f32>) -> NetworkResult<bool> {
+        self.airsim_client
+            .wait_on_last_task(timeout_sec, Some(self.vehicle_name))
+            .await
+    }
+
     /// Returns true if API control is established.
     ///
     /// If false (which is default) then API calls would be ignored. After a successful call
@@ -99,14 +207,18 @@ impl MultiRotorClient {
 
     /// High level control API
     ///
-    /// Hover the vehicle in place
+    /// Hover the vehicle in place, holding its current position and altitude
     pub async fn hover_async(&self) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc("hover".into(), Some(vec![Value::String(vehicle_name)]))
+            .vehicle_rpc(
+                "hover".into(),
+                Some(vec![Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
             .await
-            .map(|response| response.result.is_ok())
+            .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
 
     /// Get the Home location of the vehicle
@@ -114,6 +226,17 @@ impl MultiRotorClient {
         self.airsim_client.get_home_geo_point(Some(self.vehicle_name)).await
     }
 
+    /// Set the Home location of the vehicle, on servers built with geo reference support
+    ///
+    /// args:
+    ///     home (GeoPoint): GPS coordinate of the home
+    #[inline(always)]
+    pub async fn set_home_geo_point(&self, home: GeoPoint) -> NetworkResult<()> {
+        self.airsim_client
+            .set_home_geo_point(home, Some(self.vehicle_name))
+            .await
+    }
+
     /// High level control API
     ///
     /// Takeoff vehicle to 3m above ground. Vehicle should not be moving when this API is used
@@ -121,12 +244,13 @@ impl MultiRotorClient {
     /// Args:
     ///     timeout_sec (Option<f32>): Timeout for the vehicle to reach desired altitude
     pub async fn take_off_async(&self, timeout_sec: f32) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "takeoff".into(),
-                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name)]),
+                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name.into())]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -140,32 +264,187 @@ impl MultiRotorClient {
     /// Args:
     ///     timeout_sec (Option<f32>): Timeout for the vehicle to land
     pub async fn land_async(&self, timeout_sec: f32) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "land".into(),
-                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name)]),
+                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name.into())]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
 
+    /// High level control API
+    ///
+    /// Take off like `take_off_async`, while polling the multirotor state to report why the take off failed
+    ///
+    /// Args:
+    ///     timeout_sec (f32): Timeout for the vehicle to reach desired altitude
+    pub async fn take_off_and_wait(&self, timeout_sec: f32) -> NetworkResult<ManeuverOutcome> {
+        self.take_off_and_wait_with_progress(timeout_sec, |_| {}).await
+    }
+
+    /// High level control API
+    ///
+    /// Take off like `take_off_and_wait`, calling `progress` with every polled multirotor state
+    ///
+    /// Args:
+    ///     timeout_sec (f32): Timeout for the vehicle to reach desired altitude
+    ///     progress (FnMut(&MultiRotorState)): Called with the state of the vehicle while taking off
+    pub async fn take_off_and_wait_with_progress(
+        &self,
+        timeout_sec: f32,
+        progress: impl FnMut(&MultiRotorState),
+    ) -> NetworkResult<ManeuverOutcome> {
+        self.wait_for_maneuver(self.take_off_async(timeout_sec), LandedState::Flying, progress)
+            .await
+    }
+
+    /// High level control API
+    ///
+    /// Land like `land_async`, while polling the multirotor state to report why the landing failed
+    ///
+    /// Args:
+    ///     timeout_sec (f32): Timeout for the vehicle to land
+    pub async fn land_and_wait(&self, timeout_sec: f32) -> NetworkResult<ManeuverOutcome> {
+        self.land_and_wait_with_progress(timeout_sec, |_| {}).await
+    }
+
+    /// High level control API
+    ///
+    /// Land like `land_and_wait`, calling `progress` with every polled multirotor state
+    ///
+    /// Args:
+    ///     timeout_sec (f32): Timeout for the vehicle to land
+    ///     progress (FnMut(&MultiRotorState)): Called with the state of the vehicle while landing
+    pub async fn land_and_wait_with_progress(
+        &self,
+        timeout_sec: f32,
+        progress: impl FnMut(&MultiRotorState),
+    ) -> NetworkResult<ManeuverOutcome> {
+        self.wait_for_maneuver(self.land_async(timeout_sec), LandedState::Landed, progress)
+            .await
+    }
+
+    /// Run `maneuver` while polling the multirotor state until it completes
+    ///
+    /// A collision newer than the start of the maneuver ends the polling early. Touching the ground
+    /// while landing isn't reported as a collision once the vehicle counts as landed.
+    async fn wait_for_maneuver(
+        &self,
+        maneuver: impl Future<Output = NetworkResult<bool>>,
+        target: LandedState,
+        mut progress: impl FnMut(&MultiRotorState),
+    ) -> NetworkResult<ManeuverOutcome> {
+        let started = self.get_multirotor_state().await?;
+        let done = AtomicBool::new(false);
+
+        let maneuver = async {
+            let completed = maneuver.await;
+            done.store(true, Ordering::SeqCst);
+            completed
+        };
+
+        let watch = async {
+            loop {
+                let state = self.get_multirotor_state().await?;
+                progress(&state);
+
+                let collided = state.collision.has_collided
+                    && state.collision.timestamp > started.collision.timestamp
+                    && state.landed_state != target;
+                if collided || done.load(Ordering::SeqCst) {
+                    return Ok::<_, NetworkError>((state, collided));
+                }
+
+                task::sleep(MANEUVER_POLL_PERIOD).await;
+            }
+        };
+
+        let (completed, watched) = futures::join!(maneuver, watch);
+        let (state, collided) = watched?;
+
+        if collided {
+            Ok(ManeuverOutcome::Collision)
+        } else if completed? && state.landed_state == target {
+            Ok(ManeuverOutcome::Success)
+        } else {
+            Ok(ManeuverOutcome::Timeout {
+                final_altitude: -state.kinematics_estimated.position.z,
+            })
+        }
+    }
+
     /// High level control API
     ///
     /// Return vehicle to Home i.e. Launch location
     /// The vehicle should be in the viscinity of home when this function
     /// is called
     ///
+    /// The vehicle flies straight towards home, see `maneuvers::rtl` to climb to a safe altitude first
+    ///
     /// Args:
     ///     timeout_sec (Option<f32>): Timeout for the vehicle to reach desired altitude
     pub async fn go_home_async(&self, timeout_sec: f32) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "goHome".into(),
-                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name)]),
+                Some(vec![Value::F32(timeout_sec), Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
+            .await
+            .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// High level control API
+    ///
+    /// Rotate the vehicle in place to an absolute yaw angle
+    ///
+    /// Args:
+    ///     yaw (f32): Desired yaw angle in degrees, in the world NED frame
+    ///     timeout_sec (f32): Timeout for the vehicle to reach the desired yaw
+    ///     margin (f32): Tolerance in degrees within which the yaw counts as reached
+    pub async fn rotate_to_yaw_async(&self, yaw: f32, timeout_sec: f32, margin: f32) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
+
+        self.airsim_client
+            .vehicle_rpc(
+                "rotateToYaw".into(),
+                Some(vec![
+                    Value::F32(yaw),
+                    Value::F32(timeout_sec),
+                    Value::F32(margin),
+                    Value::String(vehicle_name.into()),
+                ]),
+                vehicle_name,
+            )
+            .await
+            .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+
+    /// High level control API
+    ///
+    /// Rotate the vehicle in place at a constant yaw rate for the given duration
+    ///
+    /// Args:
+    ///     yaw_rate (f32): Desired yaw rate in degrees per second
+    ///     duration (f32): Desired amount of time (seconds), to rotate for
+    pub async fn rotate_by_yaw_rate_async(&self, yaw_rate: f32, duration: f32) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
+
+        self.airsim_client
+            .vehicle_rpc(
+                "rotateByYawRate".into(),
+                Some(vec![
+                    Value::F32(yaw_rate),
+                    Value::F32(duration),
+                    Value::String(vehicle_name.into()),
+                ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -187,20 +466,41 @@ impl MultiRotorClient {
         drivetrain: DrivetrainType,
         yaw_mode: YawMode,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let options = MoveOptions::new().drivetrain(drivetrain).yaw_mode(yaw_mode);
+        self.move_by_velocity_body_frame_with(velocity, duration, &options)
+            .await
+    }
+
+    /// High level control API
+    ///
+    /// Set 3D velocity vector in vehicle's local NED frame
+    /// Same as `move_by_velocity_body_frame_async`, with drivetrain and yaw mode taken from `options`
+    ///
+    /// Args:
+    ///     velocity (Velocity3): desired velocity in the X,Y,Z axis's of the vehicle's local NED frame.
+    ///     duration (f32): Desired amount of time (seconds), to send this command for
+    ///     options (&MoveOptions): drivetrain and yaw_mode of the movement, velocity and lookahead are ignored
+    pub async fn move_by_velocity_body_frame_with(
+        &self,
+        velocity: Velocity3,
+        duration: f32,
+        options: &MoveOptions,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByVelocityBodyFrame".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(velocity.vx),
                     msgpack_rpc::Value::F32(velocity.vy),
                     msgpack_rpc::Value::F32(velocity.vz),
                     msgpack_rpc::Value::F32(duration),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    Value::String(vehicle_name),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -224,20 +524,43 @@ impl MultiRotorClient {
         drivetrain: DrivetrainType,
         yaw_mode: YawMode,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let options = MoveOptions::new().drivetrain(drivetrain).yaw_mode(yaw_mode);
+        self.move_by_velocity_z_body_frame_with(velocity, z, duration, &options)
+            .await
+    }
+
+    /// High level control API
+    ///
+    /// Set 2D velocity vector in vehicle's local NED frame, with desired Z altitude.
+    /// Same as `move_by_velocity_z_body_frame_async`, with drivetrain and yaw mode taken from `options`
+    ///
+    /// Args:
+    ///     velocity (Velocity2): desired velocity in the X,Y axis's of the vehicle's local NED frame.
+    ///     z (f32): desired Z value (in local NED frame of the vehicle)
+    ///     duration (f32): desired amount of time (seconds), to send this command for
+    ///     options (&MoveOptions): drivetrain and yaw_mode of the movement, velocity and lookahead are ignored
+    pub async fn move_by_velocity_z_body_frame_with(
+        &self,
+        velocity: Velocity2,
+        z: f32,
+        duration: f32,
+        options: &MoveOptions,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByVelocityZBodyFrame".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(velocity.vx),
                     msgpack_rpc::Value::F32(velocity.vy),
                     msgpack_rpc::Value::F32(z),
                     msgpack_rpc::Value::F32(duration),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    Value::String(vehicle_name),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -247,19 +570,20 @@ impl MultiRotorClient {
     ///
     /// - Sets velocity controller gains for moveByVelocityAsync().
     /// - This function should only be called if the default velocity control PID gains need to be modified.
-    /// - Passing VelocityControllerGains() sets gains to default airsim values.
+    /// - Passing `LinearControllerGains::velocity_default()` sets gains to default airsim values.
     ///
     /// args:
     ///     velocity_gains (LinearControllerGains):
     ///         - Correspond to the world X, Y, Z axes.
-    ///         - Pass LinearControllerGains() to reset gains to default recommended values.
+    ///         - Pass `LinearControllerGains::velocity_default()` to reset gains to default recommended values.
     ///         - Modifying velocity controller gains will have an affect on the behaviour of move_on_spline_async() and
     ///           move_on_spline_vel_constraints_async(), as they both use velocity control to track the trajectory.
     pub async fn set_velocity_controller_gains(&self, velocity_gains: LinearControllerGains) -> NetworkResult<bool> {
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "setVelocityControllerGains".into(),
                 Some(velocity_gains.as_msgpack(self.vehicle_name)),
+                self.vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok())
@@ -281,20 +605,40 @@ impl MultiRotorClient {
         drivetrain: DrivetrainType,
         yaw_mode: YawMode,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let options = MoveOptions::new().drivetrain(drivetrain).yaw_mode(yaw_mode);
+        self.move_by_velocity_with(velocity, duration, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Set 3D velocity vector in vehicle's local NED frame
+    /// Same as `move_by_velocity_async`, with drivetrain and yaw mode taken from `options`
+    ///
+    /// Args:
+    ///     velocity (Velocity3): desired velocity X,Y,Z in world (NED) axis
+    ///     duration (f32): desired amount of time (seconds), to send this command for
+    ///     options (&MoveOptions): drivetrain and yaw_mode of the movement, velocity and lookahead are ignored
+    pub async fn move_by_velocity_with(
+        &self,
+        velocity: Velocity3,
+        duration: f32,
+        options: &MoveOptions,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByVelocity".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(velocity.vx),
                     msgpack_rpc::Value::F32(velocity.vy),
                     msgpack_rpc::Value::F32(velocity.vz),
                     msgpack_rpc::Value::F32(duration),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    Value::String(vehicle_name),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -318,20 +662,42 @@ impl MultiRotorClient {
         drivetrain: DrivetrainType,
         yaw_mode: YawMode,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let options = MoveOptions::new().drivetrain(drivetrain).yaw_mode(yaw_mode);
+        self.move_by_velocity_z_with(velocity, z, duration, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Set 2D velocity vector in vehicle's local NED frame, with desired Z attitude.
+    /// Same as `move_by_velocity_z_async`, with drivetrain and yaw mode taken from `options`
+    ///
+    /// Args:
+    ///     velocity (Velocity2): desired velocity in the X,Y axis's of the vehicle's local NED frame.
+    ///     z (f32): desired Z value (in local NED frame of the vehicle)
+    ///     duration (f32): desired amount of time (seconds), to send this command for
+    ///     options (&MoveOptions): drivetrain and yaw_mode of the movement, velocity and lookahead are ignored
+    pub async fn move_by_velocity_z_with(
+        &self,
+        velocity: Velocity2,
+        z: f32,
+        duration: f32,
+        options: &MoveOptions,
+    ) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByVelocityZ".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(velocity.vx),
                     msgpack_rpc::Value::F32(velocity.vy),
                     msgpack_rpc::Value::F32(z),
                     msgpack_rpc::Value::F32(duration),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    Value::String(vehicle_name),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -344,12 +710,13 @@ impl MultiRotorClient {
     /// args:
     ///     position_gains (LinearControllerGains):
     ///         - Correspond to the X, Y, Z axes.
-    ///         - Pass PositionControllerGains() to reset gains to default recommended values.
+    ///         - Pass `LinearControllerGains::position_default()` to reset gains to default recommended values.
     pub async fn set_position_controller_gains(&self, position_gains: LinearControllerGains) -> NetworkResult<bool> {
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "setPositionControllerGains".into(),
                 Some(position_gains.as_msgpack(self.vehicle_name)),
+                self.vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok())
@@ -378,25 +745,45 @@ impl MultiRotorClient {
         lookahead: Option<f32>,
         adaptive_lookahead: Option<f32>,
     ) -> NetworkResult<bool> {
-        let lookahead = lookahead.unwrap_or(-1.0);
-        let adaptive_lookahead = adaptive_lookahead.unwrap_or(1.0);
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let defaults = MoveOptions::default();
+        let options = MoveOptions::new()
+            .velocity(velocity)
+            .timeout_sec(timeout_sec)
+            .drivetrain(drivetrain)
+            .yaw_mode(yaw_mode)
+            .lookahead(lookahead.unwrap_or(defaults.lookahead))
+            .adaptive_lookahead(adaptive_lookahead.unwrap_or(defaults.adaptive_lookahead));
+
+        self.move_to_position_with(position, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Send desired goal position to default PID vehicle controller
+    /// Same as `move_to_position_async`, with the remaining parameters taken from `options`
+    ///
+    /// Args:
+    ///     position (Position3): goal position of the vehicle controller
+    ///     options (&MoveOptions): velocity, timeout, drivetrain, yaw mode and lookahead of the movement
+    pub async fn move_to_position_with(&self, position: Position3, options: &MoveOptions) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveToPosition".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(position.x),
                     msgpack_rpc::Value::F32(position.y),
                     msgpack_rpc::Value::F32(position.z),
-                    msgpack_rpc::Value::F32(velocity),
-                    msgpack_rpc::Value::F32(timeout_sec),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    msgpack_rpc::Value::F32(lookahead),
-                    msgpack_rpc::Value::F32(adaptive_lookahead),
-                    Value::String(vehicle_name),
+                    msgpack_rpc::Value::F32(options.velocity),
+                    msgpack_rpc::Value::F32(options.timeout_sec),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    msgpack_rpc::Value::F32(options.lookahead),
+                    msgpack_rpc::Value::F32(options.adaptive_lookahead),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -425,28 +812,106 @@ impl MultiRotorClient {
         lookahead: Option<f32>,
         adaptive_lookahead: Option<f32>,
     ) -> NetworkResult<bool> {
-        let lookahead = lookahead.unwrap_or(-1.0);
-        let adaptive_lookahead = adaptive_lookahead.unwrap_or(1.0);
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let defaults = MoveOptions::default();
+        let options = MoveOptions::new()
+            .velocity(velocity)
+            .timeout_sec(timeout_sec)
+            .drivetrain(drivetrain)
+            .yaw_mode(yaw_mode)
+            .lookahead(lookahead.unwrap_or(defaults.lookahead))
+            .adaptive_lookahead(adaptive_lookahead.unwrap_or(defaults.adaptive_lookahead));
+
+        self.move_on_path_with(path, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Send desired goal position to default PID vehicle controller
+    /// Same as `move_on_path_async`, with the remaining parameters taken from `options`
+    ///
+    /// Args:
+    ///     path (Path): path of positions the vehicle should follow
+    ///     options (&MoveOptions): velocity, timeout, drivetrain, yaw mode and lookahead of the movement
+    pub async fn move_on_path_with(&self, path: Path, options: &MoveOptions) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveOnPath".into(),
                 Some(vec![
                     path.as_msgpack(),
-                    msgpack_rpc::Value::F32(velocity),
-                    msgpack_rpc::Value::F32(timeout_sec),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    msgpack_rpc::Value::F32(lookahead),
-                    msgpack_rpc::Value::F32(adaptive_lookahead),
-                    Value::String(vehicle_name),
+                    msgpack_rpc::Value::F32(options.velocity),
+                    msgpack_rpc::Value::F32(options.timeout_sec),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    msgpack_rpc::Value::F32(options.lookahead),
+                    msgpack_rpc::Value::F32(options.adaptive_lookahead),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
     }
 
+    /// High level control API
+    ///
+    /// Same as `move_on_path_with`, sampling the position of the vehicle while it moves to report its progress
+    ///
+    /// `on_event` is called with a `PathEvent::WaypointReached` per waypoint, in order, and a `PathEvent::Progress`
+    /// per sample, see `PathTracker`. A last sample is taken once the movement ends. Samples that fail are skipped,
+    /// the movement goes on. A path without waypoints fails with `NetworkError::InvalidArgument`
+    ///
+    /// Args:
+    ///     path (Path): path of positions the vehicle should follow
+    ///     options (&MoveOptions): velocity, timeout, drivetrain, yaw mode and lookahead of the movement
+    ///     acceptance_radius (f32): distance to a waypoint it counts as reached from, in meters
+    ///     rate_hz (f32): sampling rate in Hz
+    ///     on_event (impl FnMut(PathEvent)): called with the events of each sample
+    pub async fn move_on_path_monitored(
+        &self,
+        path: Path,
+        options: &MoveOptions,
+        acceptance_radius: f32,
+        rate_hz: f32,
+        mut on_event: impl FnMut(PathEvent),
+    ) -> NetworkResult<bool> {
+        if path.0.is_empty() {
+            return Err(NetworkError::InvalidArgument {
+                message: "the path to follow has no waypoint".into(),
+            });
+        }
+        let mut tracker = PathTracker::new(&path, acceptance_radius);
+        let mut track = |state: NetworkResult<MultiRotorState>| match state {
+            Ok(state) => tracker
+                .update(state.kinematics_estimated.position.into())
+                .into_iter()
+                .for_each(&mut on_event),
+            Err(e) => log::warn!("skipped a sample of the path progress: {e}"),
+        };
+
+        // the first sample is the start of the path, taken before the vehicle moves
+        track(self.get_multirotor_state().await);
+
+        let movement = self.move_on_path_with(path, options).fuse();
+        let states = self.state_stream(rate_hz).fuse();
+        pin_mut!(movement, states);
+
+        let reached = loop {
+            select! {
+                reached = movement => break reached,
+                state = states.next() => {
+                    if let Some(state) = state {
+                        track(state);
+                    }
+                }
+            }
+        };
+
+        track(self.get_multirotor_state().await);
+        reached
+    }
+
     /// High level control API
     ///
     /// Send desired goal position to default PID vehicle controller
@@ -470,25 +935,45 @@ impl MultiRotorClient {
         lookahead: Option<f32>,
         adaptive_lookahead: Option<f32>,
     ) -> NetworkResult<bool> {
-        let lookahead = lookahead.unwrap_or(-1.0);
-        let adaptive_lookahead = adaptive_lookahead.unwrap_or(1.0);
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let defaults = MoveOptions::default();
+        let options = MoveOptions::new()
+            .velocity(velocity)
+            .timeout_sec(timeout_sec)
+            .drivetrain(drivetrain)
+            .yaw_mode(yaw_mode)
+            .lookahead(lookahead.unwrap_or(defaults.lookahead))
+            .adaptive_lookahead(adaptive_lookahead.unwrap_or(defaults.adaptive_lookahead));
+
+        self.move_to_gps_with(geopoint, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Send desired goal position to default PID vehicle controller
+    /// Same as `move_to_gps_async`, with the remaining parameters taken from `options`
+    ///
+    /// Args:
+    ///     geopoint (GeoPoint): goal GPS position of the vehicle controller
+    ///     options (&MoveOptions): velocity, timeout, drivetrain, yaw mode and lookahead of the movement
+    pub async fn move_to_gps_with(&self, geopoint: GeoPoint, options: &MoveOptions) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveToGPS".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(geopoint.latitude),
                     msgpack_rpc::Value::F32(geopoint.longitude),
                     msgpack_rpc::Value::F32(geopoint.altitude),
-                    msgpack_rpc::Value::F32(velocity),
-                    msgpack_rpc::Value::F32(timeout_sec),
-                    drivetrain.as_msgpack(),
-                    yaw_mode.as_msgpack(),
-                    msgpack_rpc::Value::F32(lookahead),
-                    msgpack_rpc::Value::F32(adaptive_lookahead),
-                    Value::String(vehicle_name),
+                    msgpack_rpc::Value::F32(options.velocity),
+                    msgpack_rpc::Value::F32(options.timeout_sec),
+                    options.drivetrain.as_msgpack(),
+                    options.yaw_mode.as_msgpack(),
+                    msgpack_rpc::Value::F32(options.lookahead),
+                    msgpack_rpc::Value::F32(options.adaptive_lookahead),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -515,22 +1000,41 @@ impl MultiRotorClient {
         lookahead: Option<f32>,
         adaptive_lookahead: Option<f32>,
     ) -> NetworkResult<bool> {
-        let lookahead = lookahead.unwrap_or(-1.0);
-        let adaptive_lookahead = adaptive_lookahead.unwrap_or(1.0);
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let defaults = MoveOptions::default();
+        let options = MoveOptions::new()
+            .velocity(velocity)
+            .timeout_sec(timeout_sec)
+            .yaw_mode(yaw_mode)
+            .lookahead(lookahead.unwrap_or(defaults.lookahead))
+            .adaptive_lookahead(adaptive_lookahead.unwrap_or(defaults.adaptive_lookahead));
+
+        self.move_to_z_with(z, &options).await
+    }
+
+    /// High level control API
+    ///
+    /// Move to a desired altitude Z (in local NED frame of the vehicle) with a desired velocity
+    /// Same as `move_to_z_async`, with the remaining parameters taken from `options`
+    ///
+    /// Args:
+    ///     z (f32): desired Z value (in local NED frame of the vehicle)
+    ///     options (&MoveOptions): velocity, timeout, yaw mode and lookahead of the movement
+    pub async fn move_to_z_with(&self, z: f32, options: &MoveOptions) -> NetworkResult<bool> {
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveToZ".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(z),
-                    msgpack_rpc::Value::F32(velocity),
-                    msgpack_rpc::Value::F32(timeout_sec),
-                    yaw_mode.as_msgpack(),
-                    msgpack_rpc::Value::F32(lookahead),
-                    msgpack_rpc::Value::F32(adaptive_lookahead),
-                    Value::String(vehicle_name),
+                    msgpack_rpc::Value::F32(options.velocity),
+                    msgpack_rpc::Value::F32(options.timeout_sec),
+                    options.yaw_mode.as_msgpack(),
+                    msgpack_rpc::Value::F32(options.lookahead),
+                    msgpack_rpc::Value::F32(options.adaptive_lookahead),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -558,10 +1062,10 @@ impl MultiRotorClient {
         drivetrain: DrivetrainType,
         yaw_mode: YawMode,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByManual".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(v_max.vx),
@@ -570,8 +1074,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(duration),
                     drivetrain.as_msgpack(),
                     yaw_mode.as_msgpack(),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -584,12 +1089,13 @@ impl MultiRotorClient {
     /// args:
     ///     rc_data (RCData): remote control commands
     pub async fn move_by_rc(&self, rc_data: RCData) -> NetworkResult<()> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByRC".into(),
-                Some(vec![rc_data.as_msgpack(), Value::String(vehicle_name)]),
+                Some(vec![rc_data.as_msgpack(), Value::String(vehicle_name.into())]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.unwrap())
@@ -609,10 +1115,10 @@ impl MultiRotorClient {
     ///     pwm (PWM): pwm signals for each indivual rotor (4 rotors in total)
     ///     duration (f32): desired amount of time (seconds), to send this command for
     pub async fn move_by_motor_pwms_async(&self, pwm: PWM, duration: f32) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByMotorPWMs".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(pwm.front_right_pwm),
@@ -620,8 +1126,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(pwm.front_left_pwm),
                     msgpack_rpc::Value::F32(pwm.rear_right_pwm),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -637,15 +1144,16 @@ impl MultiRotorClient {
     /// args:
     ///     angle_rate_gains (AngularControllerGains):
     ///         - Correspond to the roll, pitch, yaw axes, defined in the body frame.
-    ///         - Pass AngularControllerGains() to reset gains to default recommended values.
+    ///         - Pass `AngularControllerGains::angle_rate_default()` to reset gains to default recommended values.
     pub async fn set_angle_rate_controller_gains(
         &self,
         angle_rate_gains: AngularControllerGains,
     ) -> NetworkResult<bool> {
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "setAngleRateControllerGains".into(),
                 Some(angle_rate_gains.as_msgpack(self.vehicle_name)),
+                self.vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok())
@@ -658,20 +1166,21 @@ impl MultiRotorClient {
     /// - Modifying these gains will also affect the behaviour of move_by_velocity_async() API.
     ///     This is because the AirSim flight controller will track velocity setpoints by converting them to angle set points.
     /// - This function should only be called if the default angle level control PID gains need to be modified.
-    /// - Passing AngularControllerGains() sets gains to default airsim values.
+    /// - Passing `AngularControllerGains::angle_level_default()` sets gains to default airsim values.
     ///
     /// args:
     ///     angle_level_gains (AngularControllerGains):
     ///         - Correspond to the roll, pitch, yaw axes, defined in the body frame.
-    ///         - Pass AngleLevelControllerGains() to reset gains to default recommended values.
+    ///         - Pass `AngularControllerGains::angle_level_default()` to reset gains to default recommended values.
     pub async fn set_angle_level_controller_gains(
         &self,
         angle_level_gains: AngularControllerGains,
     ) -> NetworkResult<bool> {
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "setAngleLevelControllerGains".into(),
                 Some(angle_level_gains.as_msgpack(self.vehicle_name)),
+                self.vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok())
@@ -691,10 +1200,10 @@ impl MultiRotorClient {
         z: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByRollPitchYawZ".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation.roll),
@@ -702,8 +1211,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-rotation.yaw),
                     msgpack_rpc::Value::F32(z),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -723,14 +1233,14 @@ impl MultiRotorClient {
         throttle_z: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         if throttle_z.is_sign_negative() || throttle_z > 1.0 {
             panic!("throttle_z outside of valid range 0.0 to 1.0")
         }
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByRollPitchYawThrottle".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation.roll),
@@ -738,8 +1248,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-rotation.yaw),
                     msgpack_rpc::Value::F32(throttle_z),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -761,13 +1272,13 @@ impl MultiRotorClient {
         throttle_z: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
         if throttle_z.is_sign_negative() || throttle_z > 1.0 {
             panic!("throttle_z outside of valid range 0.0 to 1.0")
         }
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByRollPitchYawrateThrottle".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation.roll),
@@ -775,8 +1286,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-yaw_rate),
                     msgpack_rpc::Value::F32(throttle_z),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -798,10 +1310,10 @@ impl MultiRotorClient {
         z: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByRollPitchYawrateZ".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation.roll),
@@ -809,8 +1321,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-yaw_rate),
                     msgpack_rpc::Value::F32(z),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -831,10 +1344,10 @@ impl MultiRotorClient {
         z: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByAngleRatesZ".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation_rates.roll),
@@ -842,8 +1355,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-rotation_rates.yaw),
                     msgpack_rpc::Value::F32(z),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -864,13 +1378,13 @@ impl MultiRotorClient {
         throttle: f32,
         duration: f32,
     ) -> NetworkResult<bool> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
         if throttle.is_sign_negative() || throttle > 1.0 {
             panic!("throttle outside of valid range 0.0 to 1.0")
         }
 
         self.airsim_client
-            .unary_rpc(
+            .vehicle_rpc(
                 "moveByAngleRatesThrottle".into(),
                 Some(vec![
                     msgpack_rpc::Value::F32(rotation_rates.roll),
@@ -878,8 +1392,9 @@ impl MultiRotorClient {
                     msgpack_rpc::Value::F32(-rotation_rates.yaw),
                     msgpack_rpc::Value::F32(throttle),
                     msgpack_rpc::Value::F32(duration),
-                    Value::String(vehicle_name),
+                    Value::String(vehicle_name.into()),
                 ]),
+                vehicle_name,
             )
             .await
             .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
@@ -887,71 +1402,440 @@ impl MultiRotorClient {
 
     /// Get the kinematic state of the multirotor vehicle
     pub async fn get_multirotor_state(&self) -> NetworkResult<MultiRotorState> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
+        let vehicle_name = self.vehicle_name;
         self.airsim_client
-            .unary_rpc("getMultirotorState".into(), Some(vec![Value::String(vehicle_name)]))
+            .vehicle_rpc(
+                "getMultirotorState".into(),
+                Some(vec![Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
             .await
             .map(MultiRotorState::from)
     }
 
-    /// Used to obtain the current state of all a multirotor's rotors. The state includes the speeds,
-    /// thrusts and torques for all rotors.
-    pub async fn get_rotor_states(&self) -> NetworkResult<RotorStates> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        self.airsim_client
-            .unary_rpc("getRotorStates".into(), Some(vec![Value::String(vehicle_name)]))
-            .await
-            .map(RotorStates::from)
-    }
-
-    /// Get the IMU data of the multirotor vehicle.  States include orientation, angular velocity, and linear acceleration.
-    pub async fn get_imu_data(&self, imu_name: Utf8String) -> NetworkResult<ImuData> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        self.airsim_client.unary_rpc("getImuData".into(), Some(vec![Value::String(imu_name), Value::String(vehicle_name)]))
-        .await
-        .map(ImuData::from)
+    /// Stream of the kinematic state of the multirotor vehicle, sampled at a fixed rate
+    ///
+    /// Samples are scheduled on a fixed grid, so the time spent in each RPC doesn't make the rate drift. If the
+    /// stream falls behind by more than a period, because of the server or a slow consumer, the missed samples are
+    /// skipped instead of being yielded in a burst. Errors are yielded and sampling goes on until the stream is dropped
+    ///
+    /// args:
+    ///     rate_hz (f32): sampling rate in Hz
+    ///
+    /// # Panics
+    /// If the sampling rate isn't positive
+    pub fn state_stream(&self, rate_hz: f32) -> impl Stream<Item = NetworkResult<MultiRotorState>> {
+        let period = periodic::period("state stream", rate_hz);
+        let client = self.clone();
+
+        periodic::sample_stream(period, move || {
+            let client = client.clone();
+            async move { client.get_multirotor_state().await }
+        })
     }
 
-    /// Get the distance sensor data of the multirotor vehicle.  States include distance.
-    pub async fn get_dist_data(&self) -> NetworkResult<DistanceSensorData> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        let dist_name: Utf8String = "".to_string().into();
-        self.airsim_client
-            .unary_rpc("getDistanceSensorData".into(), Some(vec![Value::String(dist_name), Value::String(vehicle_name)]))
-            .await
-            .map(DistanceSensorData::from)
+    /// Current simulation time in nanoseconds, read from the timestamp of the vehicle state
+    ///
+    /// All vehicles and sensors are stamped with the same clock, so this can be used to align their logs
+    pub async fn sim_time(&self) -> NetworkResult<u64> {
+        self.get_multirotor_state().await.map(|state| state.timestamp)
+    }
+
+    /// Speed of the simulation relative to the wall clock, measured from the timestamps of two states `window` apart
+    ///
+    /// 1 is real time, above 1 is faster than real time. The estimate includes the latency of the RPCs, use a window
+    /// of a second or more for a precise estimate. A paused simulation reads 0
+    ///
+    /// args:
+    ///     window (Duration): Wall clock time between the two states
+    pub async fn real_time_factor(&self, window: Duration) -> NetworkResult<f32> {
+        let started = self.sim_time().await?;
+        let wall_started = Instant::now();
+        task::sleep(window).await;
+        let ended = self.sim_time().await?;
+        let wall = wall_started.elapsed().as_secs_f64();
+
+        Ok((ended.saturating_sub(started) as f64 * 1e-9 / wall) as f32)
+    }
+
+    /// Returns the `ClockSpeed` of settings.json, see `AirsimClient::configured_clock_speed`. Needs the `serde`
+    /// feature
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub async fn configured_clock_speed(&self) -> NetworkResult<f32> {
+        self.airsim_client.configured_clock_speed().await
+    }
+
+    /// Sets the time dilation of Unreal, see `AirsimClient::sim_set_clock_speed`
+    ///
+    /// args:
+    ///     speed (f32): Speed relative to the wall clock, e.g. 2 for twice as fast
+    #[inline(always)]
+    pub async fn sim_set_clock_speed(&self, speed: f32) -> NetworkResult<bool> {
+        self.airsim_client.sim_set_clock_speed(speed).await
+    }
+
+    /// Set the pose of the vehicle
+    ///
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation)
+    /// to floating point nan values
+    ///
+    /// args:
+    ///     pose (Pose3): Desired Pose of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    #[inline(always)]
+    pub async fn sim_set_vehicle_pose(&self, pose: Pose3, ignore_collision: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_vehicle_pose(pose, ignore_collision, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Get the pose of the vehicle
+    ///
+    /// The position inside the returned Pose3 is in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn sim_get_vehicle_pose(&self) -> NetworkResult<Pose3> {
+        self.airsim_client.sim_get_vehicle_pose(Some(self.vehicle_name)).await
+    }
+
+    /// Get the ground truth kinematics of the vehicle
+    ///
+    /// The position inside the returned KinematicsState is in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn sim_get_ground_truth_kinematics(&self) -> NetworkResult<KinematicsState> {
+        self.airsim_client
+            .sim_get_ground_truth_kinematics(Some(self.vehicle_name))
+            .await
+    }
+
+    /// Altitude above the ground below the vehicle, see `get_agl_altitude_with`, with the default `AglConfig`
+    pub async fn get_agl_altitude(&self) -> NetworkResult<Option<AglAltitude>> {
+        self.get_agl_altitude_with(&AglConfig::default()).await
+    }
+
+    /// Altitude above the ground below the vehicle, rather than the NED z of its position, e.g. to follow the terrain
+    ///
+    /// Uses the distance sensor if it points down and got a return, and falls back to the center of the depth image
+    /// of the downward camera otherwise, e.g. above the range of the sensor or on vehicles without one. The readings
+    /// are corrected for the tilt of the sensor. Returns `None` if neither measured the ground, e.g. too high above it
+    ///
+    /// args:
+    ///     config (&AglConfig): Names of the distance sensor and the camera
+    pub async fn get_agl_altitude_with(&self, config: &AglConfig) -> NetworkResult<Option<AglAltitude>> {
+        // vehicles without a distance sensor fail the RPC, fall back to the camera then
+        match self.get_dist_data(Some(&config.distance_sensor)).await {
+            Ok(data) => {
+                let pose = self.sim_get_vehicle_pose().await?;
+                if let Some(agl) = AglAltitude::from_distance(&data, pose.orientation) {
+                    return Ok(Some(agl));
+                }
+            }
+            Err(NetworkError::Rpc { .. }) => {}
+            Err(e) => return Err(e),
+        }
+
+        let requests = ImageRequests(vec![ImageRequest::depth_perspective(&config.camera)]);
+        let responses = self.sim_get_images(requests, None).await?;
+        Ok(responses
+            .first()
+            .and_then(|response| AglAltitude::from_depth(response, config.window)))
+    }
+
+    /// Get the last collision of the vehicle
+    ///
+    /// The info is kept until the next collision, compare the timestamps to tell collisions apart or use
+    /// `collision_stream`
+    #[inline(always)]
+    pub async fn sim_get_collision_info(&self) -> NetworkResult<CollisionInfo> {
+        self.airsim_client.sim_get_collision_info(Some(self.vehicle_name)).await
+    }
+
+    /// Stream of the collisions of the vehicle, polled at a fixed rate
+    ///
+    /// Each collision is yielded once, when its timestamp is first seen, including a collision which happened before
+    /// the stream was started. Collisions closer together than the polling period are reported as the last one.
+    /// Errors are yielded and polling goes on until the stream is dropped
+    ///
+    /// args:
+    ///     rate_hz (f32): polling rate in Hz
+    ///
+    /// # Panics
+    /// If the polling rate isn't positive
+    pub fn collision_stream(&self, rate_hz: f32) -> impl Stream<Item = NetworkResult<CollisionInfo>> {
+        let period = periodic::period("collision stream", rate_hz);
+        let client = self.clone();
+        let mut last = None;
+
+        periodic::sample_stream(period, move || {
+            let client = client.clone();
+            async move { client.sim_get_collision_info().await }
+        })
+        .filter(move |collision| {
+            let new = match collision {
+                Ok(collision) => {
+                    collision.has_collided && last.replace(collision.timestamp) != Some(collision.timestamp)
+                }
+                Err(_) => true,
+            };
+            future::ready(new)
+        })
+    }
+
+    /// Run `callback` on every new collision of the vehicle in a background task, see `collision_stream`
+    ///
+    /// args:
+    ///     rate_hz (f32): polling rate in Hz
+    ///     callback (FnMut(&CollisionInfo)): Called once per collision
+    pub fn watch_collisions<F>(&self, rate_hz: f32, callback: F) -> CollisionWatcher
+    where
+        F: FnMut(&CollisionInfo) + Send + 'static,
+    {
+        CollisionWatcher::start(self, rate_hz, callback)
+    }
+
+    /// Get the ground truth environment state of the vehicle
+    ///
+    /// The environment state holds the position, geo point, gravity and air pressure, temperature and density at the
+    /// vehicle's location
+    #[inline(always)]
+    pub async fn sim_get_ground_truth_environment(&self) -> NetworkResult<EnvironmentState> {
+        self.airsim_client
+            .sim_get_ground_truth_environment(Some(self.vehicle_name))
+            .await
+    }
+
+    /// Returns whether the target point is visible from the perspective of the vehicle
+    ///
+    /// args:
+    ///     point (GeoPoint): Target point
+    #[inline(always)]
+    pub async fn sim_test_line_of_sight_to_point(&self, point: GeoPoint) -> NetworkResult<bool> {
+        self.airsim_client
+            .sim_test_line_of_sight_to_point(point, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Modify the color and thickness of the line traced behind the vehicle
+    ///
+    /// args:
+    ///     color_rgba ([f32; 4]): Desired RGBA values from 0.0 to 1.0
+    ///     thickness (f32): Thickness of the line
+    #[inline(always)]
+    pub async fn sim_set_trace_line(&self, color_rgba: [f32; 4], thickness: f32) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_trace_line(color_rgba, thickness, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Set the kinematics state of the vehicle
+    ///
+    /// Lets an external dynamics model drive the vehicle while AirSim only renders it.
+    /// If you don't want to change position (or orientation) then just set components of position (or orientation) to
+    /// floating point nan values
+    ///
+    /// args:
+    ///     state (KinematicsState): Desired kinematics state of the vehicle
+    ///     ignore_collision (bool): Whether to ignore any collision or not
+    #[inline(always)]
+    pub async fn sim_set_kinematics(&self, state: KinematicsState, ignore_collision: bool) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_kinematics(state, ignore_collision, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Set an external force applied until it's set again, to every vehicle of the simulation
+    ///
+    /// args:
+    ///     force (Vector3): force in Newtons, in the world (NED) frame
+    #[inline(always)]
+    pub async fn sim_set_ext_force(&self, force: Vector3) -> NetworkResult<()> {
+        self.airsim_client.sim_set_ext_force(force).await
+    }
+
+    /// Apply an external wrench for `duration` seconds, then remove it, e.g. for disturbance rejection tests
+    ///
+    /// AirSim only applies forces, to every vehicle of the simulation, a wrench with a torque fails with
+    /// `NetworkError::InvalidArgument`
+    ///
+    /// args:
+    ///     wrench (Wrench): force in Newtons, in the world (NED) frame
+    ///     duration (f32): time to apply the wrench, in seconds
+    #[inline(always)]
+    pub async fn sim_apply_wrench(&self, wrench: Wrench, duration: f32) -> NetworkResult<()> {
+        self.airsim_client.sim_apply_wrench(wrench, duration).await
+    }
+
+    /// Used to obtain the current state of all a multirotor's rotors. The state includes the speeds,
+    /// thrusts and torques for all rotors.
+    pub async fn get_rotor_states(&self) -> NetworkResult<RotorStates> {
+        let vehicle_name = self.vehicle_name;
+        self.airsim_client
+            .vehicle_rpc(
+                "getRotorStates".into(),
+                Some(vec![Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
+            .await
+            .map(RotorStates::from)
+    }
+
+    /// Returns the sensors of the multirotor, as defined by the settings.json AirSim is currently running with
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub async fn sim_list_sensors(&self) -> NetworkResult<Vec<SensorInfo>> {
+        self.airsim_client.sim_list_sensors(Some(self.vehicle_name)).await
+    }
+
+    /// Get the IMU data of the multirotor vehicle.  States include orientation, angular velocity, and linear acceleration.
+    ///
+    /// args:
+    ///     imu_name (Option<&str>): Name of the IMU to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_imu_data(&self, imu_name: Option<&str>) -> NetworkResult<ImuData> {
+        let vehicle_name = self.vehicle_name;
+        let imu_name: Utf8String = imu_name.unwrap_or("").into();
+        self.airsim_client
+            .vehicle_rpc(
+                "getImuData".into(),
+                Some(vec![Value::String(imu_name), Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
+            .await
+            .map(ImuData::from)
+    }
+
+    /// Get the distance sensor data of the multirotor vehicle.  States include distance.
+    ///
+    /// args:
+    ///     distance_sensor_name (Option<&str>): Name of the distance sensor to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_dist_data(&self, distance_sensor_name: Option<&str>) -> NetworkResult<DistanceSensorData> {
+        let vehicle_name = self.vehicle_name;
+        let distance_sensor_name: Utf8String = distance_sensor_name.unwrap_or("").into();
+        self.airsim_client
+            .vehicle_rpc(
+                "getDistanceSensorData".into(),
+                Some(vec![
+                    Value::String(distance_sensor_name),
+                    Value::String(vehicle_name.into()),
+                ]),
+                vehicle_name,
+            )
+            .await
+            .map(DistanceSensorData::from)
+    }
+
+    /// Get the readings of several distance sensors as a single planar scan, e.g. for obstacle avoidance without lidar
+    ///
+    /// The sensors are queried concurrently and ordered by the yaw of their mounting pose
+    ///
+    /// args:
+    ///     sensor_names (&[&str]): Names of the distance sensors, specified in settings.json
+    pub async fn get_distance_sensor_scan(&self, sensor_names: &[&str]) -> NetworkResult<PolarScan> {
+        let readings = join_all(sensor_names.iter().map(|name| self.get_dist_data(Some(name)))).await;
+        let readings = readings.into_iter().collect::<NetworkResult<Vec<_>>>()?;
+        Ok(PolarScan::from_readings(&readings))
     }
 
     /// Get the magnetometer data of the multirotor vehicle.  States include magnetic field.
-    pub async fn get_magnetometer_data(&self) -> NetworkResult<MagnetometerData> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        let magnetometer_name: Utf8String = "".to_string().into();
+    ///
+    /// args:
+    ///     magnetometer_name (Option<&str>): Name of the magnetometer to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_magnetometer_data(&self, magnetometer_name: Option<&str>) -> NetworkResult<MagnetometerData> {
+        let vehicle_name = self.vehicle_name;
+        let magnetometer_name: Utf8String = magnetometer_name.unwrap_or("").into();
         self.airsim_client
-            .unary_rpc("getMagnetometerData".into(), Some(vec![Value::String(magnetometer_name), Value::String(vehicle_name)]))
+            .vehicle_rpc(
+                "getMagnetometerData".into(),
+                Some(vec![
+                    Value::String(magnetometer_name),
+                    Value::String(vehicle_name.into()),
+                ]),
+                vehicle_name,
+            )
             .await
             .map(MagnetometerData::from)
     }
 
     /// Get the barometer data of the multirotor vehicle.  States include pressure, temperature, and relative altitude.
-    pub async fn get_barometer_data(&self) -> NetworkResult<BarometerData> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        let barometer_name: Utf8String = "".to_string().into();
+    ///
+    /// args:
+    ///     barometer_name (Option<&str>): Name of the barometer to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_barometer_data(&self, barometer_name: Option<&str>) -> NetworkResult<BarometerData> {
+        let vehicle_name = self.vehicle_name;
+        let barometer_name: Utf8String = barometer_name.unwrap_or("").into();
         self.airsim_client
-            .unary_rpc("getBarometerData".into(), Some(vec![Value::String(barometer_name), Value::String(vehicle_name)]))
+            .vehicle_rpc(
+                "getBarometerData".into(),
+                Some(vec![Value::String(barometer_name), Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
             .await
             .map(BarometerData::from)
     }
 
     /// Get GPS data of the multirotor vehicle.  States include time, LLA, and is_valid
-    pub async fn get_gnss_data(&self) -> NetworkResult<GpsData> {
-        let vehicle_name: Utf8String = self.vehicle_name.into();
-        let gps_name: Utf8String = "".to_string().into();
+    ///
+    /// args:
+    ///     gps_name (Option<&str>): Name of the GPS to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_gnss_data(&self, gps_name: Option<&str>) -> NetworkResult<GpsData> {
+        let vehicle_name = self.vehicle_name;
+        let gps_name: Utf8String = gps_name.unwrap_or("").into();
         self.airsim_client
-            .unary_rpc("getGpsData".into(), Some(vec![Value::String(gps_name), Value::String(vehicle_name)]))
+            .vehicle_rpc(
+                "getGpsData".into(),
+                Some(vec![Value::String(gps_name), Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
             .await
             .map(GpsData::from)
     }
 
+    /// Get the last scan of a lidar of the multirotor vehicle
+    ///
+    /// args:
+    ///     lidar_name (Option<&str>): Name of the lidar to get data from, specified in settings.json. Defaults to the first one
+    pub async fn get_lidar_data(&self, lidar_name: Option<&str>) -> NetworkResult<LidarData> {
+        let vehicle_name = self.vehicle_name;
+        let lidar_name: Utf8String = lidar_name.unwrap_or("").into();
+        self.airsim_client
+            .vehicle_rpc(
+                "getLidarData".into(),
+                Some(vec![Value::String(lidar_name), Value::String(vehicle_name.into())]),
+                vehicle_name,
+            )
+            .await
+            .map(LidarData::from)
+    }
+
+    /// Get the last scan of a GPU lidar of the multirotor vehicle, Cosys-AirSim only
+    ///
+    /// args:
+    ///     lidar_name (Option<&str>): Name of the lidar, specified in settings.json. Defaults to the first one
+    #[cfg(feature = "colosseum")]
+    #[inline(always)]
+    pub async fn get_gpu_lidar_data(&self, lidar_name: Option<&str>) -> NetworkResult<crate::GpuLidarData> {
+        self.airsim_client
+            .get_gpu_lidar_data(lidar_name, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Returns the index of the last gate the vehicle passed through, see `AirsimClient::sim_list_gates`
+    #[cfg(feature = "racing")]
+    #[inline(always)]
+    pub async fn sim_get_last_gate_passed(&self) -> NetworkResult<i64> {
+        self.airsim_client
+            .sim_get_last_gate_passed(Some(self.vehicle_name))
+            .await
+    }
+
+    /// Returns True if the vehicle was disqualified from the race, e.g. for missing a gate
+    #[cfg(feature = "racing")]
+    #[inline(always)]
+    pub async fn sim_is_racer_disqualified(&self) -> NetworkResult<bool> {
+        self.airsim_client
+            .sim_is_racer_disqualified(Some(self.vehicle_name))
+            .await
+    }
+
     /// Camera API
     ///
     /// Returns binary string literal of compressed png image in presented as an vector of bytes
@@ -959,9 +1843,10 @@ impl MultiRotorClient {
     /// Returns bytes of png format image which can be dumped into abinary file to create .png image
     /// See https://microsoft.github.io/AirSim/image_apis/ for details
     ///
+    /// Faster than `sim_get_images` when only the png bytes of a single image are needed
+    ///
     /// args:
-    ///     vehicle_name (Option<&str>): Name of the vehicle to send this command to
-    ///     camera_name (String): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
     ///     image_type (ImageType): Type of image required
     ///     external (Option<bool>): Whether the camera is an External Camera
     #[inline(always)]
@@ -976,6 +1861,312 @@ impl MultiRotorClient {
             .await
     }
 
+    /// Camera API
+    ///
+    /// Get details about the camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_camera_info(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<CameraInfo> {
+        self.airsim_client
+            .sim_get_camera_info(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the distance the camera is focused at. Takes effect once manual focus is enabled
+    ///
+    /// args:
+    ///     focus_distance_cm (f32): Focus distance in cm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focus_distance(
+        &self,
+        focus_distance_cm: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focus_distance(focus_distance_cm, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the distance the camera is focused at, in cm
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focus_distance(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focus_distance(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the aperture of the camera, lower f-stops give a shallower depth of field
+    ///
+    /// args:
+    ///     focus_aperture (f32): Aperture as f-stop
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focus_aperture(
+        &self,
+        focus_aperture: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focus_aperture(focus_aperture, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the aperture of the camera, as f-stop
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focus_aperture(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focus_aperture(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the camera between manual focus and auto focus
+    ///
+    /// args:
+    ///     enable (bool): True to focus at the distance set by `sim_set_focus_distance`, False for auto focus
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_enable_manual_focus(
+        &self,
+        enable: bool,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_enable_manual_focus(enable, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the lens presets of a cine camera, e.g. `"12mm"` or `"85mm Prime f/1.8"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_preset_lens_settings(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        self.airsim_client
+            .sim_get_preset_lens_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the lens of a cine camera: focal and aperture ranges and diaphragm blades
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_lens_settings(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<String> {
+        self.airsim_client
+            .sim_get_lens_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the lens of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_lens_settings (&str): Name of the preset, see `sim_get_preset_lens_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_preset_lens_settings(
+        &self,
+        preset_lens_settings: &str,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_preset_lens_settings(preset_lens_settings, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the names of the filmback presets of a cine camera, e.g. `"35mm Full Aperture"` or `"Super 35mm"`
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_preset_filmback_settings(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<String>> {
+        self.airsim_client
+            .sim_get_preset_filmback_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Switch the filmback, i.e. the sensor, of a cine camera to a preset
+    ///
+    /// args:
+    ///     preset_filmback_settings (&str): Name of the preset, see `sim_get_preset_filmback_settings`
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_preset_filmback_settings(
+        &self,
+        preset_filmback_settings: &str,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_preset_filmback_settings(preset_filmback_settings, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns a description of the filmback of a cine camera: sensor width, height and aspect ratio
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_filmback_settings(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<String> {
+        self.airsim_client
+            .sim_get_filmback_settings(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the sensor size of a cine camera, e.g. to match the sensor of a real camera
+    ///
+    /// args:
+    ///     sensor_width (f32): Width of the sensor in mm
+    ///     sensor_height (f32): Height of the sensor in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_filmback_settings(
+        &self,
+        sensor_width: f32,
+        sensor_height: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_filmback_settings(
+                sensor_width,
+                sensor_height,
+                camera_name,
+                Some(self.vehicle_name),
+                external,
+            )
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the focal length of a cine camera, in mm
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_focal_length(&self, camera_name: &str, external: Option<bool>) -> NetworkResult<f32> {
+        self.airsim_client
+            .sim_get_focal_length(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the focal length of a cine camera, the field of view follows from it and the filmback
+    ///
+    /// args:
+    ///     focal_length (f32): Focal length in mm
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_focal_length(
+        &self,
+        focal_length: f32,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_focal_length(focal_length, camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Returns the lens distortion of a camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_distortion_params(
+        &self,
+        camera_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<DistortionParams> {
+        self.airsim_client
+            .sim_get_distortion_params(camera_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Set the lens distortion of a camera, e.g. the one calibrated on a real camera
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to be controlled
+    ///     params (DistortionParams): Coefficients of the distortion
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_distortion_params(
+        &self,
+        camera_name: &str,
+        params: DistortionParams,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_distortion_params(camera_name, params, Some(self.vehicle_name), external)
+            .await
+    }
+
     /// Camera API
     ///
     /// Get multiple images
@@ -986,10 +2177,144 @@ impl MultiRotorClient {
     ///     vehicle_name (Option<&str>): Name of vehicle associated with the camera
     ///     external (Option<bool>): Whether the camera is an External Camera
     #[inline(always)]
-    pub async fn sim_get_images(&self, _requests: ImageRequests, _external: Option<bool>) -> Result<(), NetworkError> {
-        // self.airsim_client
-        //     .sim_get_images(requests, Some(self.vehicle_name), external)
-        //     .await
-        unimplemented!("todo");
+    pub async fn sim_get_images(
+        &self,
+        requests: ImageRequests,
+        external: Option<bool>,
+    ) -> Result<Vec<ImageResponse>, NetworkError> {
+        self.airsim_client
+            .sim_get_images(requests, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Captures images and the ground truth kinematics of the vehicle at the same simulation tick,
+    /// pausing the simulation while both are queried
+    ///
+    /// args:
+    ///     requests (ImageRequests): Images required
+    #[inline(always)]
+    pub async fn capture_snapshot(&self, requests: ImageRequests) -> NetworkResult<Snapshot> {
+        self.airsim_client
+            .capture_snapshot(requests, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Camera API
+    ///
+    /// Captures the images of two cameras in a single request, with the baseline between them
+    ///
+    /// args:
+    ///     left_camera (&str): Name of the left camera
+    ///     right_camera (&str): Name of the right camera
+    ///     image_type (ImageType): Type of image required from both cameras
+    #[inline(always)]
+    pub async fn capture_stereo(
+        &self,
+        left_camera: &str,
+        right_camera: &str,
+        image_type: ImageType,
+    ) -> NetworkResult<StereoPair> {
+        self.airsim_client
+            .capture_stereo(left_camera, right_camera, image_type, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Turns a camera of the vehicle towards a point, keeping the camera at its mounting position
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera to turn
+    ///     target (Vector3): Point to look at, in the frame of the vehicle's starting point
+    #[inline(always)]
+    pub async fn camera_look_at(&self, camera_name: &str, target: Vector3) -> NetworkResult<()> {
+        self.airsim_client
+            .camera_look_at(camera_name, target, Some(self.vehicle_name))
+            .await
+    }
+
+    /// Detection API
+    ///
+    /// Set detection radius for all cameras
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     radius_cm (f32): Radius in [cm]
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_set_detection_filter_radius(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        radius_cm: f32,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_set_detection_filter_radius(camera_name, image_type, radius_cm, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Detection API
+    ///
+    /// Add mesh name to detect in wild card format
+    /// For example: mesh name "Car_*" will detect all instances named "Car_*"
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     mesh_name (&str): mesh name in wild card format
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_add_detection_filter_mesh_name(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        mesh_name: &str,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_add_detection_filter_mesh_name(camera_name, image_type, mesh_name, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Detection API
+    ///
+    /// Clear all mesh names from detection filter
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_clear_detection_mesh_names(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        external: Option<bool>,
+    ) -> NetworkResult<()> {
+        self.airsim_client
+            .sim_clear_detection_mesh_names(camera_name, image_type, Some(self.vehicle_name), external)
+            .await
+    }
+
+    /// Detection API
+    ///
+    /// Get current detections
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     external (Option<bool>): Whether the camera is an External Camera
+    #[inline(always)]
+    pub async fn sim_get_detections(
+        &self,
+        camera_name: &str,
+        image_type: ImageType,
+        external: Option<bool>,
+    ) -> NetworkResult<Vec<DetectionInfo>> {
+        self.airsim_client
+            .sim_get_detections(camera_name, image_type, Some(self.vehicle_name), external)
+            .await
     }
 }
@@ -0,0 +1,152 @@
+use rmpv::Value;
+
+use crate::error::{NetworkError, NetworkResult};
+use crate::{Gate, Vector3};
+
+use super::airsim_client::AirsimClient;
+
+/// Regex of the names of the gates in the Drone Racing Lab environments
+const GATE_NAMES: &str = "Gate.*";
+
+/// RPCs of the AirSim Drone Racing Lab, the simulator of the Game of Drones competition
+///
+/// Other AirSim builds answer them with a "rpc method not found" server error
+impl AirsimClient {
+    /// Start the race, the drones of the tier are released
+    ///
+    /// args:
+    ///     tier (u8): Tier of the competition, 1 to 3: time trial, with an opponent, with perception noise
+    pub async fn sim_start_race(&self, tier: u8) -> NetworkResult<()> {
+        self.unary_rpc("simStartRace".into(), Some(vec![Value::from(tier)]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Reset the race and the drones to the start
+    pub async fn sim_reset_race(&self) -> NetworkResult<()> {
+        self.unary_rpc("simResetRace".into(), None).await.map(|_| ())
+    }
+
+    /// Stop writing the race log, which costs a lot of frames per second
+    pub async fn sim_disable_race_log(&self) -> NetworkResult<()> {
+        self.unary_rpc("simDisableRaceLog".into(), None).await.map(|_| ())
+    }
+
+    /// Returns the size of the opening of a gate of scale 1, in meters
+    pub async fn sim_get_nominal_gate_inner_dimensions(&self) -> NetworkResult<Vector3> {
+        self.nominal_gate_dimensions("simGetNominalGateInnerDimensions").await
+    }
+
+    /// Returns the outer size of a gate of scale 1, in meters
+    pub async fn sim_get_nominal_gate_outer_dimensions(&self) -> NetworkResult<Vector3> {
+        self.nominal_gate_dimensions("simGetNominalGateOuterDimensions").await
+    }
+
+    async fn nominal_gate_dimensions(&self, method: &str) -> NetworkResult<Vector3> {
+        self.unary_rpc(method.into(), None)
+            .await
+            .and_then(|response| match response.result {
+                Ok(dimensions) if is_vector(&dimensions) => Ok(dimensions.into()),
+                _ => Err(NetworkError::UnexpectedResponse {
+                    method: method.to_owned(),
+                    message: "not a vector".into(),
+                }),
+            })
+    }
+
+    /// Returns the gates of the track in the order of the race, by `Gate::index`, gates without an index last
+    ///
+    /// The gates of some tracks are moving, their poses are the ones at the time of the call
+    pub async fn sim_list_gates(&self) -> NetworkResult<Vec<Gate>> {
+        let mut names = self.sim_list_scene_objects(GATE_NAMES).await?.0;
+        names.sort();
+
+        let mut gates = Vec::with_capacity(names.len());
+        for name in names {
+            let pose = self.sim_get_object_pose(&name).await?;
+            let scale = self.sim_get_object_scale(&name).await?;
+            gates.push(Gate { name, pose, scale });
+        }
+        // Gate10 sorts before Gate2 by name, and names with the same index keep their order
+        gates.sort_by_key(|gate| gate.index().unwrap_or(u32::MAX));
+        Ok(gates)
+    }
+
+    /// Returns the index of the last gate the vehicle passed through
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle
+    pub(crate) async fn sim_get_last_gate_passed(&self, vehicle_name: Option<&str>) -> NetworkResult<i64> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simGetLastGatePassed".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .and_then(|response| {
+            response
+                .result
+                .ok()
+                .and_then(|gate| gate.as_i64())
+                .ok_or_else(|| NetworkError::UnexpectedResponse {
+                    method: "simGetLastGatePassed".into(),
+                    message: "not a gate index".into(),
+                })
+        })
+    }
+
+    /// Returns True if the vehicle was disqualified, e.g. for missing a gate
+    ///
+    /// args:
+    ///     vehicle_name (Option<&str>): Name of the vehicle
+    pub(crate) async fn sim_is_racer_disqualified(&self, vehicle_name: Option<&str>) -> NetworkResult<bool> {
+        let vehicle_name = vehicle_name.unwrap_or("");
+
+        self.vehicle_rpc(
+            "simIsRacerDisqualified".into(),
+            Some(vec![Value::String(vehicle_name.into())]),
+            vehicle_name,
+        )
+        .await
+        .map(|response| response.result.is_ok() && response.result.unwrap().as_bool() == Some(true))
+    }
+}
+
+/// A vector is a map of its x, y and z values
+fn is_vector(value: &Value) -> bool {
+    matches!(value.as_map(), Some(fields) if fields.len() == 3 && fields.iter().all(|(_, v)| v.as_f64().is_some()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClient, NetworkError, Pose3, Vector3};
+
+    #[test]
+    fn test_gates_decoding_and_order() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond(
+            "simListSceneObjects",
+            Value::Array(vec![Value::from("Gate10"), Value::from("Gate2"), Value::from("Gate1")]),
+        );
+        server.respond("simGetObjectPose", Pose3::identity().as_msgpack());
+        server.respond("simGetObjectScale", Vector3::new(1.0, 1.0, 1.0).as_msgpack());
+        server.respond("simGetNominalGateInnerDimensions", Value::Nil);
+        server.respond("simGetLastGatePassed", Value::from("Gate2"));
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(&server.addr().to_string(), "").await.unwrap();
+
+            let gates = client.sim_list_gates().await.unwrap();
+            let names: Vec<_> = gates.iter().map(|gate| gate.name.as_str()).collect();
+            assert_eq!(names, ["Gate1", "Gate2", "Gate10"]);
+
+            let dimensions = client.sim_get_nominal_gate_inner_dimensions().await;
+            assert!(matches!(dimensions, Err(NetworkError::UnexpectedResponse { .. })));
+            let last = client.sim_get_last_gate_passed(Some("Drone1")).await;
+            assert!(matches!(last, Err(NetworkError::UnexpectedResponse { .. })));
+        });
+    }
+}
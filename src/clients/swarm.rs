@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use futures::future::join_all;
+
+use crate::error::NetworkResult;
+use crate::{AirsimClientBuilder, MoveOptions, MultiRotorClient, NetworkError, Position3};
+
+use super::airsim_client::AirsimClient;
+
+/// Result of a swarm command, per vehicle name
+pub type SwarmResults<T> = Vec<(&'static str, NetworkResult<T>)>;
+
+/// Multiple multirotors controlled over one shared connection
+///
+/// Commands are issued to all vehicles concurrently and the result of each vehicle is reported
+/// separately, so one failing vehicle doesn't hide the outcome of the others.
+pub struct Swarm {
+    drones: Vec<MultiRotorClient>,
+}
+
+impl Swarm {
+    /// Connect to AirSim and enable API control for every vehicle in `vehicle_names`
+    ///
+    /// Use `AirsimClientBuilder::connect_swarm` for other connection settings
+    pub async fn connect(addrs: &str, vehicle_names: &[&'static str]) -> NetworkResult<Self> {
+        AirsimClientBuilder::new()
+            .address(addrs)
+            .connect_swarm(vehicle_names)
+            .await
+    }
+
+    /// Control the vehicles in `vehicle_names` over an already established connection
+    pub(crate) async fn with_connection(
+        airsim_client: Arc<AirsimClient>,
+        vehicle_names: &[&'static str],
+        enable_api_control: bool,
+    ) -> NetworkResult<Self> {
+        let drones: Vec<_> = vehicle_names
+            .iter()
+            .map(|vehicle_name| MultiRotorClient::with_connection(Arc::clone(&airsim_client), vehicle_name))
+            .collect();
+
+        if enable_api_control {
+            for drone in &drones {
+                drone.enable_api_control(true).await?;
+            }
+        }
+        Ok(Self { drones })
+    }
+
+    /// Clients of all vehicles in the swarm
+    pub fn drones(&self) -> &[MultiRotorClient] {
+        &self.drones
+    }
+
+    /// Client of a single vehicle in the swarm
+    pub fn drone(&self, vehicle_name: &str) -> Option<&MultiRotorClient> {
+        self.drones.iter().find(|drone| drone.vehicle_name() == vehicle_name)
+    }
+
+    /// Arm or disarm all vehicles
+    ///
+    /// args:
+    ///     arm (bool): True to arm, False to disarm the vehicles
+    pub async fn arm_disarm_all(&self, arm: bool) -> SwarmResults<bool> {
+        let results = join_all(self.drones.iter().map(|drone| drone.arm_disarm(arm))).await;
+        self.zip_names(results)
+    }
+
+    /// Takeoff all vehicles to 3m above ground
+    ///
+    /// args:
+    ///     timeout_sec (f32): Timeout for the vehicles to reach desired altitude
+    pub async fn take_off_all(&self, timeout_sec: f32) -> SwarmResults<bool> {
+        let results = join_all(self.drones.iter().map(|drone| drone.take_off_async(timeout_sec))).await;
+        self.zip_names(results)
+    }
+
+    /// Hover all vehicles in place
+    pub async fn hover_all(&self) -> SwarmResults<bool> {
+        let results = join_all(self.drones.iter().map(|drone| drone.hover_async())).await;
+        self.zip_names(results)
+    }
+
+    /// Move each named vehicle to its own goal position
+    ///
+    /// Vehicles of the swarm without a goal position are left alone, names not part of the swarm
+    /// are reported as an error.
+    ///
+    /// args:
+    ///     positions (Vec<(&str, Position3)>): goal position per vehicle name
+    ///     options (&MoveOptions): velocity, timeout, drivetrain, yaw mode and lookahead of the movements
+    pub async fn move_to_positions(
+        &self,
+        positions: Vec<(&'static str, Position3)>,
+        options: &MoveOptions,
+    ) -> SwarmResults<bool> {
+        let moves = positions.into_iter().map(|(vehicle_name, position)| async move {
+            let result = match self.drone(vehicle_name) {
+                Some(drone) => drone.move_to_position_with(position, options).await,
+                None => Err(NetworkError::InvalidArgument {
+                    message: format!("vehicle {vehicle_name} is not part of the swarm"),
+                }),
+            };
+            (vehicle_name, result)
+        });
+
+        join_all(moves).await
+    }
+
+    /// Land all vehicles
+    ///
+    /// args:
+    ///     timeout_sec (f32): Timeout for the vehicles to land
+    pub async fn land_all(&self, timeout_sec: f32) -> SwarmResults<bool> {
+        let results = join_all(self.drones.iter().map(|drone| drone.land_async(timeout_sec))).await;
+        self.zip_names(results)
+    }
+
+    fn zip_names<T>(&self, results: Vec<NetworkResult<T>>) -> SwarmResults<T> {
+        self.drones
+            .iter()
+            .map(|drone| drone.vehicle_name())
+            .zip(results)
+            .collect()
+    }
+}
@@ -0,0 +1,5 @@
+mod model;
+mod monitor;
+
+pub use model::{Battery, EnergyModel};
+pub use monitor::{BatteryConfig, BatteryMonitor};
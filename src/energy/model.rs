@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use crate::{RotorStates, Vector3};
+
+/// Standard gravity, in m/s²
+const GRAVITY: f32 = 9.81;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Electrical power drawn by a multirotor, estimated from its rotors and velocity
+///
+/// The power is the sum of
+/// - `base_power`, drawn by the avionics and payload
+/// - `rotor_coefficient * thrust^1.5` for each rotor, the induced power of momentum theory divided by the efficiency
+///   of the motors and propellers
+/// - `drag_coefficient * speed^3`, the power spent against the air drag of the frame
+///
+/// Defaults to a ~1 kg quadcopter with 9" propellers, like the SimpleFlight quadcopter of AirSim, drawing ~75 W in
+/// hover
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnergyModel {
+    /// power drawn regardless of the flight, in W
+    pub base_power: f32,
+    /// power per rotor for a thrust of 1 N, in W/N^1.5. `1 / (efficiency * sqrt(2 * air density * disk area))`
+    pub rotor_coefficient: f32,
+    /// power spent against the drag at a speed of 1 m/s, in W/(m/s)³
+    pub drag_coefficient: f32,
+    /// number of rotors, used when estimating the power of a planned flight
+    pub rotor_count: u32,
+}
+
+impl Default for EnergyModel {
+    fn default() -> Self {
+        Self {
+            base_power: 5.0,
+            rotor_coefficient: 4.5,
+            drag_coefficient: 0.05,
+            rotor_count: 4,
+        }
+    }
+}
+
+impl EnergyModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base_power(mut self, base_power: f32) -> Self {
+        self.base_power = base_power;
+        self
+    }
+
+    pub fn rotor_coefficient(mut self, rotor_coefficient: f32) -> Self {
+        self.rotor_coefficient = rotor_coefficient;
+        self
+    }
+
+    pub fn drag_coefficient(mut self, drag_coefficient: f32) -> Self {
+        self.drag_coefficient = drag_coefficient;
+        self
+    }
+
+    pub fn rotor_count(mut self, rotor_count: u32) -> Self {
+        self.rotor_count = rotor_count;
+        self
+    }
+
+    /// Power drawn by the vehicle in the given state, in W
+    ///
+    /// args:
+    ///     rotors (&RotorStates): Thrust of each rotor, from `get_rotor_states`
+    ///     velocity (&Vector3): Linear velocity of the vehicle, in m/s
+    pub fn power(&self, rotors: &RotorStates, velocity: &Vector3) -> f32 {
+        let rotor_power: f32 = rotors
+            .rotors
+            .iter()
+            .map(|rotor| self.rotor_coefficient * rotor.thrust.max(0.0).powf(1.5))
+            .sum();
+        let speed = (velocity.x * velocity.x + velocity.y * velocity.y + velocity.z * velocity.z).sqrt();
+        self.base_power + rotor_power + self.drag_power(speed)
+    }
+
+    /// Power drawn by a vehicle of the given mass flying level at a constant speed, in W
+    ///
+    /// args:
+    ///     mass (f32): Mass of the vehicle, in kg
+    ///     speed (f32): Speed of the vehicle, in m/s. 0 for hover
+    pub fn cruise_power(&self, mass: f32, speed: f32) -> f32 {
+        let rotor_count = self.rotor_count.max(1) as f32;
+        let thrust = mass * GRAVITY / rotor_count;
+        self.base_power + rotor_count * self.rotor_coefficient * thrust.powf(1.5) + self.drag_power(speed)
+    }
+
+    /// Energy needed to fly `distance` meters at a constant speed, in Wh
+    ///
+    /// args:
+    ///     mass (f32): Mass of the vehicle, in kg
+    ///     speed (f32): Speed of the vehicle, in m/s
+    ///     distance (f32): Distance to fly, in meters
+    pub fn cruise_energy(&self, mass: f32, speed: f32, distance: f32) -> f32 {
+        let duration = distance / speed;
+        self.cruise_power(mass, speed) * duration / 3600.0
+    }
+
+    fn drag_power(&self, speed: f32) -> f32 {
+        self.drag_coefficient * speed.abs().powi(3)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Battery discharged by integrating the power drawn over the simulation time
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Battery {
+    /// capacity of the full battery, in Wh
+    pub capacity: f32,
+    /// energy left, in Wh
+    pub remaining: f32,
+    /// latest power drawn, in W
+    pub power: f32,
+    /// simulation time of the latest update, in nanoseconds
+    pub timestamp: Option<u64>,
+}
+
+impl Battery {
+    /// Full battery
+    ///
+    /// args:
+    ///     capacity (f32): Capacity of the battery, in Wh
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            capacity,
+            remaining: capacity,
+            power: 0.0,
+            timestamp: None,
+        }
+    }
+
+    /// Discharge the battery with the power drawn since the previous update, the first update only sets the time
+    ///
+    /// args:
+    ///     power (f32): Power drawn, in W
+    ///     timestamp (u64): Simulation time, in nanoseconds
+    pub fn update(&mut self, power: f32, timestamp: u64) {
+        if let Some(previous) = self.timestamp {
+            let hours = timestamp.saturating_sub(previous) as f32 / 3.6e12;
+            // trapezoidal rule between the two samples
+            self.remaining = (self.remaining - (self.power + power) / 2.0 * hours).max(0.0);
+        }
+        self.power = power;
+        self.timestamp = Some(timestamp);
+    }
+
+    /// Fraction of the capacity left, between 0 and 1
+    pub fn remaining_fraction(&self) -> f32 {
+        if self.capacity > 0.0 {
+            self.remaining / self.capacity
+        } else {
+            0.0
+        }
+    }
+
+    /// Flight time left at the latest power drawn, None before the power is known or when the readings give no
+    /// finite, positive time
+    pub fn endurance(&self) -> Option<Duration> {
+        let seconds = self.remaining / self.power * 3600.0;
+        // also rejects NaN, which fails every comparison
+        if self.power > 0.0 && seconds >= 0.0 && seconds < u64::MAX as f32 {
+            Some(Duration::from_secs_f32(seconds))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::energy::{Battery, EnergyModel};
+    use crate::{RotorState, RotorStates, Vector3};
+
+    #[test]
+    fn test_hover_power_matches_rotor_states() {
+        let model = EnergyModel::new();
+        let rotor = RotorState {
+            thrust: 1.0 * 9.81 / 4.0,
+            torque_scaler: 0.0,
+            speed: 0.0,
+        };
+        let rotors = RotorStates {
+            rotors: [rotor; 4],
+            timestamp: 0,
+        };
+
+        let hover = model.power(&rotors, &Vector3::new(0.0, 0.0, 0.0));
+        assert!((hover - model.cruise_power(1.0, 0.0)).abs() < 1e-3);
+        assert!(hover > 60.0 && hover < 90.0);
+        assert!(model.cruise_power(1.0, 10.0) > hover);
+    }
+
+    #[test]
+    fn test_battery_discharge() {
+        let mut battery = Battery::new(10.0);
+        assert_eq!(battery.endurance(), None);
+
+        battery.update(100.0, 0);
+        // 100 W for 36 s is 1 Wh
+        battery.update(100.0, 36_000_000_000);
+        assert!((battery.remaining - 9.0).abs() < 1e-4);
+        assert!((battery.remaining_fraction() - 0.9).abs() < 1e-4);
+        assert_eq!(battery.endurance().unwrap().as_secs(), 324);
+
+        battery.remaining = f32::NAN;
+        assert_eq!(battery.endurance(), None);
+        battery.remaining = -1.0;
+        assert_eq!(battery.endurance(), None);
+        battery.remaining = 10.0;
+        battery.power = f32::MIN_POSITIVE;
+        assert_eq!(battery.endurance(), None);
+    }
+}
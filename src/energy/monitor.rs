@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::task::{self, JoinHandle};
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::MultiRotorClient;
+
+use super::{Battery, EnergyModel};
+
+type LowBatteryCallback = Arc<dyn Fn(&Battery) + Send + Sync>;
+
+#[derive(Clone)]
+/// Battery simulated by the `BatteryMonitor`
+///
+/// Defaults to the default `EnergyModel`, sampling at 10 Hz and a low battery event at 20% of the capacity
+pub struct BatteryConfig {
+    /// capacity of the full battery, in Wh
+    pub capacity: f32,
+    /// power drawn by the vehicle
+    pub model: EnergyModel,
+    /// fraction of the capacity under which the battery is low
+    pub low_battery: f32,
+    /// sampling rate in Hz
+    pub rate_hz: f32,
+    on_low_battery: Option<LowBatteryCallback>,
+}
+
+impl BatteryConfig {
+    /// args:
+    ///     capacity (f32): capacity of the full battery, in Wh
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            capacity,
+            model: EnergyModel::default(),
+            low_battery: 0.2,
+            rate_hz: 10.0,
+            on_low_battery: None,
+        }
+    }
+
+    pub fn model(mut self, model: EnergyModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn low_battery(mut self, low_battery: f32) -> Self {
+        self.low_battery = low_battery;
+        self
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+
+    /// Called once, when the remaining capacity first drops under `low_battery`
+    pub fn on_low_battery(mut self, on_low_battery: impl Fn(&Battery) + Send + Sync + 'static) -> Self {
+        self.on_low_battery = Some(Arc::new(on_low_battery));
+        self
+    }
+}
+
+/// Background task discharging a simulated battery with the power drawn by a multirotor
+///
+/// Sampling ends when `stop` is awaited or the monitor is dropped. The battery only models the energy left,
+/// the vehicle keeps flying once it is empty.
+pub struct BatteryMonitor {
+    running: Arc<AtomicBool>,
+    battery: Arc<Mutex<Battery>>,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl BatteryMonitor {
+    /// Start discharging a full battery with the flight of `client`
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to watch, the monitor shares its connection
+    ///     config (BatteryConfig): Battery, energy model, sampling rate and low battery event
+    ///
+    /// # Panics
+    /// If the sampling rate isn't positive
+    pub fn start(client: &MultiRotorClient, config: BatteryConfig) -> Self {
+        let period = periodic::period("battery monitor", config.rate_hz);
+        let running = Arc::new(AtomicBool::new(true));
+        let battery = Arc::new(Mutex::new(Battery::new(config.capacity)));
+        let task = task::spawn(Self::monitor(
+            client.clone(),
+            config,
+            period,
+            Arc::clone(&running),
+            Arc::clone(&battery),
+        ));
+
+        Self {
+            running,
+            battery,
+            task: Some(task),
+        }
+    }
+
+    /// Latest state of the battery
+    pub fn battery(&self) -> Battery {
+        *self.battery.lock().unwrap()
+    }
+
+    /// Whether the monitor is still sampling
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Stop sampling and return the final state of the battery
+    ///
+    /// Returns the first error the background task ran into, which also ends the sampling
+    pub async fn stop(mut self) -> NetworkResult<Battery> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(task) = self.task.take() {
+            task.await?;
+        }
+        Ok(self.battery())
+    }
+
+    async fn monitor(
+        client: MultiRotorClient,
+        config: BatteryConfig,
+        period: Duration,
+        running: Arc<AtomicBool>,
+        battery: Arc<Mutex<Battery>>,
+    ) -> NetworkResult<()> {
+        let mut ticker = Ticker::new(period);
+        let mut low_battery_reported = false;
+
+        while running.load(Ordering::SeqCst) {
+            let (rotors, state) = futures::join!(client.get_rotor_states(), client.get_multirotor_state());
+            let (rotors, state) = match (rotors, state) {
+                (Ok(rotors), Ok(state)) => (rotors, state),
+                (Err(e), _) | (_, Err(e)) => {
+                    running.store(false, Ordering::SeqCst);
+                    return Err(e);
+                }
+            };
+            let power = config.model.power(&rotors, &state.kinematics_estimated.linear_velocity);
+
+            let current = {
+                let mut battery = battery.lock().unwrap();
+                battery.update(power, rotors.timestamp);
+                *battery
+            };
+
+            if !low_battery_reported && current.remaining_fraction() < config.low_battery {
+                low_battery_reported = true;
+                log::warn!(
+                    "{} battery is low: {:.1} Wh left",
+                    client.vehicle_name(),
+                    current.remaining
+                );
+                if let Some(on_low_battery) = &config.on_low_battery {
+                    on_low_battery(&current);
+                }
+            }
+
+            ticker.tick().await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for BatteryMonitor {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
@@ -1,5 +1,5 @@
 use async_std::channel::RecvError;
-use msgpack_rpc::DecodeError;
+use msgpack_rpc::{DecodeError, Value};
 use std::io;
 use thiserror::Error;
 
@@ -13,6 +13,38 @@ pub enum NetworkError {
     Io(#[from] io::Error),
     #[error("Could not send message: {message}")]
     Send { message: String },
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    #[error("Unexpected response to {method}: {message}")]
+    UnexpectedResponse {
+        /// name of the RPC
+        method: String,
+        /// what the response lacks
+        message: String,
+    },
     #[error("Could not decode the message that was received")]
     Decode(#[from] DecodeError),
+    #[error("{method} failed: {message}")]
+    Rpc {
+        /// name of the RPC that failed
+        method: String,
+        /// error message sent by the server
+        message: String,
+        /// error payload as sent by the server
+        raw: Value,
+    },
+    #[error("AirSim {outdated} is too old: client version {client_version}, server version {server_version}")]
+    Incompatible {
+        /// "client" or "server", whichever has to be upgraded
+        outdated: &'static str,
+        /// version of this client
+        client_version: u64,
+        /// version reported by the server, 0 for servers too old to report it
+        server_version: u64,
+    },
+    #[error("Could not parse the settings sent by the server: {message}")]
+    Settings {
+        /// error of the JSON parser
+        message: String,
+    },
 }
@@ -0,0 +1,144 @@
+use async_std::channel::{self, Receiver, Sender};
+
+use crate::error::NetworkResult;
+use crate::{LandedState, ManeuverOutcome, MultiRotorClient, NetworkError};
+
+use super::{FlightEvent, FlightState};
+
+/// Tracks the flight of a multirotor through arming, take off and landing
+///
+/// Commands sent through the machine are checked against the current state, a command the state doesn't allow fails
+/// with `NetworkError::InvalidArgument` without being sent. Sent commands are confirmed with the landed state
+/// reported by the vehicle. Every change is sent as a `FlightEvent` to the receiver returned by `new`, events are
+/// dropped once the receiver is.
+pub struct FlightStateMachine {
+    client: MultiRotorClient,
+    state: FlightState,
+    events: Sender<FlightEvent>,
+}
+
+impl FlightStateMachine {
+    /// Start tracking a disarmed vehicle, see `update` to start from the state reported by the vehicle
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to control, the machine shares its connection
+    pub fn new(client: &MultiRotorClient) -> (Self, Receiver<FlightEvent>) {
+        let (events, receiver) = channel::unbounded();
+        let machine = Self {
+            client: client.clone(),
+            state: FlightState::Disarmed,
+            events,
+        };
+        (machine, receiver)
+    }
+
+    /// Current state of the flight
+    pub fn state(&self) -> FlightState {
+        self.state
+    }
+
+    /// Arm the motors of the disarmed vehicle
+    pub async fn arm(&mut self) -> NetworkResult<()> {
+        self.check(FlightState::Armed)?;
+        if self.client.arm_disarm(true).await? {
+            self.transition(FlightState::Armed);
+        }
+        Ok(())
+    }
+
+    /// Disarm the motors of the vehicle, which has to be on the ground
+    pub async fn disarm(&mut self) -> NetworkResult<()> {
+        self.check(FlightState::Disarmed)?;
+        if self.client.arm_disarm(false).await? {
+            self.transition(FlightState::Disarmed);
+        }
+        Ok(())
+    }
+
+    /// Take off and wait until the vehicle is flying
+    ///
+    /// args:
+    ///     timeout_sec (f32): Timeout for the vehicle to reach desired altitude
+    pub async fn take_off(&mut self, timeout_sec: f32) -> NetworkResult<ManeuverOutcome> {
+        self.check(FlightState::TakingOff)?;
+        self.transition(FlightState::TakingOff);
+
+        let outcome = self.client.take_off_and_wait(timeout_sec).await?;
+        self.finish_maneuver(outcome, FlightState::Flying).await?;
+        Ok(outcome)
+    }
+
+    /// Land and wait until the vehicle is on the ground
+    ///
+    /// args:
+    ///     timeout_sec (f32): Timeout for the vehicle to land
+    pub async fn land(&mut self, timeout_sec: f32) -> NetworkResult<ManeuverOutcome> {
+        self.check(FlightState::Landing)?;
+        self.transition(FlightState::Landing);
+
+        let outcome = self.client.land_and_wait(timeout_sec).await?;
+        self.finish_maneuver(outcome, FlightState::Landed).await?;
+        Ok(outcome)
+    }
+
+    /// Align the state with the landed state reported by the vehicle, e.g. after a crash or a command sent
+    /// outside of the machine
+    ///
+    /// Whether the motors are armed isn't reported by AirSim, a vehicle on the ground keeps its state
+    pub async fn update(&mut self) -> NetworkResult<FlightState> {
+        let landed_state = self.client.get_multirotor_state().await?.landed_state;
+        match landed_state {
+            LandedState::Flying if self.state != FlightState::Flying && self.state != FlightState::Disarmed => {
+                self.transition(FlightState::Flying)
+            }
+            LandedState::Landed if !self.state.is_on_ground() => self.transition(FlightState::Landed),
+            _ => {}
+        }
+        Ok(self.state)
+    }
+
+    async fn finish_maneuver(&mut self, outcome: ManeuverOutcome, target: FlightState) -> NetworkResult<()> {
+        if outcome.is_success() {
+            self.transition(target);
+            return Ok(());
+        }
+
+        self.emit(FlightEvent::ManeuverFailed {
+            state: self.state,
+            outcome,
+        });
+        self.update().await?;
+        Ok(())
+    }
+
+    fn check(&self, requested: FlightState) -> NetworkResult<()> {
+        if self.state.can_transition_to(requested) {
+            return Ok(());
+        }
+
+        self.emit(FlightEvent::Rejected {
+            state: self.state,
+            requested,
+        });
+        Err(NetworkError::InvalidArgument {
+            message: format!(
+                "{} can't go from {:?} to {:?}",
+                self.client.vehicle_name(),
+                self.state,
+                requested
+            ),
+        })
+    }
+
+    fn transition(&mut self, to: FlightState) {
+        let from = self.state;
+        self.state = to;
+        log::debug!("{} flight state {:?} -> {:?}", self.client.vehicle_name(), from, to);
+        self.emit(FlightEvent::Transition { from, to });
+    }
+
+    fn emit(&self, event: FlightEvent) {
+        // the receiver may have been dropped by a caller not interested in events
+        let _ = self.events.try_send(event);
+    }
+}
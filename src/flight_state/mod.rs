@@ -0,0 +1,5 @@
+mod machine;
+mod state;
+
+pub use machine::FlightStateMachine;
+pub use state::{FlightEvent, FlightState};
@@ -0,0 +1,96 @@
+use crate::ManeuverOutcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Phase of the flight of a multirotor, as tracked by the `FlightStateMachine`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlightState {
+    /// On the ground with the motors disarmed
+    Disarmed,
+    /// On the ground with the motors armed, ready to take off
+    Armed,
+    /// Take off requested, the vehicle isn't flying yet
+    TakingOff,
+    /// In the air
+    Flying,
+    /// Landing requested, the vehicle isn't on the ground yet
+    Landing,
+    /// Back on the ground after a flight, the motors are still armed
+    Landed,
+}
+
+impl FlightState {
+    /// Whether the machine can move from this state to `to`
+    ///
+    /// Besides the commanded transitions, the vehicle may be reported flying or landed at any time, e.g. after a
+    /// failed take off, a crash or a command sent outside of the machine
+    pub fn can_transition_to(&self, to: FlightState) -> bool {
+        use FlightState::*;
+
+        matches!(
+            (self, to),
+            (Disarmed, Armed)
+                | (Armed, Disarmed)
+                | (Landed, Disarmed)
+                | (Armed, TakingOff)
+                | (Landed, TakingOff)
+                | (TakingOff, Flying)
+                | (TakingOff, Landed)
+                | (Flying, Landing)
+                | (Landing, Landed)
+                | (Landing, Flying)
+                | (Armed, Flying)
+                | (Landed, Flying)
+                | (Flying, Landed)
+        )
+    }
+
+    /// Whether the vehicle is expected to be on the ground
+    pub fn is_on_ground(&self) -> bool {
+        matches!(self, FlightState::Disarmed | FlightState::Armed | FlightState::Landed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Event emitted by the `FlightStateMachine`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlightEvent {
+    /// The machine moved to a new state
+    Transition {
+        /// state before the transition
+        from: FlightState,
+        /// state after the transition
+        to: FlightState,
+    },
+    /// A take off or landing didn't reach its target, the state is then reconciled with the vehicle
+    ManeuverFailed {
+        /// `TakingOff` or `Landing`
+        state: FlightState,
+        /// why the maneuver failed
+        outcome: ManeuverOutcome,
+    },
+    /// A command was refused because it isn't allowed in the current state
+    Rejected {
+        /// state when the command was sent
+        state: FlightState,
+        /// state the command would have moved to
+        requested: FlightState,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flight_state::FlightState::*;
+
+    #[test]
+    fn test_transitions() {
+        let flight = [Disarmed, Armed, TakingOff, Flying, Landing, Landed, Disarmed];
+        assert!(flight.windows(2).all(|w| w[0].can_transition_to(w[1])));
+
+        assert!(!Disarmed.can_transition_to(TakingOff));
+        assert!(!Flying.can_transition_to(Disarmed));
+        assert!(!Landing.can_transition_to(TakingOff));
+        // feedback from the vehicle
+        assert!(TakingOff.can_transition_to(Landed));
+        assert!(Landing.can_transition_to(Flying));
+    }
+}
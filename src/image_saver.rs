@@ -0,0 +1,175 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use async_std::fs;
+use futures::{Stream, StreamExt};
+
+use crate::{FloatImage, ImageResponse};
+
+/// Writes images returned by `simGetImages` to a directory without blocking the caller on disk IO
+///
+/// File names are built from a template in which `{timestamp}`, `{frame}`, `{camera}` and `{image_type}` are
+/// replaced by the capture time in nanoseconds, the zero padded frame index, the camera name and the image type.
+/// The extension depends on the image: compressed images are written as `.png`, float images as `.pfm` and
+/// uncompressed images as `.ppm`.
+pub struct ImageSaver {
+    directory: PathBuf,
+    template: String,
+    frame: u64,
+}
+
+impl ImageSaver {
+    /// Default file name template, e.g. `000042_front_Scene`
+    pub const DEFAULT_TEMPLATE: &'static str = "{frame}_{camera}_{image_type}";
+
+    /// Create a saver writing into `directory`, which is created when the first frame is saved
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            template: Self::DEFAULT_TEMPLATE.to_owned(),
+            frame: 0,
+        }
+    }
+
+    /// Use `template` to name the files instead of `DEFAULT_TEMPLATE`
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = template.to_owned();
+        self
+    }
+
+    /// Index of the next frame to be saved
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Save a single image as its own frame
+    pub async fn save(&mut self, response: &ImageResponse) -> io::Result<PathBuf> {
+        let mut paths = self.save_frame(std::slice::from_ref(response)).await?;
+        Ok(paths.remove(0))
+    }
+
+    /// Save the images of one `simGetImages` call under the same frame index
+    pub async fn save_frame(&mut self, responses: &[ImageResponse]) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(&self.directory).await?;
+
+        let mut paths = vec![];
+        for response in responses {
+            let (extension, bytes) = encode(response)?;
+            let path = self.directory.join(format!("{}.{extension}", self.file_stem(response)));
+            fs::write(&path, bytes).await?;
+            paths.push(path);
+        }
+
+        self.frame += 1;
+        Ok(paths)
+    }
+
+    /// Save every frame of `frames` until the stream ends, returns the number of frames saved
+    pub async fn save_stream<S>(&mut self, frames: S) -> io::Result<u64>
+    where
+        S: Stream<Item = Vec<ImageResponse>>,
+    {
+        futures::pin_mut!(frames);
+
+        let mut saved = 0;
+        while let Some(frame) = frames.next().await {
+            self.save_frame(&frame).await?;
+            saved += 1;
+        }
+        Ok(saved)
+    }
+
+    /// Directory the images are written to
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn file_stem(&self, response: &ImageResponse) -> String {
+        self.template
+            .replace("{timestamp}", &response.time_stamp.to_string())
+            .replace("{frame}", &format!("{:06}", self.frame))
+            .replace("{camera}", &response.camera_name)
+            .replace("{image_type}", &format!("{:?}", response.image_type))
+    }
+}
+
+/// File extension and content of an image
+fn encode(response: &ImageResponse) -> io::Result<(&'static str, Vec<u8>)> {
+    if response.pixels_as_float {
+        Ok(("pfm", encode_pfm(response)?))
+    } else if response.compress {
+        Ok(("png", response.image_data_uint8.clone()))
+    } else {
+        Ok(("ppm", encode_ppm(response)?))
+    }
+}
+
+/// Grayscale PFM, see `FloatImage::to_pfm`
+fn encode_pfm(response: &ImageResponse) -> io::Result<Vec<u8>> {
+    check_len(
+        response.image_data_float.len(),
+        response.width as usize * response.height as usize,
+    )?;
+    let image = FloatImage::from_response(response).expect("float image with a valid size");
+    Ok(image.to_pfm())
+}
+
+/// Binary PPM, AirSim sends the pixels as BGR
+fn encode_ppm(response: &ImageResponse) -> io::Result<Vec<u8>> {
+    let (width, height) = (response.width as usize, response.height as usize);
+    check_len(response.image_data_uint8.len(), width * height * 3)?;
+
+    let mut bytes = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for pixel in response.image_data_uint8.chunks(3) {
+        bytes.extend_from_slice(&[pixel[2], pixel[1], pixel[0]]);
+    }
+    Ok(bytes)
+}
+
+fn check_len(len: usize, expected: usize) -> io::Result<()> {
+    if len == expected {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("image has {len} values, expected {expected} from its size"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image_saver::encode_pfm;
+    use crate::{ImageResponse, ImageSaver, ImageType, Quaternion, Vector3};
+
+    fn depth_image() -> ImageResponse {
+        ImageResponse {
+            image_data_uint8: vec![],
+            image_data_float: vec![1.0, 2.0, 3.0, 4.0],
+            camera_name: "front".to_owned(),
+            camera_position: Vector3::new(0.0, 0.0, 0.0),
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            time_stamp: 1234,
+            message: String::new(),
+            pixels_as_float: true,
+            compress: false,
+            width: 2,
+            height: 2,
+            image_type: ImageType::DepthPlanar,
+        }
+    }
+
+    #[test]
+    fn test_file_stem() {
+        let saver = ImageSaver::new("images").template("{timestamp}-{frame}-{camera}-{image_type}");
+        assert_eq!(saver.file_stem(&depth_image()), "1234-000000-front-DepthPlanar");
+    }
+
+    #[test]
+    fn test_pfm_rows_bottom_to_top() {
+        let bytes = encode_pfm(&depth_image()).unwrap();
+        let header = b"Pf\n2 2\n-1\n";
+        assert_eq!(&bytes[..header.len()], header);
+        assert_eq!(&bytes[header.len()..header.len() + 4], &3.0_f32.to_le_bytes());
+    }
+}
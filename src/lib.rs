@@ -1,26 +1,93 @@
 pub use clients::airsim_client::AirsimClient;
+pub use clients::api_control_guard::ApiControlGuard;
+pub use clients::batch::RpcBatch;
+pub use clients::builder::AirsimClientBuilder;
 pub use clients::car_client::CarClient;
+pub use clients::computer_vision_client::ComputerVisionClient;
+pub use clients::fleet::{Fleet, Vehicle};
+pub use clients::middleware::Middleware;
 pub use clients::multi_rotor_client::MultiRotorClient;
+pub use clients::swarm::{Swarm, SwarmResults};
 pub use error::{NetworkError, NetworkResult};
-pub use msgpack_rpc::DecodeError;
+pub use image_saver::ImageSaver;
+#[cfg(feature = "mavlink")]
+pub use mavlink_bridge::{MavlinkBridge, MavlinkConfig};
+pub use msgpack_rpc::{DecodeError, Value};
+pub use telemetry::recorder::{TelemetryConfig, TelemetryFormat, TelemetryRecorder};
+pub use types::agl::{AglAltitude, AglConfig, AglSource};
+pub use types::camera_info::CameraInfo;
+pub use types::collision_info::CollisionInfo;
+pub use types::debug_plot::{Color, PlotDuration};
+pub use types::detection::{Box2D, Box3D, DetectionInfo};
+pub use types::distortion::DistortionParams;
 pub use types::drive_train::DrivetrainType;
+pub use types::environment::EnvironmentState;
+pub use types::float_encoding::FloatEncoding;
 pub use types::gains::{AngularControllerGains, LinearControllerGains, PIDGains};
-pub use types::geopoint::GeoPoint;
-pub use types::image::{CompressedImage, ImageRequest, ImageRequests, ImageType};
-pub use types::path::Path;
-pub use types::pose::{Orientation2, Orientation3, Pose3, Position3, Quaternion, Velocity2, Velocity3};
+#[cfg(feature = "racing")]
+pub use types::gate::Gate;
+pub use types::geopoint::{GeoPoint, GeoPointError};
+#[cfg(feature = "colosseum")]
+pub use types::gpu_lidar::{GpuLidarData, GpuLidarPoint};
+pub use types::image::{
+    CameraProfile, CompressedImage, FloatImage, ImageEncoding, ImageRequest, ImageRequests, ImageResponse, ImageType,
+    OpticalFlowImage, RgbImage,
+};
+pub use types::maneuver::ManeuverOutcome;
+pub use types::mesh::MeshData;
+pub use types::move_options::MoveOptions;
+pub use types::multi_rotor_state::{LandedState, MultiRotorState};
+pub use types::path::{Path, PathEvent, PathProgress, PathTracker};
+pub use types::polar_scan::PolarScan;
+pub use types::pose::{
+    KinematicsState, Orientation2, Orientation3, Pose3, Position3, Quaternion, Velocity2, Velocity3,
+};
 pub use types::pwm::PWM;
-pub use types::rc_data::RCData;
+pub use types::quaternion::Quaternionr;
+pub use types::rc_data::{RCData, RCDataState};
+pub use types::retry_policy::RetryPolicy;
 pub use types::rotor_states::{RotorState, RotorStates};
+pub use types::sensors::{
+    BarometerData, DistanceSensorData, GnssFixType, GnssReport, GpsData, ImuData, LidarData, MagnetometerData,
+};
+pub use types::settings::{ClockType, SensorInfo};
+#[cfg(feature = "serde")]
+pub use types::settings::{AirsimSettings, CameraSettings, CaptureSettings, SensorSettings, VehicleSettings};
 pub use types::simulation::SceneObjects;
-pub use types::vector::Vector3;
+pub use types::snapshot::Snapshot;
+pub use types::stereo::StereoPair;
+pub use types::time_of_day::TimeOfDay;
+pub use types::timestamped::Timestamped;
+pub use types::vector::{Vector2, Vector3};
 pub use types::weather::WeatherParameter;
+pub use types::wrench::Wrench;
 pub use types::yaw_mode::YawMode;
 
 pub(crate) use msgpack::MsgPackClient;
+pub mod camera;
 mod clients;
+pub mod energy;
 mod error;
+pub mod flight_state;
+mod image_saver;
+pub mod maneuvers;
+#[cfg(feature = "mavlink")]
+mod mavlink_bridge;
+pub mod metrics;
+#[cfg(feature = "mission")]
+pub mod mission;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 mod msgpack;
+pub mod offboard;
+pub mod ops;
+mod periodic;
+pub mod replay;
+pub mod safety;
+pub mod scenarios;
+pub mod sensors;
+mod telemetry;
+pub mod teleop;
 mod types;
 
 #[cfg(test)]
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use async_std::future;
+use futures::stream::{Stream, StreamExt};
+
+use crate::error::NetworkResult;
+use crate::{DrivetrainType, MoveOptions, MultiRotorClient, Position3, Vector3, Velocity3, YawMode};
+
+#[derive(Debug, Clone, Copy)]
+/// Settings of the follow-target maneuver
+///
+/// Defaults keep 5 m behind and 3 m above the target (offset (-5, 0, -3) in NED), fly at up to 8 m/s and stop the
+/// vehicle if no target position comes for a second
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FollowConfig {
+    /// position to hold relative to the target, in the NED axis
+    pub offset: Vector3,
+    /// largest speed of the vehicle in m/s
+    pub max_speed: f32,
+    /// speed per meter of distance to the held position, in 1/s
+    pub gain: f32,
+    /// duration of every velocity command, the vehicle stops if the stream stalls for longer
+    pub command_duration_sec: f32,
+    /// turn the vehicle towards the target, else keep its heading
+    pub face_target: bool,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            offset: Vector3::new(-5.0, 0.0, -3.0),
+            max_speed: 8.0,
+            gain: 1.0,
+            command_duration_sec: 1.0,
+            face_target: true,
+        }
+    }
+}
+
+impl FollowConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn offset(mut self, offset: Vector3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+
+    pub fn gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    pub fn command_duration_sec(mut self, command_duration_sec: f32) -> Self {
+        self.command_duration_sec = command_duration_sec;
+        self
+    }
+
+    pub fn face_target(mut self, face_target: bool) -> Self {
+        self.face_target = face_target;
+        self
+    }
+}
+
+/// Velocity bringing the vehicle at `position` to `target` plus the offset of `config`, within its speed limit
+pub(crate) fn follow_velocity(position: Position3, target: Position3, config: &FollowConfig) -> Velocity3 {
+    let goal = Vector3::from(target) + config.offset;
+    let velocity = ((goal - Vector3::from(position)) * config.gain).clamp_norm(config.max_speed);
+    Velocity3::from(velocity)
+}
+
+/// Follow a moving target, e.g. another vehicle, a character or a tracked detection, until its stream ends
+///
+/// A velocity command is sent for every target position received, so the stream sets the rate of the control loop.
+/// The vehicle hovers once the stream ends
+///
+/// args:
+///     client (&MultiRotorClient): Vehicle to fly, armed and flying
+///     targets (impl Stream<Item = Position3>): Positions of the target, in the NED frame of the vehicle's starting point
+///     config (&FollowConfig): Offset, speed and gain of the maneuver
+pub async fn follow_target(
+    client: &MultiRotorClient,
+    targets: impl Stream<Item = Position3>,
+    config: &FollowConfig,
+) -> NetworkResult<()> {
+    futures::pin_mut!(targets);
+    let timeout = Duration::from_secs_f32(config.command_duration_sec);
+
+    while let Some(target) = targets.next().await {
+        let position = client.get_multirotor_state().await?.kinematics_estimated.position;
+        let velocity = follow_velocity(position, target, config);
+
+        let yaw_mode = if config.face_target && (target - position).norm() > 1.0 {
+            YawMode::angle((target.y - position.y).atan2(target.x - position.x).to_degrees())
+        } else {
+            YawMode::rate(0.0)
+        };
+        let options = MoveOptions::new()
+            .drivetrain(DrivetrainType::MaxDegreeOfFreedom)
+            .yaw_mode(yaw_mode);
+
+        // the next target replaces the command, don't wait for it to expire
+        let command = client.move_by_velocity_with(velocity, config.command_duration_sec, &options);
+        if let Ok(result) = future::timeout(timeout, command).await {
+            result?;
+        }
+    }
+
+    client.hover_async().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maneuvers::follow::follow_velocity;
+    use crate::maneuvers::FollowConfig;
+    use crate::{Position3, Vector3};
+
+    #[test]
+    fn test_follow_velocity() {
+        let config = FollowConfig::new().offset(Vector3::new(-5.0, 0.0, -3.0)).max_speed(4.0);
+        let target = Position3::new(20.0, 10.0, 0.0);
+
+        // already at the offset: stay
+        let velocity = follow_velocity(Position3::new(15.0, 10.0, -3.0), target, &config);
+        assert!(velocity.norm() < 1e-5);
+
+        // far behind: fly towards it at the speed limit
+        let velocity = follow_velocity(Position3::new(-85.0, 10.0, -3.0), target, &config);
+        assert!((velocity.vx - 4.0).abs() < 1e-5 && velocity.vy.abs() < 1e-5);
+    }
+}
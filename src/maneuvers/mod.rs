@@ -0,0 +1,7 @@
+mod follow;
+mod orbit;
+mod rtl;
+
+pub use follow::{follow_target, FollowConfig};
+pub use orbit::{orbit_async, OrbitYaw};
+pub use rtl::{rtl, RtlConfig};
@@ -0,0 +1,142 @@
+use std::f32::consts::PI;
+
+use async_std::future;
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::{
+    DrivetrainType, ManeuverOutcome, MoveOptions, MultiRotorClient, Position3, Quaternionr, Velocity3, YawMode,
+};
+
+/// rate of the velocity commands of the maneuvers, in Hz
+const COMMAND_RATE_HZ: f32 = 20.0;
+/// pull towards the circle and the altitude of the center, in 1/s
+const POSITION_GAIN: f32 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Where the vehicle looks while orbiting
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrbitYaw {
+    /// Towards the center, e.g. to film or scan it
+    FaceCenter,
+    /// Along the direction of travel
+    FaceForward,
+    /// Keep the heading the vehicle had when the orbit started
+    Hold,
+}
+
+/// Velocity flying the circle around `center` counterclockwise seen from above for a positive speed, pulled back
+/// onto the circle and to the altitude of the center
+pub(crate) fn orbit_velocity(position: Position3, center: Position3, radius: f32, speed: f32) -> Velocity3 {
+    let (dx, dy) = (position.x - center.x, position.y - center.y);
+    let distance = dx.hypot(dy);
+    let angle = dy.atan2(dx);
+
+    // NED seen from above: x north, y east, so increasing angles turn clockwise
+    let (radial, tangential) = ((angle.cos(), angle.sin()), (angle.sin(), -angle.cos()));
+    let pull = -POSITION_GAIN * (distance - radius);
+
+    Velocity3::new(
+        tangential.0 * speed + radial.0 * pull,
+        tangential.1 * speed + radial.1 * pull,
+        POSITION_GAIN * (center.z - position.z),
+    )
+}
+
+/// Circle a point at constant speed, as for a survey or inspection orbit
+///
+/// The vehicle first converges onto the circle from wherever it is, the laps are counted from there on. The altitude
+/// of the orbit is the one of the center.
+///
+/// args:
+///     client (&MultiRotorClient): Vehicle to fly, armed and flying
+///     center (Position3): Center of the orbit, in the NED frame of the vehicle's starting point
+///     radius (f32): Radius of the orbit in meters
+///     speed (f32): Speed along the circle in m/s, positive counterclockwise seen from above, negative clockwise
+///     laps (f32): Number of turns, fractions fly part of a turn
+///     yaw (OrbitYaw): Where the vehicle looks while orbiting
+pub async fn orbit_async(
+    client: &MultiRotorClient,
+    center: Position3,
+    radius: f32,
+    speed: f32,
+    laps: f32,
+    yaw: OrbitYaw,
+) -> NetworkResult<ManeuverOutcome> {
+    if radius.is_nan() || radius <= 0.0 {
+        panic!("orbit radius has to be positive, got {radius} m")
+    }
+
+    let period = periodic::period("orbit", COMMAND_RATE_HZ);
+    let start = client.get_multirotor_state().await?;
+    let collided_before = start.collision.timestamp;
+    let (_, _, initial_yaw) = Quaternionr::from(start.kinematics_estimated.orientation).to_euler_angles();
+
+    let mut last_angle: Option<f32> = None;
+    let mut swept = 0.0_f32;
+
+    let mut ticker = Ticker::new(period);
+    loop {
+        let state = client.get_multirotor_state().await?;
+        if state.collision.has_collided && state.collision.timestamp != collided_before {
+            client.hover_async().await?;
+            return Ok(ManeuverOutcome::Collision);
+        }
+
+        let position = state.kinematics_estimated.position;
+        let angle = (position.y - center.y).atan2(position.x - center.x);
+        // count the laps from the first time the vehicle reaches the circle
+        let on_circle = ((position.x - center.x).hypot(position.y - center.y) - radius).abs() < 0.1 * radius + 0.5;
+        if let Some(last) = last_angle {
+            // unwrap the step across ±π
+            swept += (angle - last + PI).rem_euclid(2.0 * PI) - PI;
+            last_angle = Some(angle);
+        } else if on_circle {
+            last_angle = Some(angle);
+        }
+        if swept.abs() >= laps * 2.0 * PI {
+            break;
+        }
+
+        let velocity = orbit_velocity(position, center, radius, speed);
+        let yaw_mode = match yaw {
+            OrbitYaw::FaceCenter => YawMode::angle((angle + PI).to_degrees()),
+            OrbitYaw::FaceForward => YawMode::angle(velocity.vy.atan2(velocity.vx).to_degrees()),
+            OrbitYaw::Hold => YawMode::angle(initial_yaw.to_degrees()),
+        };
+        let options = MoveOptions::new()
+            .drivetrain(DrivetrainType::MaxDegreeOfFreedom)
+            .yaw_mode(yaw_mode);
+
+        // AirSim answers a command once it expires or the next one replaces it, stop waiting at the next tick
+        let command = client.move_by_velocity_with(velocity, 3.0 * period.as_secs_f32(), &options);
+        if let Ok(result) = future::timeout(period, command).await {
+            result?;
+        }
+        ticker.tick().await;
+    }
+
+    client.hover_async().await?;
+    Ok(ManeuverOutcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maneuvers::orbit::orbit_velocity;
+    use crate::Position3;
+
+    #[test]
+    fn test_orbit_velocity() {
+        let center = Position3::new(10.0, 0.0, -20.0);
+
+        // north of the center, on the circle and at its altitude: fly west, counterclockwise seen from above
+        let velocity = orbit_velocity(Position3::new(15.0, 0.0, -20.0), center, 5.0, 2.0);
+        assert!(velocity.vx.abs() < 1e-5 && velocity.vz.abs() < 1e-5);
+        assert!((velocity.vy + 2.0).abs() < 1e-5);
+
+        // outside of the circle and too low: pulled inwards and up
+        let velocity = orbit_velocity(Position3::new(18.0, 0.0, -15.0), center, 5.0, -2.0);
+        assert!((velocity.vy - 2.0).abs() < 1e-5);
+        assert!(velocity.vx < 0.0 && velocity.vz < 0.0);
+    }
+}
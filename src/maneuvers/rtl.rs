@@ -0,0 +1,105 @@
+use crate::error::NetworkResult;
+use crate::{ManeuverOutcome, MoveOptions, MultiRotorClient, Position3};
+
+#[derive(Debug, Clone, Copy)]
+/// Settings of the return-to-launch maneuver
+///
+/// Defaults climb to 20 m above home at 2 m/s, fly home at 5 m/s and allow 60 seconds per stage
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtlConfig {
+    /// altitude above home to fly back at, in meters. The vehicle doesn't descend if it already flies higher
+    pub climb_altitude: f32,
+    /// vertical speed of the climb in m/s
+    pub climb_velocity: f32,
+    /// horizontal speed of the flight home in m/s
+    pub cruise_velocity: f32,
+    /// timeout of each of the climb, cruise and landing stages
+    pub stage_timeout_sec: f32,
+}
+
+impl Default for RtlConfig {
+    fn default() -> Self {
+        Self {
+            climb_altitude: 20.0,
+            climb_velocity: 2.0,
+            cruise_velocity: 5.0,
+            stage_timeout_sec: 60.0,
+        }
+    }
+}
+
+impl RtlConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn climb_altitude(mut self, climb_altitude: f32) -> Self {
+        self.climb_altitude = climb_altitude;
+        self
+    }
+
+    pub fn climb_velocity(mut self, climb_velocity: f32) -> Self {
+        self.climb_velocity = climb_velocity;
+        self
+    }
+
+    pub fn cruise_velocity(mut self, cruise_velocity: f32) -> Self {
+        self.cruise_velocity = cruise_velocity;
+        self
+    }
+
+    pub fn stage_timeout_sec(mut self, stage_timeout_sec: f32) -> Self {
+        self.stage_timeout_sec = stage_timeout_sec;
+        self
+    }
+}
+
+/// Return to launch: climb to a safe altitude, fly home at that altitude, then land
+///
+/// Unlike `go_home_async`, which flies straight towards home and can run into obstacles on the way,
+/// the climb first clears the obstacles between the vehicle and home. Home is taken from the home geo point
+/// of the vehicle, so it's found even if the local frame drifted from the launch location.
+///
+/// args:
+///     client (&MultiRotorClient): Vehicle to bring home
+///     config (&RtlConfig): Climb altitude, speeds and timeouts of the maneuver
+pub async fn rtl(client: &MultiRotorClient, config: &RtlConfig) -> NetworkResult<ManeuverOutcome> {
+    let state = client.get_multirotor_state().await?;
+    let home = client.get_home_geo_point().await?;
+
+    let position = state.kinematics_estimated.position;
+    let to_home = home.to_ned(&state.gps_location);
+    let home = Position3::new(position.x + to_home.x, position.y + to_home.y, position.z + to_home.z);
+
+    // NED: up is negative z, never descend to the safe altitude
+    let safe_z = (home.z - config.climb_altitude).min(position.z);
+
+    let climb = MoveOptions::new()
+        .velocity(config.climb_velocity)
+        .timeout_sec(config.stage_timeout_sec);
+    if !client
+        .move_to_position_with(Position3::new(position.x, position.y, safe_z), &climb)
+        .await?
+    {
+        return timeout(client).await;
+    }
+
+    let cruise = MoveOptions::new()
+        .velocity(config.cruise_velocity)
+        .timeout_sec(config.stage_timeout_sec);
+    if !client
+        .move_to_position_with(Position3::new(home.x, home.y, safe_z), &cruise)
+        .await?
+    {
+        return timeout(client).await;
+    }
+
+    client.land_and_wait(config.stage_timeout_sec).await
+}
+
+async fn timeout(client: &MultiRotorClient) -> NetworkResult<ManeuverOutcome> {
+    let state = client.get_multirotor_state().await?;
+    Ok(ManeuverOutcome::Timeout {
+        final_altitude: -state.kinematics_estimated.position.z,
+    })
+}
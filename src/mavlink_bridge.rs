@@ -0,0 +1,266 @@
+//! Bridge mirroring the state of a multirotor to a ground control station over MAVLink, e.g. QGroundControl
+//!
+//! Only the messages a GCS needs to show the vehicle on its map are sent: a HEARTBEAT every second and a
+//! GLOBAL_POSITION_INT at the rate of the bridge. The GCS can't command the vehicle through the bridge.
+
+use std::f32::consts::PI;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_std::net::UdpSocket;
+use async_std::task::{self, JoinHandle};
+use mavlink::common::{
+    MavAutopilot, MavMessage, MavModeFlag, MavState, MavType, GLOBAL_POSITION_INT_DATA, HEARTBEAT_DATA,
+};
+use mavlink::MavHeader;
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::{GeoPoint, KinematicsState, MultiRotorClient, MultiRotorState};
+
+/// Period of the HEARTBEAT messages, a GCS drops the vehicle after a few missed ones
+const HEARTBEAT_PERIOD: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+/// Settings of the `MavlinkBridge`
+///
+/// Defaults to sending to a QGroundControl running on the same machine, as system 1 at 10 Hz
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MavlinkConfig {
+    /// UDP address the GCS listens on
+    pub gcs_addr: String,
+    /// MAVLink system id of the vehicle, one per vehicle when bridging a swarm
+    pub system_id: u8,
+    /// Rate of the GLOBAL_POSITION_INT messages in Hz
+    pub rate_hz: f32,
+}
+
+impl Default for MavlinkConfig {
+    fn default() -> Self {
+        Self {
+            gcs_addr: "127.0.0.1:14550".to_owned(),
+            system_id: 1,
+            rate_hz: 10.0,
+        }
+    }
+}
+
+impl MavlinkConfig {
+    pub fn gcs_addr(mut self, gcs_addr: &str) -> Self {
+        self.gcs_addr = gcs_addr.to_owned();
+        self
+    }
+
+    pub fn system_id(mut self, system_id: u8) -> Self {
+        self.system_id = system_id;
+        self
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+}
+
+/// Background task sending the state of a multirotor to a GCS as MAVLink 2 messages over UDP
+///
+/// The vehicle is reported armed and active while it's flying. A failed read of the state skips that tick, sending
+/// runs until `stop` is awaited, the bridge is dropped or sending to the GCS fails
+pub struct MavlinkBridge {
+    running: Arc<AtomicBool>,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl MavlinkBridge {
+    /// Connect to the GCS and start mirroring `client`
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to mirror, the bridge shares its connection
+    ///     config (MavlinkConfig): Address of the GCS, system id and rate
+    ///
+    /// Fails with `NetworkError::InvalidArgument` on a rate which isn't positive
+    pub async fn start(client: &MultiRotorClient, config: MavlinkConfig) -> NetworkResult<Self> {
+        let period = periodic::try_period("MAVLink", config.rate_hz)?;
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.connect(&config.gcs_addr).await?;
+        let running = Arc::new(AtomicBool::new(true));
+
+        let task = task::spawn(Self::mirror(
+            client.clone(),
+            socket,
+            config.system_id,
+            period,
+            Arc::clone(&running),
+        ));
+
+        Ok(Self {
+            running,
+            task: Some(task),
+        })
+    }
+
+    /// Stop sending and wait for the background task to end
+    ///
+    /// Returns the error sending to the GCS which ended the bridge, if any
+    pub async fn stop(mut self) -> NetworkResult<()> {
+        self.running.store(false, Ordering::SeqCst);
+        match self.task.take() {
+            Some(task) => task.await,
+            None => Ok(()),
+        }
+    }
+
+    async fn mirror(
+        client: MultiRotorClient,
+        socket: UdpSocket,
+        system_id: u8,
+        period: Duration,
+        running: Arc<AtomicBool>,
+    ) -> NetworkResult<()> {
+        let mut header = MavHeader {
+            system_id,
+            component_id: 1,
+            sequence: 0,
+        };
+        let mut boot_timestamp = None;
+        let mut last_heartbeat: Option<Instant> = None;
+        let mut ticker = Ticker::new(period);
+
+        while running.load(Ordering::SeqCst) {
+            let started = Instant::now();
+            let state = match client.get_multirotor_state().await {
+                Ok(state) => state,
+                Err(e) => {
+                    log::warn!("MAVLink bridge of {} skips a tick: {e}", client.vehicle_name());
+                    ticker.tick().await;
+                    continue;
+                }
+            };
+
+            if last_heartbeat.map_or(true, |sent| sent.elapsed() >= HEARTBEAT_PERIOD) {
+                send(&socket, &mut header, &MavMessage::HEARTBEAT(heartbeat(&state))).await?;
+                last_heartbeat = Some(started);
+            }
+
+            let boot_timestamp = *boot_timestamp.get_or_insert(state.timestamp);
+            let time_boot_ms = (state.timestamp.saturating_sub(boot_timestamp) / 1_000_000) as u32;
+            let position = global_position_int(time_boot_ms, &state.gps_location, &state.kinematics_estimated);
+            send(&socket, &mut header, &MavMessage::GLOBAL_POSITION_INT(position)).await?;
+
+            ticker.tick().await;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MavlinkBridge {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Send `message` as a MAVLink 2 frame, the sequence number of `header` is incremented
+async fn send(socket: &UdpSocket, header: &mut MavHeader, message: &MavMessage) -> io::Result<()> {
+    let mut frame = Vec::new();
+    mavlink::write_v2_msg(&mut frame, *header, message)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{e:?}")))?;
+    header.sequence = header.sequence.wrapping_add(1);
+
+    socket.send(&frame).await.map(|_| ())
+}
+
+fn heartbeat(state: &MultiRotorState) -> HEARTBEAT_DATA {
+    let (base_mode, system_status) = if state.is_flying() {
+        (
+            MavModeFlag::MAV_MODE_FLAG_GUIDED_ENABLED | MavModeFlag::MAV_MODE_FLAG_SAFETY_ARMED,
+            MavState::MAV_STATE_ACTIVE,
+        )
+    } else {
+        (MavModeFlag::MAV_MODE_FLAG_GUIDED_ENABLED, MavState::MAV_STATE_STANDBY)
+    };
+
+    HEARTBEAT_DATA {
+        custom_mode: 0,
+        mavtype: MavType::MAV_TYPE_QUADROTOR,
+        autopilot: MavAutopilot::MAV_AUTOPILOT_GENERIC,
+        base_mode,
+        system_status,
+        mavlink_version: 3,
+    }
+}
+
+/// GLOBAL_POSITION_INT of a vehicle at `gps`, the altitude above home being the height above the start point
+fn global_position_int(time_boot_ms: u32, gps: &GeoPoint, kinematics: &KinematicsState) -> GLOBAL_POSITION_INT_DATA {
+    let (_, _, yaw) = kinematics.orientation.to_euler_angles();
+    let heading = yaw.rem_euclid(2.0 * PI).to_degrees();
+    let velocity = kinematics.linear_velocity;
+
+    GLOBAL_POSITION_INT_DATA {
+        time_boot_ms,
+        lat: (gps.latitude as f64 * 1e7).round() as i32,
+        lon: (gps.longitude as f64 * 1e7).round() as i32,
+        alt: (gps.altitude * 1000.0).round() as i32,
+        relative_alt: (-kinematics.position.z * 1000.0).round() as i32,
+        vx: (velocity.x * 100.0).round() as i16,
+        vy: (velocity.y * 100.0).round() as i16,
+        vz: (velocity.z * 100.0).round() as i16,
+        hdg: (heading * 100.0).round() as u16 % 36000,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+    use std::time::Duration;
+
+    use super::global_position_int;
+    use crate::mock::MockAirsimServer;
+    use crate::{
+        GeoPoint, KinematicsState, MavlinkBridge, MavlinkConfig, MultiRotorClient, NetworkError, Position3, Quaternion,
+        Vector3,
+    };
+
+    #[test]
+    fn test_global_position_int() {
+        let kinematics = KinematicsState {
+            position: Position3::new(10.0, 5.0, -12.5),
+            orientation: Quaternion::from_euler(0.0, 0.0, -FRAC_PI_2),
+            linear_velocity: Vector3::new(1.5, -0.25, -0.5),
+            angular_velocity: Vector3::new(0.0, 0.0, 0.0),
+            linear_acceleration: Vector3::new(0.0, 0.0, 0.0),
+            angular_acceleration: Vector3::new(0.0, 0.0, 0.0),
+        };
+        let gps = GeoPoint::new(47.5, -122.25, 134.5);
+
+        let position = global_position_int(1500, &gps, &kinematics);
+        assert_eq!(position.time_boot_ms, 1500);
+        assert_eq!(position.lat, 475_000_000);
+        assert_eq!(position.lon, -1_222_500_000);
+        assert_eq!(position.alt, 134_500);
+        assert_eq!(position.relative_alt, 12_500);
+        assert_eq!((position.vx, position.vy, position.vz), (150, -25, -50));
+        assert_eq!(position.hdg, 27_000);
+    }
+    #[test]
+    fn test_bridge_outlives_failed_reads() {
+        let server = MockAirsimServer::start().unwrap();
+
+        async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            let invalid = MavlinkBridge::start(&client, MavlinkConfig::default().rate_hz(0.0)).await;
+            assert!(matches!(invalid, Err(NetworkError::InvalidArgument { .. })));
+
+            // getMultirotorState isn't scripted, every read fails
+            let bridge = MavlinkBridge::start(&client, MavlinkConfig::default().rate_hz(50.0))
+                .await
+                .unwrap();
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            assert!(bridge.stop().await.is_ok());
+        });
+        assert!(server.calls("getMultirotorState") > 1);
+    }
+}
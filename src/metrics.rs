@@ -0,0 +1,322 @@
+//! Per-method RPC metrics collected by a middleware: latency histograms, errors and requests in flight
+//!
+//! ```no_run
+//! use airsim_client::metrics::RpcMetrics;
+//! use airsim_client::{ImageRequest, ImageRequests, MultiRotorClient, NetworkResult};
+//!
+//! # async fn run(client: &MultiRotorClient) -> NetworkResult<()> {
+//! let metrics = RpcMetrics::new();
+//! client.add_middleware(metrics.clone());
+//!
+//! for _ in 0..300 {
+//!     client.sim_get_images(ImageRequests(vec![ImageRequest::scene("0")]), None).await?;
+//! }
+//! let images = &metrics.snapshot().methods["simGetImages"];
+//! println!("p95 {:?}, mean {:?}", images.latency.quantile(0.95), images.latency.mean());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rmpv::Value;
+
+use crate::{Middleware, NetworkError};
+
+/// Upper bounds of the latency buckets in seconds, from a local `ping` to a large `simGetImages` on a loaded server
+pub const LATENCY_BUCKETS: [f64; 14] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+/// Distribution of the latencies of an RPC, in the buckets of `LATENCY_BUCKETS`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencyHistogram {
+    /// number of latencies per bucket, not cumulative. The last count is for latencies above the last bound
+    pub counts: Vec<u64>,
+    /// sum of the latencies in seconds
+    pub sum: f64,
+    /// number of latencies recorded
+    pub count: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; LATENCY_BUCKETS.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// Mean latency, None if nothing was recorded
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(self.sum / self.count as f64))
+    }
+
+    /// Upper bound of the bucket holding the `q` quantile, None if nothing was recorded or the quantile lies above
+    /// the last bucket
+    ///
+    /// args:
+    ///     q (f64): quantile, Range 0-1, e.g. 0.95 for the 95th percentile
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = (q.clamp(0.0, 1.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(&self.counts) {
+            seen += count;
+            if seen >= rank {
+                return Some(Duration::from_secs_f64(*bound));
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Metrics of one RPC method
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MethodMetrics {
+    /// number of completed calls, successful or not. Every retry counts as a call
+    pub calls: u64,
+    /// number of failed calls, including errors reported by the server
+    pub errors: u64,
+    /// latencies of the completed calls
+    pub latency: LatencyHistogram,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Metrics collected by `RpcMetrics` up to the call of `snapshot`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricsSnapshot {
+    /// number of RPCs sent and not answered yet
+    pub in_flight: u64,
+    /// metrics of every method called at least once, by method name
+    pub methods: BTreeMap<String, MethodMetrics>,
+}
+
+impl MetricsSnapshot {
+    /// Metrics in the Prometheus text exposition format, to serve on a `/metrics` endpoint
+    #[cfg(feature = "prometheus")]
+    pub fn to_prometheus(&self) -> String {
+        use std::fmt::Write;
+
+        let mut text = String::new();
+        // writing to a String can't fail
+        let _ = writeln!(
+            text,
+            "# HELP airsim_rpc_in_flight AirSim RPCs sent and not answered yet"
+        );
+        let _ = writeln!(text, "# TYPE airsim_rpc_in_flight gauge");
+        let _ = writeln!(text, "airsim_rpc_in_flight {}", self.in_flight);
+
+        let _ = writeln!(text, "# HELP airsim_rpc_errors_total Failed AirSim RPCs");
+        let _ = writeln!(text, "# TYPE airsim_rpc_errors_total counter");
+        for (method, metrics) in &self.methods {
+            let _ = writeln!(
+                text,
+                "airsim_rpc_errors_total{{method=\"{method}\"}} {}",
+                metrics.errors
+            );
+        }
+
+        let _ = writeln!(text, "# HELP airsim_rpc_latency_seconds Latency of the AirSim RPCs");
+        let _ = writeln!(text, "# TYPE airsim_rpc_latency_seconds histogram");
+        for (method, metrics) in &self.methods {
+            let histogram = &metrics.latency;
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.counts) {
+                cumulative += count;
+                let _ = writeln!(
+                    text,
+                    "airsim_rpc_latency_seconds_bucket{{method=\"{method}\",le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                text,
+                "airsim_rpc_latency_seconds_bucket{{method=\"{method}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                text,
+                "airsim_rpc_latency_seconds_sum{{method=\"{method}\"}} {}",
+                histogram.sum
+            );
+            let _ = writeln!(
+                text,
+                "airsim_rpc_latency_seconds_count{{method=\"{method}\"}} {}",
+                histogram.count
+            );
+        }
+        text
+    }
+}
+
+/// Metrics of one method, updated without locking
+#[derive(Default)]
+struct MethodCounters {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    latency_sum_ns: AtomicU64,
+}
+
+impl MethodCounters {
+    fn record(&self, latency: Duration, failed: bool) {
+        let seconds = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_ns
+            .fetch_add(latency.as_nanos() as u64, Ordering::Relaxed);
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if failed {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> MethodMetrics {
+        let counts: Vec<u64> = self.buckets.iter().map(|count| count.load(Ordering::Relaxed)).collect();
+        MethodMetrics {
+            calls: self.calls.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            latency: LatencyHistogram {
+                count: counts.iter().sum(),
+                counts,
+                sum: self.latency_sum_ns.load(Ordering::Relaxed) as f64 / 1e9,
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    in_flight: AtomicU64,
+    /// only write locked to add a method the first time it's called, and by `reset`
+    methods: RwLock<HashMap<String, MethodCounters>>,
+}
+
+#[derive(Clone, Default)]
+/// Middleware recording the metrics of every RPC of a client, cloned to read them while the client runs
+///
+/// Add it after the middlewares delaying requests, like rate limiters, to leave their delay out of the latencies.
+/// An RPC whose future is dropped before it's answered stays in flight
+pub struct RpcMetrics {
+    registry: Arc<Registry>,
+}
+
+impl RpcMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of the metrics recorded so far
+    ///
+    /// The counters are read while RPCs keep being recorded, so the counts of a method may be a call apart
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let methods = self.registry.methods.read().unwrap();
+        MetricsSnapshot {
+            in_flight: self.registry.in_flight.load(Ordering::SeqCst),
+            methods: methods
+                .iter()
+                .map(|(method, counters)| (method.clone(), counters.snapshot()))
+                .collect(),
+        }
+    }
+
+    /// Forget the calls recorded so far, RPCs in flight are still counted
+    pub fn reset(&self) {
+        self.registry.methods.write().unwrap().clear();
+    }
+
+    fn record(&self, method: &str, latency: Duration, failed: bool) {
+        // saturate rather than wrap if the metrics were added while RPCs were in flight
+        let _ = self
+            .registry
+            .in_flight
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+
+        if let Some(counters) = self.registry.methods.read().unwrap().get(method) {
+            counters.record(latency, failed);
+            return;
+        }
+        self.registry
+            .methods
+            .write()
+            .unwrap()
+            .entry(method.to_owned())
+            .or_default()
+            .record(latency, failed);
+    }
+}
+
+#[async_trait]
+impl Middleware for RpcMetrics {
+    async fn on_request(&self, _method: &str, _params: &mut Vec<Value>) {
+        self.registry.in_flight.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_response(&self, method: &str, _result: &Value, latency: Duration) {
+        self.record(method, latency, false);
+    }
+
+    fn on_error(&self, method: &str, _error: &NetworkError, latency: Duration) {
+        self.record(method, latency, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use crate::metrics::RpcMetrics;
+    use crate::{Middleware, NetworkError, Value};
+
+    #[test]
+    fn test_records_latencies_and_errors() {
+        let metrics = RpcMetrics::new();
+        async_std::task::block_on(async {
+            for _ in 0..3 {
+                metrics.on_request("simGetImages", &mut vec![]).await;
+            }
+        });
+        assert_eq!(metrics.snapshot().in_flight, 3);
+
+        metrics.on_response("simGetImages", &Value::Nil, Duration::from_millis(20));
+        metrics.on_response("simGetImages", &Value::Nil, Duration::from_millis(40));
+        let error = NetworkError::Io(io::Error::new(io::ErrorKind::BrokenPipe, "closed"));
+        metrics.on_error("simGetImages", &error, Duration::from_millis(300));
+
+        let snapshot = metrics.snapshot();
+        let images = &snapshot.methods["simGetImages"];
+        assert_eq!(snapshot.in_flight, 0);
+        assert_eq!((images.calls, images.errors), (3, 1));
+        assert!((images.latency.mean().unwrap().as_secs_f64() - 0.12).abs() < 1e-6);
+        assert_eq!(images.latency.quantile(0.5), Some(Duration::from_millis(50)));
+        assert_eq!(images.latency.quantile(1.0), Some(Duration::from_millis(500)));
+
+        #[cfg(feature = "prometheus")]
+        {
+            let text = snapshot.to_prometheus();
+            assert!(text.contains("airsim_rpc_errors_total{method=\"simGetImages\"} 1\n"));
+            assert!(text.contains("airsim_rpc_latency_seconds_bucket{method=\"simGetImages\",le=\"0.05\"} 2\n"));
+        }
+
+        metrics.reset();
+        assert!(metrics.snapshot().methods.is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path as FilePath;
+
+use crate::{GeoPoint, Path, Vector3};
+
+#[derive(Debug, Clone)]
+/// Waypoints of a route planned in a GIS tool, e.g. QGroundControl, Google Earth or QGIS
+///
+/// Only the coordinates are read, names, timestamps and styles are ignored. A missing altitude reads as 0, like in
+/// KML, see `to_path_at_altitude` to fly routes planned on the ground
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mission {
+    /// waypoints in the order of the file
+    pub waypoints: Vec<GeoPoint>,
+}
+
+impl Mission {
+    /// Read a GPX or KML file, depending on its extension
+    ///
+    /// args:
+    ///     path (impl AsRef<Path>): `.gpx` or `.kml` file
+    pub fn read(path: impl AsRef<FilePath>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+        {
+            Some(ext) if ext == "gpx" => Self::from_gpx(&text),
+            Some(ext) if ext == "kml" => Self::from_kml(&text),
+            _ => Err(invalid_mission(format!("{} isn't a .gpx or .kml file", path.display()))),
+        }
+    }
+
+    /// Parse the points of a GPX document
+    ///
+    /// The route points are used if there is a route, else the waypoints, else the track points
+    ///
+    /// args:
+    ///     text (&str): content of the GPX file
+    pub fn from_gpx(text: &str) -> io::Result<Self> {
+        let points = ["rtept", "wpt", "trkpt"]
+            .iter()
+            .map(|tag| elements(text, tag))
+            .find(|points| !points.is_empty())
+            .unwrap_or_default();
+
+        let waypoints = points
+            .into_iter()
+            .map(|(attributes, content)| {
+                let latitude = parse_number(attribute(attributes, "lat"), "lat")?;
+                let longitude = parse_number(attribute(attributes, "lon"), "lon")?;
+                let altitude = match elements(content, "ele").first() {
+                    Some((_, ele)) => parse_number(Some(ele), "ele")?,
+                    None => 0.0,
+                };
+                Ok(GeoPoint::new(latitude, longitude, altitude))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self { waypoints })
+    }
+
+    /// Parse the coordinates of every point and line of a KML document
+    ///
+    /// args:
+    ///     text (&str): content of the KML file
+    pub fn from_kml(text: &str) -> io::Result<Self> {
+        let mut waypoints = vec![];
+        for (_, coordinates) in elements(text, "coordinates") {
+            // `longitude,latitude[,altitude]` tuples separated by whitespace
+            for tuple in coordinates.split_whitespace() {
+                let mut values = tuple.split(',');
+                let longitude = parse_number(values.next(), "longitude")?;
+                let latitude = parse_number(values.next(), "latitude")?;
+                let altitude = match values.next() {
+                    Some(altitude) => parse_number(Some(altitude), "altitude")?,
+                    None => 0.0,
+                };
+                waypoints.push(GeoPoint::new(latitude, longitude, altitude));
+            }
+        }
+        Ok(Self { waypoints })
+    }
+
+    /// Waypoints in the NED frame of `home`, keeping their altitude
+    ///
+    /// args:
+    ///     home (&GeoPoint): origin of the NED frame, e.g. from `get_home_geo_point`
+    pub fn to_path(&self, home: &GeoPoint) -> Path {
+        Path(self.waypoints.iter().map(|waypoint| waypoint.to_ned(home)).collect())
+    }
+
+    /// Waypoints in the NED frame of `home`, all at the same altitude above home
+    ///
+    /// args:
+    ///     home (&GeoPoint): origin of the NED frame, e.g. from `get_home_geo_point`
+    ///     altitude (f32): altitude above home, in meters
+    pub fn to_path_at_altitude(&self, home: &GeoPoint, altitude: f32) -> Path {
+        let points = self.waypoints.iter().map(|waypoint| {
+            let ned = waypoint.to_ned(home);
+            Vector3::new(ned.x, ned.y, -altitude)
+        });
+        Path(points.collect())
+    }
+}
+
+fn invalid_mission(message: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid mission: {message}"))
+}
+
+fn parse_number(value: Option<&str>, name: &str) -> io::Result<f32> {
+    let value = value.ok_or_else(|| invalid_mission(format!("missing {name}")))?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid_mission(format!("{name} `{value}` isn't a number")))
+}
+
+/// Attributes and content of every `<name>` element, in the order of the document
+///
+/// A minimal scanner rather than an XML parser: enough for the coordinates of GPX and KML files, which never hold
+/// comments or CDATA around them
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}");
+    let mut found = vec![];
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // skip longer names sharing the prefix, e.g. `<wptType` when looking for `<wpt`
+        if !after.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            rest = after;
+            continue;
+        }
+        let end = match after.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let attributes = &after[..end];
+        let body = &after[end + 1..];
+
+        if let Some(attributes) = attributes.strip_suffix('/') {
+            found.push((attributes, ""));
+            rest = body;
+        } else {
+            let content_end = body.find(&close).unwrap_or(body.len());
+            found.push((attributes, &body[..content_end]));
+            rest = &body[content_end..];
+        }
+    }
+    found
+}
+
+/// Value of an attribute of a start tag, quoted with `"` or `'`
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(start) = rest.find(name) {
+        let preceded_by_space = rest[..start].ends_with(char::is_whitespace) || start == 0;
+        let after = rest[start + name.len()..].trim_start();
+        rest = &rest[start + name.len()..];
+
+        if let (true, Some(value)) = (preceded_by_space, after.strip_prefix('=')) {
+            let value = value.trim_start();
+            let quote = value.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let value = &value[1..];
+                return value.find(quote).map(|end| &value[..end]);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mission::Mission;
+    use crate::GeoPoint;
+
+    #[test]
+    fn test_gpx_and_kml_waypoints() {
+        let gpx = r#"<?xml version="1.0"?>
+            <gpx version="1.1" creator="test">
+              <wpt lat="47.0" lon="8.0"><name>poi</name></wpt>
+              <rte>
+                <rtept lat="47.001" lon="8.0"><ele>510.5</ele></rtept>
+                <rtept lon='8.001' lat='47.001'/>
+              </rte>
+            </gpx>"#;
+        let gpx = Mission::from_gpx(gpx).unwrap();
+        assert_eq!(gpx.waypoints.len(), 2);
+        assert_eq!(gpx.waypoints[0].altitude, 510.5);
+        assert_eq!(gpx.waypoints[1].longitude, 8.001);
+
+        let kml = r#"<kml xmlns="http://www.opengis.net/kml/2.2"><Placemark><LineString>
+              <coordinates>8.0,47.0,500 8.0,47.001,500
+                8.001,47.001,500</coordinates>
+            </LineString></Placemark></kml>"#;
+        let kml = Mission::from_kml(kml).unwrap();
+        assert_eq!(kml.waypoints.len(), 3);
+
+        let home = GeoPoint::new(47.0, 8.0, 500.0);
+        let path = kml.to_path(&home).0;
+        assert!(path[0].x.abs() < 1e-3 && path[0].z.abs() < 1e-3);
+        // 0.001 degree of latitude is roughly 111 m
+        assert!((path[1].x - 111.2).abs() < 1.0);
+        assert!(path[2].y > 70.0);
+        assert_eq!(kml.to_path_at_altitude(&home, 30.0).0[2].z, -30.0);
+
+        assert!(Mission::from_kml("<coordinates>8.0</coordinates>").is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! In-process stand-in for the AirSim RPC server, to unit test code using the clients without running Unreal
+//!
+//! ```no_run
+//! use airsim_client::mock::{MockAirsimServer, Value};
+//! use airsim_client::MultiRotorClient;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockAirsimServer::start()?;
+//! server.respond("armDisarm", Value::Boolean(true));
+//!
+//! let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1").await?;
+//! assert!(client.arm_disarm(true).await?);
+//! assert_eq!(server.calls("armDisarm"), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use msgpack_rpc::message::{Message, Request, Response};
+pub use msgpack_rpc::Value;
+
+type Handler = Box<dyn FnMut(&[Value]) -> Result<Value, Value> + Send>;
+
+#[derive(Default)]
+struct Script {
+    handlers: HashMap<String, Handler>,
+    requests: Vec<Request>,
+}
+
+/// msgpack-rpc server on a local socket answering every method with a scripted response
+///
+/// `ping` and `enableApiControl` answer true so clients can connect, methods without a script answer with
+/// an error like the AirSim server does for unknown methods. The server runs in background threads until dropped
+pub struct MockAirsimServer {
+    addr: SocketAddr,
+    script: Arc<Mutex<Script>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockAirsimServer {
+    /// Starts the server on a free port of the loopback interface, see `addr`
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = Self {
+            addr: listener.local_addr()?,
+            script: Arc::new(Mutex::new(Script::default())),
+            stopped: Arc::new(AtomicBool::new(false)),
+        };
+        server.respond("ping", Value::Boolean(true));
+        server.respond("enableApiControl", Value::Boolean(true));
+
+        let script = Arc::clone(&server.script);
+        let stopped = Arc::clone(&server.stopped);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::Acquire) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let script = Arc::clone(&script);
+                    thread::spawn(move || serve(stream, script));
+                }
+            }
+        });
+
+        Ok(server)
+    }
+
+    /// Address to connect the clients to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Answers every call of `method` with `result`
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC, e.g. `getMultirotorState`
+    ///     result (Value): Result sent back
+    pub fn respond(&self, method: &str, result: Value) {
+        self.respond_with(method, move |_| Ok(result.clone()));
+    }
+
+    /// Answers every call of `method` with an error, as the server does when a call fails
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC
+    ///     message (&str): Error message sent back
+    pub fn fail(&self, method: &str, message: &str) {
+        let message = Value::from(message);
+        self.respond_with(method, move |_| Err(message.clone()));
+    }
+
+    /// Answers every call of `method` with the result of `handler`, called with the params of the request
+    ///
+    /// args:
+    ///     method (&str): Name of the RPC
+    ///     handler (FnMut(&[Value]) -> Result<Value, Value>): Returns the result, or the error, to send back
+    pub fn respond_with<F>(&self, method: &str, handler: F)
+    where
+        F: FnMut(&[Value]) -> Result<Value, Value> + Send + 'static,
+    {
+        self.script
+            .lock()
+            .unwrap()
+            .handlers
+            .insert(method.to_owned(), Box::new(handler));
+    }
+
+    /// Requests received so far, in order of arrival
+    pub fn requests(&self) -> Vec<Request> {
+        self.script.lock().unwrap().requests.clone()
+    }
+
+    /// Number of requests received so far for `method`
+    pub fn calls(&self, method: &str) -> usize {
+        self.script
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .filter(|request| request.method == method)
+            .count()
+    }
+}
+
+impl Drop for MockAirsimServer {
+    fn drop(&mut self) {
+        // wake up the accepting thread so it sees the flag
+        self.stopped.store(true, Ordering::Release);
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+/// Answers the requests of a single connection until it is closed
+fn serve(mut stream: TcpStream, script: Arc<Mutex<Script>>) {
+    while let Ok(message) = Message::decode(&mut stream) {
+        let request = match message {
+            Message::Request(request) => request,
+            _ => continue,
+        };
+
+        let result = {
+            let mut script = script.lock().unwrap();
+            script.requests.push(request.clone());
+            match script.handlers.get_mut(&request.method) {
+                Some(handler) => handler(&request.params),
+                None => Err(Value::from(format!(
+                    "rpclib: server could not find function '{}' with argument count {}.",
+                    request.method,
+                    request.params.len()
+                ))),
+            }
+        };
+
+        let response = Message::Response(Response { id: request.id, result });
+        let sent = response.pack().and_then(|bytes| stream.write_all(&bytes));
+        if sent.is_err() {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::{AirsimClient, NetworkError};
+
+    #[test]
+    fn test_scripted_responses() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("simRunConsoleCommand", Value::Boolean(true));
+        server.fail("simPause", "paused twice");
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(server.addr(), "Drone1").await.unwrap();
+            assert!(client.sim_run_console_command("stat fps").await.unwrap());
+
+            match client.sim_pause(true).await {
+                Err(NetworkError::Rpc { method, message, .. }) => {
+                    assert_eq!(method, "simPause");
+                    assert_eq!(message, "paused twice");
+                }
+                other => panic!("unexpected result {other:?}"),
+            }
+            assert!(client.sim_is_pause().await.is_err());
+        });
+
+        assert_eq!(server.calls("simRunConsoleCommand"), 1);
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "ping");
+        assert_eq!(requests[2].params, vec![Value::from("stat fps")]);
+    }
+}
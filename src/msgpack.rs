@@ -1,19 +1,50 @@
 #![allow(dead_code)]
 use async_std::channel::{unbounded, Receiver, Sender};
+use async_std::future;
 use async_std::io::prelude::*;
 use async_std::net::{TcpStream, ToSocketAddrs};
 use async_std::sync::{Arc, Mutex};
-use async_std::task;
+use async_std::task::{self, JoinHandle};
 use futures::future::FutureExt;
 use futures::select;
 use msgpack_rpc::message::{Message, Notification, Request, Response};
+use msgpack_rpc::DecodeError;
 use std::collections::HashMap;
-use std::io::Cursor;
+use std::io::{self, Cursor};
+use std::time::Duration;
 
 use crate::error::NetworkResult;
 use crate::NetworkError;
 
+/// Settings of the TCP connection of a `MsgPackClient`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConnectionOptions {
+    /// time to establish the connection, unbounded if `None`
+    pub connect_timeout: Option<Duration>,
+    /// time to wait for the response to a request, unbounded if `None`
+    pub request_timeout: Option<Duration>,
+    /// disables Nagle's algorithm, sending small requests without delay
+    pub nodelay: bool,
+    /// size of the buffer the socket is read into, in bytes
+    pub read_buffer_size: usize,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            request_timeout: None,
+            nodelay: false,
+            // 50 kB
+            read_buffer_size: 1024 * 50,
+        }
+    }
+}
+
 /// msgpack client used to interface with the airsim msgpack server
+///
+/// Any number of requests can be in flight: they are written to the socket in the order `request` is called and
+/// each response is routed to its request by id, whatever order the server answers in
 #[derive(Clone, Debug)]
 pub struct MsgPackClient {
     request_sender: Sender<Request>,
@@ -21,6 +52,9 @@ pub struct MsgPackClient {
     pub notification_receiver: Receiver<Notification>,
     pub request_receiver: Receiver<Request>,
     response_channels: Arc<Mutex<HashMap<u32, Sender<Response>>>>,
+    shutdown_sender: Sender<()>,
+    io_task: Arc<Mutex<Option<JoinHandle<NetworkError>>>>,
+    request_timeout: Option<Duration>,
 }
 
 enum Rpc {
@@ -32,99 +66,121 @@ impl MsgPackClient {
     /// Establish a TCP socket connection to the `MessagePack-RPC` server
     /// running in a background thread
     pub async fn connect(addrs: impl ToSocketAddrs) -> NetworkResult<Self> {
-        let mut stream = TcpStream::connect(addrs).await?;
-        let response_channels = Arc::new(Mutex::new(HashMap::new()));
+        Self::connect_with(addrs, ConnectionOptions::default()).await
+    }
+
+    /// Establish the connection like `connect`, with the given settings
+    pub(crate) async fn connect_with(addrs: impl ToSocketAddrs, options: ConnectionOptions) -> NetworkResult<Self> {
+        let mut stream = match options.connect_timeout {
+            Some(connect_timeout) => async_std::io::timeout(connect_timeout, TcpStream::connect(addrs)).await?,
+            None => TcpStream::connect(addrs).await?,
+        };
+        stream.set_nodelay(options.nodelay)?;
+        let response_channels = Arc::new(Mutex::new(HashMap::<u32, Sender<Response>>::new()));
 
         let (request_sender, request_receiver) = unbounded::<Request>();
         let (inner_request_sender, inner_request_receiver) = unbounded::<Request>();
         let (notification_sender, notification_receiver) = unbounded::<Notification>();
         let (inner_notification_sender, inner_notification_receiver) = unbounded::<Notification>();
+        let (shutdown_sender, shutdown_receiver) = unbounded::<()>();
         let res_channels = Arc::clone(&response_channels);
 
-        task::spawn(async move {
+        let io_task = task::spawn(async move {
             let mut current_message: Vec<u8> = vec![];
 
-            // 1,024 bytes = 1 kB
-            // 1kB x 1000 = 1mB
-            let buf_size: usize = 1024 * 50; // 0.1mB
-
             // for some reason, msgpack expects a fixed size
             // for the bytes buffer
-            let mut buf = vec![0_u8; buf_size];
-
-            loop {
-                let to_process = select! {
-                    maybe_request = request_receiver.recv().fuse() => {
-                        if let Ok(request) = maybe_request {
-                            Some(Rpc::Send(Message::Request(request)))
-                        } else {
-                            None
-                        }
-                    },
-                    maybe_notification = notification_receiver.recv().fuse() => {
-                        if let Ok(notification) = maybe_notification {
-                            Some(Rpc::Send(Message::Notification(notification)))
-                        } else {
-                            None
-                        }
-                    },
-                    maybe_bytes_read = stream.read(&mut buf).fuse() => {
-                        if let Ok(bytes_read) = maybe_bytes_read {
-                            Some(Rpc::Receive(bytes_read))
-                        } else {
-                            None
-                        }
-                    }
-                };
-                match to_process {
-                    Some(Rpc::Send(m)) => {
-                        let message = m.pack().expect("Couldn't serialize message");
-                        stream.write_all(&message).await.expect("Couldn't send message");
-                    }
-                    Some(Rpc::Receive(n)) => {
-                        current_message.extend(&buf[..n]);
-                        let mut frame = Cursor::new(current_message.clone());
-
-                        let recv_res = match Message::decode(&mut frame) {
-                            Ok(Message::Notification(n)) => inner_notification_sender
-                                .send(n)
-                                .await
-                                .map_err(|e| NetworkError::Send { message: e.to_string() }),
-                            Ok(Message::Request(r)) => inner_request_sender
-                                .send(r)
-                                .await
-                                .map_err(|e| NetworkError::Send { message: e.to_string() }),
-                            Ok(Message::Response(r)) => {
-                                let mut senders = res_channels.lock().await;
-                                let sender: Sender<Response> =
-                                    senders.remove(&r.id).expect("Got response but no request awaiting it");
-
-                                // send response to the `request` function
-                                sender
-                                    .send(r)
-                                    .await
-                                    .map_err(|e| NetworkError::Send { message: e.to_string() })
+            let mut buf = vec![0_u8; options.read_buffer_size.max(1)];
+
+            let error = async {
+                loop {
+                    let to_process = select! {
+                        // nothing is ever sent, `close` closes the channel
+                        _ = shutdown_receiver.recv().fuse() => {
+                            Err(NetworkError::from(io::Error::from(io::ErrorKind::ConnectionAborted)))
+                        },
+                        maybe_request = request_receiver.recv().fuse() => {
+                            maybe_request.map(|request| Rpc::Send(Message::Request(request))).map_err(NetworkError::from)
+                        },
+                        maybe_notification = notification_receiver.recv().fuse() => {
+                            maybe_notification
+                                .map(|notification| Rpc::Send(Message::Notification(notification)))
+                                .map_err(NetworkError::from)
+                        },
+                        maybe_bytes_read = stream.read(&mut buf).fuse() => {
+                            match maybe_bytes_read {
+                                Ok(0) => Err(NetworkError::from(io::Error::from(io::ErrorKind::UnexpectedEof))),
+                                Ok(bytes_read) => Ok(Rpc::Receive(bytes_read)),
+                                Err(e) => Err(NetworkError::from(e)),
                             }
-                            Err(e) => {
-                                // DecodeError
-                                panic!("{e}");
+                        }
+                    };
+                    match to_process {
+                        // the client was closed or dropped, or the server closed the connection
+                        Err(e) => return e,
+                        Ok(Rpc::Send(m)) => {
+                            let written = match m.pack() {
+                                Ok(message) => stream.write_all(&message).await,
+                                Err(e) => Err(e),
+                            };
+                            if let Err(e) = written {
+                                return NetworkError::from(e);
                             }
-                        };
-
-                        // if error, return it
-                        if let Err(e) = recv_res {
-                            return e;
                         }
+                        Ok(Rpc::Receive(n)) => {
+                            current_message.extend(&buf[..n]);
 
-                        #[allow(clippy::cast_possible_truncation)]
-                        {
-                            let (_, remaining) = current_message.split_at(frame.position() as usize);
-                            current_message = remaining.to_vec();
+                            // a read can end in the middle of a message or hold several of them
+                            loop {
+                                let mut frame = Cursor::new(&current_message[..]);
+                                let message = match Message::decode(&mut frame) {
+                                    Ok(message) => message,
+                                    Err(DecodeError::Truncated) => break,
+                                    Err(e) => return NetworkError::Decode(e),
+                                };
+                                #[allow(clippy::cast_possible_truncation)]
+                                let consumed = frame.position() as usize;
+                                current_message.drain(..consumed);
+
+                                let recv_res = match message {
+                                    Message::Notification(n) => inner_notification_sender
+                                        .send(n)
+                                        .await
+                                        .map_err(|e| NetworkError::Send { message: e.to_string() }),
+                                    Message::Request(r) => inner_request_sender
+                                        .send(r)
+                                        .await
+                                        .map_err(|e| NetworkError::Send { message: e.to_string() }),
+                                    Message::Response(r) => {
+                                        // send response to the `request` function awaiting it, if it still is
+                                        match res_channels.lock().await.remove(&r.id) {
+                                            Some(sender) => {
+                                                let _ = sender.send(r).await;
+                                            }
+                                            None => log::warn!("dropping response to unknown request {}", r.id),
+                                        }
+                                        Ok(())
+                                    }
+                                };
+
+                                // if error, return it
+                                if let Err(e) = recv_res {
+                                    return e;
+                                }
+                            }
                         }
                     }
-                    None => {}
                 }
             }
+            .await;
+
+            // refuse new requests, then wake up the requests still awaiting a response, they fail with
+            // `NetworkError::Recv`. A request registered after the clear can't be queued anymore, so none is left waiting
+            log::debug!("msgpack-rpc connection closed: {error}");
+            request_receiver.close();
+            notification_receiver.close();
+            res_channels.lock().await.clear();
+            error
         });
         Ok(Self {
             request_sender,
@@ -132,25 +188,63 @@ impl MsgPackClient {
             notification_receiver: inner_notification_receiver,
             request_receiver: inner_request_receiver,
             response_channels,
+            shutdown_sender,
+            io_task: Arc::new(Mutex::new(Some(io_task))),
+            request_timeout: options.request_timeout,
         })
     }
 
+    /// Signals the background task to close the connection, without waiting for it
+    ///
+    /// Requests in flight fail with `NetworkError::Recv` and new ones with `NetworkError::Send`. Safe to call
+    /// from `Drop` and more than once
+    pub fn shutdown(&self) {
+        self.request_sender.close();
+        self.notification_sender.close();
+        self.shutdown_sender.close();
+    }
+
+    /// Closes the connection like `shutdown`, and waits until the background task has released the socket
+    pub async fn close(&self) {
+        self.shutdown();
+        let io_task = self.io_task.lock().await.take();
+        if let Some(io_task) = io_task {
+            io_task.await;
+        }
+    }
+
     pub async fn request(&self, request: Request) -> Result<Response, NetworkError> {
         let (response_sender, response_receiver) = unbounded();
 
         // add the response sender (forwards the response from the server) by request id
-        let _ = self.response_channels.lock().await.insert(request.id, response_sender);
+        let id = request.id;
+        let _ = self.response_channels.lock().await.insert(id, response_sender);
 
         // forward request to the thread that then forwards it to the MessagePack-RPC server
         // the response is added to the response channel
         let send_res = self.request_sender.send(request).await;
         if send_res.is_err() {
+            self.response_channels.lock().await.remove(&id);
             let e = format!("Failed to send request: {:?}", send_res);
             return Err(NetworkError::Send { message: e });
         }
 
         // return result from request which is forwarded from the background thread above
-        response_receiver.recv().await.map_err(NetworkError::Recv)
+        let request_timeout = match self.request_timeout {
+            Some(request_timeout) => request_timeout,
+            None => return response_receiver.recv().await.map_err(NetworkError::Recv),
+        };
+        match future::timeout(request_timeout, response_receiver.recv()).await {
+            Ok(response) => response.map_err(NetworkError::Recv),
+            Err(_) => {
+                // a late response is dropped as answering an unknown request
+                self.response_channels.lock().await.remove(&id);
+                Err(NetworkError::from(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("no response to request {id} within {request_timeout:?}"),
+                )))
+            }
+        }
     }
 
     pub async fn _notify(&self, notification: Notification) -> Result<(), NetworkError> {
@@ -162,3 +256,94 @@ impl MsgPackClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use msgpack_rpc::message::{Message, Request, Response};
+    use msgpack_rpc::Value;
+
+    use crate::{MsgPackClient, NetworkError};
+
+    fn ping(id: u32) -> Request {
+        Request {
+            id,
+            method: "ping".to_owned(),
+            params: vec![],
+        }
+    }
+
+    #[test]
+    fn test_pipelined_requests_answered_out_of_order() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // answer both requests in reverse order, the responses split over two writes in the middle of a message
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut ids = vec![];
+            while ids.len() < 2 {
+                if let Message::Request(request) = Message::decode(&mut stream).unwrap() {
+                    ids.push(request.id);
+                }
+            }
+
+            let response = |id: u32| {
+                Message::Response(Response {
+                    id,
+                    result: Ok(Value::from(id)),
+                })
+                .pack()
+                .unwrap()
+            };
+            let mut bytes = response(ids[1]);
+            bytes.extend(response(ids[0]));
+            let split = bytes.len() - 2;
+            stream.write_all(&bytes[..split]).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            stream.write_all(&bytes[split..]).unwrap();
+            stream
+        });
+
+        async_std::task::block_on(async {
+            let client = MsgPackClient::connect(addr).await.unwrap();
+            let (first, second) = futures::join!(client.request(ping(1)), client.request(ping(2)));
+            assert_eq!(first.unwrap().result, Ok(Value::from(1)));
+            assert_eq!(second.unwrap().result, Ok(Value::from(2)));
+        });
+        drop(server.join().unwrap());
+    }
+
+    #[test]
+    fn test_close_cancels_requests_in_flight() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // read the request and never answer it, until the client closes the connection
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = Message::decode(&mut stream);
+            let mut rest = vec![];
+            std::io::Read::read_to_end(&mut stream, &mut rest).unwrap();
+        });
+
+        async_std::task::block_on(async {
+            let client = MsgPackClient::connect(addr).await.unwrap();
+            let pending = async_std::task::spawn({
+                let client = client.clone();
+                async move { client.request(ping(1)).await }
+            });
+            async_std::task::sleep(Duration::from_millis(50)).await;
+
+            client.close().await;
+            assert!(matches!(pending.await, Err(NetworkError::Recv(_))));
+            assert!(matches!(client.request(ping(2)).await, Err(NetworkError::Send { .. })));
+            client.close().await;
+        });
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_std::task::{self, JoinHandle};
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::{MultiRotorClient, MultiRotorState};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Timing of the iterations run by a `ControlLoop`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlLoopStats {
+    /// number of iterations run, paused periods excluded
+    pub iterations: u64,
+    /// number of iterations which ran past the next tick, the ticks they ran over are skipped
+    pub overruns: u64,
+    /// duration of the last iteration, reading the state included
+    pub last_duration: Duration,
+    /// longest duration of an iteration
+    pub max_duration: Duration,
+}
+
+/// Background task running a closure on the latest `MultiRotorState` at a fixed rate
+///
+/// Iterations are scheduled on a grid of ticks rather than by sleeping for a period after each of them, so the cadence
+/// doesn't drift by the duration of the RPCs. An iteration running past the next tick is counted as an overrun
+/// and the ticks it missed are skipped. The loop ends when `stop` is awaited, the loop is dropped, or reading the
+/// state or the closure fails.
+///
+/// ```no_run
+/// use airsim_client::offboard::ControlLoop;
+/// use airsim_client::{MoveOptions, MultiRotorClient, NetworkResult, Velocity3};
+///
+/// # async fn run(client: &MultiRotorClient) -> NetworkResult<()> {
+/// let target_altitude = -10.0;
+/// let vehicle = client.clone();
+/// let control = ControlLoop::start(client, 20.0, move |state| {
+///     let vehicle = vehicle.clone();
+///     async move {
+///         let error = target_altitude - state.kinematics_estimated.position.z;
+///         let velocity = Velocity3::new(0.0, 0.0, 0.5 * error);
+///         vehicle.move_by_velocity_with(velocity, 0.2, &MoveOptions::new()).await.map(|_| ())
+///     }
+/// })?;
+/// async_std::task::sleep(std::time::Duration::from_secs(10)).await;
+/// println!("{:?}", control.stats());
+/// control.stop().await
+/// # }
+/// ```
+pub struct ControlLoop {
+    running: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    stats: Arc<Mutex<ControlLoopStats>>,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl ControlLoop {
+    /// Start running `step` at `rate_hz` with the state of `client`
+    ///
+    /// Fails with `NetworkError::InvalidArgument` if the rate isn't positive
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle whose state is read before every iteration
+    ///     rate_hz (f32): Rate of the iterations in Hz
+    ///     step (FnMut(MultiRotorState) -> Future<Output = NetworkResult<()>>): Body of the loop
+    pub fn start<F, Fut>(client: &MultiRotorClient, rate_hz: f32, step: F) -> NetworkResult<Self>
+    where
+        F: FnMut(MultiRotorState) -> Fut + Send + 'static,
+        Fut: Future<Output = NetworkResult<()>> + Send + 'static,
+    {
+        let period = periodic::try_period("control loop", rate_hz)?;
+        let running = Arc::new(AtomicBool::new(true));
+        let paused = Arc::new(AtomicBool::new(false));
+        let stats = Arc::new(Mutex::new(ControlLoopStats::default()));
+        let task = task::spawn(Self::run(
+            client.clone(),
+            period,
+            step,
+            Arc::clone(&running),
+            Arc::clone(&paused),
+            Arc::clone(&stats),
+        ));
+
+        Ok(Self {
+            running,
+            paused,
+            stats,
+            task: Some(task),
+        })
+    }
+
+    /// Skip the iterations until `resume`, the cadence is kept
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Run the iterations again from the next tick
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether the iterations are skipped
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Whether the loop is still running, paused or not
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Timing of the iterations so far
+    pub fn stats(&self) -> ControlLoopStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Stop the loop once the current iteration ends
+    ///
+    /// Returns the first error the loop ran into, which also ended it
+    pub async fn stop(mut self) -> NetworkResult<()> {
+        self.running.store(false, Ordering::SeqCst);
+        match self.task.take() {
+            Some(task) => task.await,
+            None => Ok(()),
+        }
+    }
+
+    async fn run<F, Fut>(
+        client: MultiRotorClient,
+        period: Duration,
+        mut step: F,
+        running: Arc<AtomicBool>,
+        paused: Arc<AtomicBool>,
+        stats: Arc<Mutex<ControlLoopStats>>,
+    ) -> NetworkResult<()>
+    where
+        F: FnMut(MultiRotorState) -> Fut + Send + 'static,
+        Fut: Future<Output = NetworkResult<()>> + Send + 'static,
+    {
+        let mut ticker = Ticker::new(period);
+
+        while running.load(Ordering::SeqCst) {
+            if !paused.load(Ordering::SeqCst) {
+                let started = Instant::now();
+                let result = match client.get_multirotor_state().await {
+                    Ok(state) => step(state).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = result {
+                    running.store(false, Ordering::SeqCst);
+                    return Err(e);
+                }
+
+                let overran = ticker.advance();
+                let mut stats = stats.lock().unwrap();
+                stats.iterations += 1;
+                stats.last_duration = started.elapsed();
+                stats.max_duration = stats.max_duration.max(stats.last_duration);
+                if overran {
+                    stats.overruns += 1;
+                    log::debug!("control loop iteration took {:?}, over its period", stats.last_duration);
+                }
+            } else {
+                ticker.advance();
+            }
+
+            ticker.wait().await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ControlLoop {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
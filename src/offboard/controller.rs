@@ -0,0 +1,212 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::task::{self, JoinHandle};
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::{MoveOptions, MultiRotorClient, Velocity3};
+
+#[derive(Debug, Clone, Copy)]
+/// Velocity commands streamed by the `OffboardController`
+///
+/// Defaults to 20 Hz, `MaxDegreeOfFreedom` drivetrain and zero yaw rate. Every command lasts `timeout_periods`
+/// periods (default 3), the vehicle stops on its own if the stream stalls for longer
+pub struct OffboardConfig {
+    /// rate of the velocity commands in Hz
+    pub rate_hz: f32,
+    /// drivetrain and yaw mode of every command, velocity and lookahead are ignored
+    pub options: MoveOptions,
+    /// duration of every command, in periods
+    pub timeout_periods: f32,
+}
+
+impl Default for OffboardConfig {
+    fn default() -> Self {
+        Self {
+            rate_hz: 20.0,
+            options: MoveOptions::default(),
+            timeout_periods: 3.0,
+        }
+    }
+}
+
+impl OffboardConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+
+    pub fn options(mut self, options: MoveOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn timeout_periods(mut self, timeout_periods: f32) -> Self {
+        self.timeout_periods = timeout_periods;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Velocity streamed by an `OffboardController`, cloned to update it from other tasks
+pub struct VelocitySetpoint(Arc<Mutex<Velocity3>>);
+
+impl VelocitySetpoint {
+    /// Velocity sent from the next command on
+    ///
+    /// args:
+    ///     velocity (Velocity3): desired velocity in world (NED) axis, in m/s
+    pub fn set(&self, velocity: Velocity3) {
+        *self.0.lock().unwrap() = velocity;
+    }
+
+    /// Velocity currently streamed
+    pub fn get(&self) -> Velocity3 {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Background task streaming `move_by_velocity_with` commands at a fixed rate, like the offboard mode of PX4
+///
+/// AirSim only applies a velocity for the duration of its command, the controller keeps sending the latest setpoint
+/// so a closed loop controller only has to update it. Streaming ends when `stop` is awaited, the controller is
+/// dropped or a command fails, the vehicle then stops once the last command expires.
+pub struct OffboardController {
+    running: Arc<AtomicBool>,
+    setpoint: VelocitySetpoint,
+    client: MultiRotorClient,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl OffboardController {
+    /// Start streaming a zero velocity, i.e. hovering, to `client`
+    ///
+    /// Fails with `NetworkError::InvalidArgument` if the rate isn't positive
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to control, the controller shares its connection
+    ///     config (OffboardConfig): Rate, duration and options of the commands
+    pub fn start(client: &MultiRotorClient, config: OffboardConfig) -> NetworkResult<Self> {
+        let period = periodic::try_period("offboard", config.rate_hz)?;
+        let running = Arc::new(AtomicBool::new(true));
+        let setpoint = VelocitySetpoint(Arc::new(Mutex::new(Velocity3::new(0.0, 0.0, 0.0))));
+        let task = task::spawn(Self::stream(
+            client.clone(),
+            config,
+            period,
+            Arc::clone(&running),
+            setpoint.clone(),
+        ));
+
+        Ok(Self {
+            running,
+            setpoint,
+            client: client.clone(),
+            task: Some(task),
+        })
+    }
+
+    /// Handle to update the velocity from other tasks
+    pub fn setpoint(&self) -> VelocitySetpoint {
+        self.setpoint.clone()
+    }
+
+    /// Velocity sent from the next command on, see `setpoint` to update it from other tasks
+    ///
+    /// args:
+    ///     velocity (Velocity3): desired velocity in world (NED) axis, in m/s
+    pub fn set_velocity(&self, velocity: Velocity3) {
+        self.setpoint.set(velocity);
+    }
+
+    /// Whether the controller is still streaming
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Stop streaming and hover in place
+    ///
+    /// Returns the first error the background task ran into, which also ends the streaming
+    pub async fn stop(mut self) -> NetworkResult<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(task) = self.task.take() {
+            task.await?;
+        }
+        self.client.hover_async().await?;
+        Ok(())
+    }
+
+    async fn stream(
+        client: MultiRotorClient,
+        config: OffboardConfig,
+        period: Duration,
+        running: Arc<AtomicBool>,
+        setpoint: VelocitySetpoint,
+    ) -> NetworkResult<()> {
+        let mut ticker = Ticker::new(period);
+        let duration = period.as_secs_f32() * config.timeout_periods;
+
+        while running.load(Ordering::SeqCst) {
+            // AirSim answers a command once it expires or the next one replaces it, stop waiting at the next tick
+            let command = client.move_by_velocity_with(setpoint.get(), duration, &config.options);
+            if let Ok(Err(e)) = future::timeout(period, command).await {
+                running.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+
+            ticker.tick().await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for OffboardController {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::offboard::{OffboardConfig, OffboardController};
+    use crate::{MultiRotorClient, Velocity3};
+
+    #[test]
+    fn test_streams_latest_setpoint() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("moveByVelocity", Value::Boolean(true));
+        server.respond("hover", Value::Boolean(true));
+
+        async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            let controller = OffboardController::start(&client, OffboardConfig::new().rate_hz(50.0)).unwrap();
+            let setpoint = controller.setpoint();
+
+            async_std::task::spawn(async move { setpoint.set(Velocity3::new(2.0, 0.0, 0.0)) }).await;
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            controller.stop().await.unwrap();
+        });
+
+        let commands: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "moveByVelocity")
+            .collect();
+        assert!(commands.len() >= 2);
+        assert_eq!(commands.last().unwrap().params[0], Value::F32(2.0));
+        assert_eq!(server.calls("hover"), 1);
+    }
+}
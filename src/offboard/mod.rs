@@ -0,0 +1,5 @@
+mod control_loop;
+mod controller;
+
+pub use control_loop::{ControlLoop, ControlLoopStats};
+pub use controller::{OffboardConfig, OffboardController, VelocitySetpoint};
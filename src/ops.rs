@@ -0,0 +1,189 @@
+//! Combinators to sequence commands returning `NetworkResult<bool>`
+//!
+//! Most commands, like `take_off_async` or `move_to_position_async`, report two kinds of failures: an `Err` when
+//! the RPC failed, and `Ok(false)` when AirSim refused or didn't complete the command. The combinators turn both
+//! into a `CommandError` so a mission script can use `?` without ignoring a `false`.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use airsim_client::ops::{CommandExt, CommandResult, RetryCommand};
+//! use airsim_client::{MultiRotorClient, RetryPolicy};
+//!
+//! # async fn run(client: &MultiRotorClient) -> CommandResult {
+//! (|| client.arm_disarm(true)).retrying(RetryPolicy::new()).await?;
+//! client
+//!     .take_off_async(20.0)
+//!     .with_timeout(Duration::from_secs(25))
+//!     .and_then_cmd(|| client.hover_async())
+//!     .await
+//! # }
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use thiserror::Error;
+
+use crate::{NetworkError, NetworkResult, RetryPolicy};
+
+/// Why a command failed
+#[derive(Error, Debug)]
+pub enum CommandError {
+    /// The RPC failed, see `NetworkError`
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+    /// The RPC succeeded but AirSim returned false, e.g. the vehicle didn't reach its goal
+    #[error("command was rejected or didn't complete")]
+    Rejected,
+    /// The command didn't complete in time, see `CommandExt::with_timeout`
+    #[error("command didn't complete within {0:?}")]
+    Timeout(Duration),
+}
+
+pub type CommandResult<T = ()> = Result<T, CommandError>;
+
+/// Results the combinators accept, to chain commands and combinators
+pub trait IntoCommandResult {
+    fn into_command_result(self) -> CommandResult;
+}
+
+impl IntoCommandResult for NetworkResult<bool> {
+    /// `Ok(false)` becomes `CommandError::Rejected`
+    fn into_command_result(self) -> CommandResult {
+        match self {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(CommandError::Rejected),
+            Err(e) => Err(CommandError::Network(e)),
+        }
+    }
+}
+
+impl IntoCommandResult for CommandResult {
+    fn into_command_result(self) -> CommandResult {
+        self
+    }
+}
+
+/// Combinators on the future of a command, e.g. `client.take_off_async(20.0)`
+pub trait CommandExt<'a>: Future + Send + Sized + 'a
+where
+    Self::Output: IntoCommandResult,
+{
+    /// Await the command, turning `Ok(false)` into `CommandError::Rejected`
+    fn checked(self) -> BoxFuture<'a, CommandResult> {
+        Box::pin(async move { self.await.into_command_result() })
+    }
+
+    /// Fail with `CommandError::Timeout` if the command doesn't complete in time
+    ///
+    /// The RPC isn't cancelled on the server, see `cancel_last_task` to stop the vehicle
+    ///
+    /// args:
+    ///     timeout (Duration): Time the command has to complete
+    fn with_timeout(self, timeout: Duration) -> BoxFuture<'a, CommandResult> {
+        Box::pin(async move {
+            match async_std::future::timeout(timeout, self).await {
+                Ok(result) => result.into_command_result(),
+                Err(_) => Err(CommandError::Timeout(timeout)),
+            }
+        })
+    }
+
+    /// Run `next` once the command succeeded, a failed or rejected command skips it
+    ///
+    /// args:
+    ///     next (FnOnce() -> Future): Command to run next
+    fn and_then_cmd<F, Fut>(self, next: F) -> BoxFuture<'a, CommandResult>
+    where
+        F: FnOnce() -> Fut + Send + 'a,
+        Fut: Future + Send + 'a,
+        Fut::Output: IntoCommandResult,
+    {
+        Box::pin(async move {
+            self.await.into_command_result()?;
+            next().await.into_command_result()
+        })
+    }
+}
+
+impl<'a, F> CommandExt<'a> for F
+where
+    F: Future + Send + 'a,
+    F::Output: IntoCommandResult,
+{
+}
+
+/// Retries of a command, built by a closure since a future can only be awaited once
+pub trait RetryCommand<'a, Fut>: FnMut() -> Fut + Send + Sized + 'a
+where
+    Fut: Future<Output = NetworkResult<bool>> + Send + 'a,
+{
+    /// Run the command until it succeeds or fails with an error `policy` doesn't retry
+    ///
+    /// Rejections are returned at once, AirSim answered so sending the same command again would be answered alike.
+    /// `policy` replaces the retry policy of the client for the RPCs of the command, see `RetryPolicy::run`
+    ///
+    /// args:
+    ///     policy (RetryPolicy): When and how often to retry
+    fn retrying(self, policy: RetryPolicy) -> BoxFuture<'a, CommandResult> {
+        Box::pin(async move { policy.run(self).await.into_command_result() })
+    }
+}
+
+impl<'a, F, Fut> RetryCommand<'a, Fut> for F
+where
+    F: FnMut() -> Fut + Send + 'a,
+    Fut: Future<Output = NetworkResult<bool>> + Send + 'a,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use futures::future::{pending, ready};
+
+    use crate::ops::{CommandError, CommandExt, RetryCommand};
+    use crate::{NetworkError, NetworkResult, RetryPolicy};
+
+    #[test]
+    fn test_combinators() {
+        async_std::task::block_on(async {
+            let skipped = AtomicBool::new(true);
+            let rejected = ready(Ok(false)).and_then_cmd(|| {
+                skipped.store(false, Ordering::SeqCst);
+                ready(Ok(true))
+            });
+            assert!(matches!(rejected.await, Err(CommandError::Rejected)));
+            assert!(skipped.load(Ordering::SeqCst));
+
+            let chained = ready(Ok(true))
+                .and_then_cmd(|| ready(Ok(true)))
+                .with_timeout(Duration::from_secs(1));
+            assert!(chained.await.is_ok());
+
+            let stuck = pending::<NetworkResult<bool>>().with_timeout(Duration::from_millis(10));
+            assert!(matches!(stuck.await, Err(CommandError::Timeout(_))));
+
+            let mut attempts = 0;
+            let retried = (move || {
+                attempts += 1;
+                ready(match attempts {
+                    1 => Err(NetworkError::Io(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))),
+                    _ => Ok(true),
+                })
+            })
+            .retrying(RetryPolicy::new().backoff(Duration::ZERO));
+            assert!(retried.await.is_ok());
+
+            // rejected unless the RPCs of the command skip the policy of the client
+            // the flag is read when the future is polled, `ready` would read it when the call builds it
+            let overridden = (|| async { Ok(RetryPolicy::is_overridden()) }).retrying(RetryPolicy::disabled());
+            assert!(overridden.await.is_ok());
+        });
+    }
+}
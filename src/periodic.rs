@@ -0,0 +1,135 @@
+//! Fixed rate scheduling shared by the background tasks and streams sampling a vehicle
+//!
+//! Iterations run on a grid of ticks rather than sleeping for a period after each of them, so the cadence doesn't
+//! drift by the duration of the RPCs, and ticks already missed are skipped rather than run back to back.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use async_std::task;
+use futures::stream::{self, Stream};
+
+use crate::error::{NetworkError, NetworkResult};
+
+/// Period of a loop running at `rate_hz`, failing with `NetworkError::InvalidArgument` unless the rate is positive
+/// and the period between a nanosecond and a day
+///
+/// args:
+///     what (&str): Name of the loop, for the error message
+///     rate_hz (f32): Rate of the loop in Hz
+pub(crate) fn try_period(what: &str, rate_hz: f32) -> NetworkResult<Duration> {
+    let period = 1.0 / rate_hz as f64;
+    if rate_hz > 0.0 && (1e-9..=86_400.0).contains(&period) {
+        Ok(Duration::from_secs_f64(period))
+    } else {
+        Err(NetworkError::InvalidArgument {
+            message: format!("{what} rate has to be positive and finite, got {rate_hz} Hz"),
+        })
+    }
+}
+
+/// Like `try_period`, for the constructors that can't fail: panics on an invalid rate
+pub(crate) fn period(what: &str, rate_hz: f32) -> Duration {
+    try_period(what, rate_hz).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Start of the iteration after the one started at `tick`, and whether that iteration, ending at `now`, overran
+pub(crate) fn next_tick(tick: Instant, now: Instant, period: Duration) -> (Instant, bool) {
+    let next = tick + period;
+    if now <= next {
+        return (next, false);
+    }
+    let missed = ((now - next).as_nanos() / period.as_nanos()) as u32 + 1;
+    (next + period * missed, true)
+}
+
+/// Grid of ticks `period` apart, the first one when the ticker is created
+pub(crate) struct Ticker {
+    period: Duration,
+    tick: Instant,
+}
+
+impl Ticker {
+    pub(crate) fn new(period: Duration) -> Self {
+        Self {
+            period,
+            tick: Instant::now(),
+        }
+    }
+
+    /// Move to the next tick still ahead, returns whether the iteration since the last tick overran
+    pub(crate) fn advance(&mut self) -> bool {
+        let (next, overran) = next_tick(self.tick, Instant::now(), self.period);
+        self.tick = next;
+        overran
+    }
+
+    /// Sleep until the current tick
+    pub(crate) async fn wait(&self) {
+        task::sleep(self.tick.saturating_duration_since(Instant::now())).await;
+    }
+
+    /// Sleep until the next tick still ahead, see `advance`
+    pub(crate) async fn tick(&mut self) -> bool {
+        let overran = self.advance();
+        self.wait().await;
+        overran
+    }
+}
+
+/// Stream of the results of `sample`, called on a grid of ticks `period` apart from the first item on, until dropped
+///
+/// A consumer falling behind by more than a period skips the missed ticks instead of getting them in a burst
+pub(crate) fn sample_stream<T, F, Fut>(period: Duration, sample: F) -> impl Stream<Item = T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    stream::unfold((None::<Ticker>, sample), move |(ticker, mut sample)| async move {
+        let ticker = match ticker {
+            Some(mut ticker) => {
+                ticker.tick().await;
+                ticker
+            }
+            None => Ticker::new(period),
+        };
+        let item = sample().await;
+        Some((item, (Some(ticker), sample)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::periodic::{next_tick, try_period};
+
+    #[test]
+    fn test_next_tick() {
+        let start = Instant::now();
+        let period = Duration::from_millis(50);
+
+        // on time: the next tick of the grid
+        assert_eq!(
+            next_tick(start, start + Duration::from_millis(10), period),
+            (start + period, false)
+        );
+        // over its period: skip the missed tick rather than catch up
+        assert_eq!(
+            next_tick(start, start + Duration::from_millis(70), period),
+            (start + 2 * period, true)
+        );
+        assert_eq!(
+            next_tick(start, start + Duration::from_millis(180), period),
+            (start + 4 * period, true)
+        );
+    }
+
+    #[test]
+    fn test_try_period() {
+        assert_eq!(try_period("test", 20.0).unwrap(), Duration::from_millis(50));
+        for rate_hz in [0.0, -1.0, f32::NAN, f32::INFINITY, 1e-30] {
+            assert!(try_period("test", rate_hz).is_err(), "{rate_hz} Hz");
+        }
+    }
+}
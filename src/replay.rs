@@ -0,0 +1,332 @@
+//! Recording of the RPC traffic of a client, and replay of the responses without a simulator
+//!
+//! The log is a sequence of entries, each a big endian `u32` length followed by the entry encoded as msgpack. Every
+//! entry is flushed as soon as it's written, so the log is complete up to the RPC a program panicked on, e.g. while
+//! decoding a response of an unusual AirSim build.
+//!
+//! ```no_run
+//! use airsim_client::replay::{read_log, RpcRecorder};
+//! use airsim_client::{AirsimClient, MultiRotorClient};
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = AirsimClient::connect("127.0.0.1:41451", "").await?.with_recorder("session.rpclog")?;
+//! client.sim_is_pause().await?;
+//! // the vehicle clients take the recorder as a middleware
+//! let drone = MultiRotorClient::connect("127.0.0.1:41451", "Drone1").await?;
+//! drone.add_middleware(RpcRecorder::create("drone.rpclog")?);
+//!
+//! for entry in read_log("session.rpclog")? {
+//!     println!("{entry:?}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rmpv::Value;
+
+use crate::{Middleware, NetworkError};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Entry of an RPC log
+pub enum RpcLogEntry {
+    /// Request sent to the server, after the middlewares added before the recorder
+    Request { method: String, params: Vec<Value> },
+    /// Response received from the server, in the order of arrival. Failures of the connection are recorded as
+    /// errors holding their message
+    Response {
+        method: String,
+        result: Result<Value, Value>,
+        latency: Duration,
+    },
+}
+
+impl RpcLogEntry {
+    fn as_msgpack(&self) -> Value {
+        match self {
+            RpcLogEntry::Request { method, params } => Value::Array(vec![
+                Value::from(0),
+                Value::from(method.as_str()),
+                Value::Array(params.clone()),
+            ]),
+            RpcLogEntry::Response {
+                method,
+                result,
+                latency,
+            } => {
+                let (ok, value) = match result {
+                    Ok(value) => (true, value.clone()),
+                    Err(value) => (false, value.clone()),
+                };
+                Value::Array(vec![
+                    Value::from(1),
+                    Value::from(method.as_str()),
+                    Value::Boolean(ok),
+                    value,
+                    Value::from(latency.as_micros() as u64),
+                ])
+            }
+        }
+    }
+
+    fn from_msgpack(msgpack: Value) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid RPC log entry");
+        let fields = match msgpack {
+            Value::Array(fields) => fields,
+            _ => return Err(invalid()),
+        };
+        let method = fields.get(1).and_then(Value::as_str).ok_or_else(invalid)?.to_owned();
+
+        match (fields.first().and_then(Value::as_u64), fields.len()) {
+            (Some(0), 3) => Ok(RpcLogEntry::Request {
+                method,
+                params: fields[2].as_array().ok_or_else(invalid)?.clone(),
+            }),
+            (Some(1), 5) => {
+                let value = fields[3].clone();
+                Ok(RpcLogEntry::Response {
+                    method,
+                    result: if fields[2].as_bool().ok_or_else(invalid)? {
+                        Ok(value)
+                    } else {
+                        Err(value)
+                    },
+                    latency: Duration::from_micros(fields[4].as_u64().ok_or_else(invalid)?),
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Reads every entry of an RPC log
+///
+/// args:
+///     path (impl AsRef<Path>): Path of the log written by an `RpcRecorder`
+pub fn read_log(path: impl AsRef<Path>) -> io::Result<Vec<RpcLogEntry>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut entries = vec![];
+
+    loop {
+        let mut length = [0; 4];
+        match reader.read_exact(&mut length) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(entries),
+            Err(e) => return Err(e),
+        }
+        let mut entry = vec![0; u32::from_be_bytes(length) as usize];
+        reader.read_exact(&mut entry)?;
+        entries.push(RpcLogEntry::from_msgpack(rmpv::decode::read_value(&mut &entry[..])?)?);
+    }
+}
+
+/// Middleware appending every request and response of a client to an RPC log, see `AirsimClient::with_recorder`
+///
+/// Entries are written in the order the hooks run, a failure to write is logged and the RPC goes on
+pub struct RpcRecorder {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl RpcRecorder {
+    /// Creates the log, replacing an existing file
+    ///
+    /// args:
+    ///     path (impl AsRef<Path>): Path of the log
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().write(true).create(true).truncate(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn append(&self, entry: &RpcLogEntry) {
+        let mut bytes = vec![];
+        // writing to a Vec can't fail
+        let _ = rmpv::encode::write_value(&mut bytes, &entry.as_msgpack());
+
+        let mut writer = self.writer.lock().unwrap();
+        let written = writer
+            .write_all(&(bytes.len() as u32).to_be_bytes())
+            .and_then(|_| writer.write_all(&bytes))
+            .and_then(|_| writer.flush());
+        if let Err(e) = written {
+            log::warn!("could not append to the RPC log: {e}");
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RpcRecorder {
+    async fn on_request(&self, method: &str, params: &mut Vec<Value>) {
+        self.append(&RpcLogEntry::Request {
+            method: method.to_owned(),
+            params: params.clone(),
+        });
+    }
+
+    fn on_response(&self, method: &str, result: &Value, latency: Duration) {
+        self.append(&RpcLogEntry::Response {
+            method: method.to_owned(),
+            result: Ok(result.clone()),
+            latency,
+        });
+    }
+
+    fn on_error(&self, method: &str, error: &NetworkError, latency: Duration) {
+        let raw = match error {
+            NetworkError::Rpc { raw, .. } => raw.clone(),
+            e => Value::from(e.to_string()),
+        };
+        self.append(&RpcLogEntry::Response {
+            method: method.to_owned(),
+            result: Err(raw),
+            latency,
+        });
+    }
+}
+
+/// Stand-in for the AirSim server answering the RPCs with the responses of a log, to connect the clients to
+///
+/// Every method is answered with its recorded responses in order, regardless of the params. Once they run out,
+/// `ping` and `enableApiControl` answer true so clients can connect, other methods fail like unknown methods do
+///
+/// ```no_run
+/// use airsim_client::replay::ReplayClient;
+/// use airsim_client::AirsimClient;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let replay = ReplayClient::open("session.rpclog")?;
+/// let client = AirsimClient::connect(replay.addr(), "").await?;
+/// client.sim_is_pause().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(any(test, feature = "mock"))]
+pub struct ReplayClient {
+    server: crate::mock::MockAirsimServer,
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl ReplayClient {
+    /// Serves the responses of a log on a free port of the loopback interface, see `addr`
+    ///
+    /// args:
+    ///     path (impl AsRef<Path>): Path of the log written by an `RpcRecorder`
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_entries(read_log(path)?)
+    }
+
+    /// Serves the responses of log entries, e.g. read with `read_log` and filtered
+    ///
+    /// args:
+    ///     entries (Vec<RpcLogEntry>): Entries of the log, requests are ignored
+    pub fn from_entries(entries: Vec<RpcLogEntry>) -> io::Result<Self> {
+        use std::collections::{HashMap, VecDeque};
+
+        let mut responses: HashMap<String, VecDeque<Result<Value, Value>>> = HashMap::new();
+        for entry in entries {
+            if let RpcLogEntry::Response { method, result, .. } = entry {
+                responses.entry(method).or_default().push_back(result);
+            }
+        }
+        for method in ["ping", "enableApiControl"] {
+            responses.entry(method.to_owned()).or_default();
+        }
+
+        let server = crate::mock::MockAirsimServer::start()?;
+        for (method, mut queue) in responses {
+            let connects = method == "ping" || method == "enableApiControl";
+            server.respond_with(&method.clone(), move |_| match queue.pop_front() {
+                Some(result) => result,
+                None if connects => Ok(Value::Boolean(true)),
+                None => Err(Value::from(format!("no recorded response left for {method}"))),
+            });
+        }
+        Ok(Self { server })
+    }
+
+    /// Address to connect the clients to
+    pub fn addr(&self) -> std::net::SocketAddr {
+        self.server.addr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rmpv::Value;
+
+    use crate::replay::{read_log, RpcLogEntry, RpcRecorder};
+    use crate::{Middleware, NetworkError};
+
+    #[test]
+    fn test_log_round_trip() {
+        let path = std::env::temp_dir().join(format!("airsim-client-{}.rpclog", std::process::id()));
+        let recorder = RpcRecorder::create(&path).unwrap();
+
+        async_std::task::block_on(recorder.on_request("simPause", &mut vec![Value::Boolean(true)]));
+        recorder.on_response("simPause", &Value::Nil, Duration::from_micros(1500));
+        let error = NetworkError::Rpc {
+            method: "simIsPause".to_owned(),
+            message: "not found".to_owned(),
+            raw: Value::from("not found"),
+        };
+        recorder.on_error("simIsPause", &error, Duration::from_millis(2));
+
+        let entries = read_log(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                RpcLogEntry::Request {
+                    method: "simPause".to_owned(),
+                    params: vec![Value::Boolean(true)],
+                },
+                RpcLogEntry::Response {
+                    method: "simPause".to_owned(),
+                    result: Ok(Value::Nil),
+                    latency: Duration::from_micros(1500),
+                },
+                RpcLogEntry::Response {
+                    method: "simIsPause".to_owned(),
+                    result: Err(Value::from("not found")),
+                    latency: Duration::from_millis(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_serves_recorded_responses() {
+        use crate::replay::ReplayClient;
+        use crate::AirsimClient;
+
+        let replay = ReplayClient::from_entries(vec![
+            RpcLogEntry::Response {
+                method: "simIsPause".to_owned(),
+                result: Ok(Value::Boolean(true)),
+                latency: Duration::from_millis(1),
+            },
+            RpcLogEntry::Response {
+                method: "simIsPause".to_owned(),
+                result: Ok(Value::Boolean(false)),
+                latency: Duration::from_millis(1),
+            },
+        ])
+        .unwrap();
+
+        async_std::task::block_on(async {
+            let client = AirsimClient::connect(replay.addr(), "").await.unwrap();
+            assert!(client.sim_is_pause().await.unwrap());
+            assert!(!client.sim_is_pause().await.unwrap());
+            assert!(client.sim_is_pause().await.is_err());
+        });
+    }
+}
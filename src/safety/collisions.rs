@@ -0,0 +1,187 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::task::{self, JoinHandle};
+use futures::StreamExt;
+
+use crate::error::NetworkResult;
+use crate::periodic;
+use crate::{CollisionInfo, MultiRotorClient};
+
+/// Background task running a callback once per collision of a multirotor, see `MultiRotorClient::watch_collisions`
+///
+/// Polling ends when `stop` is awaited, the watcher is dropped, or reading the collision info fails.
+///
+/// ```no_run
+/// use airsim_client::{MultiRotorClient, NetworkResult};
+///
+/// # async fn run(client: &MultiRotorClient) -> NetworkResult<()> {
+/// let watcher = client.watch_collisions(20.0, |collision| {
+///     println!("hit {} at {:?}", collision.object_name, collision.impact_point);
+/// });
+/// async_std::task::sleep(std::time::Duration::from_secs(60)).await;
+/// println!("{} collisions", watcher.collisions());
+/// watcher.stop().await
+/// # }
+/// ```
+pub struct CollisionWatcher {
+    running: Arc<AtomicBool>,
+    collisions: Arc<AtomicU64>,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl CollisionWatcher {
+    /// Start polling the collisions of `client`
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to watch, the watcher shares its connection
+    ///     rate_hz (f32): Polling rate in Hz
+    ///     callback (FnMut(&CollisionInfo)): Called once per collision
+    ///
+    /// # Panics
+    /// If the polling rate isn't positive
+    pub fn start<F>(client: &MultiRotorClient, rate_hz: f32, callback: F) -> Self
+    where
+        F: FnMut(&CollisionInfo) + Send + 'static,
+    {
+        let period = periodic::period("collision watcher", rate_hz);
+        let running = Arc::new(AtomicBool::new(true));
+        let collisions = Arc::new(AtomicU64::new(0));
+        let task = task::spawn(Self::watch(
+            client.clone(),
+            rate_hz,
+            period,
+            callback,
+            Arc::clone(&running),
+            Arc::clone(&collisions),
+        ));
+
+        Self {
+            running,
+            collisions,
+            task: Some(task),
+        }
+    }
+
+    /// Number of collisions seen so far
+    pub fn collisions(&self) -> u64 {
+        self.collisions.load(Ordering::SeqCst)
+    }
+
+    /// Whether the watcher is still polling
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Stop polling once the current poll ends
+    ///
+    /// Returns the first error the watcher ran into, which also ended it
+    pub async fn stop(mut self) -> NetworkResult<()> {
+        self.running.store(false, Ordering::SeqCst);
+        match self.task.take() {
+            Some(task) => task.await,
+            None => Ok(()),
+        }
+    }
+
+    async fn watch<F>(
+        client: MultiRotorClient,
+        rate_hz: f32,
+        period: Duration,
+        mut callback: F,
+        running: Arc<AtomicBool>,
+        collisions: Arc<AtomicU64>,
+    ) -> NetworkResult<()>
+    where
+        F: FnMut(&CollisionInfo) + Send + 'static,
+    {
+        let stream = client.collision_stream(rate_hz);
+        futures::pin_mut!(stream);
+
+        while running.load(Ordering::SeqCst) {
+            // the stream only yields on collisions, check whether to stop at every poll period
+            let collision = match async_std::future::timeout(period, stream.next()).await {
+                Ok(Some(collision)) => collision,
+                Ok(None) => break,
+                Err(_) => continue,
+            };
+            match collision {
+                Ok(collision) => {
+                    collisions.fetch_add(1, Ordering::SeqCst);
+                    log::info!("{} collided with {}", client.vehicle_name(), collision.object_name);
+                    callback(&collision);
+                }
+                Err(e) => {
+                    running.store(false, Ordering::SeqCst);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for CollisionWatcher {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::MultiRotorClient;
+
+    fn collision(has_collided: bool, timestamp: u64) -> Value {
+        let vector = || {
+            Value::Map(vec![
+                (Value::from("x_val"), Value::F32(0.0)),
+                (Value::from("y_val"), Value::F32(0.0)),
+                (Value::from("z_val"), Value::F32(0.0)),
+            ])
+        };
+        Value::Map(vec![
+            (Value::from("has_collided"), Value::Boolean(has_collided)),
+            (Value::from("penetration_depth"), Value::F32(0.1)),
+            (Value::from("time_stamp"), Value::from(timestamp)),
+            (Value::from("normal"), vector()),
+            (Value::from("impact_point"), vector()),
+            (Value::from("position"), vector()),
+            (Value::from("object_name"), Value::from("Wall")),
+            (Value::from("object_id"), Value::from(-1)),
+        ])
+    }
+
+    #[test]
+    fn test_collision_stream_yields_each_collision_once() {
+        let server = MockAirsimServer::start().unwrap();
+        let mut responses = vec![
+            collision(false, 0),
+            collision(true, 5),
+            collision(true, 5),
+            collision(true, 9),
+        ]
+        .into_iter();
+        server.respond_with("simGetCollisionInfo", move |_| {
+            Ok(responses.next().unwrap_or_else(|| collision(true, 9)))
+        });
+
+        let timestamps: Vec<_> = async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            let stream = client.collision_stream(200.0);
+            stream
+                .take(2)
+                .map(|collision| collision.unwrap().timestamp)
+                .collect()
+                .await
+        });
+
+        assert_eq!(timestamps, vec![5, 9]);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{GeoPoint, Position3, Vector2, Vector3};
+
+#[derive(Debug, Clone)]
+/// Area a vehicle has to stay in
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fence {
+    /// Box in the local NED frame, given by its minimum and maximum corners
+    NedBox { min: Vector3, max: Vector3 },
+    /// Polygon in the local NED frame, given by the (x, y) of its vertices. The altitude isn't limited
+    NedPolygon(Vec<Vector2>),
+    /// Polygon given by the latitude and longitude of its vertices. The altitude isn't limited
+    GeoPolygon(Vec<GeoPoint>),
+}
+
+impl Fence {
+    /// Rectangle between a south-west and a north-east corner
+    ///
+    /// args:
+    ///     south_west (GeoPoint): corner with the minimum latitude and longitude
+    ///     north_east (GeoPoint): corner with the maximum latitude and longitude
+    pub fn geo_box(south_west: GeoPoint, north_east: GeoPoint) -> Self {
+        let altitude = south_west.altitude;
+        Fence::GeoPolygon(vec![
+            south_west,
+            GeoPoint::new(south_west.latitude, north_east.longitude, altitude),
+            north_east,
+            GeoPoint::new(north_east.latitude, south_west.longitude, altitude),
+        ])
+    }
+
+    /// Whether the vehicle is inside the fence
+    ///
+    /// args:
+    ///     position (&Position3): position of the vehicle in the local NED frame, used by NED fences
+    ///     geo_point (&GeoPoint): location of the vehicle, used by geo fences
+    pub fn contains(&self, position: &Position3, geo_point: &GeoPoint) -> bool {
+        match self {
+            Fence::NedBox { min, max } => {
+                (min.x..=max.x).contains(&position.x)
+                    && (min.y..=max.y).contains(&position.y)
+                    && (min.z..=max.z).contains(&position.z)
+            }
+            Fence::NedPolygon(vertices) => {
+                let vertices: Vec<(f32, f32)> = vertices.iter().map(|v| (v.x, v.y)).collect();
+                polygon_contains(&vertices, (position.x, position.y))
+            }
+            Fence::GeoPolygon(vertices) => {
+                let vertices: Vec<(f32, f32)> = vertices.iter().map(|v| (v.longitude, v.latitude)).collect();
+                polygon_contains(&vertices, (geo_point.longitude, geo_point.latitude))
+            }
+        }
+    }
+}
+
+/// Even-odd rule: a ray cast from the point crosses the edges of the polygon an odd number of times iff it's inside
+fn polygon_contains(vertices: &[(f32, f32)], (x, y): (f32, f32)) -> bool {
+    let mut inside = false;
+    let mut j = vertices.len().wrapping_sub(1);
+    for (i, &(xi, yi)) in vertices.iter().enumerate() {
+        let (xj, yj) = vertices[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::safety::Fence;
+    use crate::{GeoPoint, Position3, Vector2, Vector3};
+
+    #[test]
+    fn test_ned_fences() {
+        let anywhere = GeoPoint::new(0.0, 0.0, 0.0);
+        let fence = Fence::NedBox {
+            min: Vector3::new(-10.0, -10.0, -30.0),
+            max: Vector3::new(10.0, 10.0, 0.0),
+        };
+        assert!(fence.contains(&Position3::new(5.0, -5.0, -2.0), &anywhere));
+        assert!(!fence.contains(&Position3::new(5.0, -5.0, 1.0), &anywhere));
+
+        // L shaped polygon, the notch at x, y > 5 is outside
+        let fence = Fence::NedPolygon(vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 5.0),
+            Vector2::new(5.0, 5.0),
+            Vector2::new(5.0, 10.0),
+            Vector2::new(0.0, 10.0),
+        ]);
+        assert!(fence.contains(&Position3::new(8.0, 2.0, -100.0), &anywhere));
+        assert!(fence.contains(&Position3::new(2.0, 8.0, 0.0), &anywhere));
+        assert!(!fence.contains(&Position3::new(8.0, 8.0, 0.0), &anywhere));
+    }
+
+    #[test]
+    fn test_geo_box() {
+        let here = Position3::new(0.0, 0.0, 0.0);
+        let fence = Fence::geo_box(GeoPoint::new(47.64, -122.14, 0.0), GeoPoint::new(47.65, -122.13, 0.0));
+        assert!(fence.contains(&here, &GeoPoint::new(47.645, -122.135, 120.0)));
+        assert!(!fence.contains(&here, &GeoPoint::new(47.645, -122.125, 120.0)));
+    }
+}
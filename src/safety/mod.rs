@@ -0,0 +1,7 @@
+mod collisions;
+mod fence;
+mod monitor;
+
+pub use collisions::CollisionWatcher;
+pub use fence::Fence;
+pub use monitor::{Breach, SafetyAction, SafetyConfig, SafetyMonitor};
@@ -0,0 +1,258 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::task::{self, JoinHandle};
+
+use crate::error::NetworkResult;
+use crate::maneuvers::{rtl, RtlConfig};
+use crate::periodic::{self, Ticker};
+use crate::{MultiRotorClient, Position3};
+
+use super::Fence;
+
+#[derive(Debug, Clone, Copy)]
+/// What the `SafetyMonitor` makes the vehicle do once a limit is breached
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SafetyAction {
+    /// Only report the breach
+    Report,
+    /// Stop and hover in place
+    Hover,
+    /// Return to launch, see `maneuvers::rtl`
+    ReturnToLaunch(RtlConfig),
+    /// Land in place, with the given timeout in seconds
+    Land(f32),
+}
+
+#[derive(Debug, Clone)]
+/// Limit breached by the vehicle
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Breach {
+    /// The vehicle left the fence, at the given NED position
+    OutsideFence(Position3),
+    /// The vehicle flew higher than the maximum altitude, at the given altitude above its starting point
+    AboveMaxAltitude(f32),
+    /// An obstacle is closer than the minimum distance, as measured by the given distance sensor
+    ObstacleTooClose { sensor: String, distance: f32 },
+    /// The limits couldn't be checked for the given number of polls in a row, e.g. the connection is lost
+    ChecksFailed(u32),
+}
+
+type BreachCallback = Arc<dyn Fn(&Breach) + Send + Sync>;
+
+#[derive(Clone)]
+/// Limits watched by the `SafetyMonitor` and the reaction to breaching them
+///
+/// Defaults to no limit at all, sampling at 10 Hz, hovering on breach and giving up on the limits after 10
+/// failed checks in a row
+pub struct SafetyConfig {
+    /// area the vehicle has to stay in
+    pub fence: Option<Fence>,
+    /// maximum altitude above the starting point, in meters
+    pub max_altitude: Option<f32>,
+    /// distance sensors and the minimum distance to obstacles they have to measure, in meters
+    pub min_obstacle_distance: Vec<(String, f32)>,
+    /// reaction to a breach
+    pub action: SafetyAction,
+    /// sampling rate in Hz
+    pub rate_hz: f32,
+    /// checks failing in a row before the monitor reacts as on a breach, `Breach::ChecksFailed`
+    pub max_failed_checks: u32,
+    on_breach: Option<BreachCallback>,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            fence: None,
+            max_altitude: None,
+            min_obstacle_distance: vec![],
+            action: SafetyAction::Hover,
+            rate_hz: 10.0,
+            max_failed_checks: 10,
+            on_breach: None,
+        }
+    }
+}
+
+impl SafetyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fence(mut self, fence: Fence) -> Self {
+        self.fence = Some(fence);
+        self
+    }
+
+    pub fn max_altitude(mut self, max_altitude: f32) -> Self {
+        self.max_altitude = Some(max_altitude);
+        self
+    }
+
+    /// Watch a distance sensor, as named in settings.json. Can be called once per sensor
+    pub fn min_obstacle_distance(mut self, sensor: &str, min_distance: f32) -> Self {
+        self.min_obstacle_distance.push((sensor.to_owned(), min_distance));
+        self
+    }
+
+    pub fn action(mut self, action: SafetyAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+
+    pub fn max_failed_checks(mut self, max_failed_checks: u32) -> Self {
+        self.max_failed_checks = max_failed_checks;
+        self
+    }
+
+    /// Called with the breach before the action is taken
+    pub fn on_breach(mut self, on_breach: impl Fn(&Breach) + Send + Sync + 'static) -> Self {
+        self.on_breach = Some(Arc::new(on_breach));
+        self
+    }
+
+    async fn check(&self, client: &MultiRotorClient) -> NetworkResult<Option<Breach>> {
+        let state = client.get_multirotor_state().await?;
+        let position = state.kinematics_estimated.position;
+
+        if let Some(fence) = &self.fence {
+            if !fence.contains(&position, &state.gps_location) {
+                return Ok(Some(Breach::OutsideFence(position)));
+            }
+        }
+
+        // NED: up is negative z
+        let altitude = -position.z;
+        if matches!(self.max_altitude, Some(max_altitude) if altitude > max_altitude) {
+            return Ok(Some(Breach::AboveMaxAltitude(altitude)));
+        }
+
+        for (sensor, min_distance) in &self.min_obstacle_distance {
+            let distance = client.get_dist_data(Some(sensor)).await?.distance;
+            if distance < *min_distance {
+                return Ok(Some(Breach::ObstacleTooClose {
+                    sensor: sensor.clone(),
+                    distance,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Background task checking the limits of a `SafetyConfig` and taking over the vehicle when one is breached
+///
+/// Monitoring ends with the first breach, after the action was taken, or when `stop` is awaited or the
+/// monitor is dropped. A failed check is logged and retried at the next poll, only `max_failed_checks` failures
+/// in a row count as a breach.
+pub struct SafetyMonitor {
+    running: Arc<AtomicBool>,
+    task: Option<JoinHandle<NetworkResult<Option<Breach>>>>,
+}
+
+impl SafetyMonitor {
+    /// Start monitoring `client`
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to watch, the monitor shares its connection
+    ///     config (SafetyConfig): Limits, sampling rate and reaction to a breach
+    ///
+    /// # Panics
+    /// If the sampling rate isn't positive
+    pub fn start(client: &MultiRotorClient, config: SafetyConfig) -> Self {
+        let period = periodic::period("safety monitor", config.rate_hz);
+        let running = Arc::new(AtomicBool::new(true));
+        let task = task::spawn(Self::monitor(client.clone(), config, period, Arc::clone(&running)));
+
+        Self {
+            running,
+            task: Some(task),
+        }
+    }
+
+    /// Whether the monitor is still checking the limits
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Stop monitoring
+    ///
+    /// Returns the breach that ended the monitoring, if any, or the error of the action taken on it
+    pub async fn stop(mut self) -> NetworkResult<Option<Breach>> {
+        self.running.store(false, Ordering::SeqCst);
+        match self.task.take() {
+            Some(task) => task.await,
+            None => Ok(None),
+        }
+    }
+
+    async fn monitor(
+        client: MultiRotorClient,
+        config: SafetyConfig,
+        period: Duration,
+        running: Arc<AtomicBool>,
+    ) -> NetworkResult<Option<Breach>> {
+        let mut ticker = Ticker::new(period);
+        let mut failed_checks = 0;
+
+        while running.load(Ordering::SeqCst) {
+            let breach = match config.check(&client).await {
+                Ok(breach) => {
+                    failed_checks = 0;
+                    breach
+                }
+                Err(e) => {
+                    failed_checks += 1;
+                    log::warn!(
+                        "safety check {} of {} for {} failed: {}",
+                        failed_checks,
+                        config.max_failed_checks,
+                        client.vehicle_name(),
+                        e
+                    );
+                    (failed_checks >= config.max_failed_checks).then(|| Breach::ChecksFailed(failed_checks))
+                }
+            };
+
+            if let Some(breach) = breach {
+                running.store(false, Ordering::SeqCst);
+                log::warn!("{} breached a safety limit: {:?}", client.vehicle_name(), breach);
+
+                if let Some(on_breach) = &config.on_breach {
+                    on_breach(&breach);
+                }
+                match config.action {
+                    SafetyAction::Report => {}
+                    SafetyAction::Hover => {
+                        client.hover_async().await?;
+                    }
+                    SafetyAction::ReturnToLaunch(rtl_config) => {
+                        rtl(&client, &rtl_config).await?;
+                    }
+                    SafetyAction::Land(timeout_sec) => {
+                        client.land_async(timeout_sec).await?;
+                    }
+                }
+                return Ok(Some(breach));
+            }
+
+            ticker.tick().await;
+        }
+
+        Ok(None)
+    }
+}
+
+impl Drop for SafetyMonitor {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
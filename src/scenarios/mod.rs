@@ -0,0 +1,21 @@
+//! Standard test flights, built as `Scenario`s which can be inspected, combined and run on any multirotor
+//!
+//! Positions are in meters in the NED frame of the vehicle's starting point, and altitudes are above it.
+//!
+//! ```no_run
+//! use airsim_client::ops::CommandResult;
+//! use airsim_client::scenarios::{box_pattern, figure_eight};
+//! use airsim_client::MultiRotorClient;
+//!
+//! # async fn run(client: &MultiRotorClient) -> CommandResult {
+//! let flight = box_pattern(20.0, 10.0, 4.0).then(figure_eight(15.0, 10.0, 5.0, 32));
+//! flight.run(client).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod patterns;
+mod scenario;
+
+pub use patterns::{box_pattern, figure_eight, survey_grid, SurveyGrid};
+pub use scenario::{Scenario, ScenarioStep};
@@ -0,0 +1,252 @@
+use std::f32::consts::TAU;
+
+use crate::{ImageRequest, ImageRequests, MoveOptions, Path, Position3, Vector3};
+
+use super::{Scenario, ScenarioStep};
+
+/// Timeout of the takeoffs and landings, like in the examples
+const TAKE_OFF_LAND_TIMEOUT_SEC: f32 = 20.0;
+
+/// Take off, fly a square at a constant altitude, back to the start, and land
+///
+/// The square starts north of the starting point and turns clockwise, seen from above
+///
+/// args:
+///     side (f32): Length of the sides in meters
+///     altitude (f32): Altitude above the starting point in meters
+///     velocity (f32): Velocity in m/s
+pub fn box_pattern(side: f32, altitude: f32, velocity: f32) -> Scenario {
+    let z = -altitude;
+    let options = MoveOptions::new().velocity(velocity);
+    let corners = vec![
+        Vector3::new(side, 0.0, z),
+        Vector3::new(side, side, z),
+        Vector3::new(0.0, side, z),
+        Vector3::new(0.0, 0.0, z),
+    ];
+
+    Scenario::new("box pattern")
+        .step(ScenarioStep::TakeOff {
+            timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+        })
+        .step(ScenarioStep::MoveTo {
+            position: Position3::new(0.0, 0.0, z),
+            options,
+        })
+        .step(ScenarioStep::MoveOnPath {
+            path: Path(corners),
+            options,
+        })
+        .step(ScenarioStep::Land {
+            timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+        })
+}
+
+/// Take off, fly a figure eight at a constant altitude centered on the starting point, and land
+///
+/// The eight is a lemniscate of Gerono, with its lobes north and south of the starting point
+///
+/// args:
+///     size (f32): Distance from the center to the tip of each lobe in meters
+///     altitude (f32): Altitude above the starting point in meters
+///     velocity (f32): Velocity in m/s
+///     points (usize): Number of waypoints of the path, more points follow the curve closer
+pub fn figure_eight(size: f32, altitude: f32, velocity: f32, points: usize) -> Scenario {
+    let z = -altitude;
+    let options = MoveOptions::new().velocity(velocity);
+    let points = points.max(4);
+    let path = (1..=points)
+        .map(|i| {
+            let t = TAU * i as f32 / points as f32;
+            Vector3::new(size * t.sin(), size * t.sin() * t.cos(), z)
+        })
+        .collect();
+
+    Scenario::new("figure eight")
+        .step(ScenarioStep::TakeOff {
+            timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+        })
+        .step(ScenarioStep::MoveTo {
+            position: Position3::new(0.0, 0.0, z),
+            options,
+        })
+        .step(ScenarioStep::MoveOnPath {
+            path: Path(path),
+            options,
+        })
+        .step(ScenarioStep::Land {
+            timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+        })
+}
+
+#[derive(Debug, Clone)]
+/// Area covered by `survey_grid` and how the images are captured
+///
+/// Defaults to a 50 m x 50 m area north east of the starting point, 10 m between images, at 20 m and 5 m/s, capturing
+/// scene images of the `bottom_center` camera after hovering for 1 s
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurveyGrid {
+    /// extent of the area to the north in meters
+    pub length: f32,
+    /// extent of the area to the east in meters
+    pub width: f32,
+    /// distance between the lanes, and between the images along a lane, in meters
+    pub spacing: f32,
+    /// altitude above the starting point in meters
+    pub altitude: f32,
+    /// velocity between the images in m/s
+    pub velocity: f32,
+    /// time to hover before each image, for the vehicle to settle, in seconds
+    pub settle_sec: f32,
+    /// images captured at each point of the grid
+    pub requests: ImageRequests,
+}
+
+impl Default for SurveyGrid {
+    fn default() -> Self {
+        Self {
+            length: 50.0,
+            width: 50.0,
+            spacing: 10.0,
+            altitude: 20.0,
+            velocity: 5.0,
+            settle_sec: 1.0,
+            requests: ImageRequests(vec![ImageRequest::scene("bottom_center")]),
+        }
+    }
+}
+
+impl SurveyGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn area(mut self, length: f32, width: f32) -> Self {
+        self.length = length;
+        self.width = width;
+        self
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn altitude(mut self, altitude: f32) -> Self {
+        self.altitude = altitude;
+        self
+    }
+
+    pub fn velocity(mut self, velocity: f32) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn settle_sec(mut self, settle_sec: f32) -> Self {
+        self.settle_sec = settle_sec;
+        self
+    }
+
+    pub fn requests(mut self, requests: ImageRequests) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    /// Points where images are captured, lane by lane in a lawnmower pattern
+    pub fn points(&self) -> Vec<Position3> {
+        if self.spacing.is_nan() || self.spacing <= 0.0 {
+            panic!("survey grid spacing has to be positive, got {} m", self.spacing)
+        }
+        let steps = |extent: f32| (extent.max(0.0) / self.spacing + 1e-3).floor() as usize;
+
+        let mut points = vec![];
+        for lane in 0..=steps(self.width) {
+            let east = lane as f32 * self.spacing;
+            let mut norths: Vec<f32> = (0..=steps(self.length)).map(|i| i as f32 * self.spacing).collect();
+            // every other lane is flown southwards
+            if lane % 2 == 1 {
+                norths.reverse();
+            }
+            points.extend(
+                norths
+                    .into_iter()
+                    .map(|north| Position3::new(north, east, -self.altitude)),
+            );
+        }
+        points
+    }
+}
+
+/// Take off, capture images at every point of a grid, and land, e.g. to build an orthomosaic or a dataset
+///
+/// The images of the returned scenario are returned by `Scenario::run`, in the order of `SurveyGrid::points`
+///
+/// args:
+///     grid (&SurveyGrid): Area to cover and images to capture
+pub fn survey_grid(grid: &SurveyGrid) -> Scenario {
+    let options = MoveOptions::new().velocity(grid.velocity);
+    let mut scenario = Scenario::new("survey grid").step(ScenarioStep::TakeOff {
+        timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+    });
+    for position in grid.points() {
+        scenario = scenario
+            .step(ScenarioStep::MoveTo { position, options })
+            .step(ScenarioStep::Hover {
+                duration_sec: grid.settle_sec,
+            })
+            .step(ScenarioStep::Capture {
+                requests: grid.requests.clone(),
+            });
+    }
+    scenario.step(ScenarioStep::Land {
+        timeout_sec: TAKE_OFF_LAND_TIMEOUT_SEC,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scenarios::{box_pattern, figure_eight, survey_grid, ScenarioStep, SurveyGrid};
+
+    #[test]
+    fn test_survey_grid_lawnmower() {
+        let grid = SurveyGrid::new().area(20.0, 10.0).spacing(10.0).altitude(30.0);
+        let points: Vec<_> = grid.points().iter().map(|p| (p.x, p.y, p.z)).collect();
+        assert_eq!(
+            points,
+            vec![
+                (0.0, 0.0, -30.0),
+                (10.0, 0.0, -30.0),
+                (20.0, 0.0, -30.0),
+                (20.0, 10.0, -30.0),
+                (10.0, 10.0, -30.0),
+                (0.0, 10.0, -30.0),
+            ]
+        );
+
+        let scenario = survey_grid(&grid);
+        let captures = scenario
+            .steps
+            .iter()
+            .filter(|step| matches!(step, ScenarioStep::Capture { .. }))
+            .count();
+        assert_eq!(captures, 6);
+        assert_eq!(scenario.waypoints().len(), 6);
+    }
+
+    #[test]
+    fn test_patterns_close_and_chain() {
+        let eight = figure_eight(10.0, 5.0, 3.0, 16);
+        let last = *eight.waypoints().last().unwrap();
+        assert!(last.x.abs() < 1e-4 && last.y.abs() < 1e-4 && last.z == -5.0);
+
+        let flight = box_pattern(20.0, 5.0, 3.0).then(eight);
+        let takeoffs = flight
+            .steps
+            .iter()
+            .filter(|step| matches!(step, ScenarioStep::TakeOff { .. }))
+            .count();
+        assert_eq!(takeoffs, 1);
+        assert!(matches!(flight.steps.last(), Some(ScenarioStep::Land { .. })));
+        assert_eq!(flight.name, "box pattern then figure eight");
+    }
+}
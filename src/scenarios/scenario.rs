@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use async_std::task;
+
+use crate::ops::{CommandExt, CommandResult};
+use crate::{ImageRequests, ImageResponse, MoveOptions, MultiRotorClient, Path, Position3};
+
+#[derive(Debug, Clone)]
+/// Step of a `Scenario`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScenarioStep {
+    /// Arm the vehicle and take off, with the timeout in seconds
+    TakeOff { timeout_sec: f32 },
+    /// Fly to a position, see `MultiRotorClient::move_to_position_with`
+    MoveTo { position: Position3, options: MoveOptions },
+    /// Fly along a path, see `MultiRotorClient::move_on_path_with`
+    MoveOnPath { path: Path, options: MoveOptions },
+    /// Hover in place for the duration in seconds
+    Hover { duration_sec: f32 },
+    /// Capture images with the cameras of the vehicle
+    Capture { requests: ImageRequests },
+    /// Land and disarm the vehicle, with the timeout in seconds
+    Land { timeout_sec: f32 },
+}
+
+#[derive(Debug, Clone)]
+/// Sequence of steps flown one after the other, see `scenarios` for the standard ones
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario {
+    pub name: String,
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    /// Scenario without steps, add them with `step`
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            steps: vec![],
+        }
+    }
+
+    /// Add a step at the end
+    pub fn step(mut self, step: ScenarioStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Fly `other` once this scenario is done
+    ///
+    /// The landing of this scenario and the takeoff of `other` are dropped when this one ends with a landing and
+    /// `other` starts with a takeoff, so the vehicle flies both in one go
+    pub fn then(mut self, other: Scenario) -> Self {
+        let mut steps = other.steps.into_iter().peekable();
+        if matches!(self.steps.last(), Some(ScenarioStep::Land { .. }))
+            && matches!(steps.peek(), Some(ScenarioStep::TakeOff { .. }))
+        {
+            self.steps.pop();
+            steps.next();
+        }
+        self.steps.extend(steps);
+        self.name = format!("{} then {}", self.name, other.name);
+        self
+    }
+
+    /// Waypoints of the scenario in the order they are flown, e.g. to draw them with `sim_plot_line_strip`
+    pub fn waypoints(&self) -> Vec<Position3> {
+        self.steps
+            .iter()
+            .flat_map(|step| match step {
+                ScenarioStep::MoveTo { position, .. } => vec![*position],
+                ScenarioStep::MoveOnPath { path, .. } => path.0.iter().copied().map(Position3::from).collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    /// Fly the steps in order, stopping at the first one which fails or AirSim rejects, see `CommandError`
+    ///
+    /// Returns the images of the `Capture` steps, in the order they were captured
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to fly, API control has to be enabled
+    pub async fn run(&self, client: &MultiRotorClient) -> CommandResult<Vec<ImageResponse>> {
+        let mut images = vec![];
+        for (i, step) in self.steps.iter().enumerate() {
+            log::debug!("{}: step {} of {}: {:?}", self.name, i + 1, self.steps.len(), step);
+            match step {
+                ScenarioStep::TakeOff { timeout_sec } => {
+                    client.arm_disarm(true).checked().await?;
+                    client.take_off_async(*timeout_sec).checked().await?;
+                }
+                ScenarioStep::MoveTo { position, options } => {
+                    client.move_to_position_with(*position, options).checked().await?;
+                }
+                ScenarioStep::MoveOnPath { path, options } => {
+                    client.move_on_path_with(path.clone(), options).checked().await?;
+                }
+                ScenarioStep::Hover { duration_sec } => {
+                    client.hover_async().checked().await?;
+                    task::sleep(Duration::from_secs_f32(duration_sec.max(0.0))).await;
+                }
+                ScenarioStep::Capture { requests } => {
+                    images.extend(client.sim_get_images(requests.clone(), None).await?);
+                }
+                ScenarioStep::Land { timeout_sec } => {
+                    client.land_async(*timeout_sec).checked().await?;
+                    client.arm_disarm(false).checked().await?;
+                }
+            }
+        }
+        log::info!("{}: done, {} images captured", self.name, images.len());
+        Ok(images)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::ops::CommandError;
+    use crate::scenarios::{Scenario, ScenarioStep};
+    use crate::{MoveOptions, MultiRotorClient, Position3};
+
+    #[test]
+    fn test_run_stops_at_a_rejected_step() {
+        let server = MockAirsimServer::start().unwrap();
+        server.respond("armDisarm", Value::Boolean(true));
+        server.respond("takeoff", Value::Boolean(false));
+        server.respond("moveToPosition", Value::Boolean(true));
+
+        let scenario = Scenario::new("test")
+            .step(ScenarioStep::TakeOff { timeout_sec: 20.0 })
+            .step(ScenarioStep::MoveTo {
+                position: Position3::new(0.0, 0.0, -10.0),
+                options: MoveOptions::new(),
+            });
+        let result = async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            scenario.run(&client).await
+        });
+
+        assert!(matches!(result, Err(CommandError::Rejected)));
+        assert_eq!(server.calls("moveToPosition"), 0);
+    }
+}
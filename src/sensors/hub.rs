@@ -0,0 +1,307 @@
+use futures::future;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+
+use crate::error::NetworkResult;
+use crate::periodic;
+use crate::{
+    BarometerData, DistanceSensorData, GpsData, ImuData, LidarData, MagnetometerData, MultiRotorClient, SensorInfo,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Type of a sensor polled by a `SensorHub`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorKind {
+    Imu,
+    Gps,
+    Barometer,
+    Magnetometer,
+    Distance,
+    Lidar,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Sensor polled by a `SensorHub`, with its polling rate
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorDescriptor {
+    pub kind: SensorKind,
+    /// name of the sensor in settings.json, empty for the first sensor of its kind
+    pub name: String,
+    /// polling rate in Hz
+    pub rate_hz: f32,
+}
+
+impl SensorDescriptor {
+    /// Fails with `NetworkError::InvalidArgument` if the rate isn't positive
+    ///
+    /// args:
+    ///     kind (SensorKind): Type of the sensor
+    ///     name (&str): Name of the sensor in settings.json, empty for the first sensor of its kind
+    ///     rate_hz (f32): Polling rate in Hz
+    pub fn new(kind: SensorKind, name: &str, rate_hz: f32) -> NetworkResult<Self> {
+        periodic::try_period(&format!("sensor {name:?}"), rate_hz)?;
+        Ok(Self::unchecked(kind, name, rate_hz))
+    }
+
+    /// Descriptor added by the builder methods of `SensorHub`, whose stream reports an invalid rate
+    fn unchecked(kind: SensorKind, name: &str, rate_hz: f32) -> Self {
+        Self {
+            kind,
+            name: name.to_owned(),
+            rate_hz,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Reading of one of the sensors of a `SensorHub`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorReading {
+    Imu(ImuData),
+    Gps(GpsData),
+    Barometer(BarometerData),
+    Magnetometer(MagnetometerData),
+    Distance(DistanceSensorData),
+    Lidar(LidarData),
+}
+
+impl SensorReading {
+    /// Simulation time of the reading in nanoseconds
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            SensorReading::Imu(data) => data.timestamp,
+            SensorReading::Gps(data) => data.timestamp,
+            SensorReading::Barometer(data) => data.timestamp,
+            SensorReading::Magnetometer(data) => data.timestamp,
+            SensorReading::Distance(data) => data.timestamp,
+            SensorReading::Lidar(data) => data.time_stamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Reading yielded by the stream of a `SensorHub`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorSample {
+    /// position of the sample in the stream, starting at 0 and increasing by one per sample
+    pub sequence: u64,
+    /// name of the sensor as given to the hub
+    pub name: String,
+    pub reading: SensorReading,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Sensors of a vehicle polled concurrently, each at its own rate, merged into a single stream of samples
+///
+/// A sensor is only yielded when its timestamp changes, so polling faster than the sensor updates in settings.json
+/// doesn't repeat readings. The samples of different sensors are in the order they were received, not sorted by
+/// timestamp. A sensor added with a rate which isn't positive yields a single `NetworkError::InvalidArgument`
+///
+/// ```no_run
+/// use futures::StreamExt;
+///
+/// use airsim_client::sensors::{SensorHub, SensorReading};
+/// use airsim_client::MultiRotorClient;
+///
+/// # async fn run(client: &MultiRotorClient) -> airsim_client::NetworkResult<()> {
+/// let mut samples = SensorHub::new().imu("Imu", 200.0).gps("Gps", 10.0).barometer("Barometer", 50.0).stream(client);
+/// while let Some(sample) = samples.next().await {
+///     if let SensorReading::Imu(imu) = sample?.reading {
+///         println!("{:?}", imu.angular_velocity);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorHub {
+    sensors: Vec<SensorDescriptor>,
+}
+
+impl SensorHub {
+    /// Hub without sensors, add them with the builder methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hub polling the enabled sensors of a vehicle at `rate_hz`, e.g. the ones of `MultiRotorClient::sim_list_sensors`
+    ///
+    /// args:
+    ///     sensors (&[SensorInfo]): Sensors of the vehicle, the disabled ones are skipped
+    ///     rate_hz (f32): Polling rate in Hz of every sensor
+    pub fn from_sensors(sensors: &[SensorInfo], rate_hz: f32) -> Self {
+        sensors
+            .iter()
+            .filter(|sensor| sensor.enabled)
+            .fold(Self::new(), |hub, sensor| {
+                hub.sensor(SensorDescriptor::unchecked(sensor.kind, &sensor.name, rate_hz))
+            })
+    }
+
+    /// Add a sensor described by `descriptor`
+    pub fn sensor(mut self, descriptor: SensorDescriptor) -> Self {
+        self.sensors.push(descriptor);
+        self
+    }
+
+    /// Add an IMU polled at `rate_hz`
+    pub fn imu(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Imu, name, rate_hz))
+    }
+
+    /// Add a GPS polled at `rate_hz`
+    pub fn gps(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Gps, name, rate_hz))
+    }
+
+    /// Add a barometer polled at `rate_hz`
+    pub fn barometer(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Barometer, name, rate_hz))
+    }
+
+    /// Add a magnetometer polled at `rate_hz`
+    pub fn magnetometer(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Magnetometer, name, rate_hz))
+    }
+
+    /// Add a distance sensor polled at `rate_hz`
+    pub fn distance(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Distance, name, rate_hz))
+    }
+
+    /// Add a lidar polled at `rate_hz`
+    pub fn lidar(self, name: &str, rate_hz: f32) -> Self {
+        self.sensor(SensorDescriptor::unchecked(SensorKind::Lidar, name, rate_hz))
+    }
+
+    /// Sensors of the hub, in the order they were added
+    pub fn sensors(&self) -> &[SensorDescriptor] {
+        &self.sensors
+    }
+
+    /// Poll the sensors of `client` until the stream is dropped
+    ///
+    /// Errors are yielded and polling goes on, an error doesn't take a sequence number. A sensor with an invalid rate
+    /// yields its error once and isn't polled
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle carrying the sensors
+    pub fn stream(&self, client: &MultiRotorClient) -> impl Stream<Item = NetworkResult<SensorSample>> {
+        let polls = self
+            .sensors
+            .iter()
+            .map(|descriptor| poll(client.clone(), descriptor.clone()));
+
+        let mut sequence = 0;
+        stream::select_all(polls).map(move |reading| {
+            reading.map(|(name, reading)| {
+                sequence += 1;
+                SensorSample {
+                    sequence: sequence - 1,
+                    name,
+                    reading,
+                }
+            })
+        })
+    }
+}
+
+/// Readings of one sensor, on a fixed grid of `rate_hz` and skipping the readings already yielded
+fn poll(
+    client: MultiRotorClient,
+    descriptor: SensorDescriptor,
+) -> BoxStream<'static, NetworkResult<(String, SensorReading)>> {
+    let period = match periodic::try_period(&format!("sensor {:?}", descriptor.name), descriptor.rate_hz) {
+        Ok(period) => period,
+        Err(e) => return stream::once(future::ready(Err(e))).boxed(),
+    };
+    let mut last = None;
+
+    periodic::sample_stream(period, move || {
+        let (client, descriptor) = (client.clone(), descriptor.clone());
+        async move {
+            read(&client, &descriptor)
+                .await
+                .map(|reading| (descriptor.name, reading))
+        }
+    })
+    .filter(move |reading| {
+        let new = match reading {
+            Ok((_, reading)) => last.replace(reading.timestamp()) != Some(reading.timestamp()),
+            Err(_) => true,
+        };
+        future::ready(new)
+    })
+    .boxed()
+}
+
+async fn read(client: &MultiRotorClient, descriptor: &SensorDescriptor) -> NetworkResult<SensorReading> {
+    let name = Some(descriptor.name.as_str());
+    Ok(match descriptor.kind {
+        SensorKind::Imu => SensorReading::Imu(client.get_imu_data(name).await?),
+        SensorKind::Gps => SensorReading::Gps(client.get_gnss_data(name).await?),
+        SensorKind::Barometer => SensorReading::Barometer(client.get_barometer_data(name).await?),
+        SensorKind::Magnetometer => SensorReading::Magnetometer(client.get_magnetometer_data(name).await?),
+        SensorKind::Distance => SensorReading::Distance(client.get_dist_data(name).await?),
+        SensorKind::Lidar => SensorReading::Lidar(client.get_lidar_data(name).await?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::mock::{MockAirsimServer, Value};
+    use crate::sensors::{SensorDescriptor, SensorHub, SensorKind, SensorReading};
+    use crate::{MultiRotorClient, NetworkError};
+
+    #[test]
+    fn test_yields_new_readings_in_sequence() {
+        let server = MockAirsimServer::start().unwrap();
+        let mut calls = 0;
+        server.respond_with("getBarometerData", move |_| {
+            // the barometer updates every other poll
+            calls += 1;
+            Ok(Value::Map(vec![
+                (Value::from("time_stamp"), Value::from(calls / 2)),
+                (Value::from("altitude"), Value::F32(120.0)),
+                (Value::from("pressure"), Value::F32(99_900.0)),
+                (Value::from("qnh"), Value::F32(1013.25)),
+            ]))
+        });
+
+        let samples: Vec<_> = async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            let stream = SensorHub::new().barometer("Barometer", 200.0).stream(&client);
+            stream.take(3).map(Result::unwrap).collect().await
+        });
+
+        let timestamps: Vec<_> = samples.iter().map(|sample| sample.reading.timestamp()).collect();
+        assert_eq!(timestamps, vec![0, 1, 2]);
+        assert_eq!(samples[2].sequence, 2);
+        assert_eq!(samples[2].name, "Barometer");
+        assert!(matches!(samples[0].reading, SensorReading::Barometer(_)));
+        assert!(server.calls("getBarometerData") >= 4);
+    }
+
+    #[test]
+    fn test_invalid_rate_is_an_error() {
+        assert!(matches!(
+            SensorDescriptor::new(SensorKind::Imu, "Imu", 0.0),
+            Err(NetworkError::InvalidArgument { .. })
+        ));
+
+        let server = MockAirsimServer::start().unwrap();
+        let results: Vec<_> = async_std::task::block_on(async {
+            let client = MultiRotorClient::connect(&server.addr().to_string(), "Drone1")
+                .await
+                .unwrap();
+            SensorHub::new().imu("Imu", 0.0).stream(&client).collect().await
+        });
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(NetworkError::InvalidArgument { .. })));
+        assert_eq!(server.calls("getImuData"), 0);
+    }
+}
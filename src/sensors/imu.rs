@@ -0,0 +1,324 @@
+//! Preintegration of IMU readings between two keyframes, e.g. camera frames, for visual-inertial odometry
+//!
+//! The deltas follow the on-manifold preintegration of Forster et al. used by GTSAM, OKVIS and VINS: they are
+//! expressed in the body frame of the first reading of the interval and don't depend on the pose, velocity or
+//! gravity, which the backend adds. AirSim IMUs measure the specific force, so a vehicle at rest in NED reads about
+//! (0, 0, -9.81) m/s^2.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//!
+//! use airsim_client::sensors::imu::{ImuBias, Preintegrator};
+//! use airsim_client::sensors::{SensorHub, SensorReading};
+//! use airsim_client::MultiRotorClient;
+//!
+//! # async fn run(client: &MultiRotorClient) -> airsim_client::NetworkResult<()> {
+//! let mut preintegrator = Preintegrator::new(ImuBias::default());
+//! let mut samples = SensorHub::new().imu("Imu", 200.0).stream(client);
+//! while let Some(sample) = samples.next().await {
+//!     if let SensorReading::Imu(imu) = sample?.reading {
+//!         preintegrator.integrate(&imu);
+//!     }
+//!     if preintegrator.delta().delta_time >= 0.1 {
+//!         // new keyframe: hand the interval to the backend
+//!         let delta = preintegrator.take();
+//!         println!("{:?} {:?}", delta.delta_velocity, delta.delta_position);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use nalgebra::{Matrix3, UnitQuaternion};
+
+use crate::{ImuData, Quaternionr, Vector3};
+
+type Vec3 = nalgebra::Vector3<f32>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Biases subtracted from the IMU readings before they are integrated
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImuBias {
+    /// bias of the angular velocity in rad/s
+    pub gyroscope: Vector3,
+    /// bias of the linear acceleration in m/s^2
+    pub accelerometer: Vector3,
+}
+
+impl Default for ImuBias {
+    fn default() -> Self {
+        Self::new(Vector3::zeros(), Vector3::zeros())
+    }
+}
+
+impl ImuBias {
+    /// args:
+    ///     gyroscope (Vector3): Bias of the angular velocity in rad/s
+    ///     accelerometer (Vector3): Bias of the linear acceleration in m/s^2
+    pub fn new(gyroscope: Vector3, accelerometer: Vector3) -> Self {
+        Self {
+            gyroscope,
+            accelerometer,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Jacobians of the deltas of a `PreintegratedImu` with respect to the biases, to correct the deltas for a new bias
+/// estimate without integrating the readings again
+pub struct BiasJacobians {
+    /// rotation, as a tangent vector, with respect to the gyroscope bias
+    pub rotation_gyroscope: Matrix3<f32>,
+    /// velocity with respect to the gyroscope bias
+    pub velocity_gyroscope: Matrix3<f32>,
+    /// velocity with respect to the accelerometer bias
+    pub velocity_accelerometer: Matrix3<f32>,
+    /// position with respect to the gyroscope bias
+    pub position_gyroscope: Matrix3<f32>,
+    /// position with respect to the accelerometer bias
+    pub position_accelerometer: Matrix3<f32>,
+}
+
+impl Default for BiasJacobians {
+    fn default() -> Self {
+        Self {
+            rotation_gyroscope: Matrix3::zeros(),
+            velocity_gyroscope: Matrix3::zeros(),
+            velocity_accelerometer: Matrix3::zeros(),
+            position_gyroscope: Matrix3::zeros(),
+            position_accelerometer: Matrix3::zeros(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Motion of the body between the first and the last reading of an interval, in the body frame of the first one
+pub struct PreintegratedImu {
+    /// timestamp of the first reading in nanoseconds
+    pub start: u64,
+    /// timestamp of the last reading in nanoseconds
+    pub end: u64,
+    /// duration of the interval in seconds
+    pub delta_time: f32,
+    /// rotation from the body frame at `end` to the body frame at `start`
+    pub delta_rotation: Quaternionr,
+    /// change of velocity in m/s, gravity excluded
+    pub delta_velocity: Vector3,
+    /// change of position in m, gravity excluded
+    pub delta_position: Vector3,
+    /// biases the readings were integrated with
+    pub bias: ImuBias,
+    pub jacobians: BiasJacobians,
+}
+
+impl PreintegratedImu {
+    /// Deltas for the biases `bias`, to first order from the biases they were integrated with
+    ///
+    /// args:
+    ///     bias (ImuBias): New estimate of the biases
+    pub fn corrected(&self, bias: ImuBias) -> Self {
+        let gyroscope = Vec3::from(bias.gyroscope - self.bias.gyroscope);
+        let accelerometer = Vec3::from(bias.accelerometer - self.bias.accelerometer);
+        let j = &self.jacobians;
+
+        let rotation = unit(self.delta_rotation) * UnitQuaternion::from_scaled_axis(j.rotation_gyroscope * gyroscope);
+        let velocity = Vec3::from(self.delta_velocity)
+            + j.velocity_gyroscope * gyroscope
+            + j.velocity_accelerometer * accelerometer;
+        let position = Vec3::from(self.delta_position)
+            + j.position_gyroscope * gyroscope
+            + j.position_accelerometer * accelerometer;
+
+        Self {
+            delta_rotation: Quaternionr(rotation.into_inner()),
+            delta_velocity: velocity.into(),
+            delta_position: position.into(),
+            bias,
+            ..*self
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Accumulates IMU readings into a `PreintegratedImu` until `take` starts a new interval
+///
+/// Each reading is held until the next one, the interval between two readings is integrated with the first of them.
+/// Readings not newer than the last one are ignored, so a stream polled faster than the IMU updates can be fed as is.
+pub struct Preintegrator {
+    bias: ImuBias,
+    last: Option<(u64, Vec3, Vec3)>,
+    delta: PreintegratedImu,
+}
+
+impl Preintegrator {
+    /// args:
+    ///     bias (ImuBias): Biases subtracted from the readings
+    pub fn new(bias: ImuBias) -> Self {
+        Self {
+            bias,
+            last: None,
+            delta: Self::empty(0, bias),
+        }
+    }
+
+    /// Biases subtracted from the readings
+    pub fn bias(&self) -> ImuBias {
+        self.bias
+    }
+
+    /// Change the biases from the next interval on, use `PreintegratedImu::corrected` for the current one
+    ///
+    /// args:
+    ///     bias (ImuBias): Biases subtracted from the readings
+    pub fn set_bias(&mut self, bias: ImuBias) {
+        self.bias = bias;
+    }
+
+    /// Add a reading to the current interval
+    ///
+    /// args:
+    ///     imu (&ImuData): Reading of the IMU
+    pub fn integrate(&mut self, imu: &ImuData) {
+        let reading = (
+            imu.timestamp,
+            imu.angular_velocity.into(),
+            imu.linear_acceleration.into(),
+        );
+        let (timestamp, angular_velocity, linear_acceleration) = match self.last.replace(reading) {
+            Some(last) if imu.timestamp > last.0 => last,
+            Some(last) => {
+                self.last = Some(last);
+                return;
+            }
+            None => {
+                self.delta = Self::empty(imu.timestamp, self.bias);
+                return;
+            }
+        };
+
+        let dt = (imu.timestamp - timestamp) as f32 * 1e-9;
+        let omega = angular_velocity - Vec3::from(self.delta.bias.gyroscope);
+        let acceleration = linear_acceleration - Vec3::from(self.delta.bias.accelerometer);
+
+        let rotation = unit(self.delta.delta_rotation);
+        let rotation_matrix = rotation.to_rotation_matrix().into_inner();
+        let step = UnitQuaternion::from_scaled_axis(omega * dt);
+        let skew_acceleration = acceleration.cross_matrix();
+
+        // the jacobians of velocity and position use the rotation before this step
+        let j = &mut self.delta.jacobians;
+        j.position_accelerometer += j.velocity_accelerometer * dt - 0.5 * rotation_matrix * dt * dt;
+        j.position_gyroscope +=
+            j.velocity_gyroscope * dt - 0.5 * rotation_matrix * skew_acceleration * j.rotation_gyroscope * dt * dt;
+        j.velocity_accelerometer -= rotation_matrix * dt;
+        j.velocity_gyroscope -= rotation_matrix * skew_acceleration * j.rotation_gyroscope * dt;
+        j.rotation_gyroscope =
+            step.to_rotation_matrix().into_inner().transpose() * j.rotation_gyroscope - right_jacobian(omega * dt) * dt;
+
+        let velocity = Vec3::from(self.delta.delta_velocity);
+        let position =
+            Vec3::from(self.delta.delta_position) + velocity * dt + 0.5 * (rotation * acceleration) * dt * dt;
+        self.delta.delta_position = position.into();
+        self.delta.delta_velocity = (velocity + (rotation * acceleration) * dt).into();
+        self.delta.delta_rotation = Quaternionr((rotation * step).into_inner());
+        self.delta.delta_time += dt;
+        self.delta.end = imu.timestamp;
+    }
+
+    /// Deltas of the current interval
+    pub fn delta(&self) -> &PreintegratedImu {
+        &self.delta
+    }
+
+    /// Deltas of the current interval, and start the next one at its last reading
+    pub fn take(&mut self) -> PreintegratedImu {
+        let start = self.delta.end;
+        std::mem::replace(&mut self.delta, Self::empty(start, self.bias))
+    }
+
+    fn empty(start: u64, bias: ImuBias) -> PreintegratedImu {
+        PreintegratedImu {
+            start,
+            end: start,
+            delta_time: 0.0,
+            delta_rotation: Quaternionr::default(),
+            delta_velocity: Vector3::zeros(),
+            delta_position: Vector3::zeros(),
+            bias,
+            jacobians: BiasJacobians::default(),
+        }
+    }
+}
+
+fn unit(q: Quaternionr) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_quaternion(q.0)
+}
+
+/// Right jacobian of SO(3) at the tangent vector `phi`
+fn right_jacobian(phi: Vec3) -> Matrix3<f32> {
+    let theta = phi.norm();
+    let skew = phi.cross_matrix();
+    if theta < 1e-4 {
+        return Matrix3::identity() - 0.5 * skew;
+    }
+    Matrix3::identity() - (1.0 - theta.cos()) / (theta * theta) * skew
+        + (theta - theta.sin()) / (theta * theta * theta) * skew * skew
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sensors::imu::{ImuBias, Preintegrator};
+    use crate::{ImuData, Quaternionr, Vector3};
+
+    fn reading(step: u64, angular_velocity: Vector3, linear_acceleration: Vector3) -> ImuData {
+        ImuData {
+            timestamp: step * 10_000_000,
+            orientation: Quaternionr::default(),
+            angular_velocity,
+            linear_acceleration,
+        }
+    }
+
+    #[test]
+    fn test_constant_motion() {
+        let mut preintegrator = Preintegrator::new(ImuBias::default());
+        for step in 0..=100 {
+            preintegrator.integrate(&reading(step, Vector3::new(0.0, 0.0, 0.5), Vector3::zeros()));
+        }
+        let delta = preintegrator.take();
+        assert!((delta.delta_time - 1.0).abs() < 1e-4);
+        assert!((delta.delta_rotation.to_euler_angles().2 - 0.5).abs() < 1e-4);
+        assert_eq!(preintegrator.delta().start, delta.end);
+
+        // a repeated reading is ignored
+        let mut preintegrator = Preintegrator::new(ImuBias::default());
+        for step in [0, 0, 50, 100] {
+            preintegrator.integrate(&reading(step, Vector3::zeros(), Vector3::new(2.0, 0.0, 0.0)));
+        }
+        let delta = preintegrator.delta();
+        assert_eq!(delta.end - delta.start, 1_000_000_000);
+        assert!((delta.delta_velocity.x - 2.0).abs() < 1e-4);
+        assert!((delta.delta_position.x - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_bias_correction_matches_integration() {
+        let bias = ImuBias::new(Vector3::new(0.01, -0.02, 0.0), Vector3::new(0.1, 0.0, -0.05));
+        let mut biased = Preintegrator::new(bias);
+        let mut unbiased = Preintegrator::new(ImuBias::default());
+        for step in 0..=50 {
+            let t = step as f32 * 0.01;
+            let imu = reading(step, Vector3::new(0.3, 0.1 * t, -0.2), Vector3::new(1.0, t, -9.81));
+            biased.integrate(&imu);
+            unbiased.integrate(&imu);
+        }
+
+        let corrected = unbiased.delta().corrected(bias);
+        let expected = biased.delta();
+        assert!((corrected.delta_velocity - expected.delta_velocity).norm() < 1e-3);
+        assert!((corrected.delta_position - expected.delta_position).norm() < 1e-3);
+        let (roll, pitch, yaw) = corrected.delta_rotation.to_euler_angles();
+        let (roll_expected, pitch_expected, yaw_expected) = expected.delta_rotation.to_euler_angles();
+        assert!((roll - roll_expected).abs() + (pitch - pitch_expected).abs() + (yaw - yaw_expected).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,4 @@
+mod hub;
+pub mod imu;
+
+pub use hub::{SensorDescriptor, SensorHub, SensorKind, SensorReading, SensorSample};
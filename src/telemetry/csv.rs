@@ -0,0 +1,64 @@
+use std::io::{self, Write};
+
+use super::recorder::TelemetrySink;
+use super::row::{TelemetryRow, VALUE_COLUMNS};
+
+/// Writes telemetry rows as comma separated values, one line per row
+pub(crate) struct CsvSink<W: Write> {
+    writer: io::BufWriter<W>,
+}
+
+impl<W: Write> CsvSink<W> {
+    /// Create the sink and write the header line
+    pub(crate) fn new(writer: W) -> io::Result<Self> {
+        let mut writer = io::BufWriter::new(writer);
+        writeln!(writer, "timestamp,{},gps_valid", VALUE_COLUMNS.join(","))?;
+        Ok(Self { writer })
+    }
+}
+
+impl<W: Write + Send> TelemetrySink for CsvSink<W> {
+    fn write_row(&mut self, row: &TelemetryRow) -> io::Result<()> {
+        write!(self.writer, "{}", row.timestamp)?;
+        for value in row.values {
+            write!(self.writer, ",{value}")?;
+        }
+        writeln!(self.writer, ",{}", row.gps_valid)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::telemetry::csv::CsvSink;
+    use crate::telemetry::recorder::TelemetrySink;
+    use crate::telemetry::row::{TelemetryRow, VALUE_COLUMNS};
+
+    #[test]
+    fn test_csv_header_and_row() {
+        let mut out = vec![];
+        {
+            let mut sink = CsvSink::new(&mut out).unwrap();
+            let mut values = [0.0; 27];
+            values[0] = 1.5;
+            sink.write_row(&TelemetryRow {
+                timestamp: 42,
+                values,
+                gps_valid: true,
+            })
+            .unwrap();
+            sink.flush().unwrap();
+        }
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].split(',').count(), VALUE_COLUMNS.len() + 2);
+        assert_eq!(lines[1].split(',').count(), VALUE_COLUMNS.len() + 2);
+        assert!(lines[1].starts_with("42,1.5,0,"));
+        assert!(lines[1].ends_with(",true"));
+    }
+}
@@ -0,0 +1,5 @@
+pub(crate) mod csv;
+#[cfg(feature = "parquet")]
+pub(crate) mod parquet;
+pub(crate) mod recorder;
+pub(crate) mod row;
@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::io;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float32Array, RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+
+use super::recorder::TelemetrySink;
+use super::row::{TelemetryRow, VALUE_COLUMNS};
+
+/// Writes telemetry rows as a Parquet file, one row group per flush
+///
+/// Rows are buffered in memory until flushed, the file footer is written when the sink is dropped
+pub(crate) struct ParquetSink {
+    writer: Option<ArrowWriter<File>>,
+    schema: SchemaRef,
+    rows: Vec<TelemetryRow>,
+}
+
+/// Rows buffered before they are written as a row group
+const ROW_GROUP_SIZE: usize = 1024;
+
+impl ParquetSink {
+    pub(crate) fn new(file: File) -> io::Result<Self> {
+        let mut fields = vec![Field::new("timestamp", DataType::UInt64, false)];
+        fields.extend(
+            VALUE_COLUMNS
+                .iter()
+                .map(|name| Field::new(*name, DataType::Float32, false)),
+        );
+        fields.push(Field::new("gps_valid", DataType::Boolean, false));
+        let schema: SchemaRef = Arc::new(Schema::new(fields));
+
+        let writer = ArrowWriter::try_new(file, Arc::clone(&schema), None).map_err(to_io_error)?;
+
+        Ok(Self {
+            writer: Some(writer),
+            schema,
+            rows: vec![],
+        })
+    }
+
+    fn write_buffered(&mut self) -> io::Result<()> {
+        let writer = match (self.rows.is_empty(), self.writer.as_mut()) {
+            (false, Some(writer)) => writer,
+            _ => return Ok(()),
+        };
+
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(UInt64Array::from_iter_values(
+            self.rows.iter().map(|row| row.timestamp),
+        ))];
+        for i in 0..VALUE_COLUMNS.len() {
+            columns.push(Arc::new(Float32Array::from_iter_values(
+                self.rows.iter().map(|row| row.values[i]),
+            )));
+        }
+        columns.push(Arc::new(BooleanArray::from(
+            self.rows.iter().map(|row| row.gps_valid).collect::<Vec<bool>>(),
+        )));
+
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), columns).map_err(to_io_error)?;
+        writer.write(&batch).map_err(to_io_error)?;
+        writer.flush().map_err(to_io_error)?;
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+impl TelemetrySink for ParquetSink {
+    fn write_row(&mut self, row: &TelemetryRow) -> io::Result<()> {
+        self.rows.push(row.clone());
+        if self.rows.len() >= ROW_GROUP_SIZE {
+            self.write_buffered()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_buffered()
+    }
+}
+
+impl Drop for ParquetSink {
+    fn drop(&mut self) {
+        let _ = self.write_buffered();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.close();
+        }
+    }
+}
+
+fn to_io_error(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
@@ -0,0 +1,152 @@
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::task::{self, JoinHandle};
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::MultiRotorClient;
+
+use super::csv::CsvSink;
+use super::row::TelemetryRow;
+
+/// Destination of the rows sampled by the `TelemetryRecorder`
+pub(crate) trait TelemetrySink: Send {
+    fn write_row(&mut self, row: &TelemetryRow) -> io::Result<()>;
+
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+type SharedSink = Arc<Mutex<Box<dyn TelemetrySink>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// File format written by the `TelemetryRecorder`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TelemetryFormat {
+    Csv,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+#[derive(Debug, Clone)]
+/// Settings of the `TelemetryRecorder`
+///
+/// Defaults to sampling at 50 Hz into a CSV file
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TelemetryConfig {
+    /// File the telemetry is written to, truncated if it exists
+    pub path: PathBuf,
+    /// Sampling rate in Hz. The actual rate is lower if the simulator can't answer the queries in time
+    pub rate_hz: f32,
+    /// File format to write
+    pub format: TelemetryFormat,
+}
+
+impl TelemetryConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            rate_hz: 50.0,
+            format: TelemetryFormat::Csv,
+        }
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+
+    pub fn format(mut self, format: TelemetryFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// Background task sampling the multirotor state, IMU, GPS and barometer of a vehicle into a file
+///
+/// Every sample is one row holding the timestamp of the multirotor state, the estimated kinematics,
+/// the IMU angular velocity and linear acceleration, the GPS position and validity and the barometer
+/// altitude and pressure. Sampling runs until `stop` is awaited or the recorder is dropped, buffered
+/// rows are flushed in both cases.
+pub struct TelemetryRecorder {
+    running: Arc<AtomicBool>,
+    sink: SharedSink,
+    task: Option<JoinHandle<NetworkResult<()>>>,
+}
+
+impl TelemetryRecorder {
+    /// Create the telemetry file and start sampling `client`
+    ///
+    /// Fails with `NetworkError::InvalidArgument` if the sampling rate isn't positive
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to record, the recorder shares its connection
+    ///     config (TelemetryConfig): Output file, sampling rate and file format
+    pub fn start(client: &MultiRotorClient, config: TelemetryConfig) -> NetworkResult<Self> {
+        let period = periodic::try_period("telemetry", config.rate_hz)?;
+        let file = File::create(&config.path)?;
+        let sink: Box<dyn TelemetrySink> = match config.format {
+            TelemetryFormat::Csv => Box::new(CsvSink::new(file)?),
+            #[cfg(feature = "parquet")]
+            TelemetryFormat::Parquet => Box::new(super::parquet::ParquetSink::new(file)?),
+        };
+        let sink = Arc::new(Mutex::new(sink));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let task = task::spawn(Self::record(
+            client.clone(),
+            period,
+            Arc::clone(&running),
+            Arc::clone(&sink),
+        ));
+
+        Ok(Self {
+            running,
+            sink,
+            task: Some(task),
+        })
+    }
+
+    /// Stop sampling and wait for the last row to be written
+    ///
+    /// Returns the first error the background task ran into, which also ends the recording
+    pub async fn stop(mut self) -> NetworkResult<()> {
+        self.running.store(false, Ordering::SeqCst);
+        match self.task.take() {
+            Some(task) => task.await,
+            None => Ok(()),
+        }
+    }
+
+    async fn record(
+        client: MultiRotorClient,
+        period: Duration,
+        running: Arc<AtomicBool>,
+        sink: SharedSink,
+    ) -> NetworkResult<()> {
+        let mut ticker = Ticker::new(period);
+
+        while running.load(Ordering::SeqCst) {
+            let row = TelemetryRow::sample(&client).await?;
+            sink.lock().unwrap().write_row(&row)?;
+
+            ticker.tick().await;
+        }
+
+        sink.lock().unwrap().flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for TelemetryRecorder {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Ok(mut sink) = self.sink.lock() {
+            let _ = sink.flush();
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::error::NetworkResult;
+use crate::MultiRotorClient;
+
+/// Names of the `f32` columns of a telemetry row, in the order of `TelemetryRow::values`
+pub(crate) const VALUE_COLUMNS: [&str; 27] = [
+    "position_x",
+    "position_y",
+    "position_z",
+    "orientation_w",
+    "orientation_x",
+    "orientation_y",
+    "orientation_z",
+    "linear_velocity_x",
+    "linear_velocity_y",
+    "linear_velocity_z",
+    "angular_velocity_x",
+    "angular_velocity_y",
+    "angular_velocity_z",
+    "linear_acceleration_x",
+    "linear_acceleration_y",
+    "linear_acceleration_z",
+    "imu_angular_velocity_x",
+    "imu_angular_velocity_y",
+    "imu_angular_velocity_z",
+    "imu_linear_acceleration_x",
+    "imu_linear_acceleration_y",
+    "imu_linear_acceleration_z",
+    "gps_latitude",
+    "gps_longitude",
+    "gps_altitude",
+    "barometer_altitude",
+    "barometer_pressure",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+/// One sample of the multirotor state, IMU, GPS and barometer
+pub(crate) struct TelemetryRow {
+    /// timestamp of the multirotor state, in nanoseconds
+    pub timestamp: u64,
+    /// values of the `VALUE_COLUMNS`
+    pub values: [f32; 27],
+    /// whether the GPS reading is valid
+    pub gps_valid: bool,
+}
+
+impl TelemetryRow {
+    /// Query the state and sensors of the vehicle concurrently
+    pub(crate) async fn sample(client: &MultiRotorClient) -> NetworkResult<Self> {
+        let (state, imu, gps, barometer) = futures::join!(
+            client.get_multirotor_state(),
+            client.get_imu_data(None),
+            client.get_gnss_data(None),
+            client.get_barometer_data(None),
+        );
+        let (state, imu, gps, barometer) = (state?, imu?, gps?, barometer?);
+
+        let kinematics = state.kinematics_estimated;
+        let geo_point = gps.gnss_report.geo_point;
+
+        Ok(Self {
+            timestamp: state.timestamp,
+            values: [
+                kinematics.position.x,
+                kinematics.position.y,
+                kinematics.position.z,
+                kinematics.orientation.w,
+                kinematics.orientation.x,
+                kinematics.orientation.y,
+                kinematics.orientation.z,
+                kinematics.linear_velocity.x,
+                kinematics.linear_velocity.y,
+                kinematics.linear_velocity.z,
+                kinematics.angular_velocity.x,
+                kinematics.angular_velocity.y,
+                kinematics.angular_velocity.z,
+                kinematics.linear_acceleration.x,
+                kinematics.linear_acceleration.y,
+                kinematics.linear_acceleration.z,
+                imu.angular_velocity.x,
+                imu.angular_velocity.y,
+                imu.angular_velocity.z,
+                imu.linear_acceleration.x,
+                imu.linear_acceleration.y,
+                imu.linear_acceleration.z,
+                geo_point.latitude,
+                geo_point.longitude,
+                geo_point.altitude,
+                barometer.altitude,
+                barometer.pressure,
+            ],
+            gps_valid: gps.is_valid,
+        })
+    }
+}
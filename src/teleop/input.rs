@@ -0,0 +1,36 @@
+use std::io;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+/// Sticks of a manual pilot, every axis in [-1, 1] of the limits of the `TeleopConfig`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeleopInput {
+    /// forward (positive) or backward along the front of the vehicle
+    pub forward: f32,
+    /// right (positive) or left
+    pub right: f32,
+    /// down (positive) or up
+    pub down: f32,
+    /// turn clockwise (positive) or counterclockwise, seen from above
+    pub yaw: f32,
+}
+
+impl TeleopInput {
+    /// Every axis limited to [-1, 1], NaN read as 0
+    pub fn clamped(&self) -> Self {
+        let clamp = |axis: f32| if axis.is_nan() { 0.0 } else { axis.clamp(-1.0, 1.0) };
+        Self {
+            forward: clamp(self.forward),
+            right: clamp(self.right),
+            down: clamp(self.down),
+            yaw: clamp(self.yaw),
+        }
+    }
+}
+
+/// Source of the pilot's input, e.g. a keyboard, a gamepad or a network stream
+pub trait InputProvider: Send {
+    /// Latest input, called once per command. Must not block for longer than a period of the `Teleop`
+    ///
+    /// Returns None once the pilot asked to stop
+    fn poll(&mut self) -> io::Result<Option<TeleopInput>>;
+}
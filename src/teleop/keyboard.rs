@@ -0,0 +1,74 @@
+use std::io;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+
+use super::{InputProvider, TeleopInput};
+
+/// Keyboard of the terminal, read with crossterm
+///
+/// Terminals don't report released keys, so every press moves an axis by `step` and the input holds until
+/// changed: `w`/`s` forward and backward, `a`/`d` left and right, `r`/`f` up and down, `q`/`e` yaw, space to
+/// center every axis and `Esc` or `Ctrl-C` to stop. The terminal is in raw mode until the input is dropped
+pub struct KeyboardInput {
+    input: TeleopInput,
+    step: f32,
+}
+
+impl KeyboardInput {
+    /// Switch the terminal to raw mode and start reading the keyboard
+    ///
+    /// args:
+    ///     step (f32): change of an axis per key press, e.g. 0.25 for 4 presses to full speed
+    pub fn new(step: f32) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self {
+            input: TeleopInput::default(),
+            step,
+        })
+    }
+
+    /// Apply a key press, false if the pilot asked to stop
+    fn press(&mut self, key: KeyEvent) -> bool {
+        let step = self.step;
+        let input = &mut self.input;
+        match key.code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('w') => input.forward += step,
+            KeyCode::Char('s') => input.forward -= step,
+            KeyCode::Char('d') => input.right += step,
+            KeyCode::Char('a') => input.right -= step,
+            KeyCode::Char('f') => input.down += step,
+            KeyCode::Char('r') => input.down -= step,
+            KeyCode::Char('e') => input.yaw += step,
+            KeyCode::Char('q') => input.yaw -= step,
+            KeyCode::Char(' ') => *input = TeleopInput::default(),
+            _ => {}
+        }
+        *input = input.clamped();
+        true
+    }
+}
+
+impl InputProvider for KeyboardInput {
+    fn poll(&mut self) -> io::Result<Option<TeleopInput>> {
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Release && !self.press(key) {
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(Some(self.input))
+    }
+}
+
+impl Drop for KeyboardInput {
+    fn drop(&mut self) {
+        if let Err(e) = terminal::disable_raw_mode() {
+            log::warn!("failed to restore the terminal: {e}");
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod input;
+#[cfg(feature = "keyboard")]
+mod keyboard;
+mod pilot;
+
+pub use input::{InputProvider, TeleopInput};
+#[cfg(feature = "keyboard")]
+pub use keyboard::KeyboardInput;
+pub use pilot::{Teleop, TeleopConfig};
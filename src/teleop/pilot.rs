@@ -0,0 +1,188 @@
+use std::time::{Duration, Instant};
+
+use async_std::future;
+
+use crate::error::NetworkResult;
+use crate::periodic::{self, Ticker};
+use crate::{DrivetrainType, MultiRotorClient, Velocity3, YawMode};
+
+use super::{InputProvider, TeleopInput};
+
+#[derive(Debug, Clone, Copy)]
+/// Limits of a manually piloted vehicle
+///
+/// Defaults to 20 Hz, 5 m/s horizontally, 2 m/s vertically, 45 °/s of yaw and 2 m/s² of acceleration
+pub struct TeleopConfig {
+    /// rate of the velocity commands in Hz
+    pub rate_hz: f32,
+    /// horizontal speed at full stick, in m/s
+    pub max_speed: f32,
+    /// vertical speed at full stick, in m/s
+    pub max_vertical_speed: f32,
+    /// yaw rate at full stick, in degrees/s
+    pub max_yaw_rate: f32,
+    /// largest change of the commanded velocity, in m/s²
+    pub max_acceleration: f32,
+}
+
+impl Default for TeleopConfig {
+    fn default() -> Self {
+        Self {
+            rate_hz: 20.0,
+            max_speed: 5.0,
+            max_vertical_speed: 2.0,
+            max_yaw_rate: 45.0,
+            max_acceleration: 2.0,
+        }
+    }
+}
+
+impl TeleopConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = rate_hz;
+        self
+    }
+
+    pub fn max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+
+    pub fn max_vertical_speed(mut self, max_vertical_speed: f32) -> Self {
+        self.max_vertical_speed = max_vertical_speed;
+        self
+    }
+
+    pub fn max_yaw_rate(mut self, max_yaw_rate: f32) -> Self {
+        self.max_yaw_rate = max_yaw_rate;
+        self
+    }
+
+    pub fn max_acceleration(mut self, max_acceleration: f32) -> Self {
+        self.max_acceleration = max_acceleration;
+        self
+    }
+}
+
+/// Manual piloting: turns the input of an `InputProvider` into `move_by_velocity_body_frame_async` commands
+///
+/// The sticks set the velocity in the body frame of the vehicle, which ramps towards it at `max_acceleration` so
+/// a key press doesn't jerk the vehicle
+pub struct Teleop {
+    config: TeleopConfig,
+    period: Duration,
+    velocity: Velocity3,
+}
+
+impl Teleop {
+    /// args:
+    ///     config (TeleopConfig): Rate and limits of the commands
+    ///
+    /// # Panics
+    /// If the command rate isn't positive
+    pub fn new(config: TeleopConfig) -> Self {
+        Self {
+            period: periodic::period("teleop", config.rate_hz),
+            config,
+            velocity: Velocity3::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Velocity last commanded, in the body frame in m/s
+    pub fn velocity(&self) -> Velocity3 {
+        self.velocity
+    }
+
+    /// Velocity to command after `dt` seconds of `input`, moving towards the sticks within the acceleration limit
+    ///
+    /// args:
+    ///     input (&TeleopInput): Sticks of the pilot
+    ///     dt (f32): Time since the last step, in seconds
+    pub fn step(&mut self, input: &TeleopInput, dt: f32) -> Velocity3 {
+        let input = input.clamped();
+        let target = Velocity3::new(
+            input.forward * self.config.max_speed,
+            input.right * self.config.max_speed,
+            input.down * self.config.max_vertical_speed,
+        );
+
+        let change = target - self.velocity;
+        let max_change = self.config.max_acceleration * dt.max(0.0);
+        self.velocity = if change.norm() > max_change {
+            self.velocity + change * (max_change / change.norm())
+        } else {
+            target
+        };
+        self.velocity
+    }
+
+    /// Pilot the vehicle until the input stops, then hover
+    ///
+    /// Every command lasts three periods, the vehicle stops on its own if the loop stalls or fails
+    ///
+    /// args:
+    ///     client (&MultiRotorClient): Vehicle to pilot, armed and flying
+    ///     input (&mut impl InputProvider): Source of the pilot's input
+    pub async fn run(&mut self, client: &MultiRotorClient, input: &mut impl InputProvider) -> NetworkResult<()> {
+        let period = self.period;
+        let mut ticker = Ticker::new(period);
+        let mut last_step = Instant::now();
+
+        while let Some(sticks) = input.poll()? {
+            let started = Instant::now();
+            let velocity = self.step(&sticks, last_step.elapsed().as_secs_f32());
+            last_step = started;
+
+            let yaw_mode = YawMode::rate(sticks.clamped().yaw * self.config.max_yaw_rate);
+            let command = client.move_by_velocity_body_frame_async(
+                velocity,
+                3.0 * period.as_secs_f32(),
+                DrivetrainType::MaxDegreeOfFreedom,
+                yaw_mode,
+            );
+            // AirSim answers a command once it expires or the next one replaces it, stop waiting at the next tick
+            if let Ok(result) = future::timeout(period, command).await {
+                result?;
+            }
+
+            ticker.tick().await;
+        }
+
+        self.velocity = Velocity3::new(0.0, 0.0, 0.0);
+        client.hover_async().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::teleop::{Teleop, TeleopConfig, TeleopInput};
+
+    #[test]
+    fn test_step_limits_acceleration() {
+        let mut teleop = Teleop::new(TeleopConfig::new().max_speed(4.0).max_acceleration(2.0));
+        let full_forward = TeleopInput {
+            forward: 2.0,
+            ..TeleopInput::default()
+        };
+
+        // 2 m/s² for 0.5 s
+        assert!((teleop.step(&full_forward, 0.5).vx - 1.0).abs() < 1e-5);
+        // the stick is clamped to full speed, reached within the limit
+        assert!((teleop.step(&full_forward, 5.0).vx - 4.0).abs() < 1e-5);
+
+        // turning to the right ramps from (4, 0) towards (0, 4) along the straight line
+        let right = TeleopInput {
+            right: 1.0,
+            ..TeleopInput::default()
+        };
+        let velocity = teleop.step(&right, 0.5);
+        let ramp = 1.0 / 2.0_f32.sqrt();
+        assert!((velocity.vx - (4.0 - ramp)).abs() < 1e-5);
+        assert!((velocity.vy - ramp).abs() < 1e-5);
+    }
+}
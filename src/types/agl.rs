@@ -0,0 +1,192 @@
+use crate::{DistanceSensorData, FloatImage, ImageResponse, Quaternion, Quaternionr, Vector3};
+
+/// Smallest vertical component of the direction of a sensor for its readings to be used, i.e. at most 60° from
+/// pointing straight down
+const MIN_DOWNWARD_COMPONENT: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Sensors `MultiRotorClient::get_agl_altitude_with` measures the altitude above ground with
+///
+/// Defaults to the first distance sensor of the vehicle and the `bottom_center` camera, sampling the 5x5 pixels at the
+/// center of its depth image
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AglConfig {
+    /// name of the distance sensor in settings.json, empty for the first one
+    pub distance_sensor: String,
+    /// name of the downward camera to fall back to
+    pub camera: String,
+    /// side in pixels of the square sampled at the center of the depth image
+    pub window: u32,
+}
+
+impl Default for AglConfig {
+    fn default() -> Self {
+        Self {
+            distance_sensor: String::new(),
+            camera: "bottom_center".to_owned(),
+            window: 5,
+        }
+    }
+}
+
+impl AglConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distance_sensor(mut self, distance_sensor: &str) -> Self {
+        self.distance_sensor = distance_sensor.to_owned();
+        self
+    }
+
+    pub fn camera(mut self, camera: &str) -> Self {
+        self.camera = camera.to_owned();
+        self
+    }
+
+    pub fn window(mut self, window: u32) -> Self {
+        self.window = window.max(1);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Sensor an `AglAltitude` was measured with
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AglSource {
+    DistanceSensor,
+    DepthCamera,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Altitude above the ground below the vehicle, as opposed to the NED z of its position
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AglAltitude {
+    /// vertical distance to the ground in meters
+    pub altitude: f32,
+    pub source: AglSource,
+    /// timestamp of the reading in nanoseconds
+    pub timestamp: u64,
+}
+
+impl AglAltitude {
+    /// Altitude from a distance sensor reading, None if the sensor got no return or doesn't point down
+    ///
+    /// The distance is measured along the x axis of the sensor, tilted by the attitude of the vehicle
+    pub(crate) fn from_distance(data: &DistanceSensorData, vehicle_orientation: Quaternion) -> Option<Self> {
+        if !(data.distance > data.min_distance && data.distance < data.max_distance) {
+            return None;
+        }
+        let sensor = Quaternionr::from(vehicle_orientation).0 * Quaternionr::from(data.relative_pose.orientation).0;
+        let down = Quaternionr(sensor).rotate_vector(Vector3::new(1.0, 0.0, 0.0)).z;
+        if down < MIN_DOWNWARD_COMPONENT {
+            return None;
+        }
+
+        Some(Self {
+            altitude: data.distance * down,
+            source: AglSource::DistanceSensor,
+            timestamp: data.timestamp,
+        })
+    }
+
+    /// Altitude from a `DepthPerspective` image, the median of the valid pixels of the window at its center
+    ///
+    /// None if the camera doesn't point down or no pixel of the window hit anything
+    pub(crate) fn from_depth(response: &ImageResponse, window: u32) -> Option<Self> {
+        let depth = FloatImage::from_response(response)?;
+        let down = Quaternionr::from(response.camera_orientation)
+            .rotate_vector(Vector3::new(1.0, 0.0, 0.0))
+            .z;
+        if down < MIN_DOWNWARD_COMPONENT {
+            return None;
+        }
+
+        let (u0, v0) = (
+            (depth.width / 2).saturating_sub(window / 2),
+            (depth.height / 2).saturating_sub(window / 2),
+        );
+        let mut samples: Vec<f32> = (v0..v0 + window)
+            .flat_map(|v| (u0..u0 + window).map(move |u| (u, v)))
+            .filter_map(|(u, v)| depth.get(u, v))
+            // AirSim reports the far plane for pixels hitting nothing
+            .filter(|d| d.is_finite() && *d > 0.0 && *d < 1e4)
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        Some(Self {
+            altitude: samples[samples.len() / 2] * down,
+            source: AglSource::DepthCamera,
+            timestamp: response.time_stamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::agl::{AglAltitude, AglSource};
+    use crate::{DistanceSensorData, ImageResponse, ImageType, Pose3, Position3, Quaternion, Quaternionr, Vector3};
+
+    fn pitched_down() -> Quaternion {
+        Quaternionr::from_euler(0.0, -std::f32::consts::FRAC_PI_2, 0.0).into()
+    }
+
+    #[test]
+    fn test_from_distance() {
+        let mut data = DistanceSensorData {
+            timestamp: 7,
+            distance: 10.0,
+            min_distance: 0.2,
+            max_distance: 40.0,
+            relative_pose: Pose3::new(Position3::new(0.0, 0.0, 0.0), pitched_down()),
+        };
+        let level = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+
+        let agl = AglAltitude::from_distance(&data, level).unwrap();
+        assert!((agl.altitude - 10.0).abs() < 1e-4);
+        assert_eq!((agl.source, agl.timestamp), (AglSource::DistanceSensor, 7));
+
+        // the vehicle pitched up by 45°, the beam is slanted
+        let tilted = Quaternionr::from_euler(0.0, std::f32::consts::FRAC_PI_4, 0.0).into();
+        let agl = AglAltitude::from_distance(&data, tilted).unwrap();
+        assert!((agl.altitude - 10.0 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+
+        data.distance = 40.0;
+        assert!(AglAltitude::from_distance(&data, level).is_none());
+    }
+
+    #[test]
+    fn test_from_depth() {
+        let mut data = vec![f32::INFINITY; 9 * 9];
+        data[4 * 9 + 4] = 12.0;
+        data[4 * 9 + 3] = 11.0;
+        data[3 * 9 + 4] = 13.0;
+        let response = ImageResponse {
+            image_data_uint8: vec![],
+            image_data_float: data,
+            camera_name: "bottom_center".to_owned(),
+            camera_position: Vector3::zeros(),
+            camera_orientation: pitched_down(),
+            time_stamp: 3,
+            message: String::new(),
+            pixels_as_float: true,
+            compress: false,
+            width: 9,
+            height: 9,
+            image_type: ImageType::DepthPerspective,
+        };
+
+        let agl = AglAltitude::from_depth(&response, 3).unwrap();
+        assert!((agl.altitude - 12.0).abs() < 1e-4);
+        assert_eq!(agl.source, AglSource::DepthCamera);
+
+        let forward = ImageResponse {
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            ..response
+        };
+        assert!(AglAltitude::from_depth(&forward, 3).is_none());
+    }
+}
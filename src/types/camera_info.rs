@@ -0,0 +1,39 @@
+use msgpack_rpc::{message::Response, Value};
+
+use crate::Pose3;
+
+#[derive(Debug, Clone, Copy)]
+/// Pose, field of view and projection matrix of a camera, as returned by `simGetCameraInfo`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraInfo {
+    /// pose of the camera in the world frame
+    pub pose: Pose3,
+    /// horizontal field of view in degrees
+    pub fov: f32,
+    /// row major projection matrix of the camera
+    pub proj_mat: [[f32; 4]; 4],
+}
+
+impl From<Response> for CameraInfo {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(res) => {
+                let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
+                let pose: Pose3 = payload[0].1.to_owned().into();
+                let fov = payload[1].1.as_f64().unwrap() as f32;
+
+                // ProjectionMatrix is a map holding a single `matrix` field
+                let mut proj_mat = [[0.0; 4]; 4];
+                let matrix = &payload[2].1.as_map().unwrap()[0].1;
+                for (i, row) in matrix.as_array().unwrap().iter().enumerate().take(4) {
+                    for (j, val) in row.as_array().unwrap().iter().enumerate().take(4) {
+                        proj_mat[i][j] = val.as_f64().unwrap() as f32;
+                    }
+                }
+
+                Self { pose, fov, proj_mat }
+            }
+            Err(_) => panic!("Could not decode result from CameraInfo msgpack"),
+        }
+    }
+}
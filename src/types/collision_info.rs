@@ -3,6 +3,7 @@ use msgpack_rpc::Value;
 use crate::Vector3;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollisionInfo {
     pub has_collided: bool,
     pub penetration_depth: f32,
@@ -0,0 +1,66 @@
+use msgpack_rpc::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// RGBA color used by the debug plotting APIs, each channel between 0.0 and 1.0
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const RED: Color = Color::new(1.0, 0.0, 0.0, 1.0);
+    pub const GREEN: Color = Color::new(0.0, 1.0, 0.0, 1.0);
+    pub const BLUE: Color = Color::new(0.0, 0.0, 1.0, 1.0);
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        Value::Array(vec![
+            Value::F32(self.r),
+            Value::F32(self.g),
+            Value::F32(self.b),
+            Value::F32(self.a),
+        ])
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::RED
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How long a debug plot stays in the viewport
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlotDuration {
+    /// Drawn for a single frame only
+    SingleFrame,
+    /// Drawn for the given number of seconds
+    Seconds(f32),
+    /// Drawn until `sim_flush_persistent_markers` is called
+    Persistent,
+}
+
+impl Default for PlotDuration {
+    fn default() -> Self {
+        PlotDuration::SingleFrame
+    }
+}
+
+impl PlotDuration {
+    /// Duration and is_persistent arguments of the `simPlot*` RPCs
+    pub(crate) fn as_msgpack(&self) -> (Value, Value) {
+        match self {
+            PlotDuration::SingleFrame => (Value::F32(-1.0), Value::Boolean(false)),
+            PlotDuration::Seconds(seconds) => (Value::F32(*seconds), Value::Boolean(false)),
+            PlotDuration::Persistent => (Value::F32(-1.0), Value::Boolean(true)),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use msgpack_rpc::{message::Response, Value};
+
+use crate::{GeoPoint, Pose3, Vector3};
+
+use super::vector::Vector2;
+
+#[derive(Debug, Clone, Copy)]
+/// Axis aligned 2D bounding box, in image pixel coordinates
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Box2D {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+
+impl From<Value> for Box2D {
+    fn from(msgpack: Value) -> Self {
+        let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
+        let min: Vector2 = payload[0].1.to_owned().into();
+        let max: Vector2 = payload[1].1.to_owned().into();
+        Self { min, max }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Axis aligned 3D bounding box, in meters relative to the camera
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Box3D {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+impl From<Value> for Box3D {
+    fn from(msgpack: Value) -> Self {
+        let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
+        let min: Vector3 = payload[0].1.to_owned().into();
+        let max: Vector3 = payload[1].1.to_owned().into();
+        Self { min, max }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Ground truth of an object detected by a camera
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetectionInfo {
+    /// name of the detected mesh
+    pub name: String,
+    /// location of the detected object
+    pub geo_point: GeoPoint,
+    /// bounding box in the image
+    pub box2d: Box2D,
+    /// bounding box relative to the camera
+    pub box3d: Box3D,
+    /// pose of the detected object relative to the camera
+    pub relative_pose: Pose3,
+}
+
+impl DetectionInfo {
+    /// Decode the list of detections returned by `simGetDetections`
+    pub(crate) fn list_from(msgpack: Response) -> Vec<Self> {
+        match msgpack.result {
+            Ok(res) => res.as_array().unwrap().iter().cloned().map(Self::from).collect(),
+            Err(_) => panic!("Could not decode result from DetectionInfo msgpack"),
+        }
+    }
+}
+
+impl From<Value> for DetectionInfo {
+    fn from(msgpack: Value) -> Self {
+        let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
+
+        let name = payload[0].1.as_str().unwrap().to_string();
+        let geo_point: GeoPoint = payload[1].1.to_owned().into();
+        let box2d: Box2D = payload[2].1.to_owned().into();
+        let box3d: Box3D = payload[3].1.to_owned().into();
+        let relative_pose: Pose3 = payload[4].1.to_owned().into();
+
+        Self {
+            name,
+            geo_point,
+            box2d,
+            box3d,
+            relative_pose,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use msgpack_rpc::{message::Response, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+/// Brown-Conrady lens distortion of a camera, as used by OpenCV, see `simGetDistortionParams`
+///
+/// The default is a distortion free lens
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistortionParams {
+    /// radial distortion coefficients
+    pub k1: f32,
+    pub k2: f32,
+    pub k3: f32,
+    /// tangential distortion coefficients
+    pub p1: f32,
+    pub p2: f32,
+}
+
+impl DistortionParams {
+    pub fn new(k1: f32, k2: f32, k3: f32, p1: f32, p2: f32) -> Self {
+        Self { k1, k2, k3, p1, p2 }
+    }
+
+    /// Coefficients by the names `simSetDistortionParam` knows them
+    pub(crate) fn named(&self) -> [(&'static str, f32); 5] {
+        [
+            ("K1", self.k1),
+            ("K2", self.k2),
+            ("K3", self.k3),
+            ("P1", self.p1),
+            ("P2", self.p2),
+        ]
+    }
+}
+
+impl From<Response> for DistortionParams {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(Value::Array(params)) => {
+                // K1, K2, K3, P1, P2
+                let param = |i: usize| params.get(i).and_then(Value::as_f64).unwrap_or(0.0) as f32;
+                Self::new(param(0), param(1), param(2), param(3), param(4))
+            }
+            _ => panic!("Could not decode result from DistortionParams msgpack"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use msgpack_rpc::message::Response;
+    use msgpack_rpc::Value;
+
+    use crate::DistortionParams;
+
+    #[test]
+    fn test_from_response() {
+        let response = Response {
+            id: 0,
+            result: Ok(Value::Array(vec![
+                Value::F32(-0.25),
+                Value::F32(0.125),
+                Value::F32(0.0),
+                Value::F32(0.001),
+                Value::F32(-0.002),
+            ])),
+        };
+
+        let params = DistortionParams::from(response);
+        assert_eq!(params, DistortionParams::new(-0.25, 0.125, 0.0, 0.001, -0.002));
+        assert_eq!(params.named()[4], ("P2", -0.002));
+    }
+}
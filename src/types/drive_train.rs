@@ -1,6 +1,7 @@
 use msgpack_rpc::Value;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DrivetrainType {
     MaxDegreeOfFreedom,
     ForwardOnly,
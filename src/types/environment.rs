@@ -3,6 +3,7 @@ use msgpack_rpc::{message::Response, Value};
 use crate::types::{geopoint::GeoPoint, vector::Vector3};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnvironmentState {
     pub position: Vector3,
     pub geo_point: GeoPoint,
@@ -0,0 +1,64 @@
+use rmpv::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Width of the floats in the params of the RPCs sent by a client
+///
+/// AirSim reads floats of either width, but some builds, or servers linked with a mismatched rpclib, reject F32
+/// params. The types encode their fields as F32, the client widens them before sending when asked to
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FloatEncoding {
+    /// 32 bit floats, as sent by the Python client
+    F32,
+    /// 64 bit floats
+    F64,
+}
+
+impl Default for FloatEncoding {
+    fn default() -> Self {
+        FloatEncoding::F32
+    }
+}
+
+impl FloatEncoding {
+    /// Re-encode the floats of `params`, nested in arrays and maps included
+    pub(crate) fn apply(self, params: &mut [Value]) {
+        if self == FloatEncoding::F64 {
+            params.iter_mut().for_each(widen);
+        }
+    }
+}
+
+fn widen(value: &mut Value) {
+    match value {
+        Value::F32(f) => *value = Value::F64(*f as f64),
+        Value::Array(values) => values.iter_mut().for_each(widen),
+        Value::Map(entries) => entries.iter_mut().for_each(|(_, v)| widen(v)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rmpv::Value;
+
+    use crate::{FloatEncoding, Vector3};
+
+    #[test]
+    fn test_f64_widens_nested_floats() {
+        let mut params = vec![
+            Vector3::new(1.5, 0.0, -2.0).as_msgpack(),
+            Value::F32(0.25),
+            Value::from(3),
+        ];
+        FloatEncoding::F64.apply(&mut params);
+
+        let vector = params[0].as_map().unwrap();
+        assert_eq!(vector[0].1, Value::F64(1.5));
+        assert_eq!(params[1], Value::F64(0.25));
+        assert_eq!(params[2], Value::from(3));
+
+        let mut params = vec![Value::F32(0.25)];
+        FloatEncoding::F32.apply(&mut params);
+        assert_eq!(params, vec![Value::F32(0.25)]);
+    }
+}
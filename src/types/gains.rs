@@ -2,6 +2,7 @@ use msgpack_rpc::Value;
 
 #[derive(Debug, Clone, Copy)]
 /// Struct to store values of PID gains. Used to transmit controller gain values while instantiating
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PIDGains {
     /// Proportional gain
     pub kp: f32,
@@ -24,6 +25,7 @@ impl PIDGains {
 
 #[derive(Debug, Clone, Copy)]
 /// Struct to contain controller gains used by angle rate and level PID controller
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AngularControllerGains {
     /// kp, ki, kd for roll axis
     pub roll_gains: PIDGains,
@@ -42,6 +44,18 @@ impl AngularControllerGains {
         }
     }
 
+    /// Default AirSim gains of the angle rate controller
+    pub fn angle_rate_default() -> Self {
+        let gains = PIDGains::new(0.25, 0.0, 0.0);
+        Self::new(gains, gains, gains)
+    }
+
+    /// Default AirSim gains of the angle level controller
+    pub fn angle_level_default() -> Self {
+        let gains = PIDGains::new(2.5, 0.0, 0.0);
+        Self::new(gains, gains, gains)
+    }
+
     pub(crate) fn as_msgpack(&self, vehicle_name: &'static str) -> Vec<Value> {
         let kps = Value::Array(vec![
             Value::F32(self.roll_gains.kp),
@@ -50,9 +64,9 @@ impl AngularControllerGains {
         ]);
 
         let kis = Value::Array(vec![
-            Value::F32(self.yaw_gains.ki),
             Value::F32(self.roll_gains.ki),
             Value::F32(self.pitch_gains.ki),
+            Value::F32(self.yaw_gains.ki),
         ]);
 
         let kds = Value::Array(vec![
@@ -67,6 +81,7 @@ impl AngularControllerGains {
 
 #[derive(Debug, Clone, Copy)]
 /// Struct to contain controller gains used by velocity and Position PID controller
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinearControllerGains {
     /// kp, ki, kd for X axis
     pub x_gains: PIDGains,
@@ -85,6 +100,18 @@ impl LinearControllerGains {
         }
     }
 
+    /// Default AirSim gains of the velocity controller
+    pub fn velocity_default() -> Self {
+        let xy_gains = PIDGains::new(0.2, 0.0, 0.0);
+        Self::new(xy_gains, xy_gains, PIDGains::new(2.0, 2.0, 0.0))
+    }
+
+    /// Default AirSim gains of the position controller
+    pub fn position_default() -> Self {
+        let gains = PIDGains::new(0.25, 0.0, 0.0);
+        Self::new(gains, gains, gains)
+    }
+
     pub(crate) fn as_msgpack(&self, vehicle_name: &'static str) -> Vec<Value> {
         let kps = Value::Array(vec![
             Value::F32(self.x_gains.kp),
@@ -107,3 +134,23 @@ impl LinearControllerGains {
         vec![kps, kis, kds, Value::String(vehicle_name.into())]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use msgpack_rpc::Value;
+
+    use crate::{AngularControllerGains, PIDGains};
+
+    #[test]
+    fn test_angular_gains_axis_order() {
+        let gains = AngularControllerGains::new(
+            PIDGains::new(1.0, 2.0, 3.0),
+            PIDGains::new(4.0, 5.0, 6.0),
+            PIDGains::new(7.0, 8.0, 9.0),
+        );
+        let msgpack = gains.as_msgpack("");
+
+        let kis = Value::Array(vec![Value::F32(2.0), Value::F32(5.0), Value::F32(8.0)]);
+        assert_eq!(msgpack[1], kis);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{Pose3, Vector3};
+
+#[derive(Debug, Clone)]
+/// Gate of a Drone Racing Lab track
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gate {
+    /// name of the gate object, e.g. `Gate00`
+    pub name: String,
+    /// position of the center of the gate and its orientation, in the world frame
+    pub pose: Pose3,
+    /// scale of the gate object, relative to the nominal gate
+    pub scale: Vector3,
+}
+
+impl Gate {
+    /// Size of the opening of the gate, from `AirsimClient::sim_get_nominal_gate_inner_dimensions`
+    ///
+    /// args:
+    ///     nominal (Vector3): Inner dimensions of a gate of scale 1
+    pub fn inner_dimensions(&self, nominal: Vector3) -> Vector3 {
+        nominal.component_mul(&self.scale)
+    }
+
+    /// Index of the gate in the race, parsed from the digits of its name
+    pub fn index(&self) -> Option<u32> {
+        let digits: String = self
+            .name
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Gate, Pose3, Vector3};
+
+    #[test]
+    fn test_gate_index_and_size() {
+        let gate = Gate {
+            name: "Gate07_41".to_owned(),
+            pose: Pose3::identity(),
+            scale: Vector3::new(1.0, 2.0, 0.5),
+        };
+        assert_eq!(gate.index(), Some(7));
+        assert_eq!(
+            gate.inner_dimensions(Vector3::new(1.6, 0.2, 1.6)),
+            Vector3::new(1.6, 0.4, 0.8)
+        );
+
+        let gate = Gate {
+            name: "StartGate".to_owned(),
+            ..gate
+        };
+        assert_eq!(gate.index(), None);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{GeoPoint, Vector3};
+
+/// WGS-84 semi-major axis, in meters
+pub const WGS84_SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+/// WGS-84 flattening
+pub const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+/// Mean earth radius used by the haversine distance, in meters
+pub const EARTH_MEAN_RADIUS: f64 = 6_371_008.8;
+
+/// Meridional (north-south) and prime vertical (east-west) radii of curvature
+/// of the WGS-84 ellipsoid at the given latitude (radians)
+fn radii_of_curvature(latitude: f64) -> (f64, f64) {
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let sin_lat = latitude.sin();
+    let denom = 1.0 - e2 * sin_lat * sin_lat;
+
+    let meridional = WGS84_SEMI_MAJOR_AXIS * (1.0 - e2) / denom.powf(1.5);
+    let prime_vertical = WGS84_SEMI_MAJOR_AXIS / denom.sqrt();
+    (meridional, prime_vertical)
+}
+
+impl GeoPoint {
+    /// Flat-earth conversion of this GPS coordinate into the local NED frame with origin at `home`
+    ///
+    /// The approximation holds within a few kilometers of `home`,
+    /// which covers the typical AirSim scene.
+    ///
+    /// args:
+    ///     home (&GeoPoint): origin of the NED frame, e.g. from `get_home_geo_point`
+    pub fn to_ned(&self, home: &GeoPoint) -> Vector3 {
+        let home_lat = (home.latitude as f64).to_radians();
+        let (meridional, prime_vertical) = radii_of_curvature(home_lat);
+
+        let d_lat = (self.latitude as f64 - home.latitude as f64).to_radians();
+        let d_lon = (self.longitude as f64 - home.longitude as f64).to_radians();
+
+        let north = d_lat * meridional;
+        let east = d_lon * prime_vertical * home_lat.cos();
+        let down = home.altitude as f64 - self.altitude as f64;
+
+        Vector3::new(north as f32, east as f32, down as f32)
+    }
+
+    /// Great-circle distance to `other`, in meters, using the haversine formula.
+    /// Altitude is ignored.
+    ///
+    /// args:
+    ///     other (&GeoPoint): point to measure the distance to
+    pub fn haversine_distance(&self, other: &GeoPoint) -> f32 {
+        let lat1 = (self.latitude as f64).to_radians();
+        let lat2 = (other.latitude as f64).to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude as f64 - self.longitude as f64).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+        (EARTH_MEAN_RADIUS * c) as f32
+    }
+}
+
+impl Vector3 {
+    /// Flat-earth conversion of this local NED position (x = north, y = east, z = down)
+    /// into a GPS coordinate, given the `home` origin of the NED frame
+    ///
+    /// Inverse of `GeoPoint::to_ned`, so the result can be passed straight to `move_to_gps_async`.
+    ///
+    /// args:
+    ///     home (&GeoPoint): origin of the NED frame, e.g. from `get_home_geo_point`
+    pub fn to_geopoint(&self, home: &GeoPoint) -> GeoPoint {
+        let home_lat = (home.latitude as f64).to_radians();
+        let (meridional, prime_vertical) = radii_of_curvature(home_lat);
+
+        let d_lat = (self.x as f64 / meridional).to_degrees();
+        let d_lon = (self.y as f64 / (prime_vertical * home_lat.cos())).to_degrees();
+
+        GeoPoint::new(
+            (home.latitude as f64 + d_lat) as f32,
+            (home.longitude as f64 + d_lon) as f32,
+            (home.altitude as f64 - self.z as f64) as f32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GeoPoint, Vector3};
+
+    #[test]
+    fn test_ned_round_trip() {
+        let home = GeoPoint::new(47.641_468, -122.140_17, 122.0);
+        let ned = Vector3::new(120.0, -45.0, -30.0);
+
+        // GeoPoint stores degrees as f32, which limits the resolution to roughly half a meter
+        let back = ned.to_geopoint(&home).to_ned(&home);
+        assert!((back.x - ned.x).abs() < 1.0);
+        assert!((back.y - ned.y).abs() < 1.0);
+        assert!((back.z - ned.z).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        // one degree of latitude is roughly 111.2 km
+        let a = GeoPoint::new(0.0, 0.0, 0.0);
+        let b = GeoPoint::new(1.0, 0.0, 0.0);
+        assert!((a.haversine_distance(&b) - 111_195.0).abs() < 10.0);
+    }
+}
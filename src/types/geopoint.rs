@@ -1,6 +1,25 @@
-use msgpack_rpc::{message::Response, Value};
+use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+use msgpack_rpc::{message::Response, Utf8String, Value};
+use thiserror::Error;
+
+/// Why coordinates don't make a `GeoPoint`
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum GeoPointError {
+    #[error("latitude {0}° is outside of [-90°, 90°]")]
+    Latitude(f32),
+    #[error("longitude {0}° is outside of [-180°, 180°]")]
+    Longitude(f32),
+    #[error("altitude {0} m isn't finite")]
+    Altitude(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// WGS 84 coordinates, latitude and longitude in degrees and altitude in meters
+///
+/// Compared exactly by `==`, see `approx_eq` for points a few meters apart. Displayed in decimal degrees, or in
+/// degrees, minutes and seconds with `{:#}`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeoPoint {
     pub latitude: f32,
     pub longitude: f32,
@@ -15,6 +34,94 @@ impl GeoPoint {
             altitude,
         }
     }
+
+    /// Point with validated coordinates
+    ///
+    /// args:
+    ///     latitude (f32): Latitude in degrees, Range -90-90
+    ///     longitude (f32): Longitude in degrees, Range -180-180
+    ///     altitude (f32): Altitude in meters, finite
+    pub fn checked(latitude: f32, longitude: f32, altitude: f32) -> Result<Self, GeoPointError> {
+        let point = Self::new(latitude, longitude, altitude);
+        point.validate()?;
+        Ok(point)
+    }
+
+    /// Checks the ranges of the coordinates, e.g. of a point read from a file or sent by the server
+    pub fn validate(&self) -> Result<(), GeoPointError> {
+        // written to also reject NaN
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(GeoPointError::Latitude(self.latitude));
+        }
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(GeoPointError::Longitude(self.longitude));
+        }
+        if !self.altitude.is_finite() {
+            return Err(GeoPointError::Altitude(self.altitude));
+        }
+        Ok(())
+    }
+
+    /// Whether `other` is within `tolerance` meters of this point, horizontally and in altitude
+    ///
+    /// The degrees stored as f32 resolve about a meter, pass a tolerance of at least that to compare a point with
+    /// its round trip through the NED frame. Longitudes on both sides of the antimeridian are close.
+    ///
+    /// args:
+    ///     other (&GeoPoint): Point to compare with
+    ///     tolerance (f32): Largest distance in meters, horizontally and in altitude
+    pub fn approx_eq(&self, other: &GeoPoint, tolerance: f32) -> bool {
+        self.haversine_distance(other) <= tolerance && (self.altitude - other.altitude).abs() <= tolerance
+    }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        let latitude: Utf8String = "latitude".into();
+        let longitude: Utf8String = "longitude".into();
+        let altitude: Utf8String = "altitude".into();
+
+        Value::Map(vec![
+            (Value::String(latitude), Value::F32(self.latitude)),
+            (Value::String(longitude), Value::F32(self.longitude)),
+            (Value::String(altitude), Value::F32(self.altitude)),
+        ])
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    /// `47.641468, -122.140170, 122.00 m`, or `47°38'29.28"N 122°8'24.61"W 122.00 m` with `{:#}`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return write!(f, "{:.6}, {:.6}, {:.2} m", self.latitude, self.longitude, self.altitude);
+        }
+
+        let dms = |degrees: f32| {
+            // round to the displayed hundredth of a second before splitting, 59.999" would show as 60.00"
+            let hundredths = (f64::from(degrees.abs()) * 360_000.0).round() as u64;
+            (
+                hundredths / 360_000,
+                hundredths / 6_000 % 60,
+                (hundredths % 6_000) as f64 / 100.0,
+            )
+        };
+        let (d, m, sec) = dms(self.latitude);
+        write!(f, "{d}°{m}'{sec:.2}\"{} ", if self.latitude < 0.0 { 'S' } else { 'N' })?;
+        let (d, m, sec) = dms(self.longitude);
+        write!(f, "{d}°{m}'{sec:.2}\"{} ", if self.longitude < 0.0 { 'W' } else { 'E' })?;
+        write!(f, "{:.2} m", self.altitude)
+    }
+}
+
+impl From<(f64, f64, f32)> for GeoPoint {
+    /// From `(latitude, longitude, altitude)`, as used by most GIS libraries, without validation
+    fn from((latitude, longitude, altitude): (f64, f64, f32)) -> Self {
+        Self::new(latitude as f32, longitude as f32, altitude)
+    }
+}
+
+impl From<GeoPoint> for (f64, f64, f32) {
+    fn from(point: GeoPoint) -> Self {
+        (f64::from(point.latitude), f64::from(point.longitude), point.altitude)
+    }
 }
 
 impl From<Response> for GeoPoint {
@@ -56,3 +163,31 @@ impl From<Value> for GeoPoint {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{GeoPoint, GeoPointError};
+
+    #[test]
+    fn test_validation_display_and_equality() {
+        assert_eq!(GeoPoint::checked(91.0, 0.0, 0.0), Err(GeoPointError::Latitude(91.0)));
+        assert!(matches!(
+            GeoPoint::checked(0.0, f32::NAN, 0.0),
+            Err(GeoPointError::Longitude(_))
+        ));
+        let home = GeoPoint::checked(47.641_468, -122.140_17, 122.0).unwrap();
+
+        assert_eq!(home.to_string(), "47.641468, -122.140167, 122.00 m");
+        assert_eq!(format!("{home:#}"), "47°38'29.28\"N 122°8'24.60\"W 122.00 m");
+
+        assert_ne!(home, GeoPoint::new(47.641_47, -122.140_17, 122.0));
+        // 0.4 m north and 0.3 m up
+        assert!(home.approx_eq(&GeoPoint::new(47.641_47, -122.140_17, 122.3), 0.5));
+        // 1.3 m north
+        assert!(!home.approx_eq(&GeoPoint::new(47.641_48, -122.140_17, 122.0), 0.5));
+        assert!(GeoPoint::new(0.0, 180.0, 0.0).approx_eq(&GeoPoint::new(0.0, -180.0, 0.0), 0.5));
+
+        let (latitude, longitude, altitude): (f64, f64, f32) = home.into();
+        assert_eq!(GeoPoint::from((latitude, longitude, altitude)), home);
+    }
+}
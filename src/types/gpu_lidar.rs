@@ -0,0 +1,103 @@
+use msgpack_rpc::{message::Response, Value};
+
+use crate::{Pose3, Position3, Quaternion, Vector3};
+
+/// Number of floats per point of a GPU lidar point cloud: x, y, z, rgb and intensity
+const FLOATS_PER_POINT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Point of a GPU lidar scan
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuLidarPoint {
+    /// position of the hit in the frame set by `DataFrame` in settings.json, in meters
+    pub position: Vector3,
+    /// color of the hit surface, packed as the float bits of an 0x00RRGGBB integer
+    pub rgb: f32,
+    /// intensity of the return, Range 0-1
+    pub intensity: f32,
+}
+
+#[derive(Debug, Clone)]
+/// Scan of the GPU lidar of Cosys-AirSim
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuLidarData {
+    /// timestamp of the scan in nanoseconds
+    pub time_stamp: u64,
+    /// flat point cloud, 5 floats per point, see `points`
+    pub point_cloud: Vec<f32>,
+    /// pose of the lidar when the scan was taken
+    pub pose: Pose3,
+}
+
+impl GpuLidarData {
+    /// Points of the scan, a trailing incomplete point is dropped
+    pub fn points(&self) -> Vec<GpuLidarPoint> {
+        self.point_cloud
+            .chunks_exact(FLOATS_PER_POINT)
+            .map(|point| GpuLidarPoint {
+                position: Vector3::new(point[0], point[1], point[2]),
+                rgb: point[3],
+                intensity: point[4],
+            })
+            .collect()
+    }
+}
+
+impl From<Response> for GpuLidarData {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(res) => {
+                let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
+                let field = |key: &str| payload.iter().find(|(k, _)| k.as_str() == Some(key)).map(|(_, v)| v);
+
+                // the forks keep adding fields, read the ones we know and tolerate the missing ones
+                let time_stamp = field("time_stamp").and_then(Value::as_u64).unwrap_or_default();
+                let point_cloud = field("point_cloud")
+                    .and_then(Value::as_array)
+                    .map(|cloud| cloud.iter().filter_map(Value::as_f64).map(|f| f as f32).collect())
+                    .unwrap_or_default();
+                let pose = field("pose")
+                    .map(|pose| Pose3::from(pose.to_owned()))
+                    .unwrap_or_else(|| Pose3::new(Position3::new(0.0, 0.0, 0.0), Quaternion::new(1.0, 0.0, 0.0, 0.0)));
+
+                Self {
+                    time_stamp,
+                    point_cloud,
+                    pose,
+                }
+            }
+            Err(_) => panic!("Could not decode result from GpuLidarData msgpack"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use msgpack_rpc::message::Response;
+    use msgpack_rpc::Value;
+
+    use crate::GpuLidarData;
+
+    #[test]
+    fn test_decode_tolerates_missing_and_extra_fields() {
+        let cloud = [1.0, 2.0, -3.0, 0.0, 0.5, 4.0, 5.0, -6.0, 0.0, 1.0, 7.0];
+        let response = Response {
+            id: 0,
+            result: Ok(Value::Map(vec![
+                (Value::from("time_stamp"), Value::from(42)),
+                (
+                    Value::from("point_cloud"),
+                    Value::Array(cloud.iter().map(|f| Value::F32(*f)).collect()),
+                ),
+                (Value::from("groundtruth"), Value::Array(vec![])),
+            ])),
+        };
+
+        let data = GpuLidarData::from(response);
+        let points = data.points();
+        assert_eq!(data.time_stamp, 42);
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[1].position.z, points[1].intensity), (-6.0, 1.0));
+        assert_eq!(data.pose.position.x, 0.0);
+    }
+}
@@ -1,16 +1,31 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::Path;
+
 use msgpack_rpc::{message::Response, Utf8String, Value};
 
-#[derive(Debug, Clone, Copy)]
+use crate::{Quaternion, Vector3};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Kind of image rendered by a camera, numbered as `ImageType` in AirSim's `ImageCaptureBase.hpp`
 pub enum ImageType {
     Scene,
     DepthPlanar,
     DepthPerspective,
     DepthVis,
     DisparityNormalized,
+    Segmentation,
     SurfaceNormals,
     Infrared,
     OpticalFlow,
     OpticalFlowVis,
+    /// Lighting only, without the materials. Cosys-AirSim only
+    Lighting,
+    /// Layer of annotated objects named by `ImageRequest::annotation_name`. Cosys-AirSim only
+    Annotation,
 }
 
 impl ImageType {
@@ -21,57 +36,214 @@ impl ImageType {
             ImageType::DepthPerspective => 2_i64,
             ImageType::DepthVis => 3_i64,
             ImageType::DisparityNormalized => 4_i64,
-            ImageType::SurfaceNormals => 5_i64,
-            ImageType::Infrared => 6_i64,
-            ImageType::OpticalFlow => 7_i64,
-            ImageType::OpticalFlowVis => 8_i64,
+            ImageType::Segmentation => 5_i64,
+            ImageType::SurfaceNormals => 6_i64,
+            ImageType::Infrared => 7_i64,
+            ImageType::OpticalFlow => 8_i64,
+            ImageType::OpticalFlowVis => 9_i64,
+            ImageType::Lighting => 10_i64,
+            ImageType::Annotation => 11_i64,
         };
 
         Value::Integer(val.into())
     }
 }
 
+impl From<Value> for ImageType {
+    fn from(msgpack: Value) -> Self {
+        match msgpack.as_i64().unwrap() {
+            0 => ImageType::Scene,
+            1 => ImageType::DepthPlanar,
+            2 => ImageType::DepthPerspective,
+            3 => ImageType::DepthVis,
+            4 => ImageType::DisparityNormalized,
+            5 => ImageType::Segmentation,
+            6 => ImageType::SurfaceNormals,
+            7 => ImageType::Infrared,
+            8 => ImageType::OpticalFlow,
+            9 => ImageType::OpticalFlowVis,
+            10 => ImageType::Lighting,
+            11 => ImageType::Annotation,
+            val => panic!("could not convert value {val:?} to an ImageType"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the pixels of an image are sent by the server
+///
+/// AirSim compresses 8 bit images as png only, there is no lossy compression to request
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageEncoding {
+    /// 8 bit pixels compressed as png, smallest on the wire but slow to encode and decode
+    Png,
+    /// 8 bit BGR pixels, no compression to undo, see `RgbImage::from_response`
+    Raw,
+    /// one float per pixel, for the types measured in meters or pixels, see `FloatImage::from_response`
+    Float,
+}
+
+impl ImageEncoding {
+    /// Encoding suited to an image type: floats for depth, disparity and optical flow, else raw pixels from a
+    /// server on the same machine where bandwidth is cheap and png from a remote one
+    ///
+    /// args:
+    ///     image_type (ImageType): Type of the image
+    ///     remote (bool): Whether the server runs on another machine
+    pub fn select(image_type: ImageType, remote: bool) -> Self {
+        match image_type {
+            ImageType::DepthPlanar
+            | ImageType::DepthPerspective
+            | ImageType::DisparityNormalized
+            | ImageType::OpticalFlow => ImageEncoding::Float,
+            _ if remote => ImageEncoding::Png,
+            _ => ImageEncoding::Raw,
+        }
+    }
+
+    /// `pixels_as_float` and `compress` flags of an `ImageRequest`
+    pub(crate) fn flags(&self) -> (bool, bool) {
+        match self {
+            ImageEncoding::Png => (false, true),
+            ImageEncoding::Raw => (false, false),
+            ImageEncoding::Float => (true, false),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Binary string literal of compressed png image in presented as an vector of bytes
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompressedImage(pub Vec<u8>);
 
 impl From<Response> for CompressedImage {
     fn from(msgpack: Response) -> Self {
-        let mut pixels = vec![];
-
+        // the bytes are moved out of the response, not copied
         match msgpack.result {
-            Ok(res) => {
-                let slice: &[u8] = res.as_slice().unwrap();
-                for p in slice {
-                    pixels.push(*p);
-                }
-            }
-            Err(_) => panic!("Could not decode result from CompressedImage msgpack"),
-        };
-
-        Self(pixels)
+            Ok(Value::Binary(bytes)) => Self(bytes),
+            _ => panic!("Could not decode result from CompressedImage msgpack"),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageRequest {
     pub camera_name: String,
     pub image_type: ImageType,
     pub pixels_as_float: bool,
     pub compress: bool,
+    /// annotation layer of an `ImageType::Annotation` request, empty otherwise
+    pub annotation_name: String,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageRequests(pub Vec<ImageRequest>);
 
 impl ImageRequest {
+    /// args:
+    ///     camera_name (&str): Name of the camera, for backwards compatibility, ID numbers such as 0,1,etc. can also be used
+    ///     image_type (ImageType): Type of image required
+    ///     pixels_as_float (bool): Whether to return the pixels as floats, e.g. for depth in meters
+    ///     compress (bool): Whether to compress 8 bit images as png
+    pub fn new(camera_name: &str, image_type: ImageType, pixels_as_float: bool, compress: bool) -> Self {
+        Self {
+            camera_name: camera_name.to_owned(),
+            image_type,
+            pixels_as_float,
+            compress,
+            annotation_name: String::new(),
+        }
+    }
+
+    /// args:
+    ///     camera_name (&str): Name of the camera
+    ///     image_type (ImageType): Type of image required
+    ///     encoding (ImageEncoding): How the pixels are sent, e.g. `ImageEncoding::select(image_type, false)`
+    pub fn with_encoding(camera_name: &str, image_type: ImageType, encoding: ImageEncoding) -> Self {
+        let (pixels_as_float, compress) = encoding.flags();
+        Self::new(camera_name, image_type, pixels_as_float, compress)
+    }
+
+    /// How the pixels of the image are requested
+    pub fn encoding(&self) -> ImageEncoding {
+        match (self.pixels_as_float, self.compress) {
+            (true, _) => ImageEncoding::Float,
+            (false, true) => ImageEncoding::Png,
+            (false, false) => ImageEncoding::Raw,
+        }
+    }
+
+    /// Color image, compressed as png
+    pub fn scene(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::Scene, false, true)
+    }
+
+    /// Color image as raw BGR bytes, faster to decode than png
+    pub fn scene_uncompressed(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::Scene, false, false)
+    }
+
+    /// Depth along the optical axis in meters, see `FloatImage::from_response`
+    pub fn depth_planar(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::DepthPlanar, true, false)
+    }
+
+    /// Depth along the ray of every pixel in meters, see `FloatImage::from_response`
+    pub fn depth_perspective(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::DepthPerspective, true, false)
+    }
+
+    /// Depth mapped to gray levels for display, compressed as png
+    pub fn depth_vis(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::DepthVis, false, true)
+    }
+
+    /// Normalized disparity as floats
+    pub fn disparity_normalized(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::DisparityNormalized, true, false)
+    }
+
+    /// Object ids encoded as colors, compressed as png which keeps the colors exact
+    pub fn segmentation(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::Segmentation, false, true)
+    }
+
+    /// Surface normals encoded as colors, compressed as png
+    pub fn surface_normals(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::SurfaceNormals, false, true)
+    }
+
+    /// Infrared image, compressed as png
+    pub fn infrared(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::Infrared, false, true)
+    }
+
+    /// Optical flow in pixels, see `OpticalFlowImage::from_response`
+    pub fn optical_flow(camera_name: &str) -> Self {
+        Self::new(camera_name, ImageType::OpticalFlow, true, false)
+    }
+
+    /// Objects of an annotation layer encoded as colors, compressed as png. Cosys-AirSim only
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera
+    ///     annotation_name (&str): Name of the annotation layer, as in the `Annotation` section of settings.json
+    pub fn annotation(camera_name: &str, annotation_name: &str) -> Self {
+        Self {
+            annotation_name: annotation_name.to_owned(),
+            ..Self::new(camera_name, ImageType::Annotation, false, true)
+        }
+    }
+
     pub(crate) fn as_msgpack(&self) -> Value {
         let camera_name: Utf8String = "camera_name".into();
         let image_type: Utf8String = "image_type".into();
         let pixels_as_float: Utf8String = "pixels_as_float".into();
         let compress: Utf8String = "compress".into();
 
-        let val = Value::Map(vec![
+        let mut val = Value::Map(vec![
             (
                 Value::String(camera_name),
                 Value::String(self.camera_name.to_owned().into()),
@@ -80,6 +252,16 @@ impl ImageRequest {
             (Value::String(pixels_as_float), Value::Boolean(self.pixels_as_float)),
             (Value::String(compress), Value::Boolean(self.compress)),
         ]);
+        // only Cosys-AirSim knows the field, leave it out for the other servers
+        if !self.annotation_name.is_empty() {
+            if let Value::Map(fields) = &mut val {
+                let annotation_name: Utf8String = "annotation_name".into();
+                fields.push((
+                    Value::String(annotation_name),
+                    Value::String(self.annotation_name.to_owned().into()),
+                ));
+            }
+        }
 
         let msg: Vec<(msgpack_rpc::Value, msgpack_rpc::Value)> = val.as_map().map(|x| x.to_owned()).unwrap();
         Value::Map(msg)
@@ -92,3 +274,607 @@ impl ImageRequests {
         Value::Array(images)
     }
 }
+
+#[derive(Debug, Clone, Default)]
+/// Set of images captured together, reusable across captures and cameras
+///
+/// ```no_run
+/// # use airsim_client::CameraProfile;
+/// let profile = CameraProfile::new().scene().depth_planar().segmentation();
+/// let front = profile.requests("front_center");
+/// let rear = profile.requests("back_center");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraProfile {
+    images: Vec<(ImageType, bool, bool)>,
+}
+
+impl CameraProfile {
+    /// Empty profile, add images with the builder methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Color image and planar depth, e.g. for point clouds
+    pub fn rgbd() -> Self {
+        Self::new().scene().depth_planar()
+    }
+
+    /// Color image, planar depth and segmentation, e.g. to build perception datasets
+    pub fn rgbd_segmentation() -> Self {
+        Self::rgbd().segmentation()
+    }
+
+    /// Add an image like `ImageRequest::new`
+    pub fn image(mut self, image_type: ImageType, pixels_as_float: bool, compress: bool) -> Self {
+        self.images.push((image_type, pixels_as_float, compress));
+        self
+    }
+
+    /// Add an image like `ImageRequest::with_encoding`
+    pub fn image_with_encoding(self, image_type: ImageType, encoding: ImageEncoding) -> Self {
+        let (pixels_as_float, compress) = encoding.flags();
+        self.image(image_type, pixels_as_float, compress)
+    }
+
+    /// Add an image like `ImageRequest::scene`
+    pub fn scene(self) -> Self {
+        self.image(ImageType::Scene, false, true)
+    }
+
+    /// Add an image like `ImageRequest::depth_planar`
+    pub fn depth_planar(self) -> Self {
+        self.image(ImageType::DepthPlanar, true, false)
+    }
+
+    /// Add an image like `ImageRequest::depth_perspective`
+    pub fn depth_perspective(self) -> Self {
+        self.image(ImageType::DepthPerspective, true, false)
+    }
+
+    /// Add an image like `ImageRequest::segmentation`
+    pub fn segmentation(self) -> Self {
+        self.image(ImageType::Segmentation, false, true)
+    }
+
+    /// Add an image like `ImageRequest::surface_normals`
+    pub fn surface_normals(self) -> Self {
+        self.image(ImageType::SurfaceNormals, false, true)
+    }
+
+    /// Add an image like `ImageRequest::optical_flow`
+    pub fn optical_flow(self) -> Self {
+        self.image(ImageType::OpticalFlow, true, false)
+    }
+
+    /// Requests of every image of the profile from one camera, in the order they were added
+    ///
+    /// args:
+    ///     camera_name (&str): Name of the camera
+    pub fn requests(&self, camera_name: &str) -> ImageRequests {
+        self.requests_from(&[camera_name])
+    }
+
+    /// Requests of every image of the profile from each camera, camera by camera
+    ///
+    /// args:
+    ///     camera_names (&[&str]): Names of the cameras
+    pub fn requests_from(&self, camera_names: &[&str]) -> ImageRequests {
+        let requests = camera_names.iter().flat_map(|camera_name| {
+            self.images.iter().map(move |&(image_type, pixels_as_float, compress)| {
+                ImageRequest::new(camera_name, image_type, pixels_as_float, compress)
+            })
+        });
+        ImageRequests(requests.collect())
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Image returned by `simGetImages`, together with the pose of the camera that captured it
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageResponse {
+    /// image bytes, compressed as png if `compress` was requested, raw BGR otherwise. Empty for float images
+    pub image_data_uint8: Vec<u8>,
+    /// image pixels if `pixels_as_float` was requested, empty otherwise
+    pub image_data_float: Vec<f32>,
+    pub camera_name: String,
+    pub camera_position: Vector3,
+    pub camera_orientation: Quaternion,
+    /// capture time in nanoseconds
+    pub time_stamp: u64,
+    pub message: String,
+    pub pixels_as_float: bool,
+    pub compress: bool,
+    pub width: u32,
+    pub height: u32,
+    pub image_type: ImageType,
+}
+
+impl ImageResponse {
+    /// Decode the list of images returned by `simGetImages`
+    pub(crate) fn list_from(msgpack: Response) -> Vec<Self> {
+        match msgpack.result {
+            Ok(Value::Array(images)) => images.into_iter().map(Self::from).collect(),
+            _ => panic!("Could not decode result from ImageResponse msgpack"),
+        }
+    }
+}
+
+/// The image data is moved out of the msgpack, a 1080p image is decoded without copying its bytes
+impl From<Value> for ImageResponse {
+    fn from(msgpack: Value) -> Self {
+        let mut payload = match msgpack {
+            Value::Map(payload) => payload,
+            _ => panic!("ImageResponse msgpack is not a map"),
+        };
+        let mut field = |key: &str| -> Value {
+            match payload.iter_mut().find(|(k, _)| k.as_str() == Some(key)) {
+                Some((_, v)) => mem::replace(v, Value::Nil),
+                None => panic!("ImageResponse msgpack is missing the field {key}"),
+            }
+        };
+        let string = |value: Value| match value {
+            Value::String(string) => string.into_str().unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        let image_data_uint8 = match field("image_data_uint8") {
+            Value::Binary(bytes) => bytes,
+            // older servers send the bytes as an array of integers
+            Value::Array(bytes) => bytes.iter().map(|b| b.as_u64().unwrap() as u8).collect(),
+            _ => vec![],
+        };
+        let image_data_float = field("image_data_float")
+            .as_array()
+            .map(|pixels| pixels.iter().map(|p| p.as_f64().unwrap() as f32).collect())
+            .unwrap_or_default();
+
+        Self {
+            image_data_uint8,
+            image_data_float,
+            camera_name: string(field("camera_name")),
+            camera_position: field("camera_position").into(),
+            camera_orientation: field("camera_orientation").into(),
+            time_stamp: field("time_stamp").as_u64().unwrap(),
+            message: string(field("message")),
+            pixels_as_float: field("pixels_as_float").as_bool().unwrap(),
+            compress: field("compress").as_bool().unwrap(),
+            width: field("width").as_u64().unwrap() as u32,
+            height: field("height").as_u64().unwrap() as u32,
+            image_type: field("image_type").into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// 8 bit RGB image, stored row by row starting at the top left pixel, 3 bytes per pixel
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RgbImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl RgbImage {
+    /// RGB image of a response to an uncompressed request, None for compressed or float responses or if the number
+    /// of bytes doesn't match the image size
+    ///
+    /// AirSim sends BGR pixels, or BGRA for some versions, both are converted without decoding any png
+    pub fn from_response(response: &ImageResponse) -> Option<Self> {
+        let pixels = response.width as usize * response.height as usize;
+        if response.pixels_as_float || response.compress || pixels == 0 {
+            return None;
+        }
+        let bytes = response.image_data_uint8.len();
+        let channels = bytes / pixels;
+        if bytes % pixels != 0 || !(channels == 3 || channels == 4) {
+            return None;
+        }
+
+        let data = response
+            .image_data_uint8
+            .chunks_exact(channels)
+            .flat_map(|bgr| [bgr[2], bgr[1], bgr[0]])
+            .collect();
+        Some(Self {
+            width: response.width,
+            height: response.height,
+            data,
+        })
+    }
+
+    /// Color of the pixel in column `u` and row `v`
+    pub fn get(&self, u: u32, v: u32) -> Option<[u8; 3]> {
+        if u >= self.width || v >= self.height {
+            return None;
+        }
+        let i = 3 * (v * self.width + u) as usize;
+        self.data.get(i..i + 3).map(|rgb| [rgb[0], rgb[1], rgb[2]])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Single channel float image, e.g. a depth image, stored row by row starting at the top left pixel
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<f32>,
+}
+
+impl FloatImage {
+    /// Float image of a response to a request with `pixels_as_float`, None for other responses or if the number of
+    /// pixels doesn't match the image size
+    pub fn from_response(response: &ImageResponse) -> Option<Self> {
+        let expected = response.width as usize * response.height as usize;
+        if !response.pixels_as_float || response.image_data_float.len() != expected {
+            return None;
+        }
+
+        Some(Self {
+            width: response.width,
+            height: response.height,
+            data: response.image_data_float.clone(),
+        })
+    }
+
+    /// Value of the pixel in column `u` and row `v`
+    pub fn get(&self, u: u32, v: u32) -> Option<f32> {
+        if u >= self.width || v >= self.height {
+            return None;
+        }
+        self.data.get((v * self.width + u) as usize).copied()
+    }
+
+    /// Encode as a grayscale PFM file, rows are stored bottom to top and `-1` marks little endian floats
+    pub fn to_pfm(&self) -> Vec<u8> {
+        let mut bytes = format!("Pf\n{} {}\n-1\n", self.width, self.height).into_bytes();
+        bytes.reserve(self.data.len() * 4);
+        for row in self.data.chunks(self.width.max(1) as usize).rev() {
+            for pixel in row {
+                bytes.extend_from_slice(&pixel.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decode a grayscale PFM file, in either byte order
+    ///
+    /// args:
+    ///     bytes (&[u8]): content of the file
+    pub fn from_pfm(bytes: &[u8]) -> io::Result<Self> {
+        // header: `Pf`, width, height and scale, separated by whitespace and followed by a single whitespace
+        let mut fields = Vec::with_capacity(4);
+        let mut pos = 0;
+        while fields.len() < 4 {
+            while bytes.get(pos).map_or(false, u8::is_ascii_whitespace) {
+                pos += 1;
+            }
+            let start = pos;
+            while bytes.get(pos).map_or(false, |b| !b.is_ascii_whitespace()) {
+                pos += 1;
+            }
+            if start == pos {
+                return Err(invalid_pfm("truncated header"));
+            }
+            fields.push(std::str::from_utf8(&bytes[start..pos]).map_err(|_| invalid_pfm("header isn't ASCII"))?);
+        }
+        let data = bytes.get(pos + 1..).ok_or_else(|| invalid_pfm("truncated header"))?;
+
+        if fields[0] != "Pf" {
+            return Err(invalid_pfm(format!(
+                "expected a grayscale `Pf` image, got `{}`",
+                fields[0]
+            )));
+        }
+        let size = |field: &str| {
+            field
+                .parse::<u32>()
+                .map_err(|_| invalid_pfm(format!("bad image size `{field}`")))
+        };
+        let (width, height) = (size(fields[1])?, size(fields[2])?);
+        let scale: f32 = fields[3]
+            .parse()
+            .map_err(|_| invalid_pfm(format!("bad scale `{}`", fields[3])))?;
+
+        let expected = width as usize * height as usize * 4;
+        if data.len() != expected {
+            return Err(invalid_pfm(format!(
+                "{} bytes of pixels, expected {expected}",
+                data.len()
+            )));
+        }
+
+        let mut pixels = Vec::with_capacity(expected / 4);
+        for row in data.chunks(width.max(1) as usize * 4).rev() {
+            pixels.extend(row.chunks(4).map(|b| {
+                let b = [b[0], b[1], b[2], b[3]];
+                // negative scale: little endian
+                if scale < 0.0 {
+                    f32::from_le_bytes(b)
+                } else {
+                    f32::from_be_bytes(b)
+                }
+            }));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            data: pixels,
+        })
+    }
+
+    /// Write as a PFM file, see `to_pfm`
+    ///
+    /// args:
+    ///     path (impl AsRef<Path>): file to create or overwrite
+    pub fn write_pfm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_pfm())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Optical flow of an `ImageType::OpticalFlow` image, in pixels, stored row by row starting at the top left pixel
+///
+/// `u` is the horizontal and `v` the vertical component of the apparent motion of each pixel between the previous
+/// frame and this one
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpticalFlowImage {
+    pub width: u32,
+    pub height: u32,
+    pub u: Vec<f32>,
+    pub v: Vec<f32>,
+}
+
+impl OpticalFlowImage {
+    /// Flow field of a response to an `ImageType::OpticalFlow` request with `pixels_as_float`, None for other
+    /// responses or if the number of values doesn't match the image size
+    ///
+    /// The channels of each pixel are interleaved, a third channel sent by some AirSim versions is ignored
+    pub fn from_response(response: &ImageResponse) -> Option<Self> {
+        let pixels = response.width as usize * response.height as usize;
+        if !response.pixels_as_float || !matches!(response.image_type, ImageType::OpticalFlow) || pixels == 0 {
+            return None;
+        }
+        let channels = response.image_data_float.len() / pixels;
+        if !(2..=3).contains(&channels) || response.image_data_float.len() != pixels * channels {
+            return None;
+        }
+
+        let (u, v) = response
+            .image_data_float
+            .chunks(channels)
+            .map(|pixel| (pixel[0], pixel[1]))
+            .unzip();
+        Some(Self {
+            width: response.width,
+            height: response.height,
+            u,
+            v,
+        })
+    }
+
+    /// Flow of the pixel in column `x` and row `y`, as `(u, v)`
+    pub fn get(&self, x: u32, y: u32) -> Option<(f32, f32)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y * self.width + x) as usize;
+        Some((*self.u.get(i)?, *self.v.get(i)?))
+    }
+
+    /// Where the pixel in column `x` and row `y` of the previous frame moved to, in sub-pixel coordinates
+    ///
+    /// args:
+    ///     x (u32): column of the pixel
+    ///     y (u32): row of the pixel
+    pub fn warp(&self, x: u32, y: u32) -> Option<(f32, f32)> {
+        self.get(x, y).map(|(u, v)| (x as f32 + u, y as f32 + v))
+    }
+
+    /// Magnitude of the flow of every pixel, in the same order as `u` and `v`
+    pub fn magnitude(&self) -> FloatImage {
+        FloatImage {
+            width: self.width,
+            height: self.height,
+            data: self.u.iter().zip(&self.v).map(|(u, v)| u.hypot(*v)).collect(),
+        }
+    }
+}
+
+fn invalid_pfm(message: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid PFM: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use msgpack_rpc::message::Response;
+    use msgpack_rpc::Value;
+
+    use crate::{
+        CameraProfile, CompressedImage, FloatImage, ImageEncoding, ImageRequest, ImageResponse, ImageType,
+        OpticalFlowImage, Quaternion, RgbImage, Vector3,
+    };
+
+    #[test]
+    fn test_pfm_round_trip() {
+        let image = FloatImage {
+            width: 3,
+            height: 2,
+            data: vec![1.0, 2.0, 3.0, 4.0, 5.0, f32::INFINITY],
+        };
+        assert_eq!(FloatImage::from_pfm(&image.to_pfm()).unwrap(), image);
+
+        let mut big_endian = b"Pf\n1 2\n1.0\n".to_vec();
+        big_endian.extend_from_slice(&2.0_f32.to_be_bytes());
+        big_endian.extend_from_slice(&1.0_f32.to_be_bytes());
+        assert_eq!(FloatImage::from_pfm(&big_endian).unwrap().data, vec![1.0, 2.0]);
+
+        assert!(FloatImage::from_pfm(b"PF\n1 1\n-1\n").is_err());
+        assert!(FloatImage::from_pfm(b"Pf\n2 2\n-1\n\0\0\0\0").is_err());
+    }
+
+    #[test]
+    fn test_camera_profile() {
+        let requests = CameraProfile::rgbd_segmentation().requests_from(&["front", "rear"]).0;
+        assert_eq!(requests.len(), 6);
+        assert_eq!(requests[3].camera_name, "rear");
+        assert!(requests[4].pixels_as_float);
+        assert!(matches!(requests[5].image_type, ImageType::Segmentation));
+        assert_eq!(requests[5].image_type.as_msgpack(), Value::from(5));
+    }
+
+    #[test]
+    fn test_optical_flow() {
+        let response = ImageResponse {
+            image_data_uint8: vec![],
+            image_data_float: vec![1.0, 0.0, 0.0, -0.5, 3.0, 4.0],
+            camera_name: "front".to_owned(),
+            camera_position: Vector3::new(0.0, 0.0, 0.0),
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            time_stamp: 0,
+            message: String::new(),
+            pixels_as_float: true,
+            compress: false,
+            width: 3,
+            height: 1,
+            image_type: ImageType::OpticalFlow,
+        };
+
+        let flow = OpticalFlowImage::from_response(&response).unwrap();
+        assert_eq!(flow.u, vec![1.0, 0.0, 3.0]);
+        assert_eq!(flow.v, vec![0.0, -0.5, 4.0]);
+        assert_eq!(flow.warp(1, 0), Some((1.0, -0.5)));
+        assert_eq!(flow.warp(0, 1), None);
+        assert_eq!(flow.magnitude().data[2], 5.0);
+    }
+
+    #[test]
+    fn test_image_type_values() {
+        // numbering of ImageType in AirSim's ImageCaptureBase.hpp, Lighting and Annotation from Cosys-AirSim
+        let types = [
+            ImageType::Scene,
+            ImageType::DepthPlanar,
+            ImageType::DepthPerspective,
+            ImageType::DepthVis,
+            ImageType::DisparityNormalized,
+            ImageType::Segmentation,
+            ImageType::SurfaceNormals,
+            ImageType::Infrared,
+            ImageType::OpticalFlow,
+            ImageType::OpticalFlowVis,
+            ImageType::Lighting,
+            ImageType::Annotation,
+        ];
+        for (value, image_type) in types.iter().enumerate() {
+            assert_eq!(image_type.as_msgpack(), Value::from(value));
+            assert_eq!(ImageType::from(Value::from(value)).as_msgpack(), Value::from(value));
+        }
+
+        let request = ImageRequest::annotation("front", "RGBMask").as_msgpack();
+        let fields = request.as_map().unwrap();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[4].1, Value::from("RGBMask"));
+        assert_eq!(ImageRequest::scene("front").as_msgpack().as_map().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_rgb_image_and_encoding() {
+        let mut response = ImageResponse {
+            image_data_uint8: vec![1, 2, 3, 4, 5, 6],
+            image_data_float: vec![],
+            camera_name: "front".to_owned(),
+            camera_position: Vector3::new(0.0, 0.0, 0.0),
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            time_stamp: 0,
+            message: String::new(),
+            pixels_as_float: false,
+            compress: false,
+            width: 2,
+            height: 1,
+            image_type: ImageType::Scene,
+        };
+
+        let image = RgbImage::from_response(&response).unwrap();
+        assert_eq!(image.data, vec![3, 2, 1, 6, 5, 4]);
+        assert_eq!(image.get(1, 0), Some([6, 5, 4]));
+        assert_eq!(image.get(2, 0), None);
+
+        // BGRA drops the alpha channel, png isn't decoded
+        response.image_data_uint8 = vec![1, 2, 3, 255, 4, 5, 6, 255];
+        assert_eq!(RgbImage::from_response(&response).unwrap().data, vec![3, 2, 1, 6, 5, 4]);
+        response.compress = true;
+        assert!(RgbImage::from_response(&response).is_none());
+
+        assert_eq!(
+            ImageEncoding::select(ImageType::DepthPlanar, true),
+            ImageEncoding::Float
+        );
+        assert_eq!(ImageEncoding::select(ImageType::Segmentation, true), ImageEncoding::Png);
+        let request = ImageRequest::with_encoding(
+            "front",
+            ImageType::Scene,
+            ImageEncoding::select(ImageType::Scene, false),
+        );
+        assert!(!request.compress && !request.pixels_as_float);
+        assert_eq!(request.encoding(), ImageEncoding::Raw);
+    }
+
+    #[test]
+    fn test_decode_image_responses() {
+        let vector = |x: f32, y: f32, z: f32| {
+            Value::Map(vec![
+                (Value::from("x_val"), Value::F32(x)),
+                (Value::from("y_val"), Value::F32(y)),
+                (Value::from("z_val"), Value::F32(z)),
+            ])
+        };
+        let image = |name: &str, data: Value| {
+            Value::Map(vec![
+                (Value::from("image_data_uint8"), data),
+                (Value::from("image_data_float"), Value::Array(vec![])),
+                (Value::from("camera_name"), Value::from(name)),
+                (Value::from("camera_position"), vector(1.0, 2.0, -3.0)),
+                (
+                    Value::from("camera_orientation"),
+                    Value::Map(vec![
+                        (Value::from("w_val"), Value::F32(1.0)),
+                        (Value::from("x_val"), Value::F32(0.0)),
+                        (Value::from("y_val"), Value::F32(0.0)),
+                        (Value::from("z_val"), Value::F32(0.0)),
+                    ]),
+                ),
+                (Value::from("time_stamp"), Value::from(42)),
+                (Value::from("message"), Value::from("")),
+                (Value::from("pixels_as_float"), Value::Boolean(false)),
+                (Value::from("compress"), Value::Boolean(false)),
+                (Value::from("width"), Value::from(2)),
+                (Value::from("height"), Value::from(1)),
+                (Value::from("image_type"), Value::from(0)),
+            ])
+        };
+        let response = Response {
+            id: 0,
+            result: Ok(Value::Array(vec![
+                image("front", Value::Binary(vec![1, 2, 3, 4, 5, 6])),
+                // older servers send the bytes as an array of integers
+                image("rear", Value::Array((1..=6).map(Value::from).collect())),
+            ])),
+        };
+
+        let images = ImageResponse::list_from(response);
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].camera_name, "front");
+        assert_eq!(images[0].image_data_uint8, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(images[1].image_data_uint8, images[0].image_data_uint8);
+        assert_eq!(images[1].camera_position, Vector3::new(1.0, 2.0, -3.0));
+        assert_eq!((images[1].time_stamp, images[1].width), (42, 2));
+
+        let png = Response {
+            id: 1,
+            result: Ok(Value::Binary(vec![0x89, b'P', b'N', b'G'])),
+        };
+        assert_eq!(CompressedImage::from(png).0, vec![0x89, b'P', b'N', b'G']);
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How a take off or landing ended
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ManeuverOutcome {
+    /// The vehicle reached the desired landed state
+    Success,
+    /// The vehicle didn't reach the desired landed state before the timeout
+    Timeout {
+        /// altitude above the vehicle's starting point in meters, when the maneuver ended
+        final_altitude: f32,
+    },
+    /// The vehicle collided during the maneuver
+    Collision,
+}
+
+impl ManeuverOutcome {
+    pub fn is_success(&self) -> bool {
+        *self == ManeuverOutcome::Success
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use msgpack_rpc::{message::Response, Value};
+
+use crate::{Quaternion, Vector3};
+
+#[derive(Debug, Clone)]
+/// Geometry of a static mesh of the scene, as returned by `simGetMeshPositionVertexBuffers`
+///
+/// Vertices are sent as is by AirSim, in the Unreal world frame and in centimeters
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshData {
+    /// name of the mesh component
+    pub name: String,
+    /// position of the mesh
+    pub position: Vector3,
+    /// orientation of the mesh
+    pub orientation: Quaternion,
+    /// x, y and z of every vertex, one after the other
+    pub vertices: Vec<f32>,
+    /// indices of the vertices of every triangle, three by three
+    pub indices: Vec<u32>,
+}
+
+impl MeshData {
+    /// Decode the list of meshes returned by `simGetMeshPositionVertexBuffers`
+    pub(crate) fn list_from(msgpack: Response) -> Vec<Self> {
+        match msgpack.result {
+            Ok(res) => res.as_array().unwrap().iter().cloned().map(Self::from).collect(),
+            Err(_) => panic!("Could not decode result from MeshData msgpack"),
+        }
+    }
+
+    /// Number of vertices of the mesh
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / 3
+    }
+
+    /// Vertex indices of every triangle of the mesh
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]])
+    }
+
+    /// Write meshes to a Wavefront OBJ file, one object per mesh
+    ///
+    /// args:
+    ///     meshes (&[MeshData]): meshes to write, e.g. the whole scene
+    ///     path (impl AsRef<Path>): file to create or overwrite
+    pub fn write_obj(meshes: &[MeshData], path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        encode_obj(meshes, &mut writer)?;
+        writer.flush()
+    }
+
+    /// Write meshes to a single binary PLY file
+    ///
+    /// args:
+    ///     meshes (&[MeshData]): meshes to write, e.g. the whole scene
+    ///     path (impl AsRef<Path>): file to create or overwrite
+    pub fn write_ply(meshes: &[MeshData], path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        encode_ply(meshes, &mut writer)?;
+        writer.flush()
+    }
+}
+
+impl From<Value> for MeshData {
+    fn from(msgpack: Value) -> Self {
+        let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
+        let field = |key: &str| -> Value {
+            match payload.iter().find(|(k, _)| k.as_str() == Some(key)) {
+                Some((_, v)) => v.to_owned(),
+                None => panic!("MeshData msgpack is missing the field {key}"),
+            }
+        };
+
+        let vertices = field("vertices")
+            .as_array()
+            .map(|values| values.iter().map(|v| v.as_f64().unwrap() as f32).collect())
+            .unwrap_or_default();
+        let indices = field("indices")
+            .as_array()
+            .map(|values| values.iter().map(|i| i.as_u64().unwrap() as u32).collect())
+            .unwrap_or_default();
+
+        Self {
+            name: field("name").as_str().unwrap_or_default().to_owned(),
+            position: field("position").into(),
+            orientation: field("orientation").into(),
+            vertices,
+            indices,
+        }
+    }
+}
+
+/// OBJ indices start at 1 and count the vertices of all previous objects
+fn encode_obj(meshes: &[MeshData], writer: &mut impl Write) -> io::Result<()> {
+    let mut offset = 1;
+    for mesh in meshes {
+        writeln!(writer, "o {}", mesh.name)?;
+        for v in mesh.vertices.chunks_exact(3) {
+            writeln!(writer, "v {} {} {}", v[0], v[1], v[2])?;
+        }
+        for [a, b, c] in mesh.triangles() {
+            writeln!(writer, "f {} {} {}", a + offset, b + offset, c + offset)?;
+        }
+        offset += mesh.vertex_count() as u32;
+    }
+    Ok(())
+}
+
+fn encode_ply(meshes: &[MeshData], writer: &mut impl Write) -> io::Result<()> {
+    let vertex_count: usize = meshes.iter().map(MeshData::vertex_count).sum();
+    let face_count: usize = meshes.iter().map(|mesh| mesh.indices.len() / 3).sum();
+
+    write!(
+        writer,
+        "ply\nformat binary_little_endian 1.0\nelement vertex {vertex_count}\nproperty float x\nproperty float y\n\
+         property float z\nelement face {face_count}\nproperty list uchar uint vertex_indices\nend_header\n"
+    )?;
+    for mesh in meshes {
+        for value in &mesh.vertices[..mesh.vertex_count() * 3] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    let mut offset = 0;
+    for mesh in meshes {
+        for triangle in mesh.triangles() {
+            writer.write_all(&[3])?;
+            for index in triangle {
+                writer.write_all(&(index + offset).to_le_bytes())?;
+            }
+        }
+        offset += mesh.vertex_count() as u32;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::mesh::{encode_obj, encode_ply};
+    use crate::{MeshData, Quaternion, Vector3};
+
+    fn triangle(name: &str) -> MeshData {
+        MeshData {
+            name: name.to_owned(),
+            position: Vector3::new(0.0, 0.0, 0.0),
+            orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            indices: vec![0, 1, 2],
+        }
+    }
+
+    #[test]
+    fn test_obj_indices_are_offset_per_mesh() {
+        let mut obj = vec![];
+        encode_obj(&[triangle("floor"), triangle("wall")], &mut obj).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+
+        let faces: Vec<&str> = obj.lines().filter(|line| line.starts_with("f ")).collect();
+        assert_eq!(faces, vec!["f 1 2 3", "f 4 5 6"]);
+        assert!(obj.starts_with("o floor\nv 0 0 0\n"));
+
+        let mut ply = vec![];
+        encode_ply(&[triangle("floor"), triangle("wall")], &mut ply).unwrap();
+        let header_end = b"end_header\n";
+        let body = ply.windows(header_end.len()).position(|w| w == header_end).unwrap() + header_end.len();
+        // 6 vertices of 3 floats, 2 faces of a count and 3 indices
+        assert_eq!(ply.len() - body, 6 * 12 + 2 * 13);
+    }
+}
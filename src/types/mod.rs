@@ -1,17 +1,39 @@
+pub mod agl;
+pub mod camera_info;
 pub mod collision_info;
+pub mod debug_plot;
+pub mod detection;
+pub mod distortion;
 pub mod drive_train;
+pub mod float_encoding;
 pub mod gains;
+#[cfg(feature = "racing")]
+pub mod gate;
+pub mod geo;
 pub mod geopoint;
+#[cfg(feature = "colosseum")]
+pub mod gpu_lidar;
 pub mod image;
+pub mod maneuver;
+pub mod mesh;
+pub mod move_options;
 pub mod multi_rotor_state;
 pub mod path;
+pub mod polar_scan;
 pub mod pose;
 pub mod pwm;
 pub mod rc_data;
+pub mod retry_policy;
 pub mod rotor_states;
+pub mod settings;
 pub mod simulation;
+pub mod snapshot;
+pub mod stereo;
+pub mod time_of_day;
+pub mod timestamped;
 pub mod vector;
 pub mod weather;
+pub mod wrench;
 pub mod yaw_mode;
 pub mod sensors;
 pub mod quaternion;
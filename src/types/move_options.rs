@@ -0,0 +1,71 @@
+use crate::{DrivetrainType, YawMode};
+
+#[derive(Debug, Clone, Copy)]
+/// Options shared by the movement APIs of the multirotor client
+///
+/// Defaults match the AirSim python client, apart from `velocity` which has no default there:
+/// 5 m/s, no timeout, `MaxDegreeOfFreedom` drivetrain, zero yaw rate, lookahead `-1` and adaptive lookahead `1`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveOptions {
+    /// desired velocity in NED frame of the vehicle
+    pub velocity: f32,
+    /// timeout for the vehicle to reach the goal
+    pub timeout_sec: f32,
+    /// when ForwardOnly, vehicle rotates itself so that its front is always facing the direction of travel. If MaxDegreeOfFreedom then it doesn't do that (crab-like movement)
+    pub drivetrain: DrivetrainType,
+    /// specifies if vehicle should face at given angle (is_rate=False) or should be rotating around its axis at given rate (is_rate=True)
+    pub yaw_mode: YawMode,
+    /// how far ahead on the path the carrot is placed, `-1` lets AirSim decide
+    pub lookahead: f32,
+    /// scales the lookahead with the velocity
+    pub adaptive_lookahead: f32,
+}
+
+impl Default for MoveOptions {
+    fn default() -> Self {
+        Self {
+            velocity: 5.0,
+            timeout_sec: 3e38,
+            drivetrain: DrivetrainType::MaxDegreeOfFreedom,
+            yaw_mode: YawMode::rate(0.0),
+            lookahead: -1.0,
+            adaptive_lookahead: 1.0,
+        }
+    }
+}
+
+impl MoveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn velocity(mut self, velocity: f32) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn timeout_sec(mut self, timeout_sec: f32) -> Self {
+        self.timeout_sec = timeout_sec;
+        self
+    }
+
+    pub fn drivetrain(mut self, drivetrain: DrivetrainType) -> Self {
+        self.drivetrain = drivetrain;
+        self
+    }
+
+    pub fn yaw_mode(mut self, yaw_mode: YawMode) -> Self {
+        self.yaw_mode = yaw_mode;
+        self
+    }
+
+    pub fn lookahead(mut self, lookahead: f32) -> Self {
+        self.lookahead = lookahead;
+        self
+    }
+
+    pub fn adaptive_lookahead(mut self, adaptive_lookahead: f32) -> Self {
+        self.adaptive_lookahead = adaptive_lookahead;
+        self
+    }
+}
@@ -4,7 +4,8 @@ use crate::GeoPoint;
 
 use super::{collision_info::CollisionInfo, pose::KinematicsState, rc_data::RCDataState};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LandedState {
     Landed, // 0
     Flying, // 1
@@ -24,49 +25,102 @@ impl From<Value> for LandedState {
 }
 
 #[derive(Debug, Clone)]
+/// State of a multirotor as reported by `getMultirotorState`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultiRotorState {
+    /// latest collision of the vehicle
     pub collision: CollisionInfo,
+    /// kinematics estimated by the flight controller
     pub kinematics_estimated: KinematicsState,
+    /// location reported by the GPS
     pub gps_location: GeoPoint,
+    /// timestamp of the state in nanoseconds
     pub timestamp: u64,
+    /// whether the vehicle is on the ground or in the air
     pub landed_state: LandedState,
+    /// state of the remote control
     pub rc_data: RCDataState,
+    /// whether the flight controller is ready to fly, false if the server doesn't report it
+    pub ready: bool,
+    /// reason why the flight controller isn't ready, empty if it is
+    pub ready_message: String,
+    /// whether the vehicle can be armed, false if the server doesn't report it
+    pub can_arm: bool,
 }
 
-impl From<Response> for MultiRotorState {
-    fn from(msgpack: Response) -> Self {
-        match msgpack.result {
-            Ok(res) => {
-                let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
+impl MultiRotorState {
+    pub fn is_landed(&self) -> bool {
+        self.landed_state == LandedState::Landed
+    }
 
-                // collision
-                let collision: CollisionInfo = payload[0].1.to_owned().into();
+    pub fn is_flying(&self) -> bool {
+        self.landed_state == LandedState::Flying
+    }
 
-                // kinematics estimated
-                let kinematics_estimated: KinematicsState = payload[1].1.to_owned().into();
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
 
-                // gps location
-                let gps_location: GeoPoint = payload[2].1.to_owned().into();
+    pub fn has_collided(&self) -> bool {
+        self.collision.has_collided
+    }
+}
 
-                // timestamp
-                let timestamp = payload[3].1.as_u64().unwrap();
+/// Value of the field named `key` in a msgpack map
+fn field<'a>(payload: &'a [(Value, Value)], key: &str) -> Option<&'a Value> {
+    payload.iter().find(|(k, _)| k.as_str() == Some(key)).map(|(_, v)| v)
+}
 
-                // landed state
-                let landed_state: LandedState = payload[4].1.to_owned().into();
+/// Value of the field named `key` in a msgpack map, panics if the field is missing
+fn required_field(payload: &[(Value, Value)], key: &str) -> Value {
+    match field(payload, key) {
+        Some(value) => value.to_owned(),
+        None => panic!("MultiRotorState msgpack is missing the field {key}"),
+    }
+}
 
-                // rc data
-                let rc_data: RCDataState = payload[5].1.to_owned().into();
+impl From<Response> for MultiRotorState {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(res) => {
+                let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
 
                 Self {
-                    collision,
-                    kinematics_estimated,
-                    gps_location,
-                    timestamp,
-                    landed_state,
-                    rc_data,
+                    collision: required_field(payload, "collision").into(),
+                    kinematics_estimated: required_field(payload, "kinematics_estimated").into(),
+                    gps_location: required_field(payload, "gps_location").into(),
+                    timestamp: required_field(payload, "timestamp").as_u64().unwrap(),
+                    landed_state: required_field(payload, "landed_state").into(),
+                    rc_data: required_field(payload, "rc_data").into(),
+                    ready: field(payload, "ready").and_then(Value::as_bool).unwrap_or(false),
+                    ready_message: field(payload, "ready_message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_owned(),
+                    can_arm: field(payload, "can_arm").and_then(Value::as_bool).unwrap_or(false),
                 }
             }
             Err(_) => panic!("Could not decode result from MultiRotorState msgpack"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::types::multi_rotor_state::field;
+    use msgpack_rpc::{Utf8String, Value};
+
+    #[test]
+    fn test_field_by_key() {
+        let ready: Utf8String = "ready".into();
+        let timestamp: Utf8String = "timestamp".into();
+        let payload = vec![
+            (Value::String(timestamp), Value::Integer(42.into())),
+            (Value::String(ready), Value::Boolean(true)),
+        ];
+
+        assert_eq!(field(&payload, "ready").and_then(Value::as_bool), Some(true));
+        assert_eq!(field(&payload, "timestamp").and_then(Value::as_u64), Some(42));
+        assert!(field(&payload, "can_arm").is_none());
+    }
+}
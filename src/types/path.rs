@@ -1,8 +1,11 @@
+use std::cmp::Ordering;
+
 use msgpack_rpc::Value;
 
 use crate::Vector3;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Path(pub Vec<Vector3>);
 
 impl Path {
@@ -11,3 +14,168 @@ impl Path {
         Value::Array(v3_msgpack)
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Progress of a vehicle along a `Path`, as tracked by a `PathTracker`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathProgress {
+    /// number of waypoints reached, the index of the next waypoint while it's lower than the length of the path
+    pub completed: usize,
+    /// index of the waypoint closest to the vehicle
+    pub nearest_waypoint: usize,
+    /// distance from the vehicle to the segment it is flying, in meters
+    pub cross_track_error: f32,
+    /// distance from the vehicle to the next waypoint, in meters. 0 once the path is completed
+    pub distance_to_next: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Event of a vehicle following a `Path`, see `MultiRotorClient::move_on_path_monitored`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathEvent {
+    /// The waypoint of the given index was reached, waypoints are reached in order
+    WaypointReached(usize),
+    /// Progress at a sample of the vehicle position
+    Progress(PathProgress),
+}
+
+#[derive(Debug, Clone)]
+/// Tracks the progress of a vehicle along a `Path` from samples of its position
+///
+/// As `moveOnPath`, the path starts at the first sampled position. A waypoint is reached when the vehicle comes
+/// within the acceptance radius or gets closer to the segment after the waypoint than to the one before it, as the
+/// lookahead of `moveOnPath` makes the vehicle cut corners
+pub struct PathTracker {
+    waypoints: Vec<Vector3>,
+    acceptance_radius: f32,
+    start: Option<Vector3>,
+    completed: usize,
+}
+
+impl PathTracker {
+    /// Panics on a path without waypoints
+    ///
+    /// args:
+    ///     path (&Path): Waypoints the vehicle follows
+    ///     acceptance_radius (f32): Distance to a waypoint it counts as reached from, in meters
+    pub fn new(path: &Path, acceptance_radius: f32) -> Self {
+        if path.0.is_empty() {
+            panic!("the path to track has no waypoint")
+        }
+        Self {
+            waypoints: path.0.clone(),
+            acceptance_radius,
+            start: None,
+            completed: 0,
+        }
+    }
+
+    /// Number of waypoints reached
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// Update the progress with a sample of the vehicle position, returns the waypoints reached since the previous
+    /// sample followed by the progress
+    pub fn update(&mut self, position: Vector3) -> Vec<PathEvent> {
+        let start = *self.start.get_or_insert(position);
+        let mut events = vec![];
+
+        while self.completed < self.waypoints.len() {
+            let next = self.waypoints[self.completed];
+            let from = self.completed.checked_sub(1).map_or(start, |i| self.waypoints[i]);
+            let within_radius = (position - next).norm() <= self.acceptance_radius;
+            let past_corner = match self.waypoints.get(self.completed + 1) {
+                Some(&after) => segment_distance(position, next, after) < segment_distance(position, from, next),
+                None => false,
+            };
+            if !(within_radius || past_corner) {
+                break;
+            }
+            events.push(PathEvent::WaypointReached(self.completed));
+            self.completed += 1;
+        }
+
+        let (segment, distance_to_next) = match self.waypoints.get(self.completed) {
+            Some(&next) => (self.completed, (position - next).norm()),
+            None => (self.waypoints.len() - 1, 0.0),
+        };
+        let from = segment.checked_sub(1).map_or(start, |i| self.waypoints[i]);
+        let nearest_waypoint = (0..self.waypoints.len())
+            .min_by(|&a, &b| {
+                let a = (position - self.waypoints[a]).norm();
+                let b = (position - self.waypoints[b]).norm();
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(0);
+
+        events.push(PathEvent::Progress(PathProgress {
+            completed: self.completed,
+            nearest_waypoint,
+            cross_track_error: segment_distance(position, from, self.waypoints[segment]),
+            distance_to_next,
+        }));
+        events
+    }
+}
+
+/// Distance from `point` to the segment between `a` and `b`
+fn segment_distance(point: Vector3, a: Vector3, b: Vector3) -> f32 {
+    let ab = b - a;
+    let length_squared = ab.norm_squared();
+    if length_squared == 0.0 {
+        return (point - a).norm();
+    }
+    let t = ((point - a).dot(&ab) / length_squared).clamp(0.0, 1.0);
+    (point - (a + ab * t)).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Path, PathEvent, PathTracker, Vector3};
+
+    fn progress(events: &[PathEvent]) -> (usize, f32) {
+        match events.last() {
+            Some(PathEvent::Progress(progress)) => (progress.completed, progress.cross_track_error),
+            _ => panic!("the last event has to be the progress"),
+        }
+    }
+
+    #[test]
+    fn test_tracker_reaches_waypoints_in_order() {
+        let path = Path(vec![
+            Vector3::new(10.0, 0.0, -5.0),
+            Vector3::new(10.0, 10.0, -5.0),
+            Vector3::new(0.0, 10.0, -5.0),
+        ]);
+        let mut tracker = PathTracker::new(&path, 1.0);
+
+        let events = tracker.update(Vector3::new(0.0, 0.0, -5.0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(progress(&events), (0, 0.0));
+
+        // off track by 2 m on the first leg
+        let events = tracker.update(Vector3::new(5.0, 2.0, -5.0));
+        assert_eq!(progress(&events), (0, 2.0));
+
+        // cutting the first corner
+        let events = tracker.update(Vector3::new(9.0, 2.0, -5.0));
+        assert_eq!(events[0], PathEvent::WaypointReached(0));
+        assert_eq!(progress(&events), (1, 1.0));
+
+        // the last waypoint is only reached within the radius
+        let events = tracker.update(Vector3::new(1.5, 10.0, -5.0));
+        assert_eq!(events[0], PathEvent::WaypointReached(1));
+        assert_eq!(progress(&events).0, 2);
+        let events = tracker.update(Vector3::new(0.5, 10.0, -5.0));
+        assert_eq!(events[0], PathEvent::WaypointReached(2));
+        assert_eq!(tracker.completed(), 3);
+        match events[1] {
+            PathEvent::Progress(progress) => {
+                assert_eq!(progress.nearest_waypoint, 2);
+                assert_eq!(progress.distance_to_next, 0.0);
+            }
+            _ => panic!("expected the progress after the waypoint"),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+
+use crate::types::sensors::DistanceSensorData;
+use crate::Quaternionr;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Planar scan assembled from several distance sensors, like the scan of a 2D lidar
+///
+/// Beams are sorted by angle. Sensors that didn't hit anything within their range read `f32::INFINITY`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolarScan {
+    /// yaw of every beam relative to the front of the vehicle in radians, in [-π, π), positive to the right
+    pub angles: Vec<f32>,
+    /// distance measured by every beam, in meters
+    pub ranges: Vec<f32>,
+    /// timestamp of the latest reading in nanoseconds
+    pub timestamp: u64,
+}
+
+impl PolarScan {
+    /// Assemble the readings of distance sensors, using the yaw of their pose relative to the vehicle
+    ///
+    /// args:
+    ///     readings (&[DistanceSensorData]): one reading per sensor, in any order
+    pub fn from_readings(readings: &[DistanceSensorData]) -> Self {
+        let mut beams: Vec<(f32, f32)> = readings
+            .iter()
+            .map(|reading| {
+                let (_, _, yaw) = Quaternionr::from(reading.relative_pose.orientation).to_euler_angles();
+                let angle = (yaw + PI).rem_euclid(2.0 * PI) - PI;
+                let range = if reading.distance >= reading.max_distance {
+                    f32::INFINITY
+                } else {
+                    reading.distance
+                };
+                (angle, range)
+            })
+            .collect();
+        beams.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        Self {
+            angles: beams.iter().map(|(angle, _)| *angle).collect(),
+            ranges: beams.iter().map(|(_, range)| *range).collect(),
+            timestamp: readings.iter().map(|reading| reading.timestamp).max().unwrap_or(0),
+        }
+    }
+
+    /// Angle and range of the closest obstacle, None if no beam hit anything
+    pub fn closest(&self) -> Option<(f32, f32)> {
+        self.angles
+            .iter()
+            .copied()
+            .zip(self.ranges.iter().copied())
+            .filter(|(_, range)| range.is_finite())
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+    }
+
+    /// Shortest range measured by the beams between two angles, `f32::INFINITY` if none hit anything
+    ///
+    /// args:
+    ///     from (f32): first angle of the sector in radians
+    ///     to (f32): last angle of the sector in radians, the sector wraps around behind the vehicle if `to < from`
+    pub fn min_range(&self, from: f32, to: f32) -> f32 {
+        self.angles
+            .iter()
+            .zip(&self.ranges)
+            .filter(|(angle, _)| {
+                if from <= to {
+                    (from..=to).contains(*angle)
+                } else {
+                    **angle >= from || **angle <= to
+                }
+            })
+            .map(|(_, range)| *range)
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::types::polar_scan::PolarScan;
+    use crate::types::sensors::DistanceSensorData;
+    use crate::{Pose3, Position3, Quaternionr};
+
+    fn reading(yaw: f32, distance: f32) -> DistanceSensorData {
+        DistanceSensorData {
+            timestamp: 10,
+            distance,
+            min_distance: 0.2,
+            max_distance: 40.0,
+            relative_pose: Pose3::new(
+                Position3::new(0.0, 0.0, 0.0),
+                Quaternionr::from_euler(0.0, 0.0, yaw).into(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_scan_from_readings() {
+        let scan = PolarScan::from_readings(&[reading(FRAC_PI_2, 3.0), reading(0.0, 40.0), reading(-FRAC_PI_2, 5.0)]);
+
+        assert!((scan.angles[0] + FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(scan.ranges, vec![5.0, f32::INFINITY, 3.0]);
+        assert_eq!(scan.closest().unwrap().1, 3.0);
+        assert_eq!(scan.min_range(-0.1, 0.1), f32::INFINITY);
+        assert_eq!(scan.min_range(1.0, -1.0), 3.0_f32.min(5.0));
+    }
+}
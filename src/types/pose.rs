@@ -1,8 +1,74 @@
 use msgpack_rpc::{message::Response, Utf8String, Value};
+use nalgebra::{Isometry3, Translation3, UnitQuaternion};
 
-use crate::Vector3;
+use crate::{Quaternionr, Vector2, Vector3};
+
+/// Component-wise arithmetic, euclidean norm and `Display` of a vector-like struct
+macro_rules! impl_vector_ops {
+    ($name:ident { $($field:ident),+ }) => {
+        impl $name {
+            /// Euclidean norm
+            pub fn norm(&self) -> f32 {
+                self.norm_squared().sqrt()
+            }
+
+            pub fn norm_squared(&self) -> f32 {
+                0.0 $(+ self.$field * self.$field)+
+            }
+        }
+
+        impl std::ops::Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl std::ops::Mul<f32> for $name {
+            type Output = Self;
+
+            fn mul(self, scale: f32) -> Self {
+                Self { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let components = [$(self.$field),+];
+                write!(f, "(")?;
+                for (i, component) in components.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    std::fmt::Display::fmt(component, f)?;
+                }
+                write!(f, ")")
+            }
+        }
+    };
+}
+
+pub(crate) use impl_vector_ops;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position3 {
     pub x: f32,
     pub y: f32,
@@ -13,6 +79,24 @@ impl Position3 {
     pub fn new(x: f32, y: f32, z: f32) -> Self {
         Position3 { x, y, z }
     }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        Vector3::new(self.x, self.y, self.z).as_msgpack()
+    }
+}
+
+impl_vector_ops!(Position3 { x, y, z });
+
+impl From<Vector3> for Position3 {
+    fn from(vector: Vector3) -> Self {
+        Position3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Position3> for Vector3 {
+    fn from(position: Position3) -> Self {
+        Vector3::new(position.x, position.y, position.z)
+    }
 }
 
 impl From<Value> for Position3 {
@@ -30,6 +114,7 @@ impl From<Value> for Position3 {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Orientation3 {
     /// roll angle, in radians
     pub roll: f32,
@@ -60,6 +145,7 @@ impl From<Value> for Quaternion {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quaternion {
     pub w: f32,
     pub x: f32,
@@ -71,9 +157,14 @@ impl Quaternion {
     pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
         Self { w, x, y, z }
     }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        Quaternionr::from(*self).as_msgpack()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pose3 {
     pub position: Position3,
     pub orientation: Quaternion,
@@ -84,6 +175,29 @@ impl Pose3 {
         Self { position, orientation }
     }
 
+    /// Pose without translation nor rotation
+    pub fn identity() -> Self {
+        Self::new(Position3::new(0.0, 0.0, 0.0), Quaternion::new(1.0, 0.0, 0.0, 0.0))
+    }
+
+    /// Pose of `other`, expressed in the frame of this pose, in the parent frame of this pose
+    ///
+    /// E.g. the world pose of a sensor is the vehicle's world pose composed with the sensor's relative pose
+    pub fn compose(&self, other: &Pose3) -> Pose3 {
+        (Isometry3::from(*self) * Isometry3::from(*other)).into()
+    }
+
+    /// Pose of the parent frame, expressed in the frame of this pose
+    pub fn inverse(&self) -> Pose3 {
+        Isometry3::from(*self).inverse().into()
+    }
+
+    /// Point expressed in the frame of this pose, in the parent frame of this pose
+    pub fn transform_point(&self, point: Vector3) -> Vector3 {
+        let p = Isometry3::from(*self).transform_point(&nalgebra::Point3::new(point.x, point.y, point.z));
+        Vector3::new(p.x, p.y, p.z)
+    }
+
     pub(crate) fn as_msgpack(&self) -> Value {
         // position
         let x_val: Utf8String = "x_val".into();
@@ -127,20 +241,42 @@ impl Pose3 {
     }
 }
 
+/// The orientation is normalized, nalgebra's isometries only hold unit quaternions
+impl From<Pose3> for Isometry3<f32> {
+    fn from(pose: Pose3) -> Self {
+        let q = pose.orientation;
+        Isometry3::from_parts(
+            Translation3::new(pose.position.x, pose.position.y, pose.position.z),
+            UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(q.w, q.x, q.y, q.z)),
+        )
+    }
+}
+
+impl From<Isometry3<f32>> for Pose3 {
+    fn from(isometry: Isometry3<f32>) -> Self {
+        let t = isometry.translation;
+        let q = isometry.rotation;
+        Self::new(Position3::new(t.x, t.y, t.z), Quaternion::new(q.w, q.i, q.j, q.k))
+    }
+}
+
+impl Default for Pose3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
 impl From<Response> for Pose3 {
     fn from(msgpack: Response) -> Self {
-        println!("\n received pose: {msgpack:?} \n \n");
         match msgpack.result {
             Ok(res) => {
                 let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
 
                 // position
                 let position: Position3 = payload[0].1.to_owned().into();
-                // println!("pose3 position: {position:?}");
 
                 // orientation
                 let orientation: Quaternion = payload[1].1.to_owned().into();
-                // println!("pose3 orientation: {orientation:?}");
 
                 Self { position, orientation }
             }
@@ -159,6 +295,7 @@ impl From<Value> for Pose3 {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Orientation2 {
     /// roll angle, in radians
     pub roll: f32,
@@ -173,6 +310,7 @@ impl Orientation2 {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Velocity3 {
     pub vx: f32,
     pub vy: f32,
@@ -185,7 +323,22 @@ impl Velocity3 {
     }
 }
 
+impl_vector_ops!(Velocity3 { vx, vy, vz });
+
+impl From<Vector3> for Velocity3 {
+    fn from(vector: Vector3) -> Self {
+        Velocity3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Velocity3> for Vector3 {
+    fn from(velocity: Velocity3) -> Self {
+        Vector3::new(velocity.vx, velocity.vy, velocity.vz)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Velocity2 {
     pub vx: f32,
     pub vy: f32,
@@ -197,13 +350,28 @@ impl Velocity2 {
     }
 }
 
+impl_vector_ops!(Velocity2 { vx, vy });
+
+impl From<Vector2> for Velocity2 {
+    fn from(vector: Vector2) -> Self {
+        Velocity2::new(vector.x, vector.y)
+    }
+}
+
+impl From<Velocity2> for Vector2 {
+    fn from(velocity: Velocity2) -> Self {
+        Vector2::new(velocity.vx, velocity.vy)
+    }
+}
+
 /// The kinematic state of the vehicle
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KinematicsState {
     /// position in the frame of the vehicle's starting point
     pub position: Position3,
     /// orientation in the frame of the vehicle's starting point
-    pub orientation: Orientation3,
+    pub orientation: Quaternion,
     /// linear velocity in ENU body frame
     pub linear_velocity: Vector3,
     /// angular velocity in ENU body frame
@@ -217,7 +385,7 @@ pub struct KinematicsState {
 impl KinematicsState {
     pub fn new(
         position: Position3,
-        orientation: Orientation3,
+        orientation: Quaternion,
         linear_velocity: Vector3,
         angular_velocity: Vector3,
         linear_acceleration: Vector3,
@@ -232,65 +400,42 @@ impl KinematicsState {
             angular_acceleration,
         }
     }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        let position: Utf8String = "position".into();
+        let orientation: Utf8String = "orientation".into();
+        let linear_velocity: Utf8String = "linear_velocity".into();
+        let angular_velocity: Utf8String = "angular_velocity".into();
+        let linear_acceleration: Utf8String = "linear_acceleration".into();
+        let angular_acceleration: Utf8String = "angular_acceleration".into();
+
+        Value::Map(vec![
+            (Value::String(position), self.position.as_msgpack()),
+            (Value::String(orientation), self.orientation.as_msgpack()),
+            (Value::String(linear_velocity), self.linear_velocity.as_msgpack()),
+            (Value::String(angular_velocity), self.angular_velocity.as_msgpack()),
+            (
+                Value::String(linear_acceleration),
+                self.linear_acceleration.as_msgpack(),
+            ),
+            (
+                Value::String(angular_acceleration),
+                self.angular_acceleration.as_msgpack(),
+            ),
+        ])
+    }
 }
 
 impl From<Value> for KinematicsState {
     fn from(msgpack: Value) -> Self {
         let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
 
-        // position
-        let mut points = vec![];
-        let position_msgpack: &Vec<(Value, Value)> = payload[0].1.as_map().unwrap();
-        for (_, v) in position_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let position = Position3::new(points[0], points[1], points[2]);
-
-        // orientation
-        let mut points = vec![];
-        let orientation_msgpack: &Vec<(Value, Value)> = payload[1].1.as_map().unwrap();
-        for (_, v) in orientation_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let orientation = Orientation3::new(points[0], points[1], points[2]);
-
-        // linear velocity
-        let mut points = vec![];
-        let linear_velocity_msgpack: &Vec<(Value, Value)> = payload[2].1.as_map().unwrap();
-        for (_, v) in linear_velocity_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let linear_velocity = Vector3::new(points[0], points[1], points[2]);
-
-        // angular velocity
-        let mut points = vec![];
-        let angular_velocity_msgpack: &Vec<(Value, Value)> = payload[3].1.as_map().unwrap();
-        for (_, v) in angular_velocity_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let angular_velocity = Vector3::new(points[0], points[1], points[2]);
-
-        // linear acceleration
-        let mut points = vec![];
-        let linear_acceleration_msgpack: &Vec<(Value, Value)> = payload[4].1.as_map().unwrap();
-        for (_, v) in linear_acceleration_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let linear_acceleration = Vector3::new(points[0], points[1], points[2]);
-
-        // linear acceleration
-        let mut points = vec![];
-        let angular_acceleration_msgpack: &Vec<(Value, Value)> = payload[5].1.as_map().unwrap();
-        for (_, v) in angular_acceleration_msgpack {
-            let p = v.as_f64().unwrap() as f32;
-            points.push(p);
-        }
-        let angular_acceleration = Vector3::new(points[0], points[1], points[2]);
+        let position: Position3 = payload[0].1.to_owned().into();
+        let orientation: Quaternion = payload[1].1.to_owned().into();
+        let linear_velocity: Vector3 = payload[2].1.to_owned().into();
+        let angular_velocity: Vector3 = payload[3].1.to_owned().into();
+        let linear_acceleration: Vector3 = payload[4].1.to_owned().into();
+        let angular_acceleration: Vector3 = payload[5].1.to_owned().into();
 
         Self {
             position,
@@ -302,3 +447,72 @@ impl From<Value> for KinematicsState {
         }
     }
 }
+
+impl From<Response> for KinematicsState {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(res) => res.into(),
+            Err(_) => panic!("Could not decode result from KinematicsState msgpack"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Pose3, Position3, Quaternionr, Vector3, Velocity2, Velocity3};
+    use std::f32::consts::FRAC_PI_2;
+
+    fn yawed(x: f32, y: f32, yaw: f32) -> Pose3 {
+        Pose3::new(Position3::new(x, y, 0.0), Quaternionr::from_euler(0.0, 0.0, yaw).into())
+    }
+
+    #[test]
+    fn test_transform_point() {
+        // a sensor 1 m in front of a vehicle facing east is 1 m east of the vehicle
+        let vehicle = yawed(10.0, 0.0, FRAC_PI_2);
+        let p = vehicle.transform_point(Vector3::new(1.0, 0.0, 0.0));
+        assert!((p.x - 10.0).abs() < 1e-5);
+        assert!((p.y - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_compose_inverse() {
+        let pose = yawed(3.0, -2.0, 0.7);
+        let identity = pose.compose(&pose.inverse());
+        assert!(identity.position.x.abs() < 1e-5);
+        assert!(identity.position.y.abs() < 1e-5);
+        assert!((identity.orientation.w.abs() - 1.0).abs() < 1e-5);
+
+        let sensor = yawed(1.0, 0.0, 0.0);
+        let world = pose.compose(&sensor);
+        let expected = pose.transform_point(Vector3::new(1.0, 0.0, 0.0));
+        assert!((world.position.x - expected.x).abs() < 1e-5);
+        assert!((world.position.y - expected.y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_isometry_roundtrip() {
+        let pose = yawed(3.0, -2.0, 0.7);
+        let isometry = nalgebra::Isometry3::<f32>::from(pose);
+        assert_eq!(isometry.translation.vector, nalgebra::Vector3::new(3.0, -2.0, 0.0));
+        assert!((isometry.rotation.euler_angles().2 - 0.7).abs() < 1e-5);
+
+        let back = Pose3::from(isometry);
+        assert_eq!(back.position.x, 3.0);
+        assert!((back.orientation.w - pose.orientation.w).abs() < 1e-6);
+        assert!((back.orientation.z - pose.orientation.z).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_vector_ops() {
+        let position = Position3::new(1.0, 2.0, -3.0) + Position3::from(Vector3::new(2.0, 2.0, -1.0));
+        assert_eq!(position.norm_squared(), 41.0);
+        assert_eq!(position.to_string(), "(3, 4, -4)");
+
+        let velocity = -(Velocity3::new(3.0, 0.0, 4.0) - Velocity3::new(0.0, 0.0, 0.0)) * 2.0;
+        assert_eq!(velocity.norm(), 10.0);
+        assert_eq!(velocity.vz, -8.0);
+
+        assert_eq!(format!("{:.1}", Velocity2::new(0.26, 1.0)), "(0.3, 1.0)");
+    }
+}
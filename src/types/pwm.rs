@@ -1,4 +1,5 @@
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PWM {
     /// PWM value for the front right motor (between 0.0 to 1.0)
     pub front_right_pwm: f32,
@@ -1,9 +1,70 @@
-use msgpack_rpc::Value;
-use nalgebra::Quaternion;
+use msgpack_rpc::{Utf8String, Value};
+use nalgebra::{Quaternion, UnitQuaternion};
 
-#[derive(Debug, Clone)]
+use super::vector::Vector3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Quaternion backed by nalgebra, with helpers for the attitude conventions used by AirSim
+///
+/// Euler angles are in radians and follow the roll (x), pitch (y), yaw (z) convention of the NED body frame
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "crate::Quaternion", into = "crate::Quaternion"))]
 pub struct Quaternionr(pub Quaternion<f32>);
 
+impl Default for Quaternionr {
+    fn default() -> Self {
+        Self(Quaternion::identity())
+    }
+}
+
+impl Quaternionr {
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self(Quaternion::new(w, x, y, z))
+    }
+
+    /// Quaternion rotating by `roll`, then `pitch`, then `yaw`, all in radians
+    pub fn from_euler(roll: f32, pitch: f32, yaw: f32) -> Self {
+        Self(UnitQuaternion::from_euler_angles(roll, pitch, yaw).into_inner())
+    }
+
+    /// Roll, pitch and yaw in radians, the quaternion is normalized first
+    pub fn to_euler_angles(&self) -> (f32, f32, f32) {
+        UnitQuaternion::from_quaternion(self.0).euler_angles()
+    }
+
+    /// Rotate `v` by the quaternion, the quaternion is normalized first
+    pub fn rotate_vector(&self, v: Vector3) -> Vector3 {
+        let rotated = UnitQuaternion::from_quaternion(self.0) * nalgebra::Vector3::new(v.x, v.y, v.z);
+        Vector3::new(rotated.x, rotated.y, rotated.z)
+    }
+
+    pub(crate) fn as_msgpack(&self) -> Value {
+        let w_val: Utf8String = "w_val".into();
+        let x_val: Utf8String = "x_val".into();
+        let y_val: Utf8String = "y_val".into();
+        let z_val: Utf8String = "z_val".into();
+
+        Value::Map(vec![
+            (Value::String(w_val), Value::F32(self.0.w)),
+            (Value::String(x_val), Value::F32(self.0.i)),
+            (Value::String(y_val), Value::F32(self.0.j)),
+            (Value::String(z_val), Value::F32(self.0.k)),
+        ])
+    }
+}
+
+impl From<crate::Quaternion> for Quaternionr {
+    fn from(q: crate::Quaternion) -> Self {
+        Self::new(q.w, q.x, q.y, q.z)
+    }
+}
+
+impl From<Quaternionr> for crate::Quaternion {
+    fn from(q: Quaternionr) -> Self {
+        crate::Quaternion::new(q.0.w, q.0.i, q.0.j, q.0.k)
+    }
+}
+
 impl From<Value> for Quaternionr {
     fn from(msgpack: Value) -> Self {
         let mut points = vec![];
@@ -16,3 +77,28 @@ impl From<Value> for Quaternionr {
         Self(Quaternion::new(points[0], points[1], points[2], points[3]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Quaternionr, Vector3};
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_euler_round_trip() {
+        let q = Quaternionr::from_euler(0.1, -0.2, 0.3);
+        let (roll, pitch, yaw) = q.to_euler_angles();
+        assert!((roll - 0.1).abs() < 1e-5);
+        assert!((pitch + 0.2).abs() < 1e-5);
+        assert!((yaw - 0.3).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        // yawing by 90 degrees turns north into east
+        let q = Quaternionr::from_euler(0.0, 0.0, FRAC_PI_2);
+        let v = q.rotate_vector(Vector3::new(1.0, 0.0, 0.0));
+        assert!(v.x.abs() < 1e-6);
+        assert!((v.y - 1.0).abs() < 1e-6);
+        assert!(v.z.abs() < 1e-6);
+    }
+}
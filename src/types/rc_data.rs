@@ -3,6 +3,7 @@ use msgpack_rpc::{Utf8String, Value};
 use super::pose::Orientation3;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RCData {
     pub timestamp: u64,
     pub orientation: Orientation3,
@@ -74,6 +75,7 @@ impl RCData {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RCDataState {
     pub timestamp: u64,
     pub orientation: Orientation3,
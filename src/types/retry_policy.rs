@@ -0,0 +1,210 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::time::Duration;
+
+use async_std::task;
+use futures::future;
+
+use crate::{NetworkError, NetworkResult};
+
+pub(crate) const SPAWN_OBJECT: &str = "simSpawnObject";
+pub(crate) const ADD_VEHICLE: &str = "simAddVehicle";
+pub(crate) const CONTINUE_FOR_TIME: &str = "simContinueForTime";
+pub(crate) const CONTINUE_FOR_FRAMES: &str = "simContinueForFrames";
+
+/// RPCs that must not be sent twice, since a request that failed in transport may still have been executed
+const NON_IDEMPOTENT_RPCS: [&str; 4] = [SPAWN_OBJECT, ADD_VEHICLE, CONTINUE_FOR_TIME, CONTINUE_FOR_FRAMES];
+
+thread_local! {
+    /// Number of `RetryPolicy::run` calls currently polling their call on this thread
+    static RUNNING: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Marks the call polled by `RetryPolicy::run`, until dropped
+struct RunScope;
+
+impl RunScope {
+    fn enter() -> Self {
+        RUNNING.with(|running| running.set(running.get() + 1));
+        RunScope
+    }
+}
+
+impl Drop for RunScope {
+    fn drop(&mut self) {
+        RUNNING.with(|running| running.set(running.get() - 1));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// When and how often failed RPCs are retried
+///
+/// Defaults to 3 attempts, waiting 100 ms before the first retry and doubling the wait after every retry,
+/// and retrying transport errors only: I/O, send and receive errors. Errors reported by the server are not retried
+pub struct RetryPolicy {
+    /// attempts in total, including the first one. 1 disables retries
+    pub max_attempts: u32,
+    /// wait before the first retry
+    pub backoff: Duration,
+    /// factor the wait is multiplied with after every retry, finite and not negative
+    pub backoff_factor: f32,
+    /// returns whether an error is worth retrying
+    pub retryable: fn(&NetworkError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(100),
+            backoff_factor: 2.0,
+            retryable: Self::is_transport_error,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Policy sending every RPC once, as clients do unless configured otherwise
+    pub fn disabled() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Factor the wait is multiplied with after every retry
+    ///
+    /// # Panics
+    /// If the factor is negative, infinite or NaN
+    pub fn backoff_factor(mut self, backoff_factor: f32) -> Self {
+        if !(backoff_factor >= 0.0 && backoff_factor.is_finite()) {
+            panic!("backoff_factor {backoff_factor} is not a finite factor of 0.0 or more")
+        }
+        self.backoff_factor = backoff_factor;
+        self
+    }
+
+    pub fn retryable(mut self, retryable: fn(&NetworkError) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Default classifier, retrying errors of the connection but not errors reported by the server
+    pub fn is_transport_error(error: &NetworkError) -> bool {
+        matches!(
+            error,
+            NetworkError::Io(_) | NetworkError::Send { .. } | NetworkError::Recv(_)
+        )
+    }
+
+    /// Runs `call` until it succeeds, fails with an error that isn't retryable or runs out of attempts
+    ///
+    /// Use it to retry a single call with a different policy than the one of the client, e.g. more attempts for
+    /// a critical query. The policy overrides the one of the client: RPCs sent by `call` are not retried by the
+    /// client as well, so attempts don't multiply
+    ///
+    /// args:
+    ///     call (FnMut() -> Future<Output = NetworkResult<T>>): Call to run, invoked again for every attempt
+    pub async fn run<T, F, Fut>(&self, mut call: F) -> NetworkResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = NetworkResult<T>>,
+    {
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+
+        loop {
+            let mut attempt_call = Box::pin(call());
+            let attempt_result = future::poll_fn(|cx| {
+                let _scope = RunScope::enter();
+                attempt_call.as_mut().poll(cx)
+            })
+            .await;
+
+            match attempt_result {
+                Err(e) if attempt < self.max_attempts && (self.retryable)(&e) => {
+                    log::warn!("attempt {} of {} failed, retrying: {}", attempt, self.max_attempts, e);
+                    task::sleep(backoff).await;
+                    backoff = backoff.mul_f32(self.backoff_factor);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Whether the RPC being sent is part of a call run by `RetryPolicy::run`, whose policy replaces the client's
+    pub(crate) fn is_overridden() -> bool {
+        RUNNING.with(|running| running.get() > 0)
+    }
+
+    /// Whether sending the RPC again after a transport error is safe
+    pub(crate) fn is_idempotent(method: &str) -> bool {
+        !NON_IDEMPOTENT_RPCS.contains(&method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::io;
+    use std::time::Duration;
+
+    use crate::{NetworkError, RetryPolicy};
+
+    #[test]
+    fn test_run_retries_transport_errors() {
+        let policy = RetryPolicy::new().backoff(Duration::ZERO);
+        let attempts = Cell::new(0);
+
+        let result = async_std::task::block_on(policy.run(|| async {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(NetworkError::Io(io::Error::new(io::ErrorKind::BrokenPipe, "closed")))
+            } else {
+                Ok(attempts.get())
+            }
+        }));
+        assert_eq!(result.unwrap(), 3);
+
+        attempts.set(0);
+        let result: Result<(), _> = async_std::task::block_on(policy.run(|| async {
+            attempts.set(attempts.get() + 1);
+            Err(NetworkError::Send {
+                message: "refused".to_owned(),
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn test_run_overrides_client_policy() {
+        let policy = RetryPolicy::disabled();
+        assert!(!RetryPolicy::is_overridden());
+
+        let overridden = async_std::task::block_on(policy.run(|| async {
+            async_std::task::yield_now().await;
+            Ok(RetryPolicy::is_overridden())
+        }));
+        assert!(overridden.unwrap());
+        assert!(!RetryPolicy::is_overridden());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_backoff_factor_rejects_nan() {
+        RetryPolicy::new().backoff_factor(f32::NAN);
+    }
+}
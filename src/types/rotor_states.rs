@@ -1,6 +1,7 @@
 use msgpack_rpc::{message::Response, Value};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RotorState {
     pub thrust: f32,
     pub torque_scaler: f32,
@@ -25,6 +26,7 @@ impl From<Value> for RotorState {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RotorStates {
     pub rotors: [RotorState; 4],
     pub timestamp: u64,
@@ -1,103 +1,12 @@
-use msgpack_rpc::{message::Response, Utf8String, Value};
 use crate::{GeoPoint, Pose3};
+use msgpack_rpc::{message::Response, Value};
 
 use super::vector::Vector3;
 
 use super::quaternion::Quaternionr;
 
-#[derive(Debug, Clone, Copy)]
-pub enum ImageType {
-    Scene,
-    DepthPlanar,
-    DepthPerspective,
-    DepthVis,
-    DisparityNormalized,
-    SurfaceNormals,
-    Infrared,
-    OpticalFlow,
-    OpticalFlowVis,
-}
-
-impl ImageType {
-    pub(crate) fn as_msgpack(&self) -> Value {
-        let val = match self {
-            ImageType::Scene => 0_i64,
-            ImageType::DepthPlanar => 1_i64,
-            ImageType::DepthPerspective => 2_i64,
-            ImageType::DepthVis => 3_i64,
-            ImageType::DisparityNormalized => 4_i64,
-            ImageType::SurfaceNormals => 5_i64,
-            ImageType::Infrared => 6_i64,
-            ImageType::OpticalFlow => 7_i64,
-            ImageType::OpticalFlowVis => 8_i64,
-        };
-
-        Value::Integer(val.into())
-    }
-}
-
 #[derive(Debug, Clone)]
-/// Binary string literal of compressed png image in presented as an vector of bytes
-pub struct CompressedImage(pub Vec<u8>);
-
-impl From<Response> for CompressedImage {
-    fn from(msgpack: Response) -> Self {
-        let mut pixels = vec![];
-
-        match msgpack.result {
-            Ok(res) => {
-                let slice: &[u8] = res.as_slice().unwrap();
-                for p in slice {
-                    pixels.push(*p);
-                }
-            }
-            Err(_) => panic!("Could not decode result from CompressedImage msgpack"),
-        };
-
-        Self(pixels)
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct ImageRequest {
-    pub camera_name: String,
-    pub image_type: ImageType,
-    pub pixels_as_float: bool,
-    pub compress: bool,
-}
-
-#[derive(Debug, Clone)]
-pub struct ImageRequests(pub Vec<ImageRequest>);
-
-impl ImageRequest {
-    pub(crate) fn as_msgpack(&self) -> Value {
-        let camera_name: Utf8String = "camera_name".into();
-        let image_type: Utf8String = "image_type".into();
-        let pixels_as_float: Utf8String = "pixels_as_float".into();
-        let compress: Utf8String = "compress".into();
-
-        let val = Value::Map(vec![
-            (
-                Value::String(camera_name),
-                Value::String(self.camera_name.to_owned().into()),
-            ),
-            (Value::String(image_type), self.image_type.as_msgpack()),
-            (Value::String(pixels_as_float), Value::Boolean(self.pixels_as_float)),
-            (Value::String(compress), Value::Boolean(self.compress)),
-        ]);
-
-        let msg: Vec<(msgpack_rpc::Value, msgpack_rpc::Value)> = val.as_map().map(|x| x.to_owned()).unwrap();
-        Value::Map(msg)
-    }
-}
-
-impl ImageRequests {
-    pub(crate) fn as_msgpack(&self) -> Value {
-        let images = self.0.iter().cloned().map(|img| img.as_msgpack()).collect();
-        Value::Array(images)
-    }
-}
-
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImuData {
     pub timestamp: u64,
     pub orientation: Quaternionr,
@@ -128,6 +37,8 @@ impl From<Response> for ImuData {
 }
 
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DistanceSensorData {
     pub timestamp: u64,
     pub distance: f32, // meters
@@ -154,6 +65,8 @@ impl From<Response> for DistanceSensorData {
 }
 
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MagnetometerData {
     pub timestamp: u64,
     pub magnetic_field: Vector3,
@@ -175,6 +88,8 @@ impl From<Response> for MagnetometerData {
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BarometerData {
     pub timestamp: u64,
     pub altitude: f32,
@@ -198,6 +113,8 @@ impl From<Response> for BarometerData {
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GpsData {
     pub timestamp: u64,
     pub gnss_report: GnssReport,
@@ -214,14 +131,13 @@ impl From<Response> for GpsData {
                 let is_valid: bool = payload[2].1.as_bool().unwrap();
                 Self { timestamp, gnss_report, is_valid }
             }
-            Err(e) => {
-                println!("Error decoding Response for GpsData: {:?}", e);
-                panic!("Couldn't decode result from GpsData msgpack")
-            }
+            Err(e) => panic!("Couldn't decode result from GpsData msgpack: {e:?}"),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GnssReport {
     pub geo_point: GeoPoint,
     pub eph: f32,
@@ -250,6 +166,7 @@ impl From<Value> for GnssReport {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GnssFixType {
     GnssFixNoFix = 0,
     GnssFixTimeOnly = 1,
@@ -304,4 +221,59 @@ struct GnssReport
 
                 return d;
             }
-        }; */
\ No newline at end of file
+        }; */
+#[derive(Debug, Clone)]
+/// Scan of a lidar, see `MultiRotorClient::get_lidar_data`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LidarData {
+    /// timestamp of the scan in nanoseconds
+    pub time_stamp: u64,
+    /// flat point cloud, 3 floats per point, in the frame set by `DataFrame` in settings.json. See `points`
+    pub point_cloud: Vec<f32>,
+    /// pose of the lidar when the scan was taken
+    pub pose: Pose3,
+    /// segmentation id of the object hit by every point
+    pub segmentation: Vec<i32>,
+}
+
+impl LidarData {
+    /// Points of the scan, empty when nothing was hit
+    pub fn points(&self) -> Vec<Vector3> {
+        // AirSim sends a single 0 when nothing was hit
+        self.point_cloud
+            .chunks_exact(3)
+            .map(|point| Vector3::new(point[0], point[1], point[2]))
+            .collect()
+    }
+}
+
+impl From<Response> for LidarData {
+    fn from(msgpack: Response) -> Self {
+        match msgpack.result {
+            Ok(res) => {
+                let payload: &Vec<(Value, Value)> = res.as_map().unwrap();
+                let field = |key: &str| match payload.iter().find(|(k, _)| k.as_str() == Some(key)) {
+                    Some((_, v)) => v.to_owned(),
+                    None => panic!("LidarData msgpack is missing the field {key}"),
+                };
+                let numbers = |value: Value| value.as_array().cloned().unwrap_or_default();
+
+                Self {
+                    time_stamp: field("time_stamp").as_u64().unwrap(),
+                    point_cloud: numbers(field("point_cloud"))
+                        .iter()
+                        .filter_map(Value::as_f64)
+                        .map(|f| f as f32)
+                        .collect(),
+                    pose: field("pose").into(),
+                    segmentation: numbers(field("segmentation"))
+                        .iter()
+                        .filter_map(Value::as_i64)
+                        .map(|id| id as i32)
+                        .collect(),
+                }
+            }
+            Err(_) => panic!("Couldn't decode result from LidarData msgpack"),
+        }
+    }
+}
@@ -0,0 +1,374 @@
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+
+use crate::sensors::SensorKind;
+
+/// Subset of AirSim's settings.json, as returned by `getSettingsString`
+///
+/// Only the fields needed to discover vehicles, cameras and sensors are parsed, everything else is ignored.
+/// Fields that are absent from the settings are `None` or empty, AirSim then uses its own defaults
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AirsimSettings {
+    pub settings_version: Option<f32>,
+    /// `Multirotor`, `Car` or `ComputerVision`. Empty or `None` lets AirSim ask the user
+    pub sim_mode: Option<String>,
+    pub clock_speed: Option<f32>,
+    /// `ScalableClock` or `SteppableClock`, see `clock_type` for the default
+    pub clock_type: Option<String>,
+    /// vehicles by name
+    pub vehicles: BTreeMap<String, VehicleSettings>,
+    /// external cameras by name, not attached to any vehicle
+    pub cameras: BTreeMap<String, CameraSettings>,
+    /// sensors by name of the vehicles without `Sensors`, replacing the defaults of the sim mode
+    pub default_sensors: BTreeMap<String, SensorSettings>,
+}
+
+#[cfg(feature = "serde")]
+impl AirsimSettings {
+    /// Parse the content of a settings.json
+    pub fn from_json(settings: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(settings)
+    }
+
+    /// Names of the vehicles defined in the settings
+    pub fn vehicle_names(&self) -> impl Iterator<Item = &str> {
+        self.vehicles.keys().map(String::as_str)
+    }
+
+    /// Sensors AirSim creates for a vehicle, in the order of their names
+    ///
+    /// The `Sensors` of a vehicle replace the defaults altogether, the defaults being `DefaultSensors` or, without
+    /// it, those of the vehicle type: IMU, magnetometer, GPS and barometer for a multirotor, GPS for a car and none
+    /// for ComputerVision. A vehicle that isn't in the settings gets the defaults
+    ///
+    /// args:
+    ///     vehicle_name (&str): Name of the vehicle, empty for the only vehicle of the settings
+    pub fn vehicle_sensors(&self, vehicle_name: &str) -> Vec<SensorInfo> {
+        let vehicle = match vehicle_name {
+            "" if self.vehicles.len() == 1 => self.vehicles.values().next(),
+            name => self.vehicles.get(name),
+        };
+
+        let sensors = match vehicle {
+            Some(vehicle) if !vehicle.sensors.is_empty() => &vehicle.sensors,
+            _ if !self.default_sensors.is_empty() => &self.default_sensors,
+            _ => return self.builtin_sensors(vehicle),
+        };
+        sensors
+            .iter()
+            .filter_map(|(name, sensor)| {
+                sensor.kind().map(|kind| SensorInfo {
+                    name: name.clone(),
+                    kind,
+                    enabled: sensor.enabled.unwrap_or(true),
+                })
+            })
+            .collect()
+    }
+
+    /// Sensors AirSim creates without `Sensors` nor `DefaultSensors`, by vehicle type or else by sim mode
+    fn builtin_sensors(&self, vehicle: Option<&VehicleSettings>) -> Vec<SensorInfo> {
+        let vehicle_type = vehicle.map(|vehicle| vehicle.vehicle_type.as_str());
+        let kinds: &[(&str, SensorKind)] = match (vehicle_type, self.sim_mode.as_deref()) {
+            (Some("PhysXCar" | "ArduRover"), _) | (None, Some("Car")) => &[("gps", SensorKind::Gps)],
+            (Some("ComputerVision"), _) | (None, Some("ComputerVision")) => &[],
+            (Some(_), _) | (None, Some("Multirotor")) => &[
+                ("barometer", SensorKind::Barometer),
+                ("gps", SensorKind::Gps),
+                ("imu", SensorKind::Imu),
+                ("magnetometer", SensorKind::Magnetometer),
+            ],
+            (None, _) => &[],
+        };
+        kinds
+            .iter()
+            .map(|&(name, kind)| SensorInfo {
+                name: name.to_owned(),
+                kind,
+                enabled: true,
+            })
+            .collect()
+    }
+
+    /// Clock the simulation runs with
+    ///
+    /// Without `ClockType` AirSim picks a steppable clock in multirotor mode, unless a PX4 vehicle is created,
+    /// and a scalable clock otherwise
+    pub fn clock_type(&self) -> ClockType {
+        match self.clock_type.as_deref() {
+            Some("ScalableClock") => ClockType::Scalable,
+            Some("SteppableClock") => ClockType::Steppable,
+            _ if self.sim_mode.as_deref() != Some("Multirotor") => ClockType::Scalable,
+            _ => {
+                let has_px4 = self
+                    .vehicles
+                    .values()
+                    .any(|vehicle| vehicle.vehicle_type == "PX4Multirotor" && vehicle.auto_create.unwrap_or(true));
+                if has_px4 {
+                    ClockType::Scalable
+                } else {
+                    ClockType::Steppable
+                }
+            }
+        }
+    }
+}
+
+/// Clock driving the simulation time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockType {
+    /// Follows the wall clock, sped up or slowed down by `ClockSpeed`
+    Scalable,
+    /// Advances by the fixed steps of the physics engine, regardless of the wall clock
+    Steppable,
+}
+
+/// Settings of a single vehicle
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct VehicleSettings {
+    /// e.g. `SimpleFlight`, `PX4Multirotor`, `PhysXCar` or `ComputerVision`
+    pub vehicle_type: String,
+    pub default_vehicle_state: Option<String>,
+    pub auto_create: Option<bool>,
+    /// initial position in NED frame, in meters
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    /// initial orientation, in degrees
+    pub roll: Option<f32>,
+    pub pitch: Option<f32>,
+    pub yaw: Option<f32>,
+    /// cameras by name
+    pub cameras: BTreeMap<String, CameraSettings>,
+    /// sensors by name
+    pub sensors: BTreeMap<String, SensorSettings>,
+}
+
+#[cfg(feature = "serde")]
+impl VehicleSettings {
+    /// Names of the cameras defined for this vehicle
+    pub fn camera_names(&self) -> impl Iterator<Item = &str> {
+        self.cameras.keys().map(String::as_str)
+    }
+
+    /// Names of the enabled sensors of the given type, e.g. `SensorSettings::LIDAR`
+    pub fn sensor_names(&self, sensor_type: u8) -> impl Iterator<Item = &str> {
+        self.sensors
+            .iter()
+            .filter(move |(_, sensor)| sensor.sensor_type == sensor_type && sensor.enabled.unwrap_or(true))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Settings of a single camera
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CameraSettings {
+    /// position relative to the vehicle, in meters
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    /// orientation relative to the vehicle, in degrees
+    pub roll: Option<f32>,
+    pub pitch: Option<f32>,
+    pub yaw: Option<f32>,
+    pub capture_settings: Vec<CaptureSettings>,
+}
+
+/// Capture settings of a camera, for a single image type
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CaptureSettings {
+    /// `ImageType` id the settings apply to
+    pub image_type: i32,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(rename = "FOV_Degrees")]
+    pub fov_degrees: Option<f32>,
+}
+
+/// Settings of a single sensor
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SensorSettings {
+    /// one of the `SensorSettings` type ids, e.g. `SensorSettings::LIDAR`
+    pub sensor_type: u8,
+    pub enabled: Option<bool>,
+}
+
+#[cfg(feature = "serde")]
+impl SensorSettings {
+    // sensor type ids, as used by the `SensorType` field
+    pub const BAROMETER: u8 = 1;
+    pub const IMU: u8 = 2;
+    pub const GPS: u8 = 3;
+    pub const MAGNETOMETER: u8 = 4;
+    pub const DISTANCE: u8 = 5;
+    pub const LIDAR: u8 = 6;
+
+    /// Type of the sensor, `None` for the types this crate can't read
+    pub fn kind(&self) -> Option<SensorKind> {
+        match self.sensor_type {
+            Self::BAROMETER => Some(SensorKind::Barometer),
+            Self::IMU => Some(SensorKind::Imu),
+            Self::GPS => Some(SensorKind::Gps),
+            Self::MAGNETOMETER => Some(SensorKind::Magnetometer),
+            Self::DISTANCE => Some(SensorKind::Distance),
+            Self::LIDAR => Some(SensorKind::Lidar),
+            _ => None,
+        }
+    }
+}
+
+/// Sensor of a vehicle, as listed by `AirsimClient::sim_list_sensors`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorInfo {
+    /// name of the sensor, to pass to the `get_*_data` methods
+    pub name: String,
+    pub kind: SensorKind,
+    /// disabled sensors are listed but not created, reading them fails
+    pub enabled: bool,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::sensors::SensorKind;
+    use crate::{AirsimSettings, ClockType, SensorSettings, VehicleSettings};
+
+    #[test]
+    fn test_from_json() {
+        let settings = r#"{
+            "SettingsVersion": 1.2,
+            "SimMode": "Multirotor",
+            "Vehicles": {
+                "Drone1": {
+                    "VehicleType": "SimpleFlight",
+                    "X": 0, "Y": 0, "Z": -2,
+                    "Cameras": {
+                        "front_center": {
+                            "CaptureSettings": [{ "ImageType": 0, "Width": 640, "Height": 480, "FOV_Degrees": 90 }],
+                            "X": 0.5, "Pitch": -10
+                        }
+                    },
+                    "Sensors": {
+                        "Lidar1": { "SensorType": 6, "Enabled": true, "NumberOfChannels": 16 },
+                        "Lidar2": { "SensorType": 6, "Enabled": false },
+                        "Distance": { "SensorType": 5 }
+                    }
+                }
+            },
+            "Recording": { "RecordOnMove": false }
+        }"#;
+
+        let settings = AirsimSettings::from_json(settings).unwrap();
+        assert_eq!(settings.sim_mode.as_deref(), Some("Multirotor"));
+        assert_eq!(settings.vehicle_names().collect::<Vec<_>>(), vec!["Drone1"]);
+
+        let drone = &settings.vehicles["Drone1"];
+        assert_eq!(drone.vehicle_type, "SimpleFlight");
+        assert_eq!(drone.z, Some(-2.0));
+        assert_eq!(drone.camera_names().collect::<Vec<_>>(), vec!["front_center"]);
+        assert_eq!(drone.cameras["front_center"].capture_settings[0].width, Some(640));
+        assert_eq!(
+            drone.sensor_names(SensorSettings::LIDAR).collect::<Vec<_>>(),
+            vec!["Lidar1"]
+        );
+        assert_eq!(
+            drone.sensor_names(SensorSettings::DISTANCE).collect::<Vec<_>>(),
+            vec!["Distance"]
+        );
+    }
+
+    #[test]
+    fn test_clock_type_default() {
+        let mut settings = AirsimSettings {
+            sim_mode: Some("Multirotor".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(settings.clock_type(), ClockType::Steppable);
+
+        let px4 = VehicleSettings {
+            vehicle_type: "PX4Multirotor".to_owned(),
+            ..Default::default()
+        };
+        settings.vehicles.insert("PX4".to_owned(), px4);
+        assert_eq!(settings.clock_type(), ClockType::Scalable);
+
+        settings.clock_type = Some("SteppableClock".to_owned());
+        assert_eq!(settings.clock_type(), ClockType::Steppable);
+
+        let car = AirsimSettings {
+            sim_mode: Some("Car".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(car.clock_type(), ClockType::Scalable);
+    }
+
+    #[test]
+    fn test_vehicle_sensors() {
+        let settings = r#"{
+            "SimMode": "Multirotor",
+            "Vehicles": {
+                "Drone1": {
+                    "VehicleType": "SimpleFlight",
+                    "Sensors": {
+                        "Lidar1": { "SensorType": 6, "Enabled": false },
+                        "Imu": { "SensorType": 2 },
+                        "Camera": { "SensorType": 42 }
+                    }
+                },
+                "Drone2": { "VehicleType": "SimpleFlight" }
+            }
+        }"#;
+        let mut settings = AirsimSettings::from_json(settings).unwrap();
+
+        let sensors = settings.vehicle_sensors("Drone1");
+        assert_eq!(sensors.len(), 2);
+        assert_eq!((sensors[0].name.as_str(), sensors[0].kind), ("Imu", SensorKind::Imu));
+        assert_eq!((sensors[1].name.as_str(), sensors[1].enabled), ("Lidar1", false));
+
+        let kinds: Vec<_> = settings
+            .vehicle_sensors("Drone2")
+            .iter()
+            .map(|sensor| sensor.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SensorKind::Barometer,
+                SensorKind::Gps,
+                SensorKind::Imu,
+                SensorKind::Magnetometer
+            ]
+        );
+
+        settings.default_sensors.insert(
+            "Distance".to_owned(),
+            SensorSettings {
+                sensor_type: SensorSettings::DISTANCE,
+                enabled: None,
+            },
+        );
+        let names: Vec<_> = settings
+            .vehicle_sensors("Drone2")
+            .into_iter()
+            .map(|sensor| sensor.name)
+            .collect();
+        assert_eq!(names, vec!["Distance"]);
+
+        let car = AirsimSettings {
+            sim_mode: Some("Car".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(car.vehicle_sensors("")[0].kind, SensorKind::Gps);
+    }
+}
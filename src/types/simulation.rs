@@ -2,6 +2,7 @@ use msgpack_rpc::{message::Response, Value};
 
 #[derive(Debug, Clone)]
 /// List containing all the names of objects in the simulation scene
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SceneObjects(pub Vec<String>);
 
 impl From<Response> for SceneObjects {
@@ -0,0 +1,13 @@
+use crate::{ImageResponse, KinematicsState};
+
+#[derive(Debug, Clone)]
+/// Images and ground truth kinematics of a vehicle, captured at the same simulation tick
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// responses to the image requests, in the order of the requests
+    pub images: Vec<ImageResponse>,
+    /// ground truth kinematics of the vehicle
+    pub state: KinematicsState,
+    /// capture time of the images in nanoseconds, 0 if no image was requested
+    pub timestamp: u64,
+}
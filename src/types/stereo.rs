@@ -0,0 +1,132 @@
+use crate::camera::Intrinsics;
+use crate::{CameraInfo, ImageRequest, ImageRequests, ImageResponse, ImageType, Vector3};
+
+#[derive(Debug, Clone)]
+/// Images of two cameras captured by the same `simGetImages` call, with the geometry of the camera pair
+///
+/// Both cameras are assumed to share their resolution and field of view, as for a rectified stereo rig
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StereoPair {
+    /// image of the left camera
+    pub left: ImageResponse,
+    /// image of the right camera
+    pub right: ImageResponse,
+    /// pose and field of view of the left camera
+    pub left_info: CameraInfo,
+    /// pose and field of view of the right camera
+    pub right_info: CameraInfo,
+    /// position of the right camera in the frame of the left camera, in meters
+    pub baseline: Vector3,
+}
+
+impl StereoPair {
+    /// Assemble a pair, deriving the baseline from the poses of the cameras
+    ///
+    /// args:
+    ///     left (ImageResponse): image of the left camera
+    ///     right (ImageResponse): image of the right camera
+    ///     left_info (CameraInfo): details of the left camera, from `simGetCameraInfo`
+    ///     right_info (CameraInfo): details of the right camera, from `simGetCameraInfo`
+    pub fn new(left: ImageResponse, right: ImageResponse, left_info: CameraInfo, right_info: CameraInfo) -> Self {
+        let baseline = left_info
+            .pose
+            .inverse()
+            .transform_point(Vector3::from(right_info.pose.position));
+
+        Self {
+            left,
+            right,
+            left_info,
+            right_info,
+            baseline,
+        }
+    }
+
+    /// Distance between the two cameras in meters
+    pub fn baseline_length(&self) -> f32 {
+        let Vector3 { x, y, z } = self.baseline;
+        (x * x + y * y + z * z).sqrt()
+    }
+
+    /// Intrinsics of the left camera, for the size of its image
+    pub fn intrinsics(&self) -> Intrinsics {
+        Intrinsics::from_camera_info(&self.left_info, self.left.width, self.left.height)
+    }
+
+    /// Depth in meters of a point matched with the given disparity, infinite for a zero disparity
+    ///
+    /// args:
+    ///     disparity (f32): horizontal offset of the point between the left and right image, in pixels
+    pub fn depth_from_disparity(&self, disparity: f32) -> f32 {
+        self.intrinsics().fx * self.baseline_length() / disparity.abs()
+    }
+
+    /// One uncompressed request per camera, with float pixels for the types measured in meters or pixels
+    pub(crate) fn requests(left_camera: &str, right_camera: &str, image_type: ImageType) -> ImageRequests {
+        let pixels_as_float = matches!(
+            image_type,
+            ImageType::DepthPlanar
+                | ImageType::DepthPerspective
+                | ImageType::DisparityNormalized
+                | ImageType::OpticalFlow
+        );
+
+        ImageRequests(vec![
+            ImageRequest::new(left_camera, image_type, pixels_as_float, false),
+            ImageRequest::new(right_camera, image_type, pixels_as_float, false),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::stereo::StereoPair;
+    use crate::{CameraInfo, ImageResponse, ImageType, Pose3, Position3, Quaternion, Quaternionr, Vector3};
+
+    fn scene(camera_name: &str) -> ImageResponse {
+        ImageResponse {
+            image_data_uint8: vec![0; 640 * 480 * 3],
+            image_data_float: vec![],
+            camera_name: camera_name.to_owned(),
+            camera_position: Vector3::new(0.0, 0.0, 0.0),
+            camera_orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            time_stamp: 1234,
+            message: String::new(),
+            pixels_as_float: false,
+            compress: false,
+            width: 640,
+            height: 480,
+            image_type: ImageType::Scene,
+        }
+    }
+
+    fn camera(position: Position3, yaw: f32) -> CameraInfo {
+        CameraInfo {
+            pose: Pose3::new(position, Quaternionr::from_euler(0.0, 0.0, yaw).into()),
+            fov: 90.0,
+            proj_mat: [[0.0; 4]; 4],
+        }
+    }
+
+    #[test]
+    fn test_baseline_in_left_camera_frame() {
+        // vehicle facing east, the right camera is 0.25 m further south
+        let yaw = std::f32::consts::FRAC_PI_2;
+        let pair = StereoPair::new(
+            scene("left"),
+            scene("right"),
+            camera(Position3::new(1.0, 2.0, -3.0), yaw),
+            camera(Position3::new(0.75, 2.0, -3.0), yaw),
+        );
+
+        assert!(pair.baseline.x.abs() < 1e-5);
+        assert!((pair.baseline.y - 0.25).abs() < 1e-5);
+        assert!((pair.baseline_length() - 0.25).abs() < 1e-5);
+        // 90° over 640 pixels gives a focal length of 320 pixels
+        assert!((pair.depth_from_disparity(8.0) - 10.0).abs() < 1e-3);
+
+        let requests = StereoPair::requests("left", "right", ImageType::DepthPlanar).0;
+        assert!(requests[1].pixels_as_float && !requests[1].compress);
+        assert_eq!(requests[1].camera_name, "right");
+    }
+}
@@ -0,0 +1,82 @@
+use msgpack_rpc::{Utf8String, Value};
+
+#[derive(Debug, Clone)]
+/// Settings of the time-of-day effect, which controls the position of the Sun in the environment
+///
+/// Defaults match the AirSim python client: current date & time, no DST adjustment,
+/// celestial clock running at simulation speed, Sun position updated every 60 seconds and the Sun moving.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeOfDay {
+    /// True to enable time-of-day effect, False to reset the position to original
+    pub is_enabled: bool,
+    /// Date & Time in %Y-%m-%d %H:%M:%S format, e.g. `2018-02-12 15:20:00`. Empty string means current date & time
+    pub start_datetime: String,
+    /// True to adjust for Daylight Savings Time
+    pub is_start_datetime_dst: bool,
+    /// Run celestial clock faster or slower than simulation clock
+    pub celestial_clock_speed: f32,
+    /// Interval to update the Sun's position, in seconds
+    pub update_interval_secs: f32,
+    /// Whether or not to move the Sun
+    pub move_sun: bool,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            start_datetime: String::new(),
+            is_start_datetime_dst: false,
+            celestial_clock_speed: 1.0,
+            update_interval_secs: 60.0,
+            move_sun: true,
+        }
+    }
+}
+
+impl TimeOfDay {
+    pub fn new(is_enabled: bool) -> Self {
+        Self {
+            is_enabled,
+            ..Default::default()
+        }
+    }
+
+    pub fn start_datetime(mut self, start_datetime: &str) -> Self {
+        self.start_datetime = start_datetime.to_owned();
+        self
+    }
+
+    pub fn is_start_datetime_dst(mut self, is_start_datetime_dst: bool) -> Self {
+        self.is_start_datetime_dst = is_start_datetime_dst;
+        self
+    }
+
+    pub fn celestial_clock_speed(mut self, celestial_clock_speed: f32) -> Self {
+        self.celestial_clock_speed = celestial_clock_speed;
+        self
+    }
+
+    pub fn update_interval_secs(mut self, update_interval_secs: f32) -> Self {
+        self.update_interval_secs = update_interval_secs;
+        self
+    }
+
+    pub fn move_sun(mut self, move_sun: bool) -> Self {
+        self.move_sun = move_sun;
+        self
+    }
+
+    pub(crate) fn as_msgpack(&self) -> Vec<Value> {
+        let start_datetime: Utf8String = self.start_datetime.as_str().into();
+
+        vec![
+            Value::Boolean(self.is_enabled),
+            Value::String(start_datetime),
+            Value::Boolean(self.is_start_datetime_dst),
+            Value::F32(self.celestial_clock_speed),
+            Value::F32(self.update_interval_secs),
+            Value::Boolean(self.move_sun),
+        ]
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    AglAltitude, BarometerData, CollisionInfo, DistanceSensorData, GpsData, ImageResponse, ImuData, LidarData,
+    MagnetometerData, MultiRotorState, PolarScan, RCDataState, RotorStates, Snapshot,
+};
+
+/// Response stamped with the simulation clock, which all vehicles and sensors share
+///
+/// Use it to align the logs of several vehicles and sensors, whatever RPC the values came from. A ComputerVision
+/// vehicle has no state of its own, its images carry the simulation time instead
+pub trait Timestamped {
+    /// Simulation time the value was sampled at, in nanoseconds
+    fn sim_time(&self) -> u64;
+}
+
+/// Implements `Timestamped` for structs keeping the simulation time in a field
+macro_rules! impl_timestamped {
+    ($($name:ty => $field:ident),+ $(,)?) => {
+        $(
+            impl Timestamped for $name {
+                fn sim_time(&self) -> u64 {
+                    self.$field
+                }
+            }
+        )+
+    };
+}
+
+impl_timestamped!(
+    AglAltitude => timestamp,
+    BarometerData => timestamp,
+    CollisionInfo => timestamp,
+    DistanceSensorData => timestamp,
+    GpsData => timestamp,
+    ImageResponse => time_stamp,
+    ImuData => timestamp,
+    LidarData => time_stamp,
+    MagnetometerData => timestamp,
+    MultiRotorState => timestamp,
+    PolarScan => timestamp,
+    RCDataState => timestamp,
+    RotorStates => timestamp,
+    Snapshot => timestamp,
+);
+
+#[cfg(feature = "colosseum")]
+impl_timestamped!(crate::GpuLidarData => time_stamp);
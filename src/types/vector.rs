@@ -1,6 +1,11 @@
+use std::ops::{AddAssign, Div, Index, IndexMut, MulAssign, SubAssign};
+
 use msgpack_rpc::{Utf8String, Value};
 
-#[derive(Debug, Clone, Copy)]
+use crate::types::pose::impl_vector_ops;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
@@ -26,6 +31,135 @@ impl Vector3 {
         let msg: Vec<(msgpack_rpc::Value, msgpack_rpc::Value)> = val.as_map().map(|x| x.to_owned()).unwrap();
         Value::Map(msg)
     }
+
+    pub fn zeros() -> Self {
+        Vector3::new(0.0, 0.0, 0.0)
+    }
+
+    pub fn dot(&self, other: &Vector3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Cross product, right-handed like the NED and ENU frames
+    pub fn cross(&self, other: &Vector3) -> Vector3 {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Vector of norm 1 in the same direction, the zero vector stays zero
+    pub fn normalize(&self) -> Vector3 {
+        let norm = self.norm();
+        if norm > 0.0 {
+            *self / norm
+        } else {
+            *self
+        }
+    }
+
+    /// Linear interpolation, `self` at t = 0 and `other` at t = 1
+    ///
+    /// args:
+    ///     other (&Vector3): end of the interpolation
+    ///     t (f32): position between the two, not clamped to extrapolate beyond them
+    pub fn lerp(&self, other: &Vector3, t: f32) -> Vector3 {
+        *self + (*other - *self) * t
+    }
+
+    /// Same direction, with the norm limited to `max`, e.g. to limit a velocity
+    ///
+    /// args:
+    ///     max (f32): largest norm
+    pub fn clamp_norm(&self, max: f32) -> Vector3 {
+        let norm = self.norm();
+        if norm > max {
+            *self * (max / norm)
+        } else {
+            *self
+        }
+    }
+
+    /// Product of the components, e.g. to scale every axis differently
+    pub fn component_mul(&self, other: &Vector3) -> Vector3 {
+        Vector3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+
+    /// Quotient of the components
+    pub fn component_div(&self, other: &Vector3) -> Vector3 {
+        Vector3::new(self.x / other.x, self.y / other.y, self.z / other.z)
+    }
+
+    /// Direction of the horizontal part of this NED vector, in radians from north, positive towards east
+    ///
+    /// Pass the result in degrees to `YawMode::angle` to face the direction of travel
+    pub fn heading(&self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    /// This NED vector (x = north, y = east, z = down) in ENU (x = east, y = north, z = up), e.g. for ROS
+    pub fn ned_to_enu(&self) -> Vector3 {
+        Vector3::new(self.y, self.x, -self.z)
+    }
+
+    /// This ENU vector (x = east, y = north, z = up) in NED (x = north, y = east, z = down)
+    pub fn enu_to_ned(&self) -> Vector3 {
+        Vector3::new(self.y, self.x, -self.z)
+    }
+}
+
+impl_vector_ops!(Vector3 { x, y, z });
+
+impl Div<f32> for Vector3 {
+    type Output = Self;
+
+    fn div(self, scale: f32) -> Self {
+        Vector3::new(self.x / scale, self.y / scale, self.z / scale)
+    }
+}
+
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vector3 {
+    fn mul_assign(&mut self, scale: f32) {
+        *self = *self * scale;
+    }
+}
+
+/// Components by index: 0 is x, 1 is y and 2 is z
+impl Index<usize> for Vector3 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index {index} out of range for Vector3"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index {index} out of range for Vector3"),
+        }
+    }
 }
 
 impl From<Value> for Vector3 {
@@ -49,3 +183,107 @@ impl From<Vector3> for nalgebra::SVector<f32, 3> {
     }
 }
 
+impl From<nalgebra::SVector<f32, 3>> for Vector3 {
+    fn from(vector: nalgebra::SVector<f32, 3>) -> Self {
+        Vector3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl approx::AbsDiffEq for Vector3 {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 {
+        f32::EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon)
+            && self.y.abs_diff_eq(&other.y, epsilon)
+            && self.z.abs_diff_eq(&other.z, epsilon)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl approx::RelativeEq for Vector3 {
+    fn default_max_relative() -> f32 {
+        f32::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative)
+            && self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl approx::UlpsEq for Vector3 {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps)
+            && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+            && self.z.ulps_eq(&other.z, epsilon, max_ulps)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2 {
+    pub fn new(x: f32, y: f32) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+impl From<Value> for Vector2 {
+    fn from(msgpack: Value) -> Self {
+        let mut points = vec![];
+        let payload: &Vec<(Value, Value)> = msgpack.as_map().unwrap();
+        for (_, v) in payload {
+            let p = v.as_f64().unwrap() as f32;
+            points.push(p);
+        }
+        Vector2 {
+            x: points[0],
+            y: points[1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Vector3;
+
+    #[test]
+    fn test_vector3_ops() {
+        let north = Vector3::new(1.0, 0.0, 0.0);
+        let east = Vector3::new(0.0, 1.0, 0.0);
+
+        assert_eq!(north.cross(&east), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(north.dot(&east), 0.0);
+        assert_eq!((north * 3.0 + east * 4.0).norm(), 5.0);
+        assert_eq!((east * 2.0).normalize(), east);
+        assert_eq!(Vector3::zeros().normalize(), Vector3::zeros());
+        assert_eq!(north.lerp(&east, 0.5), Vector3::new(0.5, 0.5, 0.0));
+        assert_eq!((east * 10.0).clamp_norm(2.0), east * 2.0);
+        assert!((Vector3::new(-1.0, 1.0, 0.0).heading() - 0.75 * std::f32::consts::PI).abs() < 1e-6);
+
+        let mut v = Vector3::new(1.0, 2.0, 3.0);
+        v[2] = -3.0;
+        v += north;
+        assert_eq!(v.ned_to_enu(), Vector3::new(2.0, 2.0, 3.0));
+        assert_eq!(v.ned_to_enu().enu_to_ned(), v);
+
+        #[cfg(feature = "approx")]
+        approx::assert_relative_eq!(v.normalize() * v.norm(), v, epsilon = 1e-6);
+    }
+}
@@ -1,6 +1,7 @@
 use msgpack_rpc::Value;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WeatherParameter {
     Rain,
     Roadwetness,
@@ -14,7 +15,7 @@ pub enum WeatherParameter {
 }
 
 impl WeatherParameter {
-    pub(crate) fn _as_msgpack(&self) -> Value {
+    pub(crate) fn as_msgpack(&self) -> Value {
         let val = match self {
             WeatherParameter::Rain => 0_i64,
             WeatherParameter::Roadwetness => 1_i64,
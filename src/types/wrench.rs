@@ -0,0 +1,35 @@
+use crate::Vector3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Force and torque applied to a body from outside, e.g. a gust or a payload, in the world (NED) frame
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wrench {
+    /// force in Newtons
+    pub force: Vector3,
+    /// torque in Newton meters
+    pub torque: Vector3,
+}
+
+impl Wrench {
+    pub fn new(force: Vector3, torque: Vector3) -> Self {
+        Self { force, torque }
+    }
+
+    /// Wrench without torque
+    ///
+    /// args:
+    ///     force (Vector3): force in Newtons, in the world (NED) frame
+    pub fn force(force: Vector3) -> Self {
+        Self::new(force, Vector3::zeros())
+    }
+
+    /// No force nor torque
+    pub fn zero() -> Self {
+        Self::force(Vector3::zeros())
+    }
+
+    /// Whether the wrench holds a torque
+    pub fn has_torque(&self) -> bool {
+        self.torque != Vector3::zeros()
+    }
+}
@@ -1,16 +1,77 @@
 use msgpack_rpc::{Utf8String, Value};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How the vehicle controls its yaw while moving, either facing a fixed angle or rotating at a fixed rate
+///
+/// Built with `rate`, `angle` or `forward`. A non finite rate or angle, e.g. the NaN of a degenerate computation,
+/// makes the vehicle hold its current heading like `rate(0.0)` rather than sending it to AirSim
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct YawMode {
     is_rate: bool,
     yaw_or_rate: f32,
 }
 
 impl YawMode {
+    #[deprecated(note = "use `YawMode::rate` or `YawMode::angle`, which can't be mixed up")]
     pub fn new(is_rate: bool, yaw_or_rate: f32) -> Self {
         Self { is_rate, yaw_or_rate }
     }
 
+    /// Rotate around the vertical axis at a constant rate, `rate(0.0)` holds the current heading
+    ///
+    /// args:
+    ///     deg_per_sec (f32): yaw rate in degrees per second, positive is clockwise seen from above
+    pub fn rate(deg_per_sec: f32) -> Self {
+        if !deg_per_sec.is_finite() {
+            log::warn!("yaw rate has to be finite, got {deg_per_sec} deg/s, holding the heading instead");
+            return Self::hold();
+        }
+        Self {
+            is_rate: true,
+            yaw_or_rate: deg_per_sec,
+        }
+    }
+
+    /// Face a fixed yaw angle
+    ///
+    /// With `DrivetrainType::ForwardOnly` the angle is relative to the direction of travel, otherwise it is the
+    /// heading in the NED frame
+    ///
+    /// args:
+    ///     deg (f32): yaw angle in degrees, wrapped into [-180, 180)
+    pub fn angle(deg: f32) -> Self {
+        if !deg.is_finite() {
+            log::warn!("yaw angle has to be finite, got {deg} deg, holding the heading instead");
+            return Self::hold();
+        }
+        Self {
+            is_rate: false,
+            yaw_or_rate: (deg + 180.0).rem_euclid(360.0) - 180.0,
+        }
+    }
+
+    fn hold() -> Self {
+        Self {
+            is_rate: true,
+            yaw_or_rate: 0.0,
+        }
+    }
+
+    /// Keep the front of the vehicle facing the direction of travel, with `DrivetrainType::ForwardOnly`
+    pub fn forward() -> Self {
+        Self::angle(0.0)
+    }
+
+    /// Whether the vehicle rotates at a rate rather than facing an angle
+    pub fn is_rate(&self) -> bool {
+        self.is_rate
+    }
+
+    /// Yaw angle in degrees or yaw rate in degrees per second, depending on `is_rate`
+    pub fn yaw_or_rate(&self) -> f32 {
+        self.yaw_or_rate
+    }
+
     pub fn as_msgpack(&self) -> Value {
         let is_rate_str: Utf8String = "is_rate".into();
         let yaw_or_rate_str: Utf8String = "yaw_or_rate".into();
@@ -23,3 +84,19 @@ impl YawMode {
         Value::Map(msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::YawMode;
+
+    #[test]
+    fn test_yaw_mode_constructors() {
+        assert!(YawMode::rate(30.0).is_rate());
+        assert!(!YawMode::angle(30.0).is_rate());
+        assert_eq!(YawMode::angle(270.0).yaw_or_rate(), -90.0);
+        assert_eq!(YawMode::angle(-540.0).yaw_or_rate(), -180.0);
+        assert_eq!(YawMode::forward(), YawMode::angle(360.0));
+        assert_eq!(YawMode::angle(f32::NAN), YawMode::rate(0.0));
+        assert_eq!(YawMode::rate(f32::INFINITY), YawMode::rate(0.0));
+    }
+}